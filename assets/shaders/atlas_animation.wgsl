@@ -0,0 +1,55 @@
+// Frame stepping for animated atlas tiles, shared by the chunk and water shaders.
+
+// Tiles along each side of the atlas (block::atlas::ATLAS_TILES).
+const ATLAS_TILES: f32 = 16.0;
+
+// Per tile (TileAnimations): x = tile holding the second frame, later frames follow it;
+// y = frame count, below 2 for still tiles; z = seconds per frame; w = 1 to blend
+// each frame into the next.
+struct TileAnimations {
+    tiles: array<vec4<f32>, 256>,
+}
+
+// The frame of an animated tile showing at `time`, the one after it, and how far to blend
+// towards the second. Both are the tile's own origin for still tiles.
+struct AnimatedFrame {
+    current: vec2<f32>,
+    next: vec2<f32>,
+    blend: f32,
+}
+
+// Index of the tile whose top-left UV is `origin`.
+fn tile_index(origin: vec2<f32>) -> u32 {
+    let cell = vec2<u32>(round(origin * ATLAS_TILES));
+    return cell.y * u32(ATLAS_TILES) + cell.x;
+}
+
+fn tile_origin(tile: f32) -> vec2<f32> {
+    return vec2<f32>(tile % ATLAS_TILES, floor(tile / ATLAS_TILES)) / ATLAS_TILES;
+}
+
+fn frame_origin(base: vec2<f32>, animation: vec4<f32>, frame: f32) -> vec2<f32> {
+    if frame < 1.0 {
+        return base;
+    }
+    return tile_origin(animation.x + frame - 1.0);
+}
+
+fn animated_frame(base: vec2<f32>, animation: vec4<f32>, time: f32) -> AnimatedFrame {
+    var frame: AnimatedFrame;
+    frame.current = base;
+    frame.next = base;
+    frame.blend = 0.0;
+    let count = animation.y;
+    if count < 2.0 {
+        return frame;
+    }
+    // Wrap at the tile's own cycle, so a time that wraps at a multiple of it never
+    // jumps frames
+    let progress = (time % (count * animation.z)) / animation.z;
+    let index = floor(progress) % count;
+    frame.current = frame_origin(base, animation, index);
+    frame.next = frame_origin(base, animation, (index + 1.0) % count);
+    frame.blend = fract(progress) * animation.w;
+    return frame;
+}
@@ -1,14 +1,50 @@
 #import bevy_pbr::{
+    pbr_bindings,
     pbr_fragment::pbr_input_from_standard_material,
     pbr_functions::alpha_discard,
-    pbr_functions::apply_pbr_lighting,
     pbr_functions::main_pass_post_lighting_processing,
+    mesh_view_bindings::globals,
     forward_io::{VertexOutput, FragmentOutput},
 }
+#import "shaders/atlas_animation.wgsl"::{TileAnimations, animated_frame, tile_index}
+
+// Sky light multiplier from the day/night cycle (AtlasTileMaterial::daylight).
+@group(#{MATERIAL_BIND_GROUP}) @binding(100) var<uniform> daylight: f32;
+// Animated tiles (AtlasTileMaterial::animations).
+@group(#{MATERIAL_BIND_GROUP}) @binding(101) var<uniform> animations: TileAnimations;
+// Added to globals.time so animations carry on across its hourly wrap
+// (AtlasTileMaterial::animation_offset).
+@group(#{MATERIAL_BIND_GROUP}) @binding(102) var<uniform> animation_offset: f32;
+
+// Darkest a fully unlit face gets, so caves are never pitch black.
+const MIN_BRIGHTNESS: f32 = 0.03;
+// Brightness of a fully occluded vertex (ambient occlusion level 0).
+const DARKEST_AO: f32 = 0.4;
+// Warm tint for block light (torches).
+const BLOCK_LIGHT_TINT: vec3<f32> = vec3<f32>(1.0, 0.85, 0.6);
+
+// Maps a normalized 0..1 light level to brightness with a Minecraft-like falloff.
+fn light_curve(level: f32) -> f32 {
+    return level / (4.0 - 3.0 * level);
+}
+
+// Fixed per-direction shading so block faces stay readable without scene lights.
+fn face_shade(normal: vec3<f32>) -> f32 {
+    let n = abs(normal);
+    if n.y > 0.5 {
+        return select(0.5, 1.0, normal.y > 0.0);
+    }
+    if n.z > n.x {
+        return 0.8;
+    }
+    return 0.6;
+}
 
 @fragment
 fn fragment(
     in: VertexOutput,
+    // Biome color relative to the one baked into the atlas (chunk_vertex.wgsl)
+    @location(10) tint: vec3<f32>,
     @builtin(front_facing) is_front: bool,
 ) -> FragmentOutput {
     // Tile the UV within the atlas tile.
@@ -19,13 +55,37 @@ fn fragment(
     let local = in.uv - in.uv_b;
     // Wrap local coordinates within one tile using modulo
     let wrapped_local = local - floor(local / tile_size) * tile_size;
-    modified.uv = in.uv_b + wrapped_local;
+    // Animated tiles keep their later frames elsewhere in the atlas
+    let frame = animated_frame(in.uv_b, animations.tiles[tile_index(in.uv_b)], globals.time + animation_offset);
+    modified.uv = frame.current + wrapped_local;
+
+    // Vertex color carries baked voxel light (r = sky, g = block) and ambient
+    // occlusion (b), not a tint, so keep it out of the base color.
+#ifdef VERTEX_COLORS
+    let voxel_light = in.color;
+    modified.color = vec4<f32>(1.0);
+#else
+    let voxel_light = vec4<f32>(1.0, 0.0, 1.0, 1.0);
+#endif
 
-    // Standard PBR pipeline with tiled UVs
     var pbr_input = pbr_input_from_standard_material(modified, is_front);
+    // Sampled for every fragment, since sampling under non-uniform control flow isn't allowed
+    let next = textureSample(pbr_bindings::base_color_texture, pbr_bindings::base_color_sampler, frame.next + wrapped_local);
+    pbr_input.material.base_color = mix(pbr_input.material.base_color, next, frame.blend);
+    pbr_input.material.base_color = vec4<f32>(pbr_input.material.base_color.rgb * tint, pbr_input.material.base_color.a);
     pbr_input.material.base_color = alpha_discard(pbr_input.material, pbr_input.material.base_color);
+
+    // Brightest of sky light (scaled by time of day) and block light
+    let sky = vec3<f32>(light_curve(voxel_light.r) * daylight);
+    let block = BLOCK_LIGHT_TINT * light_curve(voxel_light.g);
+    let light = max(max(sky, block), vec3<f32>(MIN_BRIGHTNESS));
+    let occlusion = mix(DARKEST_AO, 1.0, voxel_light.b);
+
     var out: FragmentOutput;
-    out.color = apply_pbr_lighting(pbr_input);
+    out.color = vec4<f32>(
+        pbr_input.material.base_color.rgb * light * occlusion * face_shade(in.world_normal),
+        pbr_input.material.base_color.a,
+    );
     out.color = main_pass_post_lighting_processing(pbr_input, out.color);
     return out;
 }
@@ -0,0 +1,33 @@
+#import bevy_pbr::{
+    pbr_fragment::pbr_input_from_standard_material,
+    pbr_functions::main_pass_post_lighting_processing,
+    forward_io::{VertexOutput, FragmentOutput},
+}
+
+// Sky light multiplier from the day/night cycle (FarTerrainShading::daylight).
+@group(#{MATERIAL_BIND_GROUP}) @binding(100) var<uniform> daylight: f32;
+
+// Darkest the terrain gets, matching the chunk shader.
+const MIN_BRIGHTNESS: f32 = 0.03;
+// Shading of level ground and of vertical skirts, between the chunk shader's
+// top (1.0) and side (0.6 / 0.8) face shading.
+const FLAT_SHADE: f32 = 1.0;
+const STEEP_SHADE: f32 = 0.7;
+
+@fragment
+fn fragment(
+    in: VertexOutput,
+    @builtin(front_facing) is_front: bool,
+) -> FragmentOutput {
+    // Base color is the vertex color: the surface block's map color.
+    let pbr_input = pbr_input_from_standard_material(in, is_front);
+
+    // Far terrain is all open to the sky, so it gets full sky light
+    let light = max(daylight, MIN_BRIGHTNESS);
+    let shade = mix(STEEP_SHADE, FLAT_SHADE, clamp(in.world_normal.y, 0.0, 1.0));
+
+    var out: FragmentOutput;
+    out.color = vec4<f32>(pbr_input.material.base_color.rgb * light * shade, 1.0);
+    out.color = main_pass_post_lighting_processing(pbr_input, out.color);
+    return out;
+}
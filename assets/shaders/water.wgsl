@@ -0,0 +1,74 @@
+#import bevy_pbr::{
+    pbr_bindings,
+    pbr_fragment::pbr_input_from_standard_material,
+    pbr_functions::main_pass_post_lighting_processing,
+    mesh_view_bindings::globals,
+    forward_io::{VertexOutput, FragmentOutput},
+}
+#import "shaders/atlas_animation.wgsl"::{TileAnimations, animated_frame, tile_index}
+
+// Sky light multiplier from the day/night cycle (WaterTintMaterial::daylight).
+@group(#{MATERIAL_BIND_GROUP}) @binding(100) var<uniform> daylight: f32;
+// Animated tiles (WaterTintMaterial::animations).
+@group(#{MATERIAL_BIND_GROUP}) @binding(101) var<uniform> animations: TileAnimations;
+// Added to globals.time so animations carry on across its hourly wrap
+// (WaterTintMaterial::animation_offset).
+@group(#{MATERIAL_BIND_GROUP}) @binding(102) var<uniform> animation_offset: f32;
+
+// Darkest a fully unlit face gets, matching the chunk shader.
+const MIN_BRIGHTNESS: f32 = 0.03;
+// Warm tint for block light (torches).
+const BLOCK_LIGHT_TINT: vec3<f32> = vec3<f32>(1.0, 0.85, 0.6);
+// Multiplied into the (already blue) water tile: untouched over a one-block-deep
+// puddle, darker and bluer over deep water.
+const SHALLOW_TINT: vec3<f32> = vec3<f32>(1.0, 1.0, 1.0);
+const DEEP_TINT: vec3<f32> = vec3<f32>(0.45, 0.55, 0.75);
+// Opacity over shallow and deep water.
+const SHALLOW_ALPHA: f32 = 0.45;
+const DEEP_ALPHA: f32 = 0.85;
+
+// Maps a normalized 0..1 light level to brightness with a Minecraft-like falloff.
+fn light_curve(level: f32) -> f32 {
+    return level / (4.0 - 3.0 * level);
+}
+
+@fragment
+fn fragment(
+    in: VertexOutput,
+    // Biome color relative to the one baked into the atlas (chunk_vertex.wgsl)
+    @location(10) tint: vec3<f32>,
+    @builtin(front_facing) is_front: bool,
+) -> FragmentOutput {
+    // Fluid faces use a single tile, so UV_0 never leaves it; UV_1 has its origin.
+    var modified = in;
+    let local = in.uv - in.uv_b;
+    let frame = animated_frame(in.uv_b, animations.tiles[tile_index(in.uv_b)], globals.time + animation_offset);
+    modified.uv = frame.current + local;
+
+    // Vertex color carries baked voxel light (r = sky, g = block) and the water
+    // column depth (a, 0..1), not a tint, so keep it out of the base color.
+#ifdef VERTEX_COLORS
+    let voxel_light = in.color;
+    modified.color = vec4<f32>(1.0);
+#else
+    let voxel_light = vec4<f32>(1.0, 0.0, 1.0, 0.0);
+#endif
+
+    var pbr_input = pbr_input_from_standard_material(modified, is_front);
+    let next = textureSample(pbr_bindings::base_color_texture, pbr_bindings::base_color_sampler, frame.next + local);
+    pbr_input.material.base_color = mix(pbr_input.material.base_color, next, frame.blend);
+    let depth = clamp(voxel_light.a, 0.0, 1.0);
+
+    let sky = vec3<f32>(light_curve(voxel_light.r) * daylight);
+    let block = BLOCK_LIGHT_TINT * light_curve(voxel_light.g);
+    let light = max(max(sky, block), vec3<f32>(MIN_BRIGHTNESS));
+
+    let depth_tint = mix(SHALLOW_TINT, DEEP_TINT, depth);
+    var out: FragmentOutput;
+    out.color = vec4<f32>(
+        pbr_input.material.base_color.rgb * tint * depth_tint * light,
+        mix(SHALLOW_ALPHA, DEEP_ALPHA, depth),
+    );
+    out.color = main_pass_post_lighting_processing(pbr_input, out.color);
+    return out;
+}
@@ -1,6 +1,18 @@
 use bevy::prelude::*;
+use bevy::audio::Volume;
 use bevy::ecs::message::{Message, MessageReader, MessageWriter};
 
+use crate::lighting::weather::{Precipitation, WeatherState};
+use crate::world::light::{light_levels_at, MAX_LIGHT};
+use crate::world::manager::ChunkDataStore;
+
+/// Loudness of the rain loop in the heaviest rain, out in the open.
+const RAIN_VOLUME: f32 = 0.5;
+/// Share of the rain loop still heard with no sky overhead.
+const RAIN_INDOOR_VOLUME: f32 = 0.2;
+/// Thunder from strikes further than this many blocks away is at its quietest.
+const THUNDER_FALLOFF: f32 = 64.0;
+
 /// Message fired when a block is broken.
 #[derive(Message)]
 pub struct BlockBreakAudio;
@@ -39,6 +51,12 @@ pub struct SwordSwingAudio;
 #[derive(Message)]
 pub struct ItemPickupAudio;
 
+/// Message fired when lightning strikes, `distance` blocks from the camera.
+#[derive(Message)]
+pub struct ThunderAudio {
+    pub distance: f32,
+}
+
 /// Stores preloaded sound effect handles.
 #[derive(Resource)]
 struct SoundEffects {
@@ -52,8 +70,13 @@ struct SoundEffects {
     mob_death: Handle<AudioSource>,
     sword_swing: Handle<AudioSource>,
     item_pickup: Handle<AudioSource>,
+    thunder: Handle<AudioSource>,
 }
 
+/// Marker for the looping rain sound, silent until it rains.
+#[derive(Component)]
+struct RainSound;
+
 /// Timer to throttle footstep sounds.
 #[derive(Resource)]
 struct FootstepTimer(Timer);
@@ -71,6 +94,7 @@ impl Plugin for GameAudioPlugin {
             .add_message::<MobDeathAudio>()
             .add_message::<SwordSwingAudio>()
             .add_message::<ItemPickupAudio>()
+            .add_message::<ThunderAudio>()
             .add_systems(Startup, load_sounds)
             .add_systems(
                 Update,
@@ -85,6 +109,8 @@ impl Plugin for GameAudioPlugin {
                     play_mob_death_sound,
                     play_sword_swing_sound,
                     play_item_pickup_sound,
+                    play_thunder_sound,
+                    update_rain_sound,
                 ),
             );
     }
@@ -102,7 +128,13 @@ fn load_sounds(mut commands: Commands, asset_server: Res<AssetServer>) {
         mob_death: asset_server.load("sounds/mob_death.ogg"),
         sword_swing: asset_server.load("sounds/sword_swing.ogg"),
         item_pickup: asset_server.load("sounds/place.ogg"), // reuse place sound for pickup pop
+        thunder: asset_server.load("sounds/thunder.wav"),
     });
+    commands.spawn((
+        RainSound,
+        AudioPlayer::new(asset_server.load("sounds/rain.wav")),
+        PlaybackSettings::LOOP.with_volume(Volume::SILENT),
+    ));
     commands.insert_resource(FootstepTimer(Timer::from_seconds(0.4, TimerMode::Repeating)));
 }
 
@@ -256,3 +288,42 @@ fn play_item_pickup_sound(
         ));
     }
 }
+
+fn play_thunder_sound(
+    mut commands: Commands,
+    mut messages: MessageReader<ThunderAudio>,
+    sounds: Res<SoundEffects>,
+) {
+    for msg in messages.read() {
+        // Distant strikes rumble quieter and deeper
+        let near = 1.0 - (msg.distance / THUNDER_FALLOFF).clamp(0.0, 0.8);
+        commands.spawn((
+            AudioPlayer::new(sounds.thunder.clone()),
+            PlaybackSettings {
+                speed: 0.8 + 0.2 * near,
+                ..PlaybackSettings::DESPAWN.with_volume(Volume::Linear(near))
+            },
+        ));
+    }
+}
+
+/// Fade the rain loop with the rain, muffled when there's no sky overhead and silent
+/// where the rain falls as snow or not at all.
+fn update_rain_sound(
+    weather: Res<WeatherState>,
+    store: Res<ChunkDataStore>,
+    camera: Query<&GlobalTransform, With<Camera3d>>,
+    mut sink: Query<&mut AudioSink, With<RainSound>>,
+) {
+    let (Ok(cam_transform), Ok(mut sink)) = (camera.single(), sink.single_mut()) else {
+        return;
+    };
+    let cam = cam_transform.translation();
+    let volume = if weather.rain > 0.0 && Precipitation::at(cam) == Precipitation::Rain {
+        let open = light_levels_at(&store, cam.floor().as_ivec3()).0 as f32 / MAX_LIGHT as f32;
+        RAIN_VOLUME * weather.rain * (RAIN_INDOOR_VOLUME + (1.0 - RAIN_INDOOR_VOLUME) * open)
+    } else {
+        0.0
+    };
+    sink.set_volume(Volume::Linear(volume));
+}
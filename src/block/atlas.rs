@@ -1,8 +1,111 @@
-use super::{BlockType, Face};
+use super::{BlockState, BlockType, Face};
+use super::state::{Axis, Half};
 
-const ATLAS_TILES: f32 = 16.0;
+/// Tiles along each side of the atlas.
+pub const ATLAS_TILES: f32 = 16.0;
 
-/// Returns the texture tile index for a given block face.
+/// Tiles in the whole atlas. Those past the highest `TILE_SOURCES` index hold the later
+/// frames of animated textures.
+pub const ATLAS_TILE_COUNT: usize = (ATLAS_TILES * ATLAS_TILES) as usize;
+
+/// Maps tile index → texture file in a resource pack's `textures/blocks` directory.
+/// Indices must match `texture_index`.
+pub const TILE_SOURCES: &[(u32, &str)] = &[
+    (0, "stone.png"),
+    (1, "dirt.png"),
+    (2, "grass_top.png"),
+    (3, "grass_side.png"),
+    (4, "cobblestone.png"),
+    (5, "planks.png"),
+    (6, "sand.png"),
+    (7, "gravel.png"),
+    (8, "oak_log_top.png"),
+    (9, "oak_log_side.png"),
+    (10, "oak_leaves.png"),
+    (11, "glass.png"),
+    (12, "coal_ore.png"),
+    (13, "iron_ore.png"),
+    (14, "gold_ore.png"),
+    (15, "diamond_ore.png"),
+    (16, "bedrock.png"),
+    (17, "water.png"),           // animated, see water.png.mcmeta
+    (18, "crafting_table_top.png"),
+    (19, "crafting_table_side.png"),
+    (20, "furnace_top.png"),
+    (21, "furnace_front.png"),
+    (22, "furnace_side.png"),
+    (23, "snow.png"),
+    (24, "clay.png"),
+    (25, "sandstone_top.png"),
+    (26, "sandstone_bottom.png"),
+    (27, "sandstone_side.png"),
+    (28, "birch_log_top.png"),
+    (29, "birch_log_side.png"),
+    (30, "birch_leaves.png"),
+    (31, "chest_front.png"),
+    (32, "chest_side.png"),
+    (33, "chest_top.png"),
+    (34, "bed_head_top.png"),
+    (35, "bed_head_side.png"),
+    (36, "bed_head_end.png"),
+    (37, "bed_feet_top.png"),
+    (38, "bed_feet_side.png"),
+    (39, "bed_feet_end.png"),
+    (40, "door_wood_upper.png"),
+    (41, "door_wood_lower.png"),
+    (42, "sapling_oak.png"),
+    (43, "sapling_birch.png"),
+    (44, "farmland_top.png"),
+    (45, "wheat_stage_0.png"),
+    (46, "wheat_stage_1.png"),
+    (47, "wheat_stage_2.png"),
+    (48, "wheat_stage_3.png"),
+    (49, "torch_on.png"),
+    (50, "tallgrass.png"),
+    (51, "stone.png"),           // deepslate — darkened stone, see tile_tint
+    (52, "fire.png"),            // animated, see fire.png.mcmeta
+    (53, "ladder.png"),
+    (54, "vine.png"),
+];
+
+/// Tint colors multiplied into tiles: biome colors for grayscale textures (plains biome),
+/// which Minecraft ships as grayscale and tints per-biome at runtime, and recolors of
+/// textures reused for another block.
+pub fn tile_tint(tile_index: u32) -> Option<(u8, u8, u8)> {
+    match tile_index {
+        2 => Some((124, 189, 107)),  // grass_top — plains green
+        3 => Some((124, 189, 107)),  // grass_side — plains green
+        10 => Some((119, 171, 47)), // oak_leaves — plains green
+        17 => Some((63, 118, 228)), // water — default blue
+        30 => Some((128, 167, 85)), // birch_leaves — birch green
+        50 => Some((124, 189, 107)), // tallgrass — plains green
+        51 => Some((100, 100, 112)), // deepslate — dark blue-gray stone
+        54 => Some((119, 171, 47)), // vine — plains green, like oak leaves
+        _ => None,
+    }
+}
+
+/// Biome colormaps that recolor tiles in the world.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BiomeTint {
+    Grass,
+    Foliage,
+    Water,
+}
+
+/// The colormap a tile follows in the world, for tiles whose `tile_tint` is a plains biome
+/// color. The atlas keeps those baked in for items and the UI; chunk meshes carry the
+/// difference to the local biome's color.
+pub fn tile_biome_tint(tile_index: u32) -> Option<BiomeTint> {
+    match tile_index {
+        2 | 3 | 50 => Some(BiomeTint::Grass),
+        10 | 54 => Some(BiomeTint::Foliage),
+        17 => Some(BiomeTint::Water),
+        _ => None,
+    }
+}
+
+/// Returns the texture tile index for a given block face in its default state.
 /// The index maps to a position in the 16x16 texture atlas.
 pub fn texture_index(block: BlockType, face: Face) -> u32 {
     match block {
@@ -62,24 +165,58 @@ pub fn texture_index(block: BlockType, face: Face) -> u32 {
             Face::Bottom => 5,   // planks underside
             _ => 35,             // bed_head_side for all sides
         },
-        BlockType::DoorBottom | BlockType::DoorBottomOpen => 41, // door_wood_lower
-        BlockType::DoorTop | BlockType::DoorTopOpen => 40, // door_wood_upper
+        BlockType::Door => 41, // door_wood_lower
         BlockType::OakSapling => 42,
         BlockType::BirchSapling => 43,
         BlockType::Farmland => match face {
             Face::Top => 44,
             _ => 1, // dirt sides
         },
-        BlockType::WheatStage0 => 45,
-        BlockType::WheatStage1 => 46,
-        BlockType::WheatStage2 => 47,
-        BlockType::WheatStage3 => 48,
+        BlockType::WheatCrop => 45, // stages 0-3 are tiles 45-48
         BlockType::Torch => 49,
         BlockType::TallGrass => 50,
+        BlockType::Deepslate => 51,
+        BlockType::Fire => 52,
+        BlockType::Ladder => 53,
+        BlockType::Vines => 54,
         _ => 0, // Air — shouldn't be rendered
     }
 }
 
+/// Returns the texture tile index for a face of a specific block state.
+/// Rotates fronts to the state's facing, log ends to its axis, and picks
+/// door halves and crop stages.
+pub fn state_texture_index(state: BlockState, face: Face) -> u32 {
+    let block = state.block();
+    match block {
+        BlockType::Furnace | BlockType::Chest => {
+            // The default textures put the front on North
+            let front = state.facing().face();
+            if face == front {
+                texture_index(block, Face::North)
+            } else if face == Face::North {
+                texture_index(block, Face::South)
+            } else {
+                texture_index(block, face)
+            }
+        }
+        BlockType::OakLog | BlockType::BirchLog => {
+            let is_end = match state.axis() {
+                Axis::Y => matches!(face, Face::Top | Face::Bottom),
+                Axis::X => matches!(face, Face::East | Face::West),
+                Axis::Z => matches!(face, Face::North | Face::South),
+            };
+            texture_index(block, if is_end { Face::Top } else { Face::North })
+        }
+        BlockType::Door => match state.half() {
+            Half::Lower => 41, // door_wood_lower
+            Half::Upper => 40, // door_wood_upper
+        },
+        BlockType::WheatCrop => 45 + state.age() as u32,
+        _ => texture_index(block, face),
+    }
+}
+
 /// Returns UV coordinates [u_min, v_min, u_max, v_max] for a tile index in the atlas.
 pub fn tile_uvs(tile_index: u32) -> [f32; 4] {
     let col = (tile_index % ATLAS_TILES as u32) as f32;
@@ -93,16 +230,22 @@ pub fn tile_uvs(tile_index: u32) -> [f32; 4] {
     [u_min, v_min, u_max, v_max]
 }
 
+/// Index of the tile whose top-left UV is `origin`, as passed to chunk shaders in UV_1.
+pub fn tile_at(origin: [f32; 2]) -> u32 {
+    let [u, v] = origin.map(|c| (c * ATLAS_TILES).round() as u32);
+    v * ATLAS_TILES as u32 + u
+}
+
 /// Returns the 4 UV corners for a face quad: [bottom-left, bottom-right, top-right, top-left]
-pub fn face_uvs(block: BlockType, face: Face) -> [[f32; 2]; 4] {
-    face_uvs_tiled(block, face, 1, 1)
+pub fn face_uvs(state: BlockState, face: Face) -> [[f32; 2]; 4] {
+    face_uvs_tiled(state, face, 1, 1)
 }
 
 /// Returns the 4 UV corners for a greedy-merged face quad that tiles the texture
 /// `tile_w` times horizontally and `tile_h` times vertically.
 /// The UVs span beyond the single tile boundary so the texture repeats across the merged quad.
-pub fn face_uvs_tiled(block: BlockType, face: Face, tile_w: usize, tile_h: usize) -> [[f32; 2]; 4] {
-    let [u_min, v_min, _u_max, _v_max] = tile_uvs(texture_index(block, face));
+pub fn face_uvs_tiled(state: BlockState, face: Face, tile_w: usize, tile_h: usize) -> [[f32; 2]; 4] {
+    let [u_min, v_min, _u_max, _v_max] = tile_uvs(state_texture_index(state, face));
     let tile_size = 1.0 / ATLAS_TILES;
     let u_max = u_min + tile_size * tile_w as f32;
     let v_max = v_min + tile_size * tile_h as f32;
@@ -113,3 +256,34 @@ pub fn face_uvs_tiled(block: BlockType, face: Face, tile_w: usize, tile_h: usize
         [u_min, v_min], // top-left
     ]
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block::model::MODEL_FACES;
+
+    #[test]
+    fn every_block_face_has_a_texture_file() {
+        let has_source = |tile| TILE_SOURCES.iter().any(|&(index, _)| index == tile);
+        for id in 1..=u8::MAX {
+            let block = BlockType::from_id(id);
+            if block == BlockType::Air {
+                continue;
+            }
+            for face in MODEL_FACES {
+                let tile = texture_index(block, face);
+                assert!(has_source(tile), "{block:?} {face:?} uses tile {tile} with no texture");
+            }
+        }
+        // Tiles only reached through block state
+        assert!(has_source(40) && (45..=48).all(has_source));
+    }
+
+    #[test]
+    fn tile_origins_map_back_to_tiles() {
+        for tile in [0, 2, 17, 51, 255] {
+            let [u, v, _, _] = tile_uvs(tile);
+            assert_eq!(tile_at([u, v]), tile);
+        }
+    }
+}
@@ -1,4 +1,8 @@
 pub mod atlas;
+pub mod model;
+pub mod state;
+
+pub use state::BlockState;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, serde::Serialize, serde::Deserialize)]
 #[repr(u8)]
@@ -32,17 +36,22 @@ pub enum BlockType {
     TallGrass = 25,
     Chest = 26,
     Bed = 27,
-    DoorBottom = 28,
-    DoorTop = 29,
-    DoorBottomOpen = 30,
-    DoorTopOpen = 31,
+    /// Half, facing and open/closed live in the block state.
+    #[serde(alias = "DoorBottom")]
+    Door = 28,
     OakSapling = 32,
     BirchSapling = 33,
     Farmland = 34,
-    WheatStage0 = 35,
-    WheatStage1 = 36,
-    WheatStage2 = 37,
-    WheatStage3 = 38,
+    /// Growth stage lives in the block state's age.
+    #[serde(alias = "WheatStage0")]
+    WheatCrop = 35,
+    Deepslate = 39,
+    /// Started by lightning; burns out on its own.
+    Fire = 51,
+    /// Climbable. Facing points away from the wall it hangs on.
+    Ladder = 52,
+    /// Climbable, hung on a wall like ladders; grows on jungle and swamp trees.
+    Vines = 53,
 }
 
 impl BlockType {
@@ -76,17 +85,15 @@ impl BlockType {
             25 => Self::TallGrass,
             26 => Self::Chest,
             27 => Self::Bed,
-            28 => Self::DoorBottom,
-            29 => Self::DoorTop,
-            30 => Self::DoorBottomOpen,
-            31 => Self::DoorTopOpen,
+            28 => Self::Door,
             32 => Self::OakSapling,
             33 => Self::BirchSapling,
             34 => Self::Farmland,
-            35 => Self::WheatStage0,
-            36 => Self::WheatStage1,
-            37 => Self::WheatStage2,
-            38 => Self::WheatStage3,
+            35 => Self::WheatCrop,
+            39 => Self::Deepslate,
+            51 => Self::Fire,
+            52 => Self::Ladder,
+            53 => Self::Vines,
             _ => Self::Air,
         }
     }
@@ -118,9 +125,9 @@ impl BlockType {
                 | Self::BirchLeaves
                 | Self::Chest
                 | Self::Bed
-                | Self::DoorBottom
-                | Self::DoorTop
+                | Self::Door
                 | Self::Farmland
+                | Self::Deepslate
         )
     }
 
@@ -134,29 +141,37 @@ impl BlockType {
                 | Self::BirchLeaves
                 | Self::Torch
                 | Self::TallGrass
-                | Self::DoorBottom
-                | Self::DoorTop
-                | Self::DoorBottomOpen
-                | Self::DoorTopOpen
+                | Self::Door
                 | Self::OakSapling
                 | Self::BirchSapling
-                | Self::WheatStage0
-                | Self::WheatStage1
-                | Self::WheatStage2
-                | Self::WheatStage3
+                | Self::WheatCrop
+                | Self::Fire
+                | Self::Ladder
+                | Self::Vines
         )
     }
 
     /// Returns true for blocks that should not be rendered as cube geometry.
-    /// These blocks are non-solid decorations (torches, saplings, tall grass).
+    /// These blocks are non-solid decorations (torches, saplings, tall grass, fire, ladders, vines).
     pub fn is_non_cube(self) -> bool {
         matches!(
             self,
-            Self::Torch | Self::TallGrass | Self::OakSapling | Self::BirchSapling
-            | Self::WheatStage0 | Self::WheatStage1 | Self::WheatStage2 | Self::WheatStage3
+            Self::Torch
+                | Self::TallGrass
+                | Self::OakSapling
+                | Self::BirchSapling
+                | Self::WheatCrop
+                | Self::Fire
+                | Self::Ladder
+                | Self::Vines
         )
     }
 
+    /// Returns true for blocks entities can climb while inside them (ladders, vines).
+    pub fn is_climbable(self) -> bool {
+        matches!(self, Self::Ladder | Self::Vines)
+    }
+
     pub fn is_air(self) -> bool {
         self == Self::Air
     }
@@ -165,12 +180,41 @@ impl BlockType {
         self == Self::Water
     }
 
+    /// Returns true for blocks that fall when nothing is below them (sand, gravel).
+    pub fn has_gravity(self) -> bool {
+        matches!(self, Self::Sand | Self::Gravel)
+    }
+
     /// Returns true if the player's raycast should be able to target this block.
     /// Everything except Air and Water is targetable.
     pub fn is_targetable(self) -> bool {
         !matches!(self, Self::Air | Self::Water)
     }
 
+    /// Block light level (0-15) this block emits.
+    pub fn light_emission(self) -> u8 {
+        match self {
+            Self::Torch => 14,
+            Self::Fire => 15,
+            _ => 0,
+        }
+    }
+
+    /// Returns true if this block stops sky and block light completely.
+    pub fn blocks_light(self) -> bool {
+        self.is_solid() && !self.is_transparent()
+    }
+
+    /// Extra light levels lost when light passes through this block,
+    /// on top of the normal one level per block.
+    pub fn light_filter(self) -> u8 {
+        match self {
+            Self::OakLeaves | Self::BirchLeaves => 1,
+            Self::Water => 2,
+            _ => 0,
+        }
+    }
+
     pub fn display_name(self) -> &'static str {
         match self {
             Self::Air => "",
@@ -201,21 +245,66 @@ impl BlockType {
             Self::TallGrass => "Tall Grass",
             Self::Chest => "Chest",
             Self::Bed => "Bed",
-            Self::DoorBottom | Self::DoorBottomOpen => "Oak Door",
-            Self::DoorTop | Self::DoorTopOpen => "Oak Door",
+            Self::Door => "Oak Door",
             Self::OakSapling => "Oak Sapling",
             Self::BirchSapling => "Birch Sapling",
             Self::Farmland => "Farmland",
-            Self::WheatStage0 | Self::WheatStage1 | Self::WheatStage2 | Self::WheatStage3 => "Wheat",
+            Self::WheatCrop => "Wheat",
+            Self::Deepslate => "Deepslate",
+            Self::Fire => "Fire",
+            Self::Ladder => "Ladder",
+            Self::Vines => "Vines",
+        }
+    }
+
+    /// Flat color of the block seen from above, for maps and far terrain.
+    pub fn map_color(self) -> [u8; 4] {
+        match self {
+            Self::Air => [0, 0, 0, 255],
+            Self::Stone => [128, 128, 128, 255],
+            Self::Dirt => [139, 90, 43, 255],
+            Self::Grass => [76, 153, 0, 255],
+            Self::Cobblestone => [120, 120, 120, 255],
+            Self::Planks => [180, 140, 80, 255],
+            Self::Sand => [237, 201, 175, 255],
+            Self::Gravel => [150, 140, 130, 255],
+            Self::OakLog | Self::BirchLog => [101, 67, 33, 255],
+            Self::OakLeaves | Self::BirchLeaves => [34, 139, 34, 255],
+            Self::Glass => [200, 220, 255, 255],
+            Self::CoalOre => [64, 64, 64, 255],
+            Self::IronOre => [180, 140, 100, 255],
+            Self::GoldOre => [255, 215, 0, 255],
+            Self::DiamondOre => [0, 255, 255, 255],
+            Self::Bedrock => [32, 32, 32, 255],
+            Self::Water => [30, 80, 200, 255],
+            Self::CraftingTable => [160, 120, 60, 255],
+            Self::Furnace => [100, 100, 100, 255],
+            Self::Torch => [255, 200, 50, 255],
+            Self::Snow => [240, 240, 255, 255],
+            Self::Clay => [160, 165, 175, 255],
+            Self::Sandstone => [220, 200, 150, 255],
+            Self::TallGrass => [50, 130, 50, 255],
+            Self::Chest => [160, 120, 50, 255],
+            Self::Bed => [180, 50, 50, 255],
+            Self::Door => [160, 120, 60, 255],
+            Self::OakSapling => [34, 139, 34, 255],
+            Self::BirchSapling => [50, 160, 50, 255],
+            Self::Farmland => [100, 65, 25, 255],
+            Self::WheatCrop => [80, 140, 40, 255],
+            Self::Deepslate => [77, 77, 84, 255],
+            Self::Fire => [230, 120, 30, 255],
+            Self::Ladder => [140, 110, 65, 255],
+            Self::Vines => [60, 120, 30, 255],
         }
     }
 
     /// Returns the item(s) dropped when this block is broken.
     /// Returns None for blocks that drop nothing (glass, leaves, etc.).
+    /// State-dependent drops (crop age) are handled by `BlockState::drop_item`.
     pub fn drop_item(self) -> Option<crate::inventory::item::Item> {
         use crate::inventory::item::Item;
         match self {
-            Self::Air | Self::Water | Self::Bedrock => None,
+            Self::Air | Self::Water | Self::Bedrock | Self::Fire => None,
             Self::Glass => None,
             Self::OakLeaves => {
                 // Independent rolls: 5% apple, 5% sapling (apple takes priority)
@@ -243,15 +332,12 @@ impl BlockType {
                     None
                 }
             }
-            Self::DoorTop | Self::DoorTopOpen => Some(Item::Block(Self::DoorBottom)),
-            Self::DoorBottomOpen => Some(Item::Block(Self::DoorBottom)),
             Self::Grass => Some(Item::Block(Self::Dirt)),
-            Self::Stone => Some(Item::Block(Self::Cobblestone)),
+            Self::Stone | Self::Deepslate => Some(Item::Block(Self::Cobblestone)),
             Self::CoalOre => Some(Item::Coal),
             Self::DiamondOre => Some(Item::Diamond),
             Self::Farmland => Some(Item::Block(Self::Dirt)),
-            Self::WheatStage0 | Self::WheatStage1 | Self::WheatStage2 => Some(Item::Seeds),
-            Self::WheatStage3 => Some(Item::Wheat),
+            Self::WheatCrop => Some(Item::Seeds),
             _ => Some(Item::Block(self)),
         }
     }
@@ -261,15 +347,6 @@ impl BlockType {
     pub fn bonus_drops(self) -> Vec<(crate::inventory::item::Item, u8)> {
         use crate::inventory::item::Item;
         match self {
-            Self::WheatStage3 => {
-                // Mature wheat drops 0-3 seeds in addition to wheat
-                let seed_count = (rand::random::<f32>() * 4.0).floor() as u8; // 0,1,2,3
-                if seed_count > 0 {
-                    vec![(Item::Seeds, seed_count)]
-                } else {
-                    vec![]
-                }
-            }
             Self::Grass => {
                 // 10% chance to drop seeds when digging grass blocks
                 if rand::random::<f32>() < 0.1 {
@@ -287,18 +364,20 @@ impl BlockType {
         match self {
             Self::Air | Self::Water => 0.0,
             Self::TallGrass | Self::Torch | Self::OakSapling | Self::BirchSapling
-            | Self::WheatStage0 | Self::WheatStage1 | Self::WheatStage2 | Self::WheatStage3 => 0.0, // instant break
+            | Self::WheatCrop | Self::Fire => 0.0, // instant break
             Self::OakLeaves | Self::BirchLeaves => 0.3,
             Self::Glass => 0.45,
             Self::Dirt | Self::Sand | Self::Farmland => 0.75,
             Self::Gravel | Self::Clay => 0.9,
             Self::Snow => 0.3,
+            Self::Vines => 0.3,
+            Self::Ladder => 0.6,
             Self::Grass => 0.9,
-            Self::OakLog | Self::BirchLog | Self::Planks
-            | Self::DoorBottom | Self::DoorTop | Self::DoorBottomOpen | Self::DoorTopOpen => 3.0,
+            Self::OakLog | Self::BirchLog | Self::Planks | Self::Door => 3.0,
             Self::CraftingTable => 3.75,
             Self::Sandstone => 4.0,
             Self::Stone => 7.5,
+            Self::Deepslate => 15.0,
             Self::Cobblestone => 10.0,
             Self::CoalOre | Self::IronOre | Self::GoldOre | Self::DiamondOre => 15.0,
             Self::Furnace => 17.5,
@@ -313,7 +392,8 @@ impl BlockType {
     pub fn required_pickaxe_tier(self) -> Option<crate::inventory::item::ToolTier> {
         use crate::inventory::item::ToolTier;
         match self {
-            Self::Stone | Self::Cobblestone | Self::Sandstone | Self::Furnace | Self::CoalOre => {
+            Self::Stone | Self::Deepslate | Self::Cobblestone | Self::Sandstone | Self::Furnace
+            | Self::CoalOre => {
                 Some(ToolTier::Wooden) // any pickaxe
             }
             Self::IronOre => Some(ToolTier::Stone),
@@ -324,7 +404,8 @@ impl BlockType {
 }
 
 /// Face direction for block face lookups
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum Face {
     Top,
     Bottom,
@@ -387,19 +468,35 @@ mod tests {
         assert!(!BlockType::Bedrock.is_transparent());
     }
 
+    #[test]
+    fn light_properties() {
+        assert_eq!(BlockType::Torch.light_emission(), 14);
+        assert_eq!(BlockType::Stone.light_emission(), 0);
+        assert!(BlockType::Stone.blocks_light());
+        assert!(!BlockType::Glass.blocks_light());
+        assert!(!BlockType::OakLeaves.blocks_light());
+        assert!(BlockType::OakLeaves.light_filter() > 0);
+        assert_eq!(BlockType::Air.light_filter(), 0);
+    }
+
     #[test]
     fn from_id_roundtrip() {
-        for id in 0..=38u8 {
+        for id in 0..=39u8 {
             let bt = BlockType::from_id(id);
-            assert_eq!(bt as u8, id);
+            // Ids retired by block states (old door/wheat variants) decode as Air
+            if bt != BlockType::Air {
+                assert_eq!(bt as u8, id);
+            }
         }
+        assert_eq!(BlockType::from_id(28), BlockType::Door);
+        assert_eq!(BlockType::from_id(35), BlockType::WheatCrop);
     }
 
     #[test]
     fn from_id_unknown_returns_air() {
         assert_eq!(BlockType::from_id(255), BlockType::Air);
         assert_eq!(BlockType::from_id(100), BlockType::Air);
-        assert_eq!(BlockType::from_id(39), BlockType::Air);
+        assert_eq!(BlockType::from_id(40), BlockType::Air);
     }
 
     #[test]
@@ -453,6 +550,23 @@ mod tests {
         assert!(!BlockType::Stone.is_liquid());
     }
 
+    #[test]
+    fn only_sand_and_gravel_fall() {
+        assert!(BlockType::Sand.has_gravity());
+        assert!(BlockType::Gravel.has_gravity());
+        assert!(!BlockType::Sandstone.has_gravity());
+        assert!(!BlockType::Dirt.has_gravity());
+    }
+
+    #[test]
+    fn only_ladders_and_vines_climbable() {
+        assert!(BlockType::Ladder.is_climbable());
+        assert!(BlockType::Vines.is_climbable());
+        assert!(!BlockType::Ladder.is_solid());
+        assert!(!BlockType::Water.is_climbable());
+        assert!(!BlockType::OakLeaves.is_climbable());
+    }
+
     #[test]
     fn face_normals_unit_length() {
         for face in [Face::Top, Face::Bottom, Face::North, Face::South, Face::East, Face::West] {
@@ -0,0 +1,333 @@
+use std::collections::HashMap;
+use std::sync::LazyLock;
+
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use super::state::Facing;
+use super::{BlockState, BlockType, Face};
+
+/// Model files for blocks that aren't plain cubes, compiled in so meshing and
+/// collision work off the main thread without waiting on the asset server.
+const MODEL_SOURCES: &[(&str, &str)] = &[
+    ("cross", include_str!("../../assets/models/cross.json")),
+    ("torch", include_str!("../../assets/models/torch.json")),
+    ("bed", include_str!("../../assets/models/bed.json")),
+    ("farmland", include_str!("../../assets/models/farmland.json")),
+    ("door", include_str!("../../assets/models/door.json")),
+    ("door_open", include_str!("../../assets/models/door_open.json")),
+    ("wall_panel", include_str!("../../assets/models/wall_panel.json")),
+];
+
+/// Faces in the order `ModelBox::faces` stores them.
+pub const MODEL_FACES: [Face; 6] = [Face::Top, Face::Bottom, Face::North, Face::South, Face::East, Face::West];
+
+/// An axis-aligned box within a block's cell, in block units (0..1).
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModelBox {
+    pub min: Vec3,
+    pub max: Vec3,
+    /// Texture region of each drawn face as `[u_min, v_min, u_max, v_max]` within the
+    /// face's tile (0..1), indexed like `MODEL_FACES`. `None` faces aren't drawn.
+    pub faces: [Option<[f32; 4]>; 6],
+}
+
+/// A double-sided quad, for crossed plants and other flat shapes.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModelPlane {
+    /// Corners in block units: bottom-left, bottom-right, top-right, top-left.
+    pub corners: [Vec3; 4],
+    /// Texture region within the tile, as for box faces.
+    pub uv: [f32; 4],
+    /// Which face of the block state supplies the texture.
+    pub texture: Face,
+}
+
+/// Geometry of a non-cube block: boxes (drawn, collided with and targeted) and
+/// planes (drawn only).
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct BlockModel {
+    pub boxes: Vec<ModelBox>,
+    pub planes: Vec<ModelPlane>,
+}
+
+/// Model file format. Coordinates and UVs are in sixteenths of a block / tile, like
+/// Minecraft's block model JSON.
+#[derive(Deserialize)]
+struct ModelFile {
+    #[serde(default)]
+    boxes: Vec<BoxFile>,
+    #[serde(default)]
+    planes: Vec<PlaneFile>,
+}
+
+#[derive(Deserialize)]
+struct BoxFile {
+    from: [f32; 3],
+    to: [f32; 3],
+    /// Faces to draw; all six when left out.
+    faces: Option<HashMap<Face, FaceFile>>,
+}
+
+#[derive(Deserialize)]
+struct FaceFile {
+    /// Texture region; defaults to the part of the tile the face covers.
+    uv: Option<[f32; 4]>,
+}
+
+#[derive(Deserialize)]
+struct PlaneFile {
+    corners: [[f32; 3]; 4],
+    #[serde(default = "full_uv")]
+    uv: [f32; 4],
+    #[serde(default = "south")]
+    texture: Face,
+}
+
+fn full_uv() -> [f32; 4] {
+    [0.0, 0.0, 16.0, 16.0]
+}
+
+fn south() -> Face {
+    Face::South
+}
+
+/// The part of a tile a box face covers when the model doesn't give one, so a
+/// shorter box shows the bottom of its side texture, as a cut-down cube would.
+fn default_uv(face: Face, from: [f32; 3], to: [f32; 3]) -> [f32; 4] {
+    let [x0, y0, z0] = from;
+    let [x1, y1, z1] = to;
+    match face {
+        Face::Top => [x0, z0, x1, z1],
+        Face::Bottom => [x0, 16.0 - z1, x1, 16.0 - z0],
+        Face::North => [16.0 - x1, 16.0 - y1, 16.0 - x0, 16.0 - y0],
+        Face::South => [x0, 16.0 - y1, x1, 16.0 - y0],
+        Face::East => [16.0 - z1, 16.0 - y1, 16.0 - z0, 16.0 - y0],
+        Face::West => [z0, 16.0 - y1, z1, 16.0 - y0],
+    }
+}
+
+/// Parse a model file into block units.
+pub fn parse_model(source: &str) -> Result<BlockModel, serde_json::Error> {
+    let file: ModelFile = serde_json::from_str(source)?;
+    let to_block = |v: [f32; 3]| Vec3::from_array(v) / 16.0;
+    let to_tile = |uv: [f32; 4]| uv.map(|c| c / 16.0);
+
+    let boxes = file
+        .boxes
+        .into_iter()
+        .map(|b| ModelBox {
+            min: to_block(b.from).min(to_block(b.to)),
+            max: to_block(b.from).max(to_block(b.to)),
+            faces: MODEL_FACES.map(|face| {
+                let uv = match &b.faces {
+                    None => Some(None),
+                    Some(faces) => faces.get(&face).map(|f| f.uv),
+                };
+                uv.map(|uv| to_tile(uv.unwrap_or_else(|| default_uv(face, b.from, b.to))))
+            }),
+        })
+        .collect();
+    let planes = file
+        .planes
+        .into_iter()
+        .map(|p| ModelPlane {
+            corners: p.corners.map(to_block),
+            uv: to_tile(p.uv),
+            texture: p.texture,
+        })
+        .collect();
+    Ok(BlockModel { boxes, planes })
+}
+
+/// Quarter turns clockwise (seen from above) that take north to `facing`.
+fn quarter_turns(facing: Facing) -> usize {
+    match facing {
+        Facing::North => 0,
+        Facing::East => 1,
+        Facing::South => 2,
+        Facing::West => 3,
+    }
+}
+
+/// Turn a point in the cell a quarter turn clockwise about the vertical center line.
+fn turn_point(p: Vec3) -> Vec3 {
+    Vec3::new(1.0 - p.z, p.y, p.x)
+}
+
+/// Where a face points after a quarter turn clockwise.
+fn turn_face(face: Face) -> Face {
+    match face {
+        Face::North => Face::East,
+        Face::East => Face::South,
+        Face::South => Face::West,
+        Face::West => Face::North,
+        vertical => vertical,
+    }
+}
+
+impl BlockModel {
+    /// This model turned a quarter turn clockwise. Face UVs stay with their faces.
+    fn turned(&self) -> Self {
+        let boxes = self
+            .boxes
+            .iter()
+            .map(|b| {
+                let (a, c) = (turn_point(b.min), turn_point(b.max));
+                let mut faces = [None; 6];
+                for (i, &face) in MODEL_FACES.iter().enumerate() {
+                    let turned = MODEL_FACES.iter().position(|&f| f == turn_face(face)).unwrap();
+                    faces[turned] = b.faces[i];
+                }
+                ModelBox { min: a.min(c), max: a.max(c), faces }
+            })
+            .collect();
+        let planes = self
+            .planes
+            .iter()
+            .map(|p| ModelPlane { corners: p.corners.map(turn_point), uv: p.uv, texture: p.texture })
+            .collect();
+        Self { boxes, planes }
+    }
+}
+
+/// Every model in all four horizontal orientations, indexed by `quarter_turns`.
+static MODELS: LazyLock<HashMap<&'static str, [BlockModel; 4]>> = LazyLock::new(|| {
+    MODEL_SOURCES
+        .iter()
+        .map(|&(name, source)| {
+            let model = parse_model(source).unwrap_or_else(|e| panic!("invalid block model {name}: {e}"));
+            let east = model.turned();
+            let south = east.turned();
+            let west = south.turned();
+            (name, [model, east, south, west])
+        })
+        .collect()
+});
+
+/// The whole cell, the shape of every block drawn as a plain cube.
+static FULL_CUBE: [ModelBox; 1] = [ModelBox {
+    min: Vec3::ZERO,
+    max: Vec3::ONE,
+    faces: [Some([0.0, 0.0, 1.0, 1.0]); 6],
+}];
+
+/// The model a block state is drawn with, or `None` for plain cubes and air.
+pub fn block_model(state: BlockState) -> Option<&'static BlockModel> {
+    let (name, facing) = match state.block() {
+        BlockType::Torch => ("torch", Facing::North),
+        BlockType::TallGrass
+        | BlockType::OakSapling
+        | BlockType::BirchSapling
+        | BlockType::WheatCrop
+        | BlockType::Fire => {
+            ("cross", Facing::North)
+        }
+        BlockType::Bed => ("bed", Facing::North),
+        BlockType::Farmland => ("farmland", Facing::North),
+        BlockType::Door if state.is_open() => ("door_open", state.facing()),
+        BlockType::Door => ("door", state.facing()),
+        BlockType::Ladder | BlockType::Vines => ("wall_panel", state.facing()),
+        _ => return None,
+    };
+    MODELS.get(name).map(|turns| &turns[quarter_turns(facing)])
+}
+
+/// Boxes a block state fills within its cell: its model's boxes, or the whole cell
+/// for plain cubes. Models made only of planes fill nothing.
+pub fn block_shape(state: BlockState) -> &'static [ModelBox] {
+    match block_model(state) {
+        Some(model) => &model.boxes,
+        None if state.block() == BlockType::Air => &[],
+        None => &FULL_CUBE,
+    }
+}
+
+/// Boxes entities collide with. Open doors keep their (swung aside) panel solid.
+pub fn collision_boxes(state: BlockState) -> &'static [ModelBox] {
+    if state.block().is_solid() {
+        block_shape(state)
+    } else {
+        &[]
+    }
+}
+
+impl BlockType {
+    /// Whether this block is drawn from a model instead of as a full cube.
+    pub fn has_model(self) -> bool {
+        block_model(BlockState::new(self)).is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn built_in_models_parse() {
+        for &(name, source) in MODEL_SOURCES {
+            assert!(parse_model(source).is_ok(), "model {name} should parse");
+        }
+    }
+
+    #[test]
+    fn partial_blocks_have_their_heights() {
+        let height = |block| block_shape(BlockState::new(block))[0].max.y;
+        assert_eq!(height(BlockType::Bed), 9.0 / 16.0);
+        assert_eq!(height(BlockType::Farmland), 15.0 / 16.0);
+        assert_eq!(height(BlockType::Stone), 1.0);
+        assert!(block_shape(BlockState::new(BlockType::TallGrass)).is_empty());
+        assert!(collision_boxes(BlockState::new(BlockType::Torch)).is_empty());
+    }
+
+    #[test]
+    fn default_face_uvs_follow_the_box() {
+        let model = parse_model(r#"{ "boxes": [{ "from": [0, 0, 0], "to": [16, 9, 16] }] }"#).unwrap();
+        let side = model.boxes[0].faces[3].unwrap();
+        assert_eq!(side, [0.0, 7.0 / 16.0, 1.0, 1.0]);
+
+        // Faces left out of an explicit list aren't drawn
+        let model = parse_model(r#"{ "boxes": [{ "from": [0, 0, 0], "to": [16, 16, 16], "faces": { "top": {} } }] }"#)
+            .unwrap();
+        assert_eq!(model.boxes[0].faces.iter().filter(|f| f.is_some()).count(), 1);
+    }
+
+    #[test]
+    fn door_panels_turn_with_facing() {
+        let door = BlockState::new(BlockType::Door);
+        let panel = |state| block_shape(state)[0].clone();
+
+        // Facing north the closed panel runs along the south edge
+        let north = panel(door);
+        assert_eq!((north.min.z, north.max.z), (13.0 / 16.0, 1.0));
+        assert_eq!((north.min.x, north.max.x), (0.0, 1.0));
+
+        // Facing east it runs along the west edge
+        let east = panel(door.with_facing(Facing::East));
+        assert_eq!((east.min.x, east.max.x), (0.0, 3.0 / 16.0));
+        assert_eq!((east.min.z, east.max.z), (0.0, 1.0));
+
+        // Opening swings it to a side, leaving the doorway clear
+        let open = panel(door.with_open(true));
+        assert_eq!((open.min.x, open.max.x), (13.0 / 16.0, 1.0));
+        assert_eq!(collision_boxes(door.with_open(true)).len(), 1);
+    }
+
+    #[test]
+    fn wall_panels_hang_against_their_wall() {
+        let depth = |state| {
+            let model = block_model(state).unwrap();
+            assert!(model.boxes.is_empty());
+            model.planes[0].corners.map(|c| (c.x, c.z))
+        };
+
+        // Facing north, away from a wall to the south
+        let ladder = BlockState::new(BlockType::Ladder);
+        assert!(depth(ladder).iter().all(|&(_, z)| z > 0.9));
+
+        // Facing west, away from a wall to the east
+        let vines = BlockState::new(BlockType::Vines).with_facing(Facing::West);
+        assert!(depth(vines).iter().all(|&(x, _)| x > 0.9));
+        assert!(collision_boxes(vines).is_empty());
+    }
+}
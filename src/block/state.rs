@@ -0,0 +1,438 @@
+use bevy::prelude::*;
+
+use super::{BlockType, Face};
+
+/// Horizontal direction a block faces (e.g. a furnace's front).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Facing {
+    #[default]
+    North = 0,
+    South = 1,
+    East = 2,
+    West = 3,
+}
+
+impl Facing {
+    fn from_bits(bits: u8) -> Self {
+        match bits & 0b11 {
+            0 => Self::North,
+            1 => Self::South,
+            2 => Self::East,
+            _ => Self::West,
+        }
+    }
+
+    /// The block face pointing in this direction.
+    pub fn face(self) -> Face {
+        match self {
+            Self::North => Face::North,
+            Self::South => Face::South,
+            Self::East => Face::East,
+            Self::West => Face::West,
+        }
+    }
+
+    /// Unit offset to the neighboring block in this direction.
+    pub fn offset(self) -> IVec3 {
+        match self {
+            Self::North => IVec3::NEG_Z,
+            Self::South => IVec3::Z,
+            Self::East => IVec3::X,
+            Self::West => IVec3::NEG_X,
+        }
+    }
+
+    pub fn opposite(self) -> Self {
+        match self {
+            Self::North => Self::South,
+            Self::South => Self::North,
+            Self::East => Self::West,
+            Self::West => Self::East,
+        }
+    }
+
+    /// Closest horizontal facing for a direction vector (vertical component ignored).
+    pub fn from_direction(dir: Vec3) -> Self {
+        if dir.x.abs() > dir.z.abs() {
+            if dir.x > 0.0 { Self::East } else { Self::West }
+        } else if dir.z > 0.0 {
+            Self::South
+        } else {
+            Self::North
+        }
+    }
+}
+
+/// Which half of a two-block-tall block (doors).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Half {
+    #[default]
+    Lower = 0,
+    Upper = 1,
+}
+
+/// Orientation axis of a pillar-like block (logs). Defaults to upright.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Axis {
+    #[default]
+    Y = 0,
+    X = 1,
+    Z = 2,
+}
+
+impl Axis {
+    fn from_bits(bits: u8) -> Self {
+        match bits & 0b11 {
+            1 => Self::X,
+            2 => Self::Z,
+            _ => Self::Y,
+        }
+    }
+
+    /// Axis of a face normal (e.g. the face a block was placed against).
+    pub fn from_normal(normal: IVec3) -> Self {
+        if normal.x != 0 {
+            Self::X
+        } else if normal.z != 0 {
+            Self::Z
+        } else {
+            Self::Y
+        }
+    }
+}
+
+/// A property a block type can carry in its state.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Property {
+    Facing,
+    Half,
+    Open,
+    Axis,
+    /// Growth stage from 0 up to and including the given maximum.
+    Age(u8),
+    /// Fluid flow level: 0 for a source block, 1..=MAX_FLUID_LEVEL for flowing fluid.
+    Level,
+    /// Fluid falling straight down from the block above.
+    Falling,
+}
+
+impl Property {
+    /// Number of bits this property occupies in the packed state.
+    fn bits(self) -> u8 {
+        match self {
+            Self::Facing | Self::Axis => 2,
+            Self::Level => 3,
+            Self::Half | Self::Open | Self::Falling => 1,
+            Self::Age(max) => 8 - max.leading_zeros() as u8,
+        }
+    }
+
+    fn same_kind(self, other: Property) -> bool {
+        std::mem::discriminant(&self) == std::mem::discriminant(&other)
+    }
+}
+
+/// Maximum age of a wheat crop (fully grown).
+pub const WHEAT_MAX_AGE: u8 = 3;
+
+/// Highest flow level of a fluid; flow stops spreading beyond it.
+pub const MAX_FLUID_LEVEL: u8 = 7;
+
+/// Block state registry: the properties each block type carries, in packing order.
+pub fn properties(block: BlockType) -> &'static [Property] {
+    match block {
+        BlockType::Door => &[Property::Facing, Property::Half, Property::Open],
+        BlockType::Furnace | BlockType::Chest | BlockType::Ladder | BlockType::Vines => &[Property::Facing],
+        BlockType::OakLog | BlockType::BirchLog => &[Property::Axis],
+        BlockType::WheatCrop => &[Property::Age(WHEAT_MAX_AGE)],
+        BlockType::Water => &[Property::Level, Property::Falling],
+        _ => &[],
+    }
+}
+
+/// Bit offset and width of `property` within `block`'s packed properties, if it has it.
+fn property_slot(block: BlockType, property: Property) -> Option<(u8, u8)> {
+    let mut shift = 0;
+    for &p in properties(block) {
+        if p.same_kind(property) {
+            return Some((shift, p.bits()));
+        }
+        shift += p.bits();
+    }
+    None
+}
+
+/// A block type plus its property values (facing, half, open, axis, age).
+/// Packs into a `u16` for chunk storage: low byte = block id, high byte = properties.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct BlockState {
+    block: BlockType,
+    props: u8,
+}
+
+impl From<BlockType> for BlockState {
+    fn from(block: BlockType) -> Self {
+        Self::new(block)
+    }
+}
+
+impl BlockState {
+    pub const AIR: Self = Self { block: BlockType::Air, props: 0 };
+
+    /// The default state of a block type (facing north, lower half, closed, upright, age 0,
+    /// fluid source).
+    pub const fn new(block: BlockType) -> Self {
+        Self { block, props: 0 }
+    }
+
+    #[inline]
+    pub fn block(self) -> BlockType {
+        self.block
+    }
+
+    #[inline]
+    pub fn to_raw(self) -> u16 {
+        self.block as u16 | (self.props as u16) << 8
+    }
+
+    /// Decode a packed state. Unknown ids become Air and unused property bits are cleared.
+    pub fn from_raw(raw: u16) -> Self {
+        let block = BlockType::from_id(raw as u8);
+        let width: u8 = properties(block).iter().map(|p| p.bits()).sum();
+        let mask = ((1u16 << width) - 1) as u8;
+        Self { block, props: (raw >> 8) as u8 & mask }
+    }
+
+    /// Decode a block id from the old one-variant-per-state format (saves before block states).
+    pub fn from_legacy_id(id: u8) -> Self {
+        let door = Self::new(BlockType::Door);
+        let wheat = Self::new(BlockType::WheatCrop);
+        match id {
+            29 => door.with_half(Half::Upper),
+            30 => door.with_open(true),
+            31 => door.with_half(Half::Upper).with_open(true),
+            36 => wheat.with_age(1),
+            37 => wheat.with_age(2),
+            38 => wheat.with_age(3),
+            _ => Self::new(BlockType::from_id(id)),
+        }
+    }
+
+    fn get(self, property: Property) -> u8 {
+        match property_slot(self.block, property) {
+            Some((shift, bits)) => (self.props >> shift) & ((1u16 << bits) - 1) as u8,
+            None => 0,
+        }
+    }
+
+    fn with(self, property: Property, value: u8) -> Self {
+        let Some((shift, bits)) = property_slot(self.block, property) else {
+            return self;
+        };
+        let mask = (((1u16 << bits) - 1) as u8) << shift;
+        Self {
+            block: self.block,
+            props: (self.props & !mask) | ((value << shift) & mask),
+        }
+    }
+
+    pub fn facing(self) -> Facing {
+        Facing::from_bits(self.get(Property::Facing))
+    }
+
+    pub fn with_facing(self, facing: Facing) -> Self {
+        self.with(Property::Facing, facing as u8)
+    }
+
+    pub fn half(self) -> Half {
+        if self.get(Property::Half) == 0 { Half::Lower } else { Half::Upper }
+    }
+
+    pub fn with_half(self, half: Half) -> Self {
+        self.with(Property::Half, half as u8)
+    }
+
+    pub fn is_open(self) -> bool {
+        self.get(Property::Open) != 0
+    }
+
+    pub fn with_open(self, open: bool) -> Self {
+        self.with(Property::Open, open as u8)
+    }
+
+    pub fn axis(self) -> Axis {
+        Axis::from_bits(self.get(Property::Axis))
+    }
+
+    pub fn with_axis(self, axis: Axis) -> Self {
+        self.with(Property::Axis, axis as u8)
+    }
+
+    pub fn age(self) -> u8 {
+        self.get(Property::Age(0))
+    }
+
+    /// Set the age, clamped to the block's maximum.
+    pub fn with_age(self, age: u8) -> Self {
+        let max = properties(self.block).iter().find_map(|p| match p {
+            Property::Age(max) => Some(*max),
+            _ => None,
+        });
+        match max {
+            Some(max) => self.with(Property::Age(max), age.min(max)),
+            None => self,
+        }
+    }
+
+    /// Fluid flow level (0 = source). Meaningless for non-fluids.
+    pub fn fluid_level(self) -> u8 {
+        self.get(Property::Level)
+    }
+
+    /// Set the fluid flow level, clamped to MAX_FLUID_LEVEL.
+    pub fn with_fluid_level(self, level: u8) -> Self {
+        self.with(Property::Level, level.min(MAX_FLUID_LEVEL))
+    }
+
+    pub fn is_falling(self) -> bool {
+        self.get(Property::Falling) != 0
+    }
+
+    pub fn with_falling(self, falling: bool) -> Self {
+        self.with(Property::Falling, falling as u8)
+    }
+
+    /// A fluid source block (level 0, not falling), the only kind a bucket can pick up.
+    pub fn is_fluid_source(self) -> bool {
+        self.block.is_liquid() && self.fluid_level() == 0 && !self.is_falling()
+    }
+
+    /// Whether this state collides with entities. Open doors can be walked through.
+    pub fn is_solid(self) -> bool {
+        match self.block {
+            BlockType::Door => !self.is_open(),
+            block => block.is_solid(),
+        }
+    }
+
+    /// Whether this state is an opaque full cube, hiding whatever is behind it.
+    pub fn is_opaque_cube(self) -> bool {
+        self.is_solid() && !self.block.is_transparent() && !self.block.has_model()
+    }
+
+    /// Atlas tile for a face of this state.
+    pub fn texture_index(self, face: Face) -> u32 {
+        super::atlas::state_texture_index(self, face)
+    }
+
+    /// Item dropped when this state is broken. Immature crops drop seeds.
+    pub fn drop_item(self) -> Option<crate::inventory::item::Item> {
+        use crate::inventory::item::Item;
+        match self.block {
+            BlockType::WheatCrop if self.age() >= WHEAT_MAX_AGE => Some(Item::Wheat),
+            BlockType::WheatCrop => Some(Item::Seeds),
+            block => block.drop_item(),
+        }
+    }
+
+    /// Extra drops when this state is broken. Mature wheat drops 0-3 seeds.
+    pub fn bonus_drops(self) -> Vec<(crate::inventory::item::Item, u8)> {
+        use crate::inventory::item::Item;
+        match self.block {
+            BlockType::WheatCrop if self.age() >= WHEAT_MAX_AGE => {
+                let seed_count = (rand::random::<f32>() * 4.0).floor() as u8; // 0,1,2,3
+                if seed_count > 0 {
+                    vec![(Item::Seeds, seed_count)]
+                } else {
+                    vec![]
+                }
+            }
+            block => block.bonus_drops(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn raw_roundtrip_preserves_properties() {
+        let door = BlockState::new(BlockType::Door)
+            .with_facing(Facing::East)
+            .with_half(Half::Upper)
+            .with_open(true);
+        let decoded = BlockState::from_raw(door.to_raw());
+        assert_eq!(decoded, door);
+        assert_eq!(decoded.facing(), Facing::East);
+        assert_eq!(decoded.half(), Half::Upper);
+        assert!(decoded.is_open());
+    }
+
+    #[test]
+    fn properties_are_independent() {
+        let door = BlockState::new(BlockType::Door).with_open(true).with_facing(Facing::West);
+        assert!(door.is_open());
+        assert_eq!(door.half(), Half::Lower);
+        let closed = door.with_open(false);
+        assert_eq!(closed.facing(), Facing::West);
+        assert!(!closed.is_open());
+    }
+
+    #[test]
+    fn missing_property_is_ignored() {
+        let stone = BlockState::new(BlockType::Stone).with_facing(Facing::South).with_age(2);
+        assert_eq!(stone, BlockState::new(BlockType::Stone));
+        assert_eq!(stone.facing(), Facing::North);
+    }
+
+    #[test]
+    fn age_is_clamped_to_max() {
+        let wheat = BlockState::new(BlockType::WheatCrop).with_age(200);
+        assert_eq!(wheat.age(), WHEAT_MAX_AGE);
+    }
+
+    #[test]
+    fn from_raw_clears_unused_bits() {
+        let raw = BlockType::Stone as u16 | 0xFF00;
+        assert_eq!(BlockState::from_raw(raw), BlockState::new(BlockType::Stone));
+    }
+
+    #[test]
+    fn legacy_ids_map_to_states() {
+        let top_open = BlockState::from_legacy_id(31);
+        assert_eq!(top_open.block(), BlockType::Door);
+        assert_eq!(top_open.half(), Half::Upper);
+        assert!(top_open.is_open());
+        assert_eq!(BlockState::from_legacy_id(37).age(), 2);
+        assert_eq!(BlockState::from_legacy_id(1), BlockState::new(BlockType::Stone));
+    }
+
+    #[test]
+    fn water_levels() {
+        let water = BlockState::new(BlockType::Water);
+        assert!(water.is_fluid_source());
+        let flowing = water.with_fluid_level(3);
+        assert_eq!(flowing.fluid_level(), 3);
+        assert!(!flowing.is_fluid_source());
+        assert_eq!(water.with_fluid_level(200).fluid_level(), MAX_FLUID_LEVEL);
+        let falling = water.with_falling(true);
+        assert!(falling.is_falling() && !falling.is_fluid_source());
+        assert_eq!(BlockState::from_raw(falling.to_raw()), falling);
+    }
+
+    #[test]
+    fn open_door_is_not_solid() {
+        let door = BlockState::new(BlockType::Door);
+        assert!(door.is_solid());
+        assert!(!door.with_open(true).is_solid());
+    }
+
+    #[test]
+    fn wheat_drops_depend_on_age() {
+        use crate::inventory::item::Item;
+        let wheat = BlockState::new(BlockType::WheatCrop);
+        assert_eq!(wheat.drop_item(), Some(Item::Seeds));
+        assert_eq!(wheat.with_age(WHEAT_MAX_AGE).drop_item(), Some(Item::Wheat));
+    }
+}
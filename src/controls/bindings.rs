@@ -0,0 +1,427 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use bevy::prelude::*;
+
+/// Key bindings file, next to `saves/` and `resourcepacks/`.
+pub const CONTROLS_FILE: &str = "controls.json";
+
+/// Something the player does, bound to a key or mouse button. Escape isn't an action:
+/// it always backs out of screens, so a bad binding can't lock anyone out of the menus.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Action {
+    Forward,
+    Back,
+    Left,
+    Right,
+    Jump,
+    Sneak,
+    Sprint,
+    /// Break blocks and hit mobs.
+    Attack,
+    /// Place blocks, open containers, eat and use items.
+    Use,
+    Inventory,
+    Hotbar1,
+    Hotbar2,
+    Hotbar3,
+    Hotbar4,
+    Hotbar5,
+    Hotbar6,
+    Hotbar7,
+    Hotbar8,
+    Hotbar9,
+    QuickSave,
+    DebugMap,
+    CaveCulling,
+}
+
+impl Action {
+    /// Every action, in the order the controls screen lists them.
+    pub const ALL: [Action; 22] = [
+        Action::Forward,
+        Action::Back,
+        Action::Left,
+        Action::Right,
+        Action::Jump,
+        Action::Sneak,
+        Action::Sprint,
+        Action::Attack,
+        Action::Use,
+        Action::Inventory,
+        Action::Hotbar1,
+        Action::Hotbar2,
+        Action::Hotbar3,
+        Action::Hotbar4,
+        Action::Hotbar5,
+        Action::Hotbar6,
+        Action::Hotbar7,
+        Action::Hotbar8,
+        Action::Hotbar9,
+        Action::QuickSave,
+        Action::DebugMap,
+        Action::CaveCulling,
+    ];
+
+    /// Hotbar slot selection, by slot.
+    pub const HOTBAR: [Action; 9] = [
+        Action::Hotbar1,
+        Action::Hotbar2,
+        Action::Hotbar3,
+        Action::Hotbar4,
+        Action::Hotbar5,
+        Action::Hotbar6,
+        Action::Hotbar7,
+        Action::Hotbar8,
+        Action::Hotbar9,
+    ];
+
+    /// Name in the controls file.
+    pub fn id(self) -> &'static str {
+        match self {
+            Self::Forward => "forward",
+            Self::Back => "back",
+            Self::Left => "left",
+            Self::Right => "right",
+            Self::Jump => "jump",
+            Self::Sneak => "sneak",
+            Self::Sprint => "sprint",
+            Self::Attack => "attack",
+            Self::Use => "use",
+            Self::Inventory => "inventory",
+            Self::Hotbar1 => "hotbar_1",
+            Self::Hotbar2 => "hotbar_2",
+            Self::Hotbar3 => "hotbar_3",
+            Self::Hotbar4 => "hotbar_4",
+            Self::Hotbar5 => "hotbar_5",
+            Self::Hotbar6 => "hotbar_6",
+            Self::Hotbar7 => "hotbar_7",
+            Self::Hotbar8 => "hotbar_8",
+            Self::Hotbar9 => "hotbar_9",
+            Self::QuickSave => "quick_save",
+            Self::DebugMap => "debug_map",
+            Self::CaveCulling => "cave_culling",
+        }
+    }
+
+    fn from_id(id: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|action| action.id() == id)
+    }
+
+    pub fn display_name(self) -> &'static str {
+        match self {
+            Self::Forward => "Walk Forward",
+            Self::Back => "Walk Backward",
+            Self::Left => "Strafe Left",
+            Self::Right => "Strafe Right",
+            Self::Jump => "Jump / Swim Up",
+            Self::Sneak => "Sneak / Swim Down",
+            Self::Sprint => "Sprint",
+            Self::Attack => "Attack / Break",
+            Self::Use => "Use / Place",
+            Self::Inventory => "Inventory",
+            Self::Hotbar1 => "Hotbar Slot 1",
+            Self::Hotbar2 => "Hotbar Slot 2",
+            Self::Hotbar3 => "Hotbar Slot 3",
+            Self::Hotbar4 => "Hotbar Slot 4",
+            Self::Hotbar5 => "Hotbar Slot 5",
+            Self::Hotbar6 => "Hotbar Slot 6",
+            Self::Hotbar7 => "Hotbar Slot 7",
+            Self::Hotbar8 => "Hotbar Slot 8",
+            Self::Hotbar9 => "Hotbar Slot 9",
+            Self::QuickSave => "Save World",
+            Self::DebugMap => "Debug Map",
+            Self::CaveCulling => "Toggle Cave Culling",
+        }
+    }
+
+    pub fn default_binding(self) -> Binding {
+        match self {
+            Self::Forward => Binding::key(KeyCode::KeyW),
+            Self::Back => Binding::key(KeyCode::KeyS),
+            Self::Left => Binding::key(KeyCode::KeyA),
+            Self::Right => Binding::key(KeyCode::KeyD),
+            Self::Jump => Binding::key(KeyCode::Space),
+            Self::Sneak => Binding::key(KeyCode::ShiftLeft),
+            Self::Sprint => Binding::key(KeyCode::ControlLeft),
+            Self::Attack => Binding::mouse(MouseButton::Left),
+            Self::Use => Binding::mouse(MouseButton::Right),
+            Self::Inventory => Binding::key(KeyCode::KeyE),
+            Self::Hotbar1 => Binding::key(KeyCode::Digit1),
+            Self::Hotbar2 => Binding::key(KeyCode::Digit2),
+            Self::Hotbar3 => Binding::key(KeyCode::Digit3),
+            Self::Hotbar4 => Binding::key(KeyCode::Digit4),
+            Self::Hotbar5 => Binding::key(KeyCode::Digit5),
+            Self::Hotbar6 => Binding::key(KeyCode::Digit6),
+            Self::Hotbar7 => Binding::key(KeyCode::Digit7),
+            Self::Hotbar8 => Binding::key(KeyCode::Digit8),
+            Self::Hotbar9 => Binding::key(KeyCode::Digit9),
+            Self::QuickSave => Binding { ctrl: true, ..Binding::key(KeyCode::KeyS) },
+            Self::DebugMap => Binding::key(KeyCode::F4),
+            Self::CaveCulling => Binding::key(KeyCode::F7),
+        }
+    }
+}
+
+/// A key or a mouse button.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InputButton {
+    Key(KeyCode),
+    Mouse(MouseButton),
+}
+
+/// What an action is bound to: a key or mouse button, optionally with Ctrl held.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Binding {
+    pub button: InputButton,
+    /// Only counts while Ctrl is held (Ctrl+S). Other bindings don't care about Ctrl,
+    /// so sprinting doesn't stop the player walking.
+    pub ctrl: bool,
+}
+
+/// Keys that can be bound: name in the controls file (Bevy's), and label on screen.
+macro_rules! bindable_keys {
+    ($($key:ident => $label:literal),* $(,)?) => {
+        const KEYS: &[(KeyCode, &str, &str)] = &[$((KeyCode::$key, stringify!($key), $label)),*];
+    };
+}
+
+bindable_keys! {
+    KeyA => "A", KeyB => "B", KeyC => "C", KeyD => "D", KeyE => "E", KeyF => "F", KeyG => "G",
+    KeyH => "H", KeyI => "I", KeyJ => "J", KeyK => "K", KeyL => "L", KeyM => "M", KeyN => "N",
+    KeyO => "O", KeyP => "P", KeyQ => "Q", KeyR => "R", KeyS => "S", KeyT => "T", KeyU => "U",
+    KeyV => "V", KeyW => "W", KeyX => "X", KeyY => "Y", KeyZ => "Z",
+    Digit0 => "0", Digit1 => "1", Digit2 => "2", Digit3 => "3", Digit4 => "4",
+    Digit5 => "5", Digit6 => "6", Digit7 => "7", Digit8 => "8", Digit9 => "9",
+    F1 => "F1", F2 => "F2", F3 => "F3", F4 => "F4", F5 => "F5", F6 => "F6",
+    F7 => "F7", F8 => "F8", F9 => "F9", F10 => "F10", F11 => "F11", F12 => "F12",
+    Space => "Space", Enter => "Enter", Tab => "Tab", Backspace => "Backspace", CapsLock => "Caps Lock",
+    ShiftLeft => "Left Shift", ShiftRight => "Right Shift",
+    ControlLeft => "Left Ctrl", ControlRight => "Right Ctrl",
+    AltLeft => "Left Alt", AltRight => "Right Alt",
+    ArrowUp => "Up", ArrowDown => "Down", ArrowLeft => "Left", ArrowRight => "Right",
+    Insert => "Insert", Delete => "Delete", Home => "Home", End => "End",
+    PageUp => "Page Up", PageDown => "Page Down",
+    Minus => "-", Equal => "=", BracketLeft => "[", BracketRight => "]", Backslash => "\\",
+    Semicolon => ";", Quote => "'", Backquote => "`", Comma => ",", Period => ".", Slash => "/",
+    Numpad0 => "Numpad 0", Numpad1 => "Numpad 1", Numpad2 => "Numpad 2", Numpad3 => "Numpad 3",
+    Numpad4 => "Numpad 4", Numpad5 => "Numpad 5", Numpad6 => "Numpad 6", Numpad7 => "Numpad 7",
+    Numpad8 => "Numpad 8", Numpad9 => "Numpad 9",
+}
+
+/// Mouse buttons that can be bound, as for `KEYS`.
+const MOUSE_BUTTONS: &[(MouseButton, &str, &str)] = &[
+    (MouseButton::Left, "Left", "Left Click"),
+    (MouseButton::Right, "Right", "Right Click"),
+    (MouseButton::Middle, "Middle", "Middle Click"),
+    (MouseButton::Back, "Back", "Mouse Back"),
+    (MouseButton::Forward, "Forward", "Mouse Forward"),
+];
+
+const MOUSE_PREFIX: &str = "Mouse:";
+const CTRL_PREFIX: &str = "Ctrl+";
+
+fn ctrl_held(keys: &ButtonInput<KeyCode>) -> bool {
+    keys.pressed(KeyCode::ControlLeft) || keys.pressed(KeyCode::ControlRight)
+}
+
+impl Binding {
+    pub const fn key(key: KeyCode) -> Self {
+        Self { button: InputButton::Key(key), ctrl: false }
+    }
+
+    pub const fn mouse(button: MouseButton) -> Self {
+        Self { button: InputButton::Mouse(button), ctrl: false }
+    }
+
+    /// Whether a key can be bound at all.
+    pub fn is_bindable_key(key: KeyCode) -> bool {
+        KEYS.iter().any(|&(k, _, _)| k == key)
+    }
+
+    /// Whether a mouse button can be bound at all.
+    pub fn is_bindable_mouse(button: MouseButton) -> bool {
+        MOUSE_BUTTONS.iter().any(|&(b, _, _)| b == button)
+    }
+
+    /// Text form in the controls file: `KeyW`, `Mouse:Left`, `Ctrl+KeyS`.
+    pub fn to_text(self) -> String {
+        let button = match self.button {
+            InputButton::Key(key) => KEYS.iter().find(|&&(k, _, _)| k == key).map(|&(_, name, _)| name.to_string()),
+            InputButton::Mouse(button) => MOUSE_BUTTONS
+                .iter()
+                .find(|&&(b, _, _)| b == button)
+                .map(|&(_, name, _)| format!("{MOUSE_PREFIX}{name}")),
+        };
+        let button = button.unwrap_or_default();
+        if self.ctrl { format!("{CTRL_PREFIX}{button}") } else { button }
+    }
+
+    /// Parse the text form; `None` for keys and buttons that can't be bound.
+    pub fn from_text(text: &str) -> Option<Self> {
+        let (ctrl, text) = match text.strip_prefix(CTRL_PREFIX) {
+            Some(rest) => (true, rest),
+            None => (false, text),
+        };
+        let button = match text.strip_prefix(MOUSE_PREFIX) {
+            Some(name) => MOUSE_BUTTONS.iter().find(|&&(_, n, _)| n == name).map(|&(b, _, _)| InputButton::Mouse(b))?,
+            None => KEYS.iter().find(|&&(_, n, _)| n == text).map(|&(k, _, _)| InputButton::Key(k))?,
+        };
+        Some(Self { button, ctrl })
+    }
+
+    /// Short label for the controls screen: `W`, `Left Click`, `Ctrl+S`.
+    pub fn label(self) -> String {
+        let label = match self.button {
+            InputButton::Key(key) => KEYS.iter().find(|&&(k, _, _)| k == key).map_or("?", |&(_, _, label)| label),
+            InputButton::Mouse(button) => {
+                MOUSE_BUTTONS.iter().find(|&&(b, _, _)| b == button).map_or("?", |&(_, _, label)| label)
+            }
+        };
+        if self.ctrl { format!("{CTRL_PREFIX}{label}") } else { label.to_string() }
+    }
+
+    pub fn pressed(self, keys: &ButtonInput<KeyCode>, mouse: &ButtonInput<MouseButton>) -> bool {
+        let down = match self.button {
+            InputButton::Key(key) => keys.pressed(key),
+            InputButton::Mouse(button) => mouse.pressed(button),
+        };
+        down && (!self.ctrl || ctrl_held(keys))
+    }
+
+    pub fn just_pressed(self, keys: &ButtonInput<KeyCode>, mouse: &ButtonInput<MouseButton>) -> bool {
+        let down = match self.button {
+            InputButton::Key(key) => keys.just_pressed(key),
+            InputButton::Mouse(button) => mouse.just_pressed(button),
+        };
+        down && (!self.ctrl || ctrl_held(keys))
+    }
+}
+
+/// The binding of every action, loaded from and saved to the controls file.
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct Bindings(BTreeMap<Action, Binding>);
+
+impl Default for Bindings {
+    fn default() -> Self {
+        Self(Action::ALL.into_iter().map(|action| (action, action.default_binding())).collect())
+    }
+}
+
+impl Bindings {
+    pub fn get(&self, action: Action) -> Binding {
+        self.0.get(&action).copied().unwrap_or_else(|| action.default_binding())
+    }
+
+    pub fn set(&mut self, action: Action, binding: Binding) {
+        self.0.insert(action, binding);
+    }
+
+    /// Other actions bound to the same input as `action`.
+    pub fn conflicts(&self, action: Action) -> Vec<Action> {
+        let binding = self.get(action);
+        Action::ALL.into_iter().filter(|&other| other != action && self.get(other) == binding).collect()
+    }
+
+    pub fn to_json(&self) -> String {
+        let entries: BTreeMap<&str, String> =
+            Action::ALL.into_iter().map(|action| (action.id(), self.get(action).to_text())).collect();
+        serde_json::to_string_pretty(&entries).unwrap_or_default()
+    }
+
+    /// Read bindings from the controls file's JSON. Actions missing from it, or bound to
+    /// something unrecognized, keep their defaults.
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        let entries: BTreeMap<String, String> = serde_json::from_str(json)?;
+        let mut bindings = Self::default();
+        for (id, text) in entries {
+            match (Action::from_id(&id), Binding::from_text(&text)) {
+                (Some(action), Some(binding)) => bindings.set(action, binding),
+                _ => warn!("Ignoring unknown binding {} = {}", id, text),
+            }
+        }
+        Ok(bindings)
+    }
+}
+
+/// Bindings from the controls file, or the defaults when there isn't a readable one.
+pub fn load_bindings(path: &Path) -> Bindings {
+    let Ok(json) = fs::read_to_string(path) else {
+        return Bindings::default();
+    };
+    Bindings::from_json(&json).unwrap_or_else(|e| {
+        warn!("Failed to read {}: {}", path.display(), e);
+        Bindings::default()
+    })
+}
+
+pub fn save_bindings(path: &Path, bindings: &Bindings) -> Result<(), Box<dyn std::error::Error>> {
+    fs::write(path, bindings.to_json())?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn defaults_do_not_conflict() {
+        let bindings = Bindings::default();
+        for action in Action::ALL {
+            assert!(bindings.conflicts(action).is_empty(), "{action:?} conflicts by default");
+        }
+    }
+
+    #[test]
+    fn binding_text_roundtrips() {
+        for action in Action::ALL {
+            let binding = action.default_binding();
+            assert_eq!(Binding::from_text(&binding.to_text()), Some(binding));
+        }
+        assert_eq!(Binding::from_text("Mouse:Middle"), Some(Binding::mouse(MouseButton::Middle)));
+        assert_eq!(Action::QuickSave.default_binding().to_text(), "Ctrl+KeyS");
+        assert_eq!(Action::QuickSave.default_binding().label(), "Ctrl+S");
+        assert_eq!(Binding::from_text("KeyWW"), None);
+        assert_eq!(Binding::from_text("Mouse:Thumb"), None);
+    }
+
+    #[test]
+    fn shared_bindings_conflict() {
+        let mut bindings = Bindings::default();
+        bindings.set(Action::Jump, Binding::key(KeyCode::KeyW));
+        assert_eq!(bindings.conflicts(Action::Jump), vec![Action::Forward]);
+        assert_eq!(bindings.conflicts(Action::Forward), vec![Action::Jump]);
+        // Ctrl+S is a different binding from S
+        assert!(bindings.conflicts(Action::Back).is_empty());
+    }
+
+    #[test]
+    fn controls_file_keeps_defaults_for_missing_and_unknown_entries() {
+        let json = r#"{ "jump": "KeyJ", "sneak": "Nonsense", "fly": "KeyF", "use": "Mouse:Middle" }"#;
+        let bindings = Bindings::from_json(json).unwrap();
+        assert_eq!(bindings.get(Action::Jump), Binding::key(KeyCode::KeyJ));
+        assert_eq!(bindings.get(Action::Use), Binding::mouse(MouseButton::Middle));
+        assert_eq!(bindings.get(Action::Sneak), Action::Sneak.default_binding());
+        assert_eq!(bindings.get(Action::Forward), Action::Forward.default_binding());
+
+        assert_eq!(Bindings::from_json(&bindings.to_json()).unwrap(), bindings);
+        assert!(Bindings::from_json("not json").is_err());
+    }
+
+    #[test]
+    fn ctrl_bindings_need_ctrl_held() {
+        let mut keys = ButtonInput::<KeyCode>::default();
+        let mouse = ButtonInput::<MouseButton>::default();
+        let bindings = Bindings::default();
+        keys.press(KeyCode::KeyS);
+        assert!(bindings.get(Action::Back).just_pressed(&keys, &mouse));
+        assert!(!bindings.get(Action::QuickSave).just_pressed(&keys, &mouse));
+
+        // Holding Ctrl saves, and doesn't stop other bindings
+        keys.press(KeyCode::ControlLeft);
+        assert!(bindings.get(Action::QuickSave).just_pressed(&keys, &mouse));
+        assert!(bindings.get(Action::Back).pressed(&keys, &mouse));
+        assert!(bindings.get(Action::Sprint).pressed(&keys, &mouse));
+    }
+}
@@ -0,0 +1,80 @@
+use bevy::prelude::*;
+
+use super::bindings::Action;
+
+/// Stick travel ignored around the centre, so worn sticks don't drift.
+pub const MOVE_DEADZONE: f32 = 0.15;
+pub const LOOK_DEADZONE: f32 = 0.12;
+
+/// Look speed at full tilt, in radians per second.
+pub const LOOK_SPEED: f32 = 3.5;
+/// Exponent of the look curve: small tilts aim finely, full tilt turns fast.
+pub const LOOK_CURVE: f32 = 2.2;
+
+/// Virtual cursor speed at full tilt, in logical pixels per second.
+pub const CURSOR_SPEED: f32 = 900.0;
+pub const CURSOR_CURVE: f32 = 1.8;
+
+/// A stick's position after the deadzone and response curve. The deadzone is radial and
+/// the remaining travel is rescaled to 0..1, so output starts from zero at its edge
+/// instead of jumping; the curve then raises the magnitude to `exponent`, keeping the
+/// direction.
+pub fn stick_response(stick: Vec2, deadzone: f32, exponent: f32) -> Vec2 {
+    let tilt = stick.length().min(1.0);
+    if tilt <= deadzone {
+        return Vec2::ZERO;
+    }
+    let travel = (tilt - deadzone) / (1.0 - deadzone);
+    stick.normalize() * travel.powf(exponent)
+}
+
+/// Controller button for an action, in the usual layout for block games: triggers break
+/// and place, stick clicks sprint and sneak. Hotbar slots are picked with the bumpers
+/// instead (`ui::hotbar`), and the debug keys stay on the keyboard.
+pub fn gamepad_button(action: Action) -> Option<GamepadButton> {
+    match action {
+        Action::Jump => Some(GamepadButton::South),
+        Action::Sneak => Some(GamepadButton::RightThumb),
+        Action::Sprint => Some(GamepadButton::LeftThumb),
+        Action::Attack => Some(GamepadButton::RightTrigger2),
+        Action::Use => Some(GamepadButton::LeftTrigger2),
+        Action::Inventory => Some(GamepadButton::North),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn deadzone_swallows_drift_and_rescales_the_rest() {
+        assert_eq!(stick_response(Vec2::new(0.1, -0.05), MOVE_DEADZONE, 1.0), Vec2::ZERO);
+        // Just past the deadzone is barely moving, full tilt is full speed
+        let edge = stick_response(Vec2::new(MOVE_DEADZONE + 0.01, 0.0), MOVE_DEADZONE, 1.0);
+        assert!(edge.x > 0.0 && edge.x < 0.02);
+        assert!((stick_response(Vec2::new(0.0, -1.0), MOVE_DEADZONE, 1.0) - Vec2::new(0.0, -1.0)).length() < 1e-5);
+        // Corners of square gates don't go past full speed
+        assert!((stick_response(Vec2::ONE, MOVE_DEADZONE, 1.0).length() - 1.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn look_curve_is_gentle_near_the_centre() {
+        let half = stick_response(Vec2::new(0.5, 0.0), LOOK_DEADZONE, LOOK_CURVE).x;
+        let linear = stick_response(Vec2::new(0.5, 0.0), LOOK_DEADZONE, 1.0).x;
+        assert!(half < linear * 0.5);
+        // Direction is kept
+        let diagonal = stick_response(Vec2::new(-0.6, 0.6), LOOK_DEADZONE, LOOK_CURVE);
+        assert!((diagonal.x + diagonal.y).abs() < 1e-5 && diagonal.x < 0.0);
+    }
+
+    #[test]
+    fn controller_buttons_are_distinct() {
+        let buttons: Vec<GamepadButton> = Action::ALL.into_iter().filter_map(gamepad_button).collect();
+        for (i, button) in buttons.iter().enumerate() {
+            assert!(!buttons[i + 1..].contains(button), "{button:?} bound twice");
+        }
+        assert_eq!(gamepad_button(Action::Attack), Some(GamepadButton::RightTrigger2));
+        assert_eq!(gamepad_button(Action::Hotbar1), None);
+    }
+}
@@ -0,0 +1,176 @@
+pub mod bindings;
+pub mod gamepad;
+
+use bevy::prelude::*;
+use std::path::Path;
+
+pub use bindings::{Action, Binding, Bindings};
+use bindings::{load_bindings, save_bindings, CONTROLS_FILE};
+use gamepad::{gamepad_button, stick_response, MOVE_DEADZONE};
+
+/// Whether a controller is steering the mouse pointer on an inventory screen
+/// (`ui::gamepad_cursor`), where its buttons click instead of acting in the world.
+#[derive(Resource, Default)]
+pub struct GamepadCursor {
+    pub active: bool,
+}
+
+/// Input read through the player's key bindings and any connected controllers, for
+/// systems that respond to actions rather than to particular keys or buttons.
+#[derive(bevy::ecs::system::SystemParam)]
+pub struct ActionInput<'w, 's> {
+    pub bindings: Res<'w, Bindings>,
+    keys: Res<'w, ButtonInput<KeyCode>>,
+    mouse: Res<'w, ButtonInput<MouseButton>>,
+    gamepads: Query<'w, 's, &'static Gamepad>,
+    cursor: Res<'w, GamepadCursor>,
+}
+
+impl ActionInput<'_, '_> {
+    pub fn pressed(&self, action: Action) -> bool {
+        self.bindings.get(action).pressed(&self.keys, &self.mouse) || self.gamepad(action, Gamepad::pressed)
+    }
+
+    pub fn just_pressed(&self, action: Action) -> bool {
+        self.bindings.get(action).just_pressed(&self.keys, &self.mouse) || self.gamepad(action, Gamepad::just_pressed)
+    }
+
+    fn gamepad(&self, action: Action, check: fn(&Gamepad, GamepadButton) -> bool) -> bool {
+        // Clicking with the virtual cursor mustn't jump or swim; only closing the screen counts
+        if self.cursor.active && action != Action::Inventory {
+            return false;
+        }
+        gamepad_button(action).is_some_and(|button| self.gamepads.iter().any(|g| check(g, button)))
+    }
+
+    /// Walking direction relative to the view, x to the right and y forward, up to 1 long.
+    /// Keys walk at full speed; the left stick walks slower the less it's tilted.
+    pub fn movement(&self) -> Vec2 {
+        let axis = |positive, negative| self.pressed(positive) as i32 as f32 - self.pressed(negative) as i32 as f32;
+        let keys = Vec2::new(axis(Action::Right, Action::Left), axis(Action::Forward, Action::Back));
+        let keys = keys.normalize_or_zero();
+        let stick = self.gamepads.iter().map(|g| stick_response(g.left_stick(), MOVE_DEADZONE, 1.0)).sum::<Vec2>();
+        (keys + stick).clamp_length_max(1.0)
+    }
+}
+
+fn load_controls(mut commands: Commands) {
+    commands.insert_resource(load_bindings(Path::new(CONTROLS_FILE)));
+}
+
+/// Write the controls file whenever a binding is changed.
+fn save_changed_controls(bindings: Res<Bindings>) {
+    if !bindings.is_changed() || bindings.is_added() {
+        return;
+    }
+    if let Err(e) = save_bindings(Path::new(CONTROLS_FILE), &bindings) {
+        warn!("Failed to save {}: {}", CONTROLS_FILE, e);
+    }
+}
+
+pub struct ControlsPlugin;
+
+impl Plugin for ControlsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GamepadCursor>()
+            .add_systems(PreStartup, load_controls)
+            .add_systems(Update, save_changed_controls);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::input::gamepad::{
+        GamepadAxis, GamepadConnection, GamepadConnectionEvent, RawGamepadAxisChangedEvent,
+        RawGamepadButtonChangedEvent, RawGamepadEvent,
+    };
+    use bevy::input::InputPlugin;
+
+    /// What the player systems would see this frame.
+    #[derive(Resource, Default)]
+    struct Seen {
+        movement: Vec2,
+        jump: bool,
+        attack: bool,
+        inventory: bool,
+    }
+
+    fn record(actions: ActionInput, mut seen: ResMut<Seen>) {
+        *seen = Seen {
+            movement: actions.movement(),
+            jump: actions.pressed(Action::Jump),
+            attack: actions.just_pressed(Action::Attack),
+            inventory: actions.pressed(Action::Inventory),
+        };
+    }
+
+    /// A headless app with one controller connected, fed raw gamepad events the way the
+    /// gilrs backend sends them.
+    fn app_with_gamepad() -> (App, Entity) {
+        let mut app = App::new();
+        app.add_plugins(InputPlugin)
+            .init_resource::<Bindings>()
+            .init_resource::<GamepadCursor>()
+            .init_resource::<Seen>()
+            .add_systems(Update, record);
+        let gamepad = app.world_mut().spawn_empty().id();
+        app.world_mut().write_message(GamepadConnectionEvent::new(
+            gamepad,
+            GamepadConnection::Connected { name: "Test Pad".to_string(), vendor_id: None, product_id: None },
+        ));
+        app.update();
+        (app, gamepad)
+    }
+
+    fn move_axis(app: &mut App, gamepad: Entity, axis: GamepadAxis, value: f32) {
+        let event = RawGamepadAxisChangedEvent::new(gamepad, axis, value);
+        app.world_mut().write_message(RawGamepadEvent::Axis(event));
+        app.update();
+    }
+
+    fn press_button(app: &mut App, gamepad: Entity, button: GamepadButton, value: f32) {
+        let event = RawGamepadButtonChangedEvent::new(gamepad, button, value);
+        app.world_mut().write_message(RawGamepadEvent::Button(event));
+        app.update();
+    }
+
+    #[test]
+    fn left_stick_walks_with_analog_speed() {
+        let (mut app, gamepad) = app_with_gamepad();
+        move_axis(&mut app, gamepad, GamepadAxis::LeftStickY, 0.5);
+        let movement = app.world().resource::<Seen>().movement;
+        assert!(movement.x.abs() < 1e-5);
+        assert!(movement.y > 0.3 && movement.y < 0.5, "half tilt walks slower, got {movement}");
+
+        // Drift inside the deadzone doesn't walk
+        move_axis(&mut app, gamepad, GamepadAxis::LeftStickY, 0.05);
+        assert_eq!(app.world().resource::<Seen>().movement, Vec2::ZERO);
+    }
+
+    #[test]
+    fn buttons_and_triggers_fire_actions() {
+        let (mut app, gamepad) = app_with_gamepad();
+        press_button(&mut app, gamepad, GamepadButton::South, 1.0);
+        assert!(app.world().resource::<Seen>().jump);
+
+        // A trigger counts once it's pulled most of the way
+        press_button(&mut app, gamepad, GamepadButton::RightTrigger2, 0.3);
+        assert!(!app.world().resource::<Seen>().attack);
+        press_button(&mut app, gamepad, GamepadButton::RightTrigger2, 0.9);
+        assert!(app.world().resource::<Seen>().attack);
+        app.update();
+        assert!(!app.world().resource::<Seen>().attack);
+    }
+
+    #[test]
+    fn virtual_cursor_keeps_buttons_out_of_the_world() {
+        let (mut app, gamepad) = app_with_gamepad();
+        app.world_mut().resource_mut::<GamepadCursor>().active = true;
+        press_button(&mut app, gamepad, GamepadButton::South, 1.0);
+        press_button(&mut app, gamepad, GamepadButton::North, 1.0);
+        let seen = app.world().resource::<Seen>();
+        assert!(!seen.jump);
+        assert!(seen.inventory, "the inventory button still closes the screen");
+    }
+}
@@ -1,13 +1,16 @@
 use bevy::asset::RenderAssetUsages;
 use bevy::mesh::{Indices, PrimitiveTopology};
+use bevy::ecs::message::MessageReader;
 use bevy::prelude::*;
 use rand::Rng;
 
-use crate::block::atlas::{texture_index, tile_uvs};
-use crate::block::{BlockType, Face};
+use crate::block::atlas::tile_uvs;
+use crate::block::{BlockState, BlockType, Face};
 use crate::inventory::inventory::Inventory;
 use crate::inventory::item::Item;
 use crate::player::Player;
+use crate::resource_pack::{AtlasRebuilt, BlockAtlas};
+use crate::world::WorldHeight;
 
 /// How long a dropped item lives before despawning (5 minutes like vanilla).
 const DESPAWN_TIME: f32 = 300.0;
@@ -21,6 +24,15 @@ const PICKUP_DELAY: f32 = 0.5;
 /// Gravity for dropped items.
 const ITEM_GRAVITY: f32 = -20.0;
 
+/// Items in water rise to the surface at this speed and bob there.
+const ITEM_FLOAT_SPEED: f32 = 1.0;
+
+/// How fast flowing water carries items along, in blocks per second.
+const ITEM_CURRENT_SPEED: f32 = 1.2;
+
+/// How quickly water brings an item's speed round to the float and the current, per second.
+const WATER_DRAG: f32 = 3.0;
+
 /// Size of the dropped item cube.
 const ITEM_SIZE: f32 = 0.25;
 
@@ -61,13 +73,12 @@ pub fn setup_dropped_item_assets(
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
     mut materials: ResMut<Assets<StandardMaterial>>,
-    asset_server: Res<AssetServer>,
+    atlas: Res<BlockAtlas>,
 ) {
     let mesh = meshes.add(Cuboid::new(ITEM_SIZE, ITEM_SIZE, ITEM_SIZE));
 
-    let atlas_tex: Handle<Image> = asset_server.load("textures/atlas.png");
     let atlas_material = materials.add(StandardMaterial {
-        base_color_texture: Some(atlas_tex),
+        base_color_texture: Some(atlas.image.clone()),
         perceptual_roughness: 1.0,
         ..default()
     });
@@ -127,6 +138,17 @@ pub fn setup_dropped_item_assets(
     });
 }
 
+/// Mark the block item material changed after the atlas image is rebuilt.
+pub fn refresh_dropped_item_atlas(
+    mut rebuilt: MessageReader<AtlasRebuilt>,
+    assets: Res<DroppedItemAssets>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    if rebuilt.read().count() > 0 {
+        materials.get_mut(&assets.atlas_material);
+    }
+}
+
 /// Returns a colored material handle for the given item type.
 fn material_for_item(item: Item, assets: &DroppedItemAssets) -> Handle<StandardMaterial> {
     match item {
@@ -145,6 +167,7 @@ fn material_for_item(item: Item, assets: &DroppedItemAssets) -> Handle<StandardM
         Item::Leather => assets.stick_material.clone(),
         Item::Seeds => assets.seed_material.clone(),
         Item::Wheat => assets.gold_material.clone(),
+        Item::Bucket | Item::WaterBucket => assets.iron_material.clone(),
         Item::LeatherHelmet | Item::LeatherChestplate | Item::LeatherLeggings | Item::LeatherBoots => {
             assets.stick_material.clone()
         }
@@ -166,7 +189,12 @@ fn material_for_item(item: Item, assets: &DroppedItemAssets) -> Handle<StandardM
 
 /// Create a small cube mesh with UVs mapped to a specific atlas tile for a block type.
 fn block_item_mesh(block: BlockType) -> Mesh {
-    let s = ITEM_SIZE / 2.0;
+    block_cube_mesh(BlockState::new(block), ITEM_SIZE)
+}
+
+/// Create a cube mesh of the given edge length, textured like a block state.
+pub(crate) fn block_cube_mesh(state: BlockState, size: f32) -> Mesh {
+    let s = size / 2.0;
 
     // 6 faces, 4 verts each = 24 verts
     let face_data: [(Face, [[f32; 3]; 4], [f32; 3]); 6] = [
@@ -191,7 +219,7 @@ fn block_item_mesh(block: BlockType) -> Mesh {
 
     for (face, verts, normal) in &face_data {
         let idx = positions.len() as u32;
-        let ti = texture_index(block, *face);
+        let ti = state.texture_index(*face);
         let [u_min, v_min, u_max, v_max] = tile_uvs(ti);
 
         for v in verts {
@@ -253,7 +281,8 @@ pub fn spawn_dropped_item(
     ));
 }
 
-/// Apply gravity and ground collision to dropped items.
+/// Apply gravity and ground collision to dropped items. Items in water float up and
+/// drift with the current instead.
 pub fn dropped_item_physics(
     time: Res<Time>,
     store: Res<crate::world::manager::ChunkDataStore>,
@@ -267,62 +296,85 @@ pub fn dropped_item_physics(
     let half = ITEM_SIZE / 2.0;
 
     for (mut transform, mut vel, mut on_ground) in &mut items {
-        // Apply gravity
-        vel.0.y += ITEM_GRAVITY * dt;
-
-        // Horizontal drag
-        vel.0.x *= 0.95_f32.powf(dt * 20.0);
-        vel.0.z *= 0.95_f32.powf(dt * 20.0);
-
-        // Y movement
-        let new_y = transform.translation.y + vel.0.y * dt;
-        let feet_y = new_y - half;
-        let check_by = feet_y.floor() as i32;
-        let bx = transform.translation.x.floor() as i32;
-        let bz = transform.translation.z.floor() as i32;
-
-        if vel.0.y <= 0.0 && is_block_solid(&store, bx, check_by, bz) {
-            let landing_y = (check_by + 1) as f32 + half;
-            transform.translation.y = landing_y;
-            vel.0.y = 0.0;
-            on_ground.0 = true;
-        } else {
-            transform.translation.y = new_y;
-            on_ground.0 = false;
-        }
+        let cell = transform.translation.floor().as_ivec3();
+        let in_water = crate::world::fluid::state_at(&store, cell).is_some_and(|s| s.block().is_liquid());
 
-        // X movement
-        let new_x = transform.translation.x + vel.0.x * dt;
-        let check_bx = if vel.0.x > 0.0 {
-            (new_x + half).floor() as i32
+        if in_water {
+            let current = crate::world::fluid::flow_vector(cell, |p| crate::world::fluid::state_at(&store, p));
+            let target = Vec3::new(0.0, ITEM_FLOAT_SPEED, 0.0) + current * ITEM_CURRENT_SPEED;
+            vel.0 += (target - vel.0) * (WATER_DRAG * dt).min(1.0);
         } else {
-            (new_x - half).floor() as i32
-        };
-        let by = transform.translation.y.floor() as i32;
-        if is_block_solid(&store, check_bx, by, bz) {
-            vel.0.x = 0.0;
-        } else {
-            transform.translation.x = new_x;
-        }
+            // Apply gravity
+            vel.0.y += ITEM_GRAVITY * dt;
 
-        // Z movement
-        let new_z = transform.translation.z + vel.0.z * dt;
-        let check_bz = if vel.0.z > 0.0 {
-            (new_z + half).floor() as i32
-        } else {
-            (new_z - half).floor() as i32
-        };
-        let bx2 = transform.translation.x.floor() as i32;
-        if is_block_solid(&store, bx2, by, check_bz) {
-            vel.0.z = 0.0;
-        } else {
-            transform.translation.z = new_z;
+            // Horizontal drag
+            vel.0.x *= 0.95_f32.powf(dt * 20.0);
+            vel.0.z *= 0.95_f32.powf(dt * 20.0);
         }
 
+        on_ground.0 = move_with_collision(&store, &mut transform, &mut vel.0, half, dt);
+
         // No void clamp — items that fall into void get despawned by dropped_item_despawn_void
     }
 }
 
+/// Move a small axis-aligned box (half-extent `half`, centered on the transform) by its
+/// velocity, one axis at a time, stopping against solid blocks. Returns true if it landed.
+pub(crate) fn move_with_collision(
+    store: &crate::world::manager::ChunkDataStore,
+    transform: &mut Transform,
+    vel: &mut Vec3,
+    half: f32,
+    dt: f32,
+) -> bool {
+    // Y movement
+    let new_y = transform.translation.y + vel.y * dt;
+    let feet_y = new_y - half;
+    let check_by = feet_y.floor() as i32;
+    let bx = transform.translation.x.floor() as i32;
+    let bz = transform.translation.z.floor() as i32;
+
+    let landed = if vel.y <= 0.0 && is_block_solid(store, bx, check_by, bz) {
+        let landing_y = (check_by + 1) as f32 + half;
+        transform.translation.y = landing_y;
+        vel.y = 0.0;
+        true
+    } else {
+        transform.translation.y = new_y;
+        false
+    };
+
+    // X movement
+    let new_x = transform.translation.x + vel.x * dt;
+    let check_bx = if vel.x > 0.0 {
+        (new_x + half).floor() as i32
+    } else {
+        (new_x - half).floor() as i32
+    };
+    let by = transform.translation.y.floor() as i32;
+    if is_block_solid(store, check_bx, by, bz) {
+        vel.x = 0.0;
+    } else {
+        transform.translation.x = new_x;
+    }
+
+    // Z movement
+    let new_z = transform.translation.z + vel.z * dt;
+    let check_bz = if vel.z > 0.0 {
+        (new_z + half).floor() as i32
+    } else {
+        (new_z - half).floor() as i32
+    };
+    let bx2 = transform.translation.x.floor() as i32;
+    if is_block_solid(store, bx2, by, check_bz) {
+        vel.z = 0.0;
+    } else {
+        transform.translation.z = new_z;
+    }
+
+    landed
+}
+
 fn is_block_solid(store: &crate::world::manager::ChunkDataStore, x: i32, y: i32, z: i32) -> bool {
     let size = crate::world::chunk::CHUNK_SIZE as i32;
     let cx = x.div_euclid(size);
@@ -338,7 +390,7 @@ fn is_block_solid(store: &crate::world::manager::ChunkDataStore, x: i32, y: i32,
     let ly = y.rem_euclid(size) as usize;
     let lz = z.rem_euclid(size) as usize;
 
-    chunk.get(lx, ly, lz).is_solid()
+    chunk.get_state(lx, ly, lz).is_solid()
 }
 
 /// Rotate dropped items slowly for visual appeal.
@@ -360,7 +412,11 @@ pub fn pickup_dropped_items(
     player_q: Query<&Transform, With<Player>>,
     items: Query<(Entity, &Transform, &DroppedItem)>,
     mut pickup_audio: bevy::ecs::message::MessageWriter<crate::audio::ItemPickupAudio>,
+    game_mode: Res<crate::save::level::GameMode>,
 ) {
+    if !game_mode.can_interact() {
+        return;
+    }
     let Ok(player_tf) = player_q.single() else {
         return;
     };
@@ -410,13 +466,14 @@ pub fn dropped_item_despawn(
     }
 }
 
-/// Despawn dropped items that fall into the void (below Y=-20).
+/// Despawn dropped items that fall into the void (20 blocks below the world floor).
 pub fn dropped_item_despawn_void(
     mut commands: Commands,
+    height: Res<WorldHeight>,
     items: Query<(Entity, &Transform), With<DroppedItem>>,
 ) {
     for (entity, transform) in &items {
-        if transform.translation.y < -20.0 {
+        if transform.translation.y < (height.min_y - 20) as f32 {
             commands.entity(entity).despawn();
         }
     }
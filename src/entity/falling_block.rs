@@ -0,0 +1,107 @@
+use bevy::prelude::*;
+
+use crate::block::{BlockState, BlockType};
+use crate::world::fluid::state_at;
+use crate::world::manager::{mark_remesh, set_block_state_at, ChunkDataStore, ChunkManager};
+use crate::world::WorldHeight;
+
+use super::dropped_item::{block_cube_mesh, move_with_collision, spawn_dropped_item, DroppedItemAssets};
+
+/// Gravity for falling blocks (same as dropped items).
+const FALLING_GRAVITY: f32 = -20.0;
+
+/// Fastest a falling block can drop.
+const TERMINAL_VELOCITY: f32 = -40.0;
+
+/// Edge length of the falling block cube, just under a full block so it fits down one-wide holes.
+const FALLING_SIZE: f32 = 0.98;
+
+/// A sand or gravel block in mid-air, turned back into a block when it lands.
+#[derive(Component)]
+pub struct FallingBlock {
+    pub state: BlockState,
+    pub velocity: Vec3,
+}
+
+/// Whether a falling block passes through (and lands in, replacing) a cell.
+fn can_fall_into(state: BlockState) -> bool {
+    matches!(state.block(), BlockType::Air | BlockType::TallGrass) || state.block().is_liquid()
+}
+
+/// Turn unsupported sand and gravel into falling entities. Candidates are recorded
+/// by the chunk store whenever a block changes; anything stacked on top is checked
+/// once the block below it has started falling.
+pub fn start_falling_blocks(
+    mut commands: Commands,
+    mut store: ResMut<ChunkDataStore>,
+    manager: Res<ChunkManager>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    assets: Res<DroppedItemAssets>,
+) {
+    if store.gravity_checks.is_empty() {
+        return;
+    }
+    let checks: Vec<IVec3> = store.gravity_checks.drain().collect();
+    for pos in checks {
+        let Some(state) = state_at(&store, pos) else {
+            continue;
+        };
+        if !state.block().has_gravity() {
+            continue;
+        }
+        // Unloaded below counts as support, so nothing falls into missing chunks
+        if !state_at(&store, pos - IVec3::Y).is_some_and(can_fall_into) {
+            continue;
+        }
+
+        set_block_state_at(&mut store, pos, BlockState::AIR);
+        mark_remesh(pos, &manager, &mut commands);
+        commands.spawn((
+            FallingBlock { state, velocity: Vec3::ZERO },
+            Mesh3d(meshes.add(block_cube_mesh(state, FALLING_SIZE))),
+            MeshMaterial3d(assets.atlas_material.clone()),
+            Transform::from_translation(pos.as_vec3() + Vec3::splat(0.5)),
+            Visibility::default(),
+        ));
+    }
+}
+
+/// Drop falling blocks with the dropped-item collision. On landing they become a block
+/// again, or break into an item if the cell they land in is taken (a torch, a crop).
+pub fn falling_block_physics(
+    mut commands: Commands,
+    time: Res<Time>,
+    height: Res<WorldHeight>,
+    mut store: ResMut<ChunkDataStore>,
+    manager: Res<ChunkManager>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    assets: Res<DroppedItemAssets>,
+    mut blocks: Query<(Entity, &mut Transform, &mut FallingBlock)>,
+) {
+    let dt = time.delta_secs();
+    let half = FALLING_SIZE / 2.0;
+
+    for (entity, mut transform, mut falling) in &mut blocks {
+        falling.velocity.y = (falling.velocity.y + FALLING_GRAVITY * dt).max(TERMINAL_VELOCITY);
+        let mut velocity = falling.velocity;
+        let landed = move_with_collision(&store, &mut transform, &mut velocity, half, dt);
+        falling.velocity = velocity;
+
+        if transform.translation.y < (height.min_y - 20) as f32 {
+            commands.entity(entity).despawn();
+            continue;
+        }
+        if !landed {
+            continue;
+        }
+
+        commands.entity(entity).despawn();
+        let cell = transform.translation.floor().as_ivec3();
+        if state_at(&store, cell).is_some_and(can_fall_into) {
+            set_block_state_at(&mut store, cell, falling.state);
+            mark_remesh(cell, &manager, &mut commands);
+        } else if let Some(item) = falling.state.drop_item() {
+            spawn_dropped_item(&mut commands, &mut meshes, &assets, item, 1, transform.translation);
+        }
+    }
+}
@@ -4,9 +4,12 @@ use rand::Rng;
 use crate::block::BlockType;
 use crate::lighting::day_night::DayNightCycle;
 use crate::player::{Health, Player};
+use crate::save::level::GameMode;
 use crate::world::chunk::CHUNK_SIZE;
 use crate::world::coordinates::{world_to_chunk_pos, world_to_local_pos};
+use crate::world::fluid::{flow_vector, state_at};
 use crate::world::manager::ChunkDataStore;
+use crate::world::WorldHeight;
 
 const MAX_MOBS: usize = 15;
 const MAX_HOSTILE_MOBS: usize = 10;
@@ -14,6 +17,13 @@ const SPAWN_INTERVAL: f32 = 2.5;
 const DESPAWN_DISTANCE: f32 = 120.0;
 const GRAVITY: f32 = -20.0;
 const MOB_SPEED: f32 = 2.0;
+/// Mobs paddle up through water at this speed and bob at the surface, where their
+/// middle is just out of it. They swim at `MOB_SWIM_FACTOR` of their speed on land.
+const MOB_SWIM_SPEED: f32 = 1.5;
+const MOB_SWIM_ACCELERATION: f32 = 6.0;
+const MOB_SWIM_FACTOR: f32 = 0.5;
+/// How fast flowing water carries mobs along, in blocks per second.
+const MOB_CURRENT_SPEED: f32 = 1.2;
 const HOSTILE_DETECT_RANGE: f32 = 16.0;
 const HOSTILE_LOSE_RANGE: f32 = 24.0;
 const ATTACK_RANGE: f32 = 2.0;
@@ -175,7 +185,7 @@ fn is_block_solid(store: &ChunkDataStore, x: i32, y: i32, z: i32) -> bool {
     let ly = y.rem_euclid(size) as usize;
     let lz = z.rem_euclid(size) as usize;
 
-    chunk.get(lx, ly, lz).is_solid()
+    chunk.get_state(lx, ly, lz).is_solid()
 }
 
 #[derive(Resource)]
@@ -415,14 +425,15 @@ pub fn update_mob_ai(
     time: Res<Time>,
     mut mobs: Query<(&Transform, &mut MobAI, &Mob)>,
     player: Query<&Transform, With<Player>>,
+    game_mode: Res<GameMode>,
 ) {
     let dt = time.delta_secs();
     let mut rng = rand::rng();
 
-    let player_pos = if let Ok(pt) = player.single() {
-        Some(pt.translation)
-    } else {
-        None
+    // Hostile mobs leave players alone outside survival and adventure
+    let player_pos = match player.single() {
+        Ok(pt) if game_mode.is_vulnerable() => Some(pt.translation),
+        _ => None,
     };
 
     for (transform, mut ai, mob) in &mut mobs {
@@ -497,8 +508,12 @@ pub fn hostile_attack_player(
     mut mobs: Query<(&Transform, &Mob, &mut MobAttackTimer), With<Hostile>>,
     mut player: Query<(&Transform, &mut Health, &mut crate::player::ArmorSlots), With<Player>>,
     mut hurt_audio: bevy::ecs::message::MessageWriter<crate::audio::PlayerHurtAudio>,
+    game_mode: Res<GameMode>,
 ) {
     let dt = time.delta_secs();
+    if !game_mode.is_vulnerable() {
+        return;
+    }
     let Ok((player_transform, mut player_health, mut armor)) = player.single_mut() else {
         return;
     };
@@ -607,8 +622,15 @@ pub fn move_mobs(
         let half_height = hitbox.y / 2.0;
         let half_w = hitbox.x / 2.0;
 
-        // Apply gravity
-        velocity.0.y += GRAVITY * dt;
+        let center = transform.translation.floor().as_ivec3();
+        let in_water = get_block_at(&store, center.x, center.y, center.z).is_liquid();
+
+        // Float up through water, fall through air
+        if in_water {
+            velocity.0.y = (velocity.0.y + MOB_SWIM_ACCELERATION * dt).min(velocity.0.y.max(MOB_SWIM_SPEED));
+        } else {
+            velocity.0.y += GRAVITY * dt;
+        }
 
         // Horizontal movement toward target
         let moving = matches!(ai.state, MobState::Walking | MobState::Chasing);
@@ -618,11 +640,14 @@ pub fn move_mobs(
                 let horizontal = Vec3::new(diff.x, 0.0, diff.z);
                 if horizontal.length() > 0.1 {
                     let dir = horizontal.normalize();
-                    let speed = if ai.state == MobState::Chasing {
+                    let mut speed = if ai.state == MobState::Chasing {
                         MOB_SPEED * 1.2
                     } else {
                         MOB_SPEED
                     };
+                    if in_water {
+                        speed *= MOB_SWIM_FACTOR;
+                    }
                     velocity.0.x = dir.x * speed;
                     velocity.0.z = dir.z * speed;
 
@@ -636,6 +661,13 @@ pub fn move_mobs(
             velocity.0.z = 0.0;
         }
 
+        // Drift with the current on top of walking
+        let drift = if in_water {
+            flow_vector(center, |p| state_at(&store, p)) * MOB_CURRENT_SPEED
+        } else {
+            Vec3::ZERO
+        };
+
         let pos = transform.translation;
 
         // --- Y axis collision (gravity/falling) ---
@@ -701,13 +733,13 @@ pub fn move_mobs(
         }
 
         // --- X axis collision ---
-        let new_x = transform.translation.x + velocity.0.x * dt;
+        let new_x = transform.translation.x + (velocity.0.x + drift.x) * dt;
         let proposed_x = Vec3::new(new_x, transform.translation.y, transform.translation.z);
         let mut stepped_up = false;
 
         if mob_collides_horizontal(&store, proposed_x, half_w, half_height) {
-            // Blocked on X: try step-up if on ground
-            if on_ground.0 {
+            // Blocked on X: try step-up if on ground or climbing out of water
+            if on_ground.0 || in_water {
                 let step_up_pos = Vec3::new(new_x, transform.translation.y + 1.0, transform.translation.z);
                 if !mob_collides_horizontal(&store, step_up_pos, half_w, half_height) {
                     // Can step up - move up and forward
@@ -726,12 +758,12 @@ pub fn move_mobs(
         }
 
         // --- Z axis collision ---
-        let new_z = transform.translation.z + velocity.0.z * dt;
+        let new_z = transform.translation.z + (velocity.0.z + drift.z) * dt;
         let proposed_z = Vec3::new(transform.translation.x, transform.translation.y, new_z);
 
         if mob_collides_horizontal(&store, proposed_z, half_w, half_height) {
             // Blocked on Z: try step-up if on ground and haven't already stepped up this frame
-            if on_ground.0 && !stepped_up {
+            if (on_ground.0 || in_water) && !stepped_up {
                 let step_up_pos = Vec3::new(transform.translation.x, transform.translation.y + 1.0, new_z);
                 if !mob_collides_horizontal(&store, step_up_pos, half_w, half_height) {
                     transform.translation.z = new_z;
@@ -749,13 +781,14 @@ pub fn move_mobs(
     }
 }
 
-/// Despawn mobs that fall into the void (below Y=-10).
+/// Despawn mobs that fall into the void (10 blocks below the world floor).
 pub fn despawn_void_mobs(
     mut commands: Commands,
+    height: Res<WorldHeight>,
     mobs: Query<(Entity, &Transform), With<Mob>>,
 ) {
     for (entity, transform) in &mobs {
-        if transform.translation.y < -10.0 {
+        if transform.translation.y < (height.min_y - 10) as f32 {
             commands.entity(entity).despawn();
         }
     }
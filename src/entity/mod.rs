@@ -1,4 +1,5 @@
 pub mod dropped_item;
+pub mod falling_block;
 pub mod mob;
 
 use bevy::prelude::*;
@@ -25,6 +26,9 @@ impl Plugin for EntityPlugin {
                     dropped_item::pickup_dropped_items,
                     dropped_item::dropped_item_despawn,
                     dropped_item::dropped_item_despawn_void,
+                    dropped_item::refresh_dropped_item_atlas,
+                    falling_block::start_falling_blocks,
+                    falling_block::falling_block_physics.after(falling_block::start_falling_blocks),
                 ),
             );
     }
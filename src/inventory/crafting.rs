@@ -336,9 +336,9 @@ fn recipes_3x3() -> Vec<CraftingRecipe3x3> {
         // Bed: ___ / WWW / PPP (bottom-aligned)
         CraftingRecipe3x3 { pattern: [[n, n, n], [Some(Item::Wool), Some(Item::Wool), Some(Item::Wool)], [p, p, p]], output: Item::Block(BlockType::Bed), output_count: 1 },
         // Door: PP_ / PP_ / PP_ (left-aligned)
-        CraftingRecipe3x3 { pattern: [[p, p, n], [p, p, n], [p, p, n]], output: Item::Block(BlockType::DoorBottom), output_count: 3 },
+        CraftingRecipe3x3 { pattern: [[p, p, n], [p, p, n], [p, p, n]], output: Item::Block(BlockType::Door), output_count: 3 },
         // Door: _PP / _PP / _PP (right-aligned)
-        CraftingRecipe3x3 { pattern: [[n, p, p], [n, p, p], [n, p, p]], output: Item::Block(BlockType::DoorBottom), output_count: 3 },
+        CraftingRecipe3x3 { pattern: [[n, p, p], [n, p, p], [n, p, p]], output: Item::Block(BlockType::Door), output_count: 3 },
         // Torch: Coal over Stick — center column, bottom-aligned
         CraftingRecipe3x3 { pattern: [[n, n, n], [n, Some(Item::Coal), n], [n, s, n]], output: Item::Block(BlockType::Torch), output_count: 4 },
         // Torch: Coal over Stick — left column, bottom-aligned
@@ -351,6 +351,8 @@ fn recipes_3x3() -> Vec<CraftingRecipe3x3> {
         CraftingRecipe3x3 { pattern: [[Some(Item::Coal), n, n], [s, n, n], [n, n, n]], output: Item::Block(BlockType::Torch), output_count: 4 },
         // Torch: Coal over Stick — right column, top-aligned
         CraftingRecipe3x3 { pattern: [[n, n, Some(Item::Coal)], [n, n, s], [n, n, n]], output: Item::Block(BlockType::Torch), output_count: 4 },
+        // Ladder: S_S / SSS / S_S
+        CraftingRecipe3x3 { pattern: [[s, n, s], [s, s, s], [s, n, s]], output: Item::Block(BlockType::Ladder), output_count: 3 },
         // Wooden Hoe: PP_ / _S_ / _S_
         CraftingRecipe3x3 { pattern: [[p, p, n], [n, s, n], [n, s, n]], output: Item::WoodenHoe, output_count: 1 },
         // Wooden Hoe mirrored: _PP / _S_ / _S_
@@ -400,6 +402,10 @@ fn recipes_3x3() -> Vec<CraftingRecipe3x3> {
         CraftingRecipe3x3 { pattern: [[d, n, d], [d, n, d], [n, n, n]], output: Item::DiamondBoots, output_count: 1 },
         // Diamond Boots (bottom-aligned): ___ / D_D / D_D
         CraftingRecipe3x3 { pattern: [[n, n, n], [d, n, d], [d, n, d]], output: Item::DiamondBoots, output_count: 1 },
+        // Bucket (top-aligned): I_I / _I_ / ___
+        CraftingRecipe3x3 { pattern: [[i, n, i], [n, i, n], [n, n, n]], output: Item::Bucket, output_count: 1 },
+        // Bucket (bottom-aligned): ___ / I_I / _I_
+        CraftingRecipe3x3 { pattern: [[n, n, n], [i, n, i], [n, i, n]], output: Item::Bucket, output_count: 1 },
         // Bread: WWW / ___ / ___ (top-aligned)
         CraftingRecipe3x3 { pattern: [[Some(Item::Wheat), Some(Item::Wheat), Some(Item::Wheat)], [n, n, n], [n, n, n]], output: Item::Bread, output_count: 1 },
         // Bread: ___ / WWW / ___ (middle-aligned)
@@ -1682,4 +1688,30 @@ mod tests {
         assert_eq!(check_recipes_3x3(&grid), None,
             "Upside-down pickaxe should not match");
     }
+
+    #[test]
+    fn bucket_crafts_in_either_row() {
+        let top = grid3x3_with([
+            iron(), N,      iron(),
+            N,      iron(), N,
+            N,      N,      N,
+        ]);
+        assert_eq!(check_recipes_3x3(&top), Some((Item::Bucket, 1, 0)));
+        let bottom = grid3x3_with([
+            N,      N,      N,
+            iron(), N,      iron(),
+            N,      iron(), N,
+        ]);
+        assert_eq!(check_recipes_3x3(&bottom), Some((Item::Bucket, 1, 0)));
+    }
+
+    #[test]
+    fn ladder_from_seven_sticks() {
+        let grid = grid3x3_with([
+            stick(), N,       stick(),
+            stick(), stick(), stick(),
+            stick(), N,       stick(),
+        ]);
+        assert_eq!(check_recipes_3x3(&grid), Some((Item::Block(BlockType::Ladder), 3, 0)));
+    }
 }
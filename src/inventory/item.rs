@@ -56,6 +56,8 @@ pub enum Item {
     DiamondChestplate,
     DiamondLeggings,
     DiamondBoots,
+    Bucket,
+    WaterBucket,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -86,7 +88,9 @@ impl Item {
             | Self::WoodenHoe | Self::StoneHoe | Self::IronHoe | Self::DiamondHoe
             | Self::LeatherHelmet | Self::LeatherChestplate | Self::LeatherLeggings | Self::LeatherBoots
             | Self::IronHelmet | Self::IronChestplate | Self::IronLeggings | Self::IronBoots
-            | Self::DiamondHelmet | Self::DiamondChestplate | Self::DiamondLeggings | Self::DiamondBoots => 1,
+            | Self::DiamondHelmet | Self::DiamondChestplate | Self::DiamondLeggings | Self::DiamondBoots
+            | Self::WaterBucket => 1,
+            Self::Bucket => 16,
             _ => 64,
         }
     }
@@ -145,9 +149,35 @@ impl Item {
             Self::DiamondChestplate => "Diamond Chestplate",
             Self::DiamondLeggings => "Diamond Leggings",
             Self::DiamondBoots => "Diamond Boots",
+            Self::Bucket => "Bucket",
+            Self::WaterBucket => "Water Bucket",
         }
     }
 
+    /// Every item, as the creative palette lists them: each placeable block in block id
+    /// order, then everything else.
+    pub fn all() -> Vec<Self> {
+        // New items go here too; the `all_is_missing_no_item` test checks every variant is listed
+        const ITEMS: [Item; 53] = [
+            Item::Stick, Item::Coal, Item::IronIngot, Item::GoldIngot, Item::Diamond,
+            Item::WoodenPickaxe, Item::WoodenAxe, Item::WoodenShovel, Item::WoodenSword,
+            Item::StonePickaxe, Item::StoneAxe, Item::StoneShovel, Item::StoneSword, Item::IronPickaxe,
+            Item::IronAxe, Item::IronShovel, Item::IronSword, Item::DiamondPickaxe, Item::DiamondAxe,
+            Item::DiamondShovel, Item::DiamondSword, Item::Apple, Item::Bread, Item::CookedPorkchop,
+            Item::RawPorkchop, Item::RawBeef, Item::CookedBeef, Item::Leather, Item::RawMutton,
+            Item::CookedMutton, Item::Wool, Item::RottenFlesh, Item::Bone, Item::WoodenHoe,
+            Item::StoneHoe, Item::IronHoe, Item::DiamondHoe, Item::Seeds, Item::Wheat,
+            Item::LeatherHelmet, Item::LeatherChestplate, Item::LeatherLeggings, Item::LeatherBoots,
+            Item::IronHelmet, Item::IronChestplate, Item::IronLeggings, Item::IronBoots,
+            Item::DiamondHelmet, Item::DiamondChestplate, Item::DiamondLeggings, Item::DiamondBoots,
+            Item::Bucket, Item::WaterBucket,
+        ];
+        let blocks = (1..=u8::MAX).map(BlockType::from_id).filter(|block| {
+            !matches!(block, BlockType::Air | BlockType::Water | BlockType::Fire | BlockType::WheatCrop | BlockType::Farmland)
+        });
+        blocks.map(Self::Block).chain(ITEMS).collect()
+    }
+
     pub fn is_block(self) -> bool {
         matches!(self, Self::Block(_))
     }
@@ -267,3 +297,66 @@ impl Item {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn all_lists_every_item_once() {
+        let items = Item::all();
+        let unique: HashSet<Item> = items.iter().copied().collect();
+        assert_eq!(unique.len(), items.len());
+        assert!(items.contains(&Item::Block(BlockType::Stone)));
+        assert!(items.contains(&Item::WaterBucket) && items.contains(&Item::DiamondBoots));
+        // Blocks that only exist in the world aren't offered
+        assert!(!items.contains(&Item::Block(BlockType::Air)) && !items.contains(&Item::Block(BlockType::WheatCrop)));
+    }
+
+    /// Names of every `Item` variant, taken from its derived `Deserialize`, which hands
+    /// the full list to the deserializer.
+    fn variant_names() -> &'static [&'static str] {
+        use serde::de::{self, Deserializer, Visitor};
+
+        struct Names(&'static [&'static str]);
+
+        impl<'de> Deserializer<'de> for &mut Names {
+            type Error = de::value::Error;
+
+            fn deserialize_enum<V: Visitor<'de>>(
+                self,
+                _name: &'static str,
+                variants: &'static [&'static str],
+                _visitor: V,
+            ) -> Result<V::Value, Self::Error> {
+                self.0 = variants;
+                Err(de::Error::custom("only reading the variant names"))
+            }
+
+            fn deserialize_any<V: Visitor<'de>>(self, _visitor: V) -> Result<V::Value, Self::Error> {
+                Err(de::Error::custom("not an enum"))
+            }
+
+            serde::forward_to_deserialize_any! {
+                bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf
+                option unit unit_struct newtype_struct seq tuple tuple_struct map struct identifier ignored_any
+            }
+        }
+
+        let mut names = Names(&[]);
+        let _ = Item::deserialize(&mut names);
+        names.0
+    }
+
+    #[test]
+    fn all_is_missing_no_item() {
+        let items = Item::all();
+        let names = variant_names();
+        assert!(names.len() > 50, "read {} variant names", names.len());
+        for name in names.iter().filter(|&&name| name != "Block") {
+            let item: Item = serde_json::from_str(&format!("\"{}\"", name)).unwrap();
+            assert!(items.contains(&item), "{name} is missing from Item::all");
+        }
+    }
+}
@@ -27,7 +27,7 @@ impl Plugin for InventoryPlugin {
     }
 }
 
-fn load_saved_inventory() -> inventory::Inventory {
+pub(crate) fn load_saved_inventory() -> inventory::Inventory {
     if let Some(data) = crate::save::persistence::load_player() {
         if data.inventory.len() == inventory::INVENTORY_SLOTS {
             let mut slots: [inventory::Slot; inventory::INVENTORY_SLOTS] = [None; inventory::INVENTORY_SLOTS];
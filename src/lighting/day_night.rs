@@ -1,5 +1,10 @@
 use bevy::prelude::*;
 
+use crate::world::manager::{ChunkMaterial, FarTerrainMaterial, WaterMaterial};
+use crate::world::material::{ChunkMaterialType, FarTerrainMaterialType, WaterMaterialType};
+
+use super::weather::WeatherState;
+
 /// Tracks the current time of day as a value from 0.0 to 1.0.
 /// 0.0 = sunrise, 0.25 = noon, 0.5 = sunset, 0.75 = midnight
 #[derive(Resource)]
@@ -19,6 +24,15 @@ impl Default for DayNightCycle {
     }
 }
 
+impl DayNightCycle {
+    /// Sky light multiplier for chunk shading: full brightness through most of the day,
+    /// fading across sunrise/sunset down to a moonlit floor at night.
+    pub fn daylight(&self) -> f32 {
+        let sun_height = (self.time_of_day * std::f32::consts::TAU).sin();
+        (0.6 + sun_height * 0.8).clamp(0.15, 1.0)
+    }
+}
+
 /// Marker component for the sun directional light
 #[derive(Component)]
 pub struct Sun;
@@ -30,6 +44,7 @@ pub fn advance_time(time: Res<Time>, mut cycle: ResMut<DayNightCycle>) {
 
 pub fn update_sun(
     cycle: Res<DayNightCycle>,
+    weather: Res<WeatherState>,
     mut query: Query<(&mut Transform, &mut DirectionalLight), With<Sun>>,
 ) {
     let Ok((mut transform, mut light)) = query.single_mut() else {
@@ -55,9 +70,9 @@ pub fn update_sun(
     // Sun height determines intensity: sin of angle, positive = above horizon
     let sun_height = (angle).sin();
 
-    // Illuminance: bright at noon, zero when below horizon
+    // Illuminance: bright at noon, zero when below horizon, dimmed under rain clouds
     light.illuminance = if sun_height > 0.0 {
-        sun_height * 10000.0
+        sun_height * 10000.0 * weather.sky_dimming()
     } else {
         0.0
     };
@@ -72,6 +87,7 @@ pub fn update_sun(
 
 pub fn update_ambient(
     cycle: Res<DayNightCycle>,
+    weather: Res<WeatherState>,
     mut ambient: ResMut<GlobalAmbientLight>,
 ) {
     let angle = cycle.time_of_day * std::f32::consts::TAU;
@@ -84,7 +100,7 @@ pub fn update_ambient(
         100.0 + sun_height * 60.0 // Dimmer at night, minimum ~40
     };
 
-    ambient.brightness = day_brightness.max(40.0);
+    ambient.brightness = day_brightness.max(40.0) * weather.sky_dimming();
 
     // Slight blue tint at night, warmer during day
     if sun_height > 0.0 {
@@ -93,3 +109,35 @@ pub fn update_ambient(
         ambient.color = Color::srgb(0.4, 0.5, 0.8);
     }
 }
+
+/// Push the current daylight factor into the chunk, water and far terrain materials so
+/// baked sky light dims at night and under rain clouds, and lights up with lightning.
+/// Block light (torches) is unaffected.
+pub fn update_chunk_daylight(
+    cycle: Res<DayNightCycle>,
+    weather: Res<WeatherState>,
+    chunk_material: Res<ChunkMaterial>,
+    water_material: Res<WaterMaterial>,
+    far_material: Res<FarTerrainMaterial>,
+    mut materials: ResMut<Assets<ChunkMaterialType>>,
+    mut water_materials: ResMut<Assets<WaterMaterialType>>,
+    mut far_materials: ResMut<Assets<FarTerrainMaterialType>>,
+) {
+    let daylight = (cycle.daylight() * weather.sky_dimming()).max(weather.flash);
+    // Skip tiny changes so the material isn't re-uploaded every frame
+    let Some(material) = materials.get(&chunk_material.0) else {
+        return;
+    };
+    if (material.extension.daylight - daylight).abs() < 0.002 {
+        return;
+    }
+    if let Some(material) = materials.get_mut(&chunk_material.0) {
+        material.extension.daylight = daylight;
+    }
+    if let Some(material) = water_materials.get_mut(&water_material.0) {
+        material.extension.daylight = daylight;
+    }
+    if let Some(material) = far_materials.get_mut(&far_material.0) {
+        material.extension.daylight = daylight;
+    }
+}
@@ -1,9 +1,15 @@
 use bevy::prelude::*;
 
 use super::day_night::DayNightCycle;
+use super::weather::WeatherState;
 
-/// Updates the ClearColor resource to simulate sky color changes throughout the day.
-pub fn update_sky_color(cycle: Res<DayNightCycle>, mut clear_color: ResMut<ClearColor>) {
+/// Updates the ClearColor resource to simulate sky color changes throughout the day,
+/// graying and darkening it under rain clouds and flashing it with lightning.
+pub fn update_sky_color(
+    cycle: Res<DayNightCycle>,
+    weather: Res<WeatherState>,
+    mut clear_color: ResMut<ClearColor>,
+) {
     let angle = cycle.time_of_day * std::f32::consts::TAU;
     // sun_height > 0 means daytime, < 0 means nighttime
     let sun_height = angle.sin();
@@ -27,6 +33,11 @@ pub fn update_sky_color(cycle: Res<DayNightCycle>, mut clear_color: ResMut<Clear
         night_color.lerp(Vec3::new(0.01, 0.01, 0.04), t)
     };
 
+    // Clouds wash the color out towards gray before darkening it
+    let gray = Vec3::splat(sky.dot(Vec3::new(0.3, 0.59, 0.11)));
+    let sky = sky.lerp(gray, weather.rain * 0.8) * weather.sky_dimming();
+    let sky = sky.lerp(Vec3::new(0.85, 0.85, 0.95), weather.flash * 0.6);
+
     clear_color.0 = Color::srgb(sky.x, sky.y, sky.z);
 }
 
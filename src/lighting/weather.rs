@@ -0,0 +1,497 @@
+use bevy::prelude::*;
+use bevy::ecs::message::MessageWriter;
+use bevy::light::NotShadowCaster;
+use rand::Rng;
+
+use crate::audio::ThunderAudio;
+use crate::entity::mob::MobHealth;
+use crate::save::level::Weather;
+use crate::world::fluid::state_at;
+use crate::world::generation::{sample_climate, Biome};
+use crate::world::light::{light_levels_at, MAX_LIGHT};
+use crate::world::manager::{ignite, ChunkDataStore, ChunkManager, FireTracker};
+use crate::world::WorldHeight;
+
+/// Seconds of clear weather between storms: half a day to seven and a half days.
+const CLEAR_SECONDS: (f32, f32) = (600.0, 9000.0);
+/// Seconds a spell of rain or a thunderstorm lasts.
+const RAIN_SECONDS: (f32, f32) = (600.0, 1200.0);
+/// Chance that clear weather turns into a thunderstorm rather than plain rain.
+const THUNDER_CHANCE: f32 = 0.25;
+/// Seconds for rain and storm clouds to build up or clear after the weather changes.
+const FADE_SECONDS: f32 = 10.0;
+
+/// How much rain and a thunderstorm dim sky light, the sky color and ambient light.
+const RAIN_DIMMING: f32 = 0.3;
+const THUNDER_DIMMING: f32 = 0.25;
+
+/// Most precipitation particles falling at once, in the heaviest rain.
+const MAX_PARTICLES: usize = 600;
+/// Particles fall in a cylinder around the camera this wide and this far above and below it.
+const PARTICLE_RADIUS: f32 = 12.0;
+const PARTICLE_HEIGHT: f32 = 14.0;
+/// Fall speeds in blocks per second; snow also drifts sideways.
+const RAIN_SPEED: f32 = 16.0;
+const SNOW_SPEED: f32 = 2.0;
+const SNOW_DRIFT: f32 = 0.6;
+
+/// Seconds between lightning strikes in a thunderstorm.
+const STRIKE_INTERVAL: (f32, f32) = (4.0, 18.0);
+/// Strikes land at most this far from the camera, in blocks.
+const STRIKE_RADIUS: f32 = 48.0;
+/// Damage to mobs within `STRIKE_DAMAGE_RADIUS` blocks of a strike.
+const STRIKE_DAMAGE: f32 = 5.0;
+const STRIKE_DAMAGE_RADIUS: f32 = 3.0;
+/// How high a bolt reaches above the block it strikes.
+const BOLT_HEIGHT: f32 = 128.0;
+/// Seconds a bolt stays visible and its flash takes to fade.
+const BOLT_SECONDS: f32 = 0.25;
+
+/// The world's weather: what it is now, when it next changes, and how strongly it is
+/// showing while one kind fades into the next.
+#[derive(Resource)]
+pub struct WeatherState {
+    pub weather: Weather,
+    /// Seconds until the weather changes.
+    pub remaining: f32,
+    /// How hard it's raining, 0 (clear) to 1.
+    pub rain: f32,
+    /// How stormy it is, 0 to 1.
+    pub thunder: f32,
+    /// Brightness of the last lightning flash, 0 to 1.
+    pub flash: f32,
+}
+
+impl Default for WeatherState {
+    fn default() -> Self {
+        Self::load(Weather::Clear, 0.0)
+    }
+}
+
+impl WeatherState {
+    /// Weather as saved in a level file, already at full strength. Levels saved without
+    /// a duration get a fresh one.
+    pub fn load(weather: Weather, remaining: f32) -> Self {
+        let remaining = if remaining > 0.0 { remaining } else { weather_duration(weather, rand::random()) };
+        let (rain, thunder) = weather_strength(weather);
+        Self { weather, remaining, rain, thunder, flash: 0.0 }
+    }
+
+    pub fn is_raining(&self) -> bool {
+        self.weather != Weather::Clear
+    }
+
+    /// Multiplier for sky light and sky brightness under the current clouds.
+    pub fn sky_dimming(&self) -> f32 {
+        1.0 - RAIN_DIMMING * self.rain - THUNDER_DIMMING * self.thunder
+    }
+}
+
+/// Rain and storm strength a kind of weather builds up to.
+fn weather_strength(weather: Weather) -> (f32, f32) {
+    match weather {
+        Weather::Clear => (0.0, 0.0),
+        Weather::Rain => (1.0, 0.0),
+        Weather::Thunder => (1.0, 1.0),
+    }
+}
+
+/// The weather after `current` runs out; `roll` is uniform in 0..1.
+pub fn next_weather(current: Weather, roll: f32) -> Weather {
+    match current {
+        Weather::Clear if roll < THUNDER_CHANCE => Weather::Thunder,
+        Weather::Clear => Weather::Rain,
+        Weather::Rain | Weather::Thunder => Weather::Clear,
+    }
+}
+
+/// How long a spell of `weather` lasts; `roll` is uniform in 0..1.
+pub fn weather_duration(weather: Weather, roll: f32) -> f32 {
+    let (min, max) = match weather {
+        Weather::Clear => CLEAR_SECONDS,
+        Weather::Rain | Weather::Thunder => RAIN_SECONDS,
+    };
+    min + (max - min) * roll
+}
+
+/// What falls from the sky in a biome when it rains.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Precipitation {
+    #[default]
+    Dry,
+    Rain,
+    Snow,
+}
+
+impl Precipitation {
+    pub fn of_biome(biome: Biome) -> Self {
+        match biome {
+            Biome::Desert => Self::Dry,
+            Biome::SnowyPlains => Self::Snow,
+            _ => Self::Rain,
+        }
+    }
+
+    /// Precipitation over the column at a world position.
+    pub fn at(pos: Vec3) -> Self {
+        Self::of_biome(Biome::from_climate(sample_climate(pos.x.floor() as i32, pos.z.floor() as i32)))
+    }
+}
+
+/// Whether rain is falling on the block at `pos`: it's raining, the column's biome
+/// (`precipitation`, looked up once by the caller) gets rain rather than snow, and the
+/// block is open to the sky.
+pub fn is_rained_on(weather: &WeatherState, store: &ChunkDataStore, pos: IVec3, precipitation: Precipitation) -> bool {
+    weather.rain > 0.5
+        && precipitation == Precipitation::Rain
+        && light_levels_at(store, pos).0 == MAX_LIGHT
+}
+
+/// Change the weather when its time runs out, and fade rain, clouds and lightning
+/// flashes towards the current weather.
+pub fn advance_weather(time: Res<Time>, mut state: ResMut<WeatherState>) {
+    let dt = time.delta_secs();
+    state.remaining -= dt;
+    if state.remaining <= 0.0 {
+        let mut rng = rand::rng();
+        state.weather = next_weather(state.weather, rng.random());
+        state.remaining = weather_duration(state.weather, rng.random());
+        info!("[WEATHER] {:?} for {:.0}s", state.weather, state.remaining);
+    }
+
+    let (rain, thunder) = weather_strength(state.weather);
+    let step = dt / FADE_SECONDS;
+    state.rain += (rain - state.rain).clamp(-step, step);
+    state.thunder += (thunder - state.thunder).clamp(-step, step);
+    state.flash = (state.flash - dt / BOLT_SECONDS).max(0.0);
+}
+
+/// One falling raindrop or snowflake, recycled from a fixed pool around the camera.
+#[derive(Component)]
+pub struct PrecipitationParticle {
+    /// Position in the pool; only the first few are shown in light rain.
+    index: usize,
+    /// Sideways drift in blocks per second, for snow.
+    drift: Vec2,
+}
+
+/// A lightning bolt, removed once its time is up.
+#[derive(Component)]
+pub struct LightningBolt {
+    remaining: f32,
+}
+
+/// Meshes and materials for precipitation and lightning.
+#[derive(Resource)]
+pub struct WeatherAssets {
+    rain_mesh: Handle<Mesh>,
+    snow_mesh: Handle<Mesh>,
+    bolt_mesh: Handle<Mesh>,
+    rain_material: Handle<StandardMaterial>,
+    snow_material: Handle<StandardMaterial>,
+    bolt_material: Handle<StandardMaterial>,
+}
+
+/// Create the weather meshes and materials, and the hidden pool of precipitation particles.
+pub fn setup_weather(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let assets = WeatherAssets {
+        rain_mesh: meshes.add(Cuboid::new(0.03, 0.7, 0.03)),
+        snow_mesh: meshes.add(Cuboid::new(0.1, 0.1, 0.1)),
+        bolt_mesh: meshes.add(Cuboid::new(0.3, BOLT_HEIGHT, 0.3)),
+        rain_material: materials.add(StandardMaterial {
+            base_color: Color::srgba(0.6, 0.7, 0.9, 0.5),
+            alpha_mode: AlphaMode::Blend,
+            unlit: true,
+            ..default()
+        }),
+        snow_material: materials.add(StandardMaterial {
+            base_color: Color::srgb(0.95, 0.95, 1.0),
+            unlit: true,
+            ..default()
+        }),
+        bolt_material: materials.add(StandardMaterial {
+            base_color: Color::srgb(0.9, 0.9, 1.0),
+            emissive: LinearRgba::new(8.0, 8.0, 12.0, 1.0),
+            unlit: true,
+            ..default()
+        }),
+    };
+
+    let mut rng = rand::rng();
+    for index in 0..MAX_PARTICLES {
+        let angle = rng.random_range(0.0..std::f32::consts::TAU);
+        commands.spawn((
+            PrecipitationParticle { index, drift: Vec2::from_angle(angle) * SNOW_DRIFT },
+            Mesh3d(assets.rain_mesh.clone()),
+            MeshMaterial3d(assets.rain_material.clone()),
+            Transform::default(),
+            Visibility::Hidden,
+            NotShadowCaster,
+        ));
+    }
+    commands.insert_resource(assets);
+}
+
+/// Whether a particle at `pos` has landed on something.
+fn particle_landed(store: &ChunkDataStore, pos: Vec3) -> bool {
+    state_at(store, pos.floor().as_ivec3()).is_some_and(|state| state.block().is_solid() || state.block().is_liquid())
+}
+
+/// Let rain or snow fall around the camera, as many particles as the rain is heavy.
+/// Particles that land or fall out of range start again at the top, but only in the
+/// open, so nothing falls in caves or under roofs.
+pub fn update_precipitation(
+    time: Res<Time>,
+    weather: Res<WeatherState>,
+    assets: Res<WeatherAssets>,
+    store: Res<ChunkDataStore>,
+    camera: Query<&GlobalTransform, With<Camera3d>>,
+    mut shown: Local<Precipitation>,
+    mut particles: Query<(
+        &PrecipitationParticle,
+        &mut Transform,
+        &mut Visibility,
+        &mut Mesh3d,
+        &mut MeshMaterial3d<StandardMaterial>,
+    )>,
+) {
+    let Ok(cam_transform) = camera.single() else {
+        return;
+    };
+    let cam = cam_transform.translation();
+    let kind = if weather.rain > 0.0 { Precipitation::at(cam) } else { Precipitation::Dry };
+    let active = match kind {
+        Precipitation::Dry => 0,
+        _ => (weather.rain * MAX_PARTICLES as f32) as usize,
+    };
+    let swap = kind != *shown && kind != Precipitation::Dry;
+    if swap {
+        *shown = kind;
+    }
+
+    let dt = time.delta_secs();
+    let mut rng = rand::rng();
+    for (particle, mut transform, mut visibility, mut mesh, mut material) in &mut particles {
+        if particle.index >= active {
+            *visibility = Visibility::Hidden;
+            continue;
+        }
+        if swap {
+            let snow = kind == Precipitation::Snow;
+            mesh.0 = if snow { assets.snow_mesh.clone() } else { assets.rain_mesh.clone() };
+            material.0 = if snow { assets.snow_material.clone() } else { assets.rain_material.clone() };
+        }
+
+        let pos = &mut transform.translation;
+        match kind {
+            Precipitation::Snow => {
+                pos.y -= SNOW_SPEED * dt;
+                pos.x += particle.drift.x * dt;
+                pos.z += particle.drift.y * dt;
+            }
+            _ => pos.y -= RAIN_SPEED * dt,
+        }
+
+        let offset = *pos - cam;
+        let hidden = *visibility == Visibility::Hidden;
+        if hidden
+            || offset.y < -PARTICLE_HEIGHT
+            || offset.xz().length() > PARTICLE_RADIUS
+            || particle_landed(&store, *pos)
+        {
+            // Fill the whole column when first shown, otherwise start again at the top
+            let angle = rng.random_range(0.0..std::f32::consts::TAU);
+            let distance = PARTICLE_RADIUS * rng.random::<f32>().sqrt();
+            let height = if hidden {
+                rng.random_range(-PARTICLE_HEIGHT..PARTICLE_HEIGHT)
+            } else {
+                PARTICLE_HEIGHT - rng.random_range(0.0..2.0)
+            };
+            let start = cam + Vec3::new(angle.cos() * distance, height, angle.sin() * distance);
+            let open = light_levels_at(&store, start.floor().as_ivec3()).0 == MAX_LIGHT;
+            if !open || particle_landed(&store, start) {
+                *visibility = Visibility::Hidden;
+                continue;
+            }
+            *pos = start;
+        }
+        *visibility = Visibility::Visible;
+    }
+}
+
+/// Strike lightning every so often in a thunderstorm: a bolt and a flash at a random
+/// spot near the camera, setting fire to the ground where it lands and hurting mobs
+/// close by.
+pub fn strike_lightning(
+    time: Res<Time>,
+    mut weather: ResMut<WeatherState>,
+    mut next_strike: Local<f32>,
+    assets: Res<WeatherAssets>,
+    height: Res<WorldHeight>,
+    camera: Query<&GlobalTransform, With<Camera3d>>,
+    mut store: ResMut<ChunkDataStore>,
+    manager: Res<ChunkManager>,
+    mut fires: ResMut<FireTracker>,
+    mut mobs: Query<(&Transform, &mut MobHealth)>,
+    mut thunder_audio: MessageWriter<ThunderAudio>,
+    mut commands: Commands,
+) {
+    if weather.weather != Weather::Thunder {
+        return;
+    }
+    *next_strike -= time.delta_secs();
+    if *next_strike > 0.0 {
+        return;
+    }
+    let mut rng = rand::rng();
+    *next_strike = rng.random_range(STRIKE_INTERVAL.0..STRIKE_INTERVAL.1);
+
+    let Ok(cam_transform) = camera.single() else {
+        return;
+    };
+    let cam = cam_transform.translation();
+    let angle = rng.random_range(0.0..std::f32::consts::TAU);
+    let distance = STRIKE_RADIUS * rng.random::<f32>().sqrt();
+    let (x, z) = ((cam.x + angle.cos() * distance).floor() as i32, (cam.z + angle.sin() * distance).floor() as i32);
+    if Precipitation::at(Vec3::new(x as f32, 0.0, z as f32)) != Precipitation::Rain {
+        return;
+    }
+
+    // Strike the highest block in the column, if that part of the world is loaded
+    let top = (height.min_y..height.max_y()).rev().find_map(|y| {
+        let state = state_at(&store, IVec3::new(x, y, z))?;
+        (!state.block().is_air()).then_some(y)
+    });
+    let Some(top) = top else {
+        return;
+    };
+    let hit = IVec3::new(x, top, z);
+    let ground = hit.as_vec3() + Vec3::new(0.5, 1.0, 0.5);
+
+    commands.spawn((
+        LightningBolt { remaining: BOLT_SECONDS },
+        Mesh3d(assets.bolt_mesh.clone()),
+        MeshMaterial3d(assets.bolt_material.clone()),
+        Transform::from_translation(ground + Vec3::Y * BOLT_HEIGHT / 2.0),
+        NotShadowCaster,
+    ));
+    weather.flash = 1.0;
+    thunder_audio.write(ThunderAudio { distance: ground.distance(cam) });
+
+    ignite(&mut store, &mut fires, &manager, &mut commands, hit + IVec3::Y);
+    for (transform, mut health) in &mut mobs {
+        if transform.translation.distance(ground) <= STRIKE_DAMAGE_RADIUS {
+            health.current -= STRIKE_DAMAGE;
+        }
+    }
+}
+
+/// Remove lightning bolts once they've flashed.
+pub fn fade_lightning(
+    time: Res<Time>,
+    mut bolts: Query<(Entity, &mut LightningBolt)>,
+    mut commands: Commands,
+) {
+    for (entity, mut bolt) in &mut bolts {
+        bolt.remaining -= time.delta_secs();
+        if bolt.remaining <= 0.0 {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::world::light::ChunkLight;
+    use std::time::Duration;
+
+    #[test]
+    fn clear_weather_turns_to_rain_or_storm_and_back() {
+        assert_eq!(next_weather(Weather::Clear, 0.0), Weather::Thunder);
+        assert_eq!(next_weather(Weather::Clear, THUNDER_CHANCE - 0.01), Weather::Thunder);
+        assert_eq!(next_weather(Weather::Clear, THUNDER_CHANCE), Weather::Rain);
+        assert_eq!(next_weather(Weather::Clear, 0.99), Weather::Rain);
+        assert_eq!(next_weather(Weather::Rain, 0.0), Weather::Clear);
+        assert_eq!(next_weather(Weather::Thunder, 0.99), Weather::Clear);
+    }
+
+    #[test]
+    fn durations_span_their_ranges() {
+        assert_eq!(weather_duration(Weather::Clear, 0.0), CLEAR_SECONDS.0);
+        assert_eq!(weather_duration(Weather::Clear, 1.0), CLEAR_SECONDS.1);
+        for weather in [Weather::Rain, Weather::Thunder] {
+            assert_eq!(weather_duration(weather, 0.0), RAIN_SECONDS.0);
+            assert_eq!(weather_duration(weather, 1.0), RAIN_SECONDS.1);
+            let middle = weather_duration(weather, 0.5);
+            assert!(middle > RAIN_SECONDS.0 && middle < RAIN_SECONDS.1);
+        }
+        // Levels saved without a duration get one in range
+        let loaded = WeatherState::load(Weather::Rain, 0.0);
+        assert!(loaded.remaining >= RAIN_SECONDS.0 && loaded.remaining <= RAIN_SECONDS.1);
+        assert_eq!((loaded.rain, loaded.thunder), (1.0, 0.0));
+    }
+
+    #[test]
+    fn biomes_get_rain_snow_or_nothing() {
+        assert_eq!(Precipitation::of_biome(Biome::Desert), Precipitation::Dry);
+        assert_eq!(Precipitation::of_biome(Biome::SnowyPlains), Precipitation::Snow);
+        for biome in [Biome::Plains, Biome::Forest, Biome::Ocean, Biome::Mountains, Biome::Swamp] {
+            assert_eq!(Precipitation::of_biome(biome), Precipitation::Rain, "{biome:?}");
+        }
+        // Any point in a block column has that column's weather
+        assert_eq!(Precipitation::at(Vec3::new(-0.5, 80.0, 3.7)), Precipitation::at(Vec3::new(-1.0, 0.0, 3.0)));
+    }
+
+    #[test]
+    fn rain_falls_only_on_open_rainy_columns_in_heavy_rain() {
+        let mut store = ChunkDataStore::default();
+        // A chunk with no sky light at all: everything in it is under cover
+        store.lights.insert(IVec3::ZERO, ChunkLight::default());
+        let open = IVec3::new(0, 40, 0);
+        let covered = IVec3::new(1, 1, 1);
+
+        let mut weather = WeatherState::load(Weather::Rain, 100.0);
+        assert!(is_rained_on(&weather, &store, open, Precipitation::Rain));
+        assert!(!is_rained_on(&weather, &store, covered, Precipitation::Rain));
+        assert!(!is_rained_on(&weather, &store, open, Precipitation::Snow));
+        assert!(!is_rained_on(&weather, &store, open, Precipitation::Dry));
+        // Not until the rain has built up
+        weather.rain = 0.4;
+        assert!(!is_rained_on(&weather, &store, open, Precipitation::Rain));
+    }
+
+    fn advance(app: &mut App, seconds: f32) {
+        app.world_mut().resource_mut::<Time>().advance_by(Duration::from_secs_f32(seconds));
+        app.update();
+    }
+
+    #[test]
+    fn rain_fades_in_and_the_weather_changes_when_it_runs_out() {
+        let mut app = App::new();
+        app.init_resource::<Time>().add_systems(Update, advance_weather);
+        let mut state = WeatherState::load(Weather::Thunder, FADE_SECONDS * 1.25 + 0.5);
+        (state.rain, state.thunder) = (0.0, 0.0);
+        app.insert_resource(state);
+
+        advance(&mut app, FADE_SECONDS / 4.0);
+        let state = app.world().resource::<WeatherState>();
+        assert!((state.rain - 0.25).abs() < 1e-4 && (state.thunder - 0.25).abs() < 1e-4);
+        for _ in 0..4 {
+            advance(&mut app, FADE_SECONDS / 4.0);
+        }
+        let state = app.world().resource::<WeatherState>();
+        assert_eq!((state.rain, state.thunder), (1.0, 1.0));
+
+        // The storm ends and clear weather starts fading the rain out
+        advance(&mut app, 1.0);
+        let state = app.world().resource::<WeatherState>();
+        assert_eq!(state.weather, Weather::Clear);
+        assert!(state.remaining >= CLEAR_SECONDS.0 && state.remaining <= CLEAR_SECONDS.1);
+        assert!((state.rain - (1.0 - 1.0 / FADE_SECONDS)).abs() < 1e-4);
+    }
+}
@@ -9,6 +9,8 @@ mod audio;
 mod inventory;
 mod save;
 mod entity;
+mod resource_pack;
+mod controls;
 
 fn main() {
     App::new()
@@ -30,6 +32,8 @@ fn main() {
             inventory::InventoryPlugin,
             save::SavePlugin,
             entity::EntityPlugin,
+            resource_pack::ResourcePackPlugin,
+            controls::ControlsPlugin,
         ))
         .run();
 }
@@ -2,17 +2,22 @@ use bevy::input::mouse::AccumulatedMouseMotion;
 use bevy::prelude::*;
 use bevy::window::{CursorGrabMode, CursorOptions, PrimaryWindow};
 
+use crate::controls::gamepad::{stick_response, LOOK_CURVE, LOOK_DEADZONE, LOOK_SPEED};
+use crate::controls::{Action, ActionInput};
 use crate::inventory::chest::ChestOpen;
 use crate::inventory::crafting::CraftingTableOpen;
 use crate::inventory::furnace::FurnaceOpen;
+use crate::save::level::GameMode;
 use crate::ui::death_screen::PlayerDead;
 use crate::ui::inventory_screen::InventoryOpen;
 use crate::ui::main_menu::InMainMenu;
 use crate::ui::pause_menu::PauseState;
 
-use super::{Player, PlayerYaw, PlayerPitch};
+use super::{Flying, InWater, OnGround, Player, PlayerYaw, PlayerPitch, Velocity};
 
 const MOUSE_SENSITIVITY: f32 = 0.003;
+/// Longest gap between two jump presses that toggles flight.
+const DOUBLE_TAP_SECONDS: f32 = 0.3;
 
 fn any_ui_open(
     in_menu: &InMainMenu,
@@ -26,8 +31,11 @@ fn any_ui_open(
     in_menu.0 || pause.0 || inventory_open.0 || ct_open.0 || furnace_open.0.is_some() || chest_open.0.is_some() || dead.0
 }
 
+/// Turn with the mouse, or the right stick on a controller.
 pub fn mouse_look(
+    time: Res<Time>,
     motion: Res<AccumulatedMouseMotion>,
+    gamepads: Query<&Gamepad>,
     in_menu: Res<InMainMenu>,
     inventory_open: Res<InventoryOpen>,
     pause: Res<PauseState>,
@@ -42,14 +50,16 @@ pub fn mouse_look(
         return;
     }
 
-    let delta = motion.delta;
-    if delta == Vec2::ZERO {
+    // Turn in radians, right and down positive like mouse motion
+    let stick: Vec2 = gamepads.iter().map(|g| stick_response(g.right_stick(), LOOK_DEADZONE, LOOK_CURVE)).sum();
+    let turn = motion.delta * MOUSE_SENSITIVITY + Vec2::new(stick.x, -stick.y) * LOOK_SPEED * time.delta_secs();
+    if turn == Vec2::ZERO {
         return;
     }
 
     for (mut yaw, mut pitch, children) in &mut player_q {
-        yaw.0 -= delta.x * MOUSE_SENSITIVITY;
-        pitch.0 = (pitch.0 - delta.y * MOUSE_SENSITIVITY).clamp(
+        yaw.0 -= turn.x;
+        pitch.0 = (pitch.0 - turn.y).clamp(
             -89.0_f32.to_radians(),
             89.0_f32.to_radians(),
         );
@@ -62,8 +72,44 @@ pub fn mouse_look(
     }
 }
 
+/// Start or stop flying: creative players double-tap jump and land to stop, spectators
+/// always fly, and everyone else walks.
+pub fn toggle_flight(
+    time: Res<Time>,
+    input: ActionInput,
+    game_mode: Res<GameMode>,
+    dead: Res<PlayerDead>,
+    mut last_jump: Local<Option<f32>>,
+    mut query: Query<(&mut Flying, &mut Velocity, &mut OnGround), With<Player>>,
+) {
+    let Ok((mut flying, mut velocity, mut on_ground)) = query.single_mut() else {
+        return;
+    };
+    if !game_mode.can_fly() || game_mode.is_noclip() {
+        flying.0 = game_mode.is_noclip();
+        return;
+    }
+    if flying.0 && on_ground.0 {
+        flying.0 = false;
+    }
+    if dead.0 || !input.just_pressed(Action::Jump) {
+        return;
+    }
+
+    let now = time.elapsed_secs();
+    match last_jump.take() {
+        Some(last) if now - last <= DOUBLE_TAP_SECONDS => {
+            flying.0 = !flying.0;
+            velocity.0.y = 0.0;
+            // Lift off without the ground ending the flight at once
+            on_ground.0 = false;
+        }
+        _ => *last_jump = Some(now),
+    }
+}
+
 pub fn player_movement(
-    input: Res<ButtonInput<KeyCode>>,
+    input: ActionInput,
     in_menu: Res<InMainMenu>,
     pause: Res<PauseState>,
     inventory_open: Res<InventoryOpen>,
@@ -71,44 +117,53 @@ pub fn player_movement(
     furnace_open: Res<FurnaceOpen>,
     chest_open: Res<ChestOpen>,
     dead: Res<PlayerDead>,
-    mut query: Query<(&mut super::Velocity, &PlayerYaw, &super::Hunger, &mut super::Sneaking), With<Player>>,
+    mut query: Query<(&mut Velocity, &PlayerYaw, &super::Hunger, &mut super::Sneaking, &Flying, &InWater), With<Player>>,
 ) {
     if any_ui_open(&in_menu, &pause, &inventory_open, &ct_open, &furnace_open, &chest_open, &dead) {
         return;
     }
 
-    for (mut velocity, yaw, hunger, mut sneaking) in &mut query {
-        let mut dir = Vec3::ZERO;
-
+    for (mut velocity, yaw, hunger, mut sneaking, flying, in_water) in &mut query {
         let forward = Vec3::new(-yaw.0.sin(), 0.0, -yaw.0.cos());
         let right = Vec3::new(-forward.z, 0.0, forward.x);
 
-        if input.pressed(KeyCode::KeyW) {
-            dir += forward;
-        }
-        if input.pressed(KeyCode::KeyS) {
-            dir -= forward;
-        }
-        if input.pressed(KeyCode::KeyA) {
-            dir -= right;
-        }
-        if input.pressed(KeyCode::KeyD) {
-            dir += right;
+        // Full length for keys, shorter for a partly tilted stick
+        let movement = input.movement();
+        let dir = forward * movement.y + right * movement.x;
+
+        // Flying: jump rises, sneak sinks, and there's no edge to sneak along
+        if flying.0 {
+            sneaking.0 = false;
+            let speed = if input.pressed(Action::Sprint) {
+                super::FLY_SPRINT_SPEED
+            } else {
+                super::FLY_SPEED
+            };
+            let rise = input.pressed(Action::Jump) as i32 - input.pressed(Action::Sneak) as i32;
+            velocity.0 = Vec3::new(dir.x * speed, rise as f32 * super::FLY_VERTICAL_SPEED, dir.z * speed);
+            continue;
         }
 
-        let dir = if dir.length_squared() > 0.0 {
-            dir.normalize()
-        } else {
-            dir
-        };
+        // Swimming: slower, faster again when sprinting; sneak sinks instead (physics::swim)
+        if in_water.body {
+            sneaking.0 = false;
+            let speed = if input.pressed(Action::Sprint) && hunger.food_level > 6.0 {
+                super::SWIM_SPRINT_SPEED
+            } else {
+                super::SWIM_SPEED
+            };
+            velocity.0.x = dir.x * speed;
+            velocity.0.z = dir.z * speed;
+            continue;
+        }
 
-        let is_sneaking = input.pressed(KeyCode::ShiftLeft);
+        let is_sneaking = input.pressed(Action::Sneak);
         sneaking.0 = is_sneaking;
 
         // Sneak overrides sprint; can't sprint when food_level <= 6
         let speed = if is_sneaking {
             super::SNEAK_SPEED
-        } else if input.pressed(KeyCode::ControlLeft) && hunger.food_level > 6.0 {
+        } else if input.pressed(Action::Sprint) && hunger.food_level > 6.0 {
             super::SPRINT_SPEED
         } else {
             super::WALK_SPEED
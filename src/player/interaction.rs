@@ -1,7 +1,10 @@
 use bevy::prelude::*;
 use bevy::window::{CursorOptions, PrimaryWindow};
 
-use crate::block::BlockType;
+use crate::block::model::block_model;
+use crate::block::state::{Axis, Facing, Half};
+use crate::block::{BlockState, BlockType};
+use crate::controls::{Action, ActionInput};
 use crate::entity::mob::{Mob, MobHealth, MobVelocity};
 
 /// System set label for block_interact (needed because it exceeds the IntoSystemSet param limit).
@@ -21,11 +24,11 @@ use crate::inventory::crafting::CraftingTableOpen;
 use crate::inventory::furnace::{FurnaceOpen, Furnaces};
 use crate::inventory::inventory::{Inventory, INVENTORY_COLS, INVENTORY_SLOTS};
 use crate::inventory::item::{Item, ToolKind, ToolTier};
+use crate::save::level::GameMode;
 use crate::ui::hotbar::HotbarState;
 use crate::ui::inventory_screen::InventoryOpen;
 use crate::world::chunk::CHUNK_SIZE;
-use crate::world::coordinates::{world_to_chunk_pos, world_to_local_pos};
-use crate::world::manager::{ChunkDataStore, ChunkManager, NeedsMesh};
+use crate::world::manager::{mark_remesh, set_block_at, set_block_state_at, ChunkDataStore, ChunkManager};
 
 use super::{Player, Hunger, PendingExhaustion};
 
@@ -119,13 +122,12 @@ fn tool_speed_multiplier(held: Item, block: BlockType) -> f32 {
     // Check if this tool type is effective on this block
     let is_effective = match kind {
         Some(ToolKind::Pickaxe) => matches!(block,
-            BlockType::Stone | BlockType::Cobblestone | BlockType::Sandstone
+            BlockType::Stone | BlockType::Deepslate | BlockType::Cobblestone | BlockType::Sandstone
             | BlockType::CoalOre | BlockType::IronOre | BlockType::GoldOre
             | BlockType::DiamondOre | BlockType::Furnace),
         Some(ToolKind::Axe) => matches!(block,
             BlockType::OakLog | BlockType::BirchLog | BlockType::Planks
-            | BlockType::CraftingTable | BlockType::DoorBottom | BlockType::DoorTop
-            | BlockType::DoorBottomOpen | BlockType::DoorTopOpen),
+            | BlockType::CraftingTable | BlockType::Door | BlockType::Ladder),
         Some(ToolKind::Shovel) => matches!(block,
             BlockType::Dirt | BlockType::Grass | BlockType::Sand
             | BlockType::Gravel | BlockType::Clay | BlockType::Snow | BlockType::Farmland),
@@ -172,6 +174,18 @@ struct RaycastHit {
 
 /// DDA raycast through a voxel grid. Returns the first solid block hit within max_dist.
 fn voxel_raycast(origin: Vec3, direction: Vec3, max_dist: f32, store: &ChunkDataStore) -> Option<RaycastHit> {
+    voxel_raycast_matching(origin, direction, max_dist, store, |state| state.block().is_targetable())
+}
+
+/// DDA raycast that stops at the first block state accepted by `hits`. Blocks with
+/// model boxes (beds, doors, torches) are only hit where the ray meets a box.
+fn voxel_raycast_matching(
+    origin: Vec3,
+    direction: Vec3,
+    max_dist: f32,
+    store: &ChunkDataStore,
+    hits: impl Fn(BlockState) -> bool,
+) -> Option<RaycastHit> {
     let dir = direction.normalize();
 
     // Current voxel position
@@ -226,8 +240,8 @@ fn voxel_raycast(origin: Vec3, direction: Vec3, max_dist: f32, store: &ChunkData
     let mut t = 0.0_f32;
     while t < max_dist {
         // Check current voxel
-        let block = get_block(store, x, y, z);
-        if block.is_targetable() {
+        let state = get_block_state(store, x, y, z);
+        if hits(state) && ray_meets_shape(origin, dir, IVec3::new(x, y, z), state, max_dist) {
             return Some(RaycastHit {
                 block_pos: IVec3::new(x, y, z),
                 adjacent_pos: IVec3::new(prev_x, prev_y, prev_z),
@@ -263,7 +277,24 @@ fn voxel_raycast(origin: Vec3, direction: Vec3, max_dist: f32, store: &ChunkData
     None
 }
 
+/// Whether a ray meets a block's model boxes within `max_dist`. Cubes and models
+/// without boxes (plants) can be targeted anywhere in their cell.
+fn ray_meets_shape(origin: Vec3, dir: Vec3, pos: IVec3, state: BlockState, max_dist: f32) -> bool {
+    let Some(model) = block_model(state).filter(|m| !m.boxes.is_empty()) else {
+        return true;
+    };
+    let cell = pos.as_vec3();
+    model
+        .boxes
+        .iter()
+        .any(|b| ray_aabb(origin, dir, cell + b.min, cell + b.max).is_some_and(|t| t <= max_dist))
+}
+
 fn get_block(store: &ChunkDataStore, x: i32, y: i32, z: i32) -> BlockType {
+    get_block_state(store, x, y, z).block()
+}
+
+fn get_block_state(store: &ChunkDataStore, x: i32, y: i32, z: i32) -> BlockState {
     let size = CHUNK_SIZE as i32;
     let cx = x.div_euclid(size);
     let cy = y.div_euclid(size);
@@ -271,14 +302,14 @@ fn get_block(store: &ChunkDataStore, x: i32, y: i32, z: i32) -> BlockType {
     let chunk_pos = IVec3::new(cx, cy, cz);
 
     let Some(chunk) = store.chunks.get(&chunk_pos) else {
-        return BlockType::Air;
+        return BlockState::AIR;
     };
 
     let lx = x.rem_euclid(size) as usize;
     let ly = y.rem_euclid(size) as usize;
     let lz = z.rem_euclid(size) as usize;
 
-    chunk.get(lx, ly, lz)
+    chunk.get_state(lx, ly, lz)
 }
 
 fn handle_furnace_break(
@@ -382,7 +413,7 @@ fn ray_aabb(origin: Vec3, dir: Vec3, aabb_min: Vec3, aabb_max: Vec3) -> Option<f
 /// System: attack mobs on left click. Runs before break_block.
 pub fn attack_mob(
     time: Res<Time>,
-    mouse: Res<ButtonInput<MouseButton>>,
+    actions: ActionInput,
     inventory_open: Res<InventoryOpen>,
     cursor_q: Query<&CursorOptions, With<PrimaryWindow>>,
     camera_q: Query<&GlobalTransform, With<Camera3d>>,
@@ -396,12 +427,13 @@ pub fn attack_mob(
     mut swing_audio: bevy::ecs::message::MessageWriter<crate::audio::SwordSwingAudio>,
     mut mob_hurt_audio: bevy::ecs::message::MessageWriter<crate::audio::MobHurtAudio>,
     mut pending_exhaustion: ResMut<PendingExhaustion>,
+    game_mode: Res<GameMode>,
 ) {
     let dt = time.delta_secs();
     cooldown.remaining = (cooldown.remaining - dt).max(0.0);
     mob_hit.0 = false;
 
-    if !mouse.just_pressed(MouseButton::Left) {
+    if !actions.just_pressed(Action::Attack) || !game_mode.can_interact() {
         return;
     }
     if inventory_open.0 {
@@ -486,14 +518,16 @@ pub fn attack_mob(
         }
     }
 
-    // Use tool durability on attack
-    if held_item.is_tool() {
-        let slot_idx = INVENTORY_SLOTS - INVENTORY_COLS + hotbar.selected_slot;
-        inventory.use_tool(slot_idx);
-    }
+    if game_mode.consumes_items() {
+        // Use tool durability on attack
+        if held_item.is_tool() {
+            let slot_idx = INVENTORY_SLOTS - INVENTORY_COLS + hotbar.selected_slot;
+            inventory.use_tool(slot_idx);
+        }
 
-    // Attacking adds 0.1 exhaustion
-    pending_exhaustion.0 += 0.1;
+        // Attacking adds 0.1 exhaustion
+        pending_exhaustion.0 += 0.1;
+    }
 
     cooldown.remaining = PLAYER_ATTACK_COOLDOWN;
     mob_hit.0 = true;
@@ -502,7 +536,7 @@ pub fn attack_mob(
 /// System: hold-to-break block on left click with crack overlay.
 pub fn break_block(
     time: Res<Time>,
-    mouse: Res<ButtonInput<MouseButton>>,
+    actions: ActionInput,
     inventory_open: Res<InventoryOpen>,
     mob_hit: Res<MobHitThisFrame>,
     cursor_q: Query<&CursorOptions, With<PrimaryWindow>>,
@@ -514,14 +548,21 @@ pub fn break_block(
     mut commands: Commands,
     mut audio: bevy::ecs::message::MessageWriter<crate::audio::BlockBreakAudio>,
     (mut furnaces, mut furnace_open, mut chest_store, mut chest_open): (ResMut<Furnaces>, ResMut<FurnaceOpen>, ResMut<ChestStore>, ResMut<ChestOpen>),
-    (mut breaking, mut pending_exhaustion, drop_assets): (ResMut<BreakingState>, ResMut<PendingExhaustion>, Res<crate::entity::dropped_item::DroppedItemAssets>),
+    (mut breaking, mut pending_exhaustion, drop_assets, game_mode): (ResMut<BreakingState>, ResMut<PendingExhaustion>, Res<crate::entity::dropped_item::DroppedItemAssets>, Res<GameMode>),
     overlay_assets: Res<BreakOverlayAssets>,
     mut meshes: ResMut<Assets<Mesh>>,
 ) {
     let dt = time.delta_secs();
 
     // Check if we should be breaking
-    let should_break = mouse.pressed(MouseButton::Left)
+    // In creative a block goes at each click rather than one after another while held
+    let pressed = if game_mode.breaks_instantly() {
+        actions.just_pressed(Action::Attack)
+    } else {
+        actions.pressed(Action::Attack)
+    };
+    let should_break = pressed
+        && game_mode.can_build()
         && !inventory_open.0
         && !mob_hit.0
         && cursor_q.single().map_or(false, |c| !c.visible);
@@ -550,10 +591,11 @@ pub fn break_block(
         return;
     };
 
-    let block = get_block(&store, hit.block_pos.x, hit.block_pos.y, hit.block_pos.z);
+    let state = get_block_state(&store, hit.block_pos.x, hit.block_pos.y, hit.block_pos.z);
+    let block = state.block();
 
-    // Don't break bedrock or air
-    if block == BlockType::Bedrock || block == BlockType::Air {
+    // Don't break air, or bedrock outside creative
+    if (block == BlockType::Bedrock && !game_mode.breaks_instantly()) || block == BlockType::Air {
         if let Some(entity) = breaking.reset() {
             commands.entity(entity).despawn();
         }
@@ -563,7 +605,7 @@ pub fn break_block(
     // Get held item for tool mechanics
     let held_item = hotbar.slots[hotbar.selected_slot];
     let speed_mult = tool_speed_multiplier(held_item, block);
-    let effective_break_time = block.break_time() / speed_mult;
+    let effective_break_time = if game_mode.breaks_instantly() { 0.0 } else { block.break_time() / speed_mult };
 
     // Instant break for blocks with 0 break time
     if effective_break_time <= 0.0 {
@@ -573,27 +615,29 @@ pub fn break_block(
 
         handle_furnace_break(block, &hit.block_pos, &mut furnaces, &mut furnace_open, &mut inventory);
         handle_chest_break(block, &hit.block_pos, &mut chest_store, &mut chest_open, &mut inventory);
-        handle_door_break(block, &hit.block_pos, &mut store, &manager, &mut commands);
+        handle_door_break(state, &hit.block_pos, &mut store, &manager, &mut commands);
 
-        set_block(&mut store, hit.block_pos, BlockType::Air);
-        if can_harvest(held_item, block) {
+        set_block_at(&mut store, hit.block_pos, BlockType::Air);
+        if game_mode.consumes_items() && can_harvest(held_item, block) {
             let drop_pos = hit.block_pos.as_vec3() + Vec3::splat(0.5);
-            if let Some(drop) = block.drop_item() {
+            if let Some(drop) = state.drop_item() {
                 crate::entity::dropped_item::spawn_dropped_item(&mut commands, &mut meshes, &drop_assets, drop, 1, drop_pos);
             }
-            for (bonus_item, bonus_count) in block.bonus_drops() {
+            for (bonus_item, bonus_count) in state.bonus_drops() {
                 crate::entity::dropped_item::spawn_dropped_item(&mut commands, &mut meshes, &drop_assets, bonus_item, bonus_count, drop_pos);
             }
         }
-        // Use tool durability
-        if held_item.is_tool() {
-            let slot_idx = INVENTORY_SLOTS - INVENTORY_COLS + hotbar.selected_slot;
-            inventory.use_tool(slot_idx);
+        if game_mode.consumes_items() {
+            // Use tool durability
+            if held_item.is_tool() {
+                let slot_idx = INVENTORY_SLOTS - INVENTORY_COLS + hotbar.selected_slot;
+                inventory.use_tool(slot_idx);
+            }
+            // Breaking blocks adds 0.005 exhaustion
+            pending_exhaustion.0 += 0.005;
         }
-        // Breaking blocks adds 0.005 exhaustion
-        pending_exhaustion.0 += 0.005;
         audio.write(crate::audio::BlockBreakAudio);
-        mark_needs_remesh(hit.block_pos, &manager, &mut commands);
+        mark_remesh(hit.block_pos, &manager, &mut commands);
         return;
     }
 
@@ -632,6 +676,7 @@ pub fn break_block(
     if breaking.progress >= 1.0 {
         let target_pos = hit.block_pos;
         let target_block = breaking.block_type;
+        let target_state = get_block_state(&store, target_pos.x, target_pos.y, target_pos.z);
 
         if let Some(entity) = breaking.reset() {
             commands.entity(entity).despawn();
@@ -639,16 +684,16 @@ pub fn break_block(
 
         handle_furnace_break(target_block, &target_pos, &mut furnaces, &mut furnace_open, &mut inventory);
         handle_chest_break(target_block, &target_pos, &mut chest_store, &mut chest_open, &mut inventory);
-        handle_door_break(target_block, &target_pos, &mut store, &manager, &mut commands);
+        handle_door_break(target_state, &target_pos, &mut store, &manager, &mut commands);
 
-        set_block(&mut store, target_pos, BlockType::Air);
+        set_block_at(&mut store, target_pos, BlockType::Air);
         // Check tool requirements for drops
         if can_harvest(held_item, target_block) {
             let drop_pos = target_pos.as_vec3() + Vec3::splat(0.5);
-            if let Some(drop) = target_block.drop_item() {
+            if let Some(drop) = target_state.drop_item() {
                 crate::entity::dropped_item::spawn_dropped_item(&mut commands, &mut meshes, &drop_assets, drop, 1, drop_pos);
             }
-            for (bonus_item, bonus_count) in target_block.bonus_drops() {
+            for (bonus_item, bonus_count) in target_state.bonus_drops() {
                 crate::entity::dropped_item::spawn_dropped_item(&mut commands, &mut meshes, &drop_assets, bonus_item, bonus_count, drop_pos);
             }
         }
@@ -660,13 +705,13 @@ pub fn break_block(
         // Breaking blocks adds 0.005 exhaustion
         pending_exhaustion.0 += 0.005;
         audio.write(crate::audio::BlockBreakAudio);
-        mark_needs_remesh(target_pos, &manager, &mut commands);
+        mark_remesh(target_pos, &manager, &mut commands);
     }
 }
 
 /// System: place block on right click.
 pub fn place_block(
-    mouse: Res<ButtonInput<MouseButton>>,
+    actions: ActionInput,
     inventory_open: Res<InventoryOpen>,
     consumed: Res<RightClickConsumed>,
     cursor_q: Query<&CursorOptions, With<PrimaryWindow>>,
@@ -681,8 +726,9 @@ pub fn place_block(
     mut sapling_tracker: ResMut<crate::world::manager::SaplingTracker>,
     drop_assets: Res<crate::entity::dropped_item::DroppedItemAssets>,
     mut meshes: ResMut<Assets<Mesh>>,
+    game_mode: Res<GameMode>,
 ) {
-    if !mouse.just_pressed(MouseButton::Right) {
+    if !actions.just_pressed(Action::Use) || !game_mode.can_build() {
         return;
     }
 
@@ -717,7 +763,8 @@ pub fn place_block(
     let place_pos = hit.adjacent_pos;
 
     // Check that place position isn't occupied by a solid block
-    let existing = get_block(&store, place_pos.x, place_pos.y, place_pos.z);
+    let existing_state = get_block_state(&store, place_pos.x, place_pos.y, place_pos.z);
+    let existing = existing_state.block();
     if existing.is_solid() {
         return;
     }
@@ -726,13 +773,15 @@ pub fn place_block(
     // break it first and drop its item
     if existing != BlockType::Air && existing.is_non_cube() {
         let drop_pos = place_pos.as_vec3() + Vec3::splat(0.5);
-        if let Some(drop) = existing.drop_item() {
-            crate::entity::dropped_item::spawn_dropped_item(&mut commands, &mut meshes, &drop_assets, drop, 1, drop_pos);
-        }
-        for (bonus_item, bonus_count) in existing.bonus_drops() {
-            crate::entity::dropped_item::spawn_dropped_item(&mut commands, &mut meshes, &drop_assets, bonus_item, bonus_count, drop_pos);
+        if game_mode.consumes_items() {
+            if let Some(drop) = existing_state.drop_item() {
+                crate::entity::dropped_item::spawn_dropped_item(&mut commands, &mut meshes, &drop_assets, drop, 1, drop_pos);
+            }
+            for (bonus_item, bonus_count) in existing_state.bonus_drops() {
+                crate::entity::dropped_item::spawn_dropped_item(&mut commands, &mut meshes, &drop_assets, bonus_item, bonus_count, drop_pos);
+            }
         }
-        set_block(&mut store, place_pos, BlockType::Air);
+        set_block_at(&mut store, place_pos, BlockType::Air);
     }
 
     // Check overlap with player AABB
@@ -743,48 +792,69 @@ pub fn place_block(
         return;
     }
 
-    // Consume block from inventory (hotbar = bottom row of inventory)
+    // Held block (hotbar = bottom row of inventory); non-block items can't be placed
     let slot_idx = INVENTORY_SLOTS - INVENTORY_COLS + hotbar.selected_slot;
-    let Some(item) = inventory.remove_item(slot_idx) else {
-        return;
-    };
-    let Some(block_type) = item.as_block() else {
-        // Non-block items can't be placed; put it back
-        inventory.add_item(item);
+    let Some(block_type) = inventory.slots[slot_idx].and_then(|(item, _, _)| item.as_block()) else {
         return;
     };
 
     // Sapling placement: only on Air, on top of Dirt or Grass
     if block_type == BlockType::OakSapling || block_type == BlockType::BirchSapling {
         if existing != BlockType::Air {
-            inventory.add_item(item);
             return;
         }
         let below = get_block(&store, place_pos.x, place_pos.y - 1, place_pos.z);
         if below != BlockType::Dirt && below != BlockType::Grass {
-            inventory.add_item(item);
             return;
         }
     }
 
+    // Ladders and vines hang on the side of a solid block
+    let normal = hit.adjacent_pos - hit.block_pos;
+    if block_type.is_climbable()
+        && (normal.y != 0 || !get_block(&store, hit.block_pos.x, hit.block_pos.y, hit.block_pos.z).is_solid())
+    {
+        return;
+    }
+
+    // Oriented blocks face the player; logs follow the face they were placed against,
+    // and ladders and vines face away from their wall
+    let facing = Facing::from_direction(-forward);
+    let state = match block_type {
+        BlockType::Furnace | BlockType::Chest | BlockType::Door => BlockState::new(block_type).with_facing(facing),
+        BlockType::Ladder | BlockType::Vines => {
+            BlockState::new(block_type).with_facing(Facing::from_direction(normal.as_vec3()))
+        }
+        BlockType::OakLog | BlockType::BirchLog => {
+            BlockState::new(block_type).with_axis(Axis::from_normal(normal))
+        }
+        _ => BlockState::new(block_type),
+    };
+
     // Door placement: need 2 blocks of space (bottom + top)
-    if block_type == BlockType::DoorBottom {
+    if block_type == BlockType::Door {
         let top_pos = place_pos + IVec3::Y;
         let above = get_block(&store, top_pos.x, top_pos.y, top_pos.z);
         if above.is_solid() || block_overlaps_player(top_pos, player_tf.translation) {
-            // Can't place door — not enough room; put item back
-            inventory.add_item(item);
+            // Can't place door — not enough room
             return;
         }
-        set_block(&mut store, place_pos, BlockType::DoorBottom);
-        set_block(&mut store, top_pos, BlockType::DoorTop);
+        if game_mode.consumes_items() {
+            inventory.remove_item(slot_idx);
+        }
+        set_block_state_at(&mut store, place_pos, state);
+        set_block_state_at(&mut store, top_pos, state.with_half(Half::Upper));
         audio.write(crate::audio::BlockPlaceAudio);
-        mark_needs_remesh(place_pos, &manager, &mut commands);
-        mark_needs_remesh(top_pos, &manager, &mut commands);
+        mark_remesh(place_pos, &manager, &mut commands);
+        mark_remesh(top_pos, &manager, &mut commands);
         return;
     }
 
-    set_block(&mut store, place_pos, block_type);
+    // Creative placing keeps the stack
+    if game_mode.consumes_items() {
+        inventory.remove_item(slot_idx);
+    }
+    set_block_state_at(&mut store, place_pos, state);
     audio.write(crate::audio::BlockPlaceAudio);
 
     // Track placed saplings for growth
@@ -794,113 +864,61 @@ pub fn place_block(
         sapling_tracker.saplings.insert(place_pos, timer);
     }
 
-    mark_needs_remesh(place_pos, &manager, &mut commands);
-}
-
-fn set_block(store: &mut ChunkDataStore, world_pos: IVec3, block: BlockType) {
-    let chunk_pos = world_to_chunk_pos(world_pos.as_vec3());
-    let local = world_to_local_pos(world_pos);
-
-    if let Some(chunk) = store.chunks.get_mut(&chunk_pos) {
-        chunk.set(local.x as usize, local.y as usize, local.z as usize, block);
-        store.modified.insert(chunk_pos);
-    }
+    mark_remesh(place_pos, &manager, &mut commands);
 }
 
-fn mark_needs_remesh(world_pos: IVec3, manager: &ChunkManager, commands: &mut Commands) {
-    let chunk_pos = world_to_chunk_pos(world_pos.as_vec3());
-
-    // Mark the primary chunk
-    if let Some(&entity) = manager.loaded.get(&chunk_pos) {
-        commands.entity(entity).insert(NeedsMesh);
-    }
-
-    // If block is on a chunk boundary, also remesh the neighbor
-    let local = world_to_local_pos(world_pos);
-    let size = CHUNK_SIZE as u32;
-    let neighbors = [
-        (local.x == 0, IVec3::new(-1, 0, 0)),
-        (local.x == size - 1, IVec3::new(1, 0, 0)),
-        (local.y == 0, IVec3::new(0, -1, 0)),
-        (local.y == size - 1, IVec3::new(0, 1, 0)),
-        (local.z == 0, IVec3::new(0, 0, -1)),
-        (local.z == size - 1, IVec3::new(0, 0, 1)),
-    ];
-    for (on_edge, offset) in neighbors {
-        if on_edge {
-            let neighbor_chunk = chunk_pos + offset;
-            if let Some(&entity) = manager.loaded.get(&neighbor_chunk) {
-                commands.entity(entity).insert(NeedsMesh);
-            }
-        }
+/// Position of the other half of a door.
+fn other_door_half(state: BlockState, pos: IVec3) -> IVec3 {
+    match state.half() {
+        Half::Lower => pos + IVec3::Y,
+        Half::Upper => pos - IVec3::Y,
     }
 }
 
 /// When a door half is broken, also remove the other half.
 fn handle_door_break(
-    block: BlockType,
+    state: BlockState,
     pos: &IVec3,
     store: &mut ChunkDataStore,
     manager: &ChunkManager,
     commands: &mut Commands,
 ) {
-    let other_pos = match block {
-        BlockType::DoorBottom | BlockType::DoorBottomOpen => *pos + IVec3::Y,
-        BlockType::DoorTop | BlockType::DoorTopOpen => *pos - IVec3::Y,
-        _ => return,
-    };
-    let other_block = get_block(store, other_pos.x, other_pos.y, other_pos.z);
-    match other_block {
-        BlockType::DoorBottom | BlockType::DoorTop
-        | BlockType::DoorBottomOpen | BlockType::DoorTopOpen => {
-            set_block(store, other_pos, BlockType::Air);
-            mark_needs_remesh(other_pos, manager, commands);
-        }
-        _ => {}
+    if state.block() != BlockType::Door {
+        return;
+    }
+    let other_pos = other_door_half(state, *pos);
+    let other = get_block_state(store, other_pos.x, other_pos.y, other_pos.z);
+    if other.block() == BlockType::Door && other.half() != state.half() {
+        set_block_at(store, other_pos, BlockType::Air);
+        mark_remesh(other_pos, manager, commands);
     }
 }
 
 /// Toggle a door between open and closed states, updating both halves.
 fn toggle_door(
-    block: BlockType,
+    state: BlockState,
     pos: IVec3,
     store: &mut ChunkDataStore,
     manager: &ChunkManager,
     commands: &mut Commands,
 ) {
-    let (bottom_pos, top_pos) = match block {
-        BlockType::DoorBottom | BlockType::DoorBottomOpen => {
-            (pos, pos + IVec3::Y)
-        }
-        BlockType::DoorTop | BlockType::DoorTopOpen => {
-            (pos - IVec3::Y, pos)
-        }
-        _ => return,
-    };
-
-    let bottom_block = get_block(store, bottom_pos.x, bottom_pos.y, bottom_pos.z);
-    let top_block = get_block(store, top_pos.x, top_pos.y, top_pos.z);
-
-    let (new_bottom, new_top) = match (bottom_block, top_block) {
-        (BlockType::DoorBottom, BlockType::DoorTop) => {
-            (BlockType::DoorBottomOpen, BlockType::DoorTopOpen)
-        }
-        (BlockType::DoorBottomOpen, BlockType::DoorTopOpen) => {
-            (BlockType::DoorBottom, BlockType::DoorTop)
-        }
-        _ => return, // mismatched halves, don't toggle
-    };
+    let other_pos = other_door_half(state, pos);
+    let other = get_block_state(store, other_pos.x, other_pos.y, other_pos.z);
+    if other.block() != BlockType::Door || other.half() == state.half() {
+        return; // mismatched halves, don't toggle
+    }
 
-    set_block(store, bottom_pos, new_bottom);
-    set_block(store, top_pos, new_top);
-    mark_needs_remesh(bottom_pos, manager, commands);
-    mark_needs_remesh(top_pos, manager, commands);
+    let open = !state.is_open();
+    set_block_state_at(store, pos, state.with_open(open));
+    set_block_state_at(store, other_pos, other.with_open(open));
+    mark_remesh(pos, manager, commands);
+    mark_remesh(other_pos, manager, commands);
 }
 
 /// System: detect right-click on furnace or crafting table block and open the appropriate UI.
 /// Runs before place_block so we can consume the click.
 pub fn block_interact(
-    mouse: Res<ButtonInput<MouseButton>>,
+    actions: ActionInput,
     mut ui_state: UiOpenState,
     dead: Res<crate::ui::death_screen::PlayerDead>,
     mut cursor_q: Query<&mut CursorOptions, With<PrimaryWindow>>,
@@ -914,6 +932,7 @@ pub fn block_interact(
     player_q: Query<&Transform, With<Player>>,
     manager: Res<ChunkManager>,
     mut commands: Commands,
+    game_mode: Res<GameMode>,
 ) {
     consumed.0 = false;
 
@@ -921,7 +940,7 @@ pub fn block_interact(
         return;
     }
 
-    if !mouse.just_pressed(MouseButton::Right) {
+    if !actions.just_pressed(Action::Use) || !game_mode.can_interact() {
         return;
     }
 
@@ -947,9 +966,9 @@ pub fn block_interact(
         return;
     };
 
-    let block = get_block(&store, hit.block_pos.x, hit.block_pos.y, hit.block_pos.z);
+    let state = get_block_state(&store, hit.block_pos.x, hit.block_pos.y, hit.block_pos.z);
 
-    match block {
+    match state.block() {
         BlockType::Furnace => {
             furnaces.data.entry(hit.block_pos).or_default();
             ui_state.furnace_open.0 = Some(hit.block_pos);
@@ -980,9 +999,8 @@ pub fn block_interact(
                 consumed.0 = true;
             }
         }
-        BlockType::DoorBottom | BlockType::DoorTop
-        | BlockType::DoorBottomOpen | BlockType::DoorTopOpen => {
-            toggle_door(block, hit.block_pos, &mut store, &manager, &mut commands);
+        BlockType::Door => {
+            toggle_door(state, hit.block_pos, &mut store, &manager, &mut commands);
             consumed.0 = true;
         }
         _ => {}
@@ -993,7 +1011,7 @@ pub fn block_interact(
 /// System: eat food on right-click when holding a food item.
 /// Runs after block_interact (so we don't eat when interacting with furnace/chest).
 pub fn eat_food(
-    mouse: Res<ButtonInput<MouseButton>>,
+    actions: ActionInput,
     inventory_open: Res<InventoryOpen>,
     dead: Res<crate::ui::death_screen::PlayerDead>,
     mut consumed: ResMut<RightClickConsumed>,
@@ -1001,11 +1019,13 @@ pub fn eat_food(
     mut hunger_q: Query<&mut Hunger, With<Player>>,
     mut inventory: ResMut<Inventory>,
     hotbar: Res<HotbarState>,
+    game_mode: Res<GameMode>,
 ) {
-    if dead.0 {
+    // Nobody gets hungry in creative or spectator
+    if dead.0 || !game_mode.is_vulnerable() {
         return;
     }
-    if !mouse.just_pressed(MouseButton::Right) {
+    if !actions.just_pressed(Action::Use) {
         return;
     }
     if consumed.0 || inventory_open.0 {
@@ -1046,7 +1066,7 @@ pub fn eat_food(
 /// System: use hoe on dirt/grass to convert to farmland.
 /// Runs after block_interact, before place_block.
 pub fn hoe_interact(
-    mouse: Res<ButtonInput<MouseButton>>,
+    actions: ActionInput,
     inventory_open: Res<InventoryOpen>,
     dead: Res<crate::ui::death_screen::PlayerDead>,
     mut consumed: ResMut<RightClickConsumed>,
@@ -1058,11 +1078,12 @@ pub fn hoe_interact(
     hotbar: Res<HotbarState>,
     mut commands: Commands,
     mut audio: bevy::ecs::message::MessageWriter<crate::audio::BlockPlaceAudio>,
+    game_mode: Res<GameMode>,
 ) {
-    if dead.0 {
+    if dead.0 || !game_mode.can_build() {
         return;
     }
-    if !mouse.just_pressed(MouseButton::Right) {
+    if !actions.just_pressed(Action::Use) {
         return;
     }
     if consumed.0 || inventory_open.0 {
@@ -1093,20 +1114,22 @@ pub fn hoe_interact(
     }
 
     // Convert to farmland
-    set_block(&mut store, hit.block_pos, BlockType::Farmland);
+    set_block_at(&mut store, hit.block_pos, BlockType::Farmland);
     consumed.0 = true;
     audio.write(crate::audio::BlockPlaceAudio);
-    mark_needs_remesh(hit.block_pos, &manager, &mut commands);
+    mark_remesh(hit.block_pos, &manager, &mut commands);
 
     // Use hoe durability
-    let slot_idx = INVENTORY_SLOTS - INVENTORY_COLS + hotbar.selected_slot;
-    inventory.use_tool(slot_idx);
+    if game_mode.consumes_items() {
+        let slot_idx = INVENTORY_SLOTS - INVENTORY_COLS + hotbar.selected_slot;
+        inventory.use_tool(slot_idx);
+    }
 }
 
 /// System: plant seeds on farmland.
 /// Runs after hoe_interact, before place_block.
 pub fn plant_seeds(
-    mouse: Res<ButtonInput<MouseButton>>,
+    actions: ActionInput,
     inventory_open: Res<InventoryOpen>,
     dead: Res<crate::ui::death_screen::PlayerDead>,
     mut consumed: ResMut<RightClickConsumed>,
@@ -1119,11 +1142,12 @@ pub fn plant_seeds(
     mut commands: Commands,
     mut audio: bevy::ecs::message::MessageWriter<crate::audio::BlockPlaceAudio>,
     mut crop_tracker: ResMut<crate::world::manager::CropTracker>,
+    game_mode: Res<GameMode>,
 ) {
-    if dead.0 {
+    if dead.0 || !game_mode.can_build() {
         return;
     }
-    if !mouse.just_pressed(MouseButton::Right) {
+    if !actions.just_pressed(Action::Use) {
         return;
     }
     if consumed.0 || inventory_open.0 {
@@ -1162,20 +1186,114 @@ pub fn plant_seeds(
 
     // Consume 1 seed from hotbar
     let slot_idx = INVENTORY_SLOTS - INVENTORY_COLS + hotbar.selected_slot;
-    if inventory.remove_item(slot_idx).is_none() {
+    if game_mode.consumes_items() && inventory.remove_item(slot_idx).is_none() {
         return;
     }
 
-    // Place WheatStage0 above the farmland
-    set_block(&mut store, above_pos, BlockType::WheatStage0);
+    // Plant a fresh (age 0) crop above the farmland
+    set_block_at(&mut store, above_pos, BlockType::WheatCrop);
     consumed.0 = true;
     audio.write(crate::audio::BlockPlaceAudio);
-    mark_needs_remesh(above_pos, &manager, &mut commands);
+    mark_remesh(above_pos, &manager, &mut commands);
 
     // Track crop for growth
-    use crate::world::manager::{CROP_GROW_MIN, CROP_GROW_MAX};
-    let timer = CROP_GROW_MIN + rand::random::<f32>() * (CROP_GROW_MAX - CROP_GROW_MIN);
-    crop_tracker.crops.insert(above_pos, timer);
+    use crate::world::manager::{crop_grow_time, BlockTimer};
+    crop_tracker.crops.insert(above_pos, BlockTimer::new(above_pos, crop_grow_time()));
+}
+
+/// Right-click with a bucket: an empty bucket scoops up a water source block,
+/// a water bucket pours a source into the space in front of the targeted block.
+pub fn use_bucket(
+    actions: ActionInput,
+    inventory_open: Res<InventoryOpen>,
+    dead: Res<crate::ui::death_screen::PlayerDead>,
+    mut consumed: ResMut<RightClickConsumed>,
+    cursor_q: Query<&CursorOptions, With<PrimaryWindow>>,
+    camera_q: Query<&GlobalTransform, With<Camera3d>>,
+    mut store: ResMut<ChunkDataStore>,
+    mut inventory: ResMut<Inventory>,
+    manager: Res<ChunkManager>,
+    hotbar: Res<HotbarState>,
+    mut commands: Commands,
+    mut audio: bevy::ecs::message::MessageWriter<crate::audio::BlockPlaceAudio>,
+    game_mode: Res<GameMode>,
+) {
+    if dead.0 || !game_mode.can_build() {
+        return;
+    }
+    if !actions.just_pressed(Action::Use) {
+        return;
+    }
+    if consumed.0 || inventory_open.0 {
+        return;
+    }
+    let Ok(cursor) = cursor_q.single() else { return };
+    if cursor.visible {
+        return;
+    }
+
+    let held_item = hotbar.slots[hotbar.selected_slot];
+    if held_item != Item::Bucket && held_item != Item::WaterBucket {
+        return;
+    }
+
+    let Ok(cam_global) = camera_q.single() else { return };
+    let origin = cam_global.translation();
+    let forward = cam_global.forward().as_vec3();
+    let slot_idx = INVENTORY_SLOTS - INVENTORY_COLS + hotbar.selected_slot;
+
+    if held_item == Item::Bucket {
+        // Flowing water is passed through; only a source can be picked up
+        let Some(hit) = voxel_raycast_matching(origin, forward, REACH_DISTANCE, &store, |state| {
+            state.block().is_targetable() || state.is_fluid_source()
+        }) else {
+            return;
+        };
+        if !get_block_state(&store, hit.block_pos.x, hit.block_pos.y, hit.block_pos.z).is_fluid_source() {
+            return;
+        }
+
+        // Fill one bucket from the stack; the filled one goes back in the slot if it's free.
+        // Creative buckets stay empty
+        if game_mode.consumes_items() {
+            if inventory.remove_item(slot_idx).is_none() {
+                return;
+            }
+            if inventory.slots[slot_idx].is_none() {
+                inventory.slots[slot_idx] = Some((Item::WaterBucket, 1, 0));
+            } else if !inventory.add_item(Item::WaterBucket) {
+                inventory.add_item(Item::Bucket);
+                return;
+            }
+        }
+        set_block_at(&mut store, hit.block_pos, BlockType::Air);
+        mark_remesh(hit.block_pos, &manager, &mut commands);
+    } else {
+        let Some(hit) = voxel_raycast(origin, forward, REACH_DISTANCE, &store) else {
+            return;
+        };
+        // Pour into the targeted block itself if water can wash it away (tall grass)
+        let target = if get_block(&store, hit.block_pos.x, hit.block_pos.y, hit.block_pos.z) == BlockType::TallGrass {
+            hit.block_pos
+        } else {
+            hit.adjacent_pos
+        };
+        let existing = get_block_state(&store, target.x, target.y, target.z);
+        let replaceable = matches!(existing.block(), BlockType::Air | BlockType::TallGrass)
+            || (existing.block().is_liquid() && !existing.is_fluid_source());
+        if !replaceable {
+            return;
+        }
+
+        if game_mode.consumes_items() {
+            inventory.slots[slot_idx] = Some((Item::Bucket, 1, 0));
+        }
+        set_block_at(&mut store, target, BlockType::Water);
+        mark_remesh(target, &manager, &mut commands);
+    }
+
+    consumed.0 = true;
+    audio.write(crate::audio::BlockPlaceAudio);
 }
 
 fn block_overlaps_player(block_pos: IVec3, player_pos: Vec3) -> bool {
@@ -7,6 +7,11 @@ use bevy::prelude::*;
 const WALK_SPEED: f32 = 4.317;
 const SPRINT_SPEED: f32 = WALK_SPEED * 1.3;
 const SNEAK_SPEED: f32 = WALK_SPEED * 0.3;
+const FLY_SPEED: f32 = 10.9;
+const FLY_SPRINT_SPEED: f32 = FLY_SPEED * 2.0;
+const FLY_VERTICAL_SPEED: f32 = 7.5;
+const SWIM_SPEED: f32 = 2.2;
+const SWIM_SPRINT_SPEED: f32 = 5.6;
 const PLAYER_EYE_HEIGHT: f32 = 1.62;
 
 /// The player's respawn location. Defaults to world origin.
@@ -33,17 +38,28 @@ impl Plugin for PlayerPlugin {
                 (
                     controller::cursor_grab,
                     controller::mouse_look,
-                    controller::player_movement,
-                    physics::apply_gravity,
-                    physics::jump,
-                    physics::apply_velocity
+                    controller::toggle_flight,
+                    physics::detect_water,
+                    controller::player_movement
+                        .after(controller::toggle_flight)
+                        .after(physics::detect_water),
+                    physics::apply_gravity
+                        .after(controller::toggle_flight)
+                        .after(physics::detect_water),
+                    physics::jump.after(controller::toggle_flight),
+                    physics::swim
                         .after(physics::apply_gravity)
                         .after(physics::jump)
                         .after(controller::player_movement),
+                    physics::climb.after(physics::swim),
+                    physics::apply_velocity.after(physics::climb),
                     physics::ground_collision.after(physics::apply_velocity),
-                    physics::horizontal_collision.after(physics::apply_velocity),
+                    physics::horizontal_collision.after(physics::ground_collision),
                     physics::sneak_edge_protection.after(physics::horizontal_collision),
-                    physics::track_fall.after(physics::ground_collision),
+                    physics::track_fall
+                        .after(physics::ground_collision)
+                        .after(physics::detect_water)
+                        .after(physics::climb),
                     physics::drowning,
                     physics::hunger_system.after(controller::player_movement),
                     physics::void_damage,
@@ -75,10 +91,13 @@ impl Plugin for PlayerPlugin {
                         .after(interaction::eat_food),
                     interaction::plant_seeds
                         .after(interaction::hoe_interact),
+                    interaction::use_bucket
+                        .after(interaction::plant_seeds),
                     interaction::place_block
                         .after(interaction::BlockInteractSet)
                         .after(interaction::eat_food)
-                        .after(interaction::plant_seeds),
+                        .after(interaction::plant_seeds)
+                        .after(interaction::use_bucket),
                 ),
             );
     }
@@ -102,6 +121,25 @@ pub struct JustJumped(pub bool);
 #[derive(Component, Default)]
 pub struct Sneaking(pub bool);
 
+/// Whether the player is flying, as in creative (double-tap jump) and spectator (always).
+#[derive(Component, Default)]
+pub struct Flying(pub bool);
+
+/// How far the player is in water, and the current pulling at them there.
+#[derive(Component, Default)]
+pub struct InWater {
+    /// Some part of the player is in water.
+    pub body: bool,
+    /// The player's eyes are under water.
+    pub eyes: bool,
+    /// Direction the water around the player's feet flows, or zero.
+    pub current: Vec3,
+}
+
+/// Whether the player is holding on to a ladder or vines.
+#[derive(Component, Default)]
+pub struct Climbing(pub bool);
+
 /// Player's 4 armor slots: [helmet, chestplate, leggings, boots].
 /// Each slot: Option<(Item, count=1, remaining_durability)>.
 #[derive(Component)]
@@ -212,8 +250,7 @@ fn spawn_player(mut commands: Commands, mut spawn_point: ResMut<SpawnPoint>) {
             data.saturation.unwrap_or(5.0),
         )
     } else {
-        let terrain_y = crate::world::generation::sample_terrain_height(0, 0);
-        let spawn_pos = Vec3::new(0.0, (terrain_y + 1) as f32, 0.0);
+        let spawn_pos = crate::world::generation::find_spawn();
         spawn_point.0 = spawn_pos;
         (spawn_pos, 0.0, 0.0, 20.0, 10.0, 20.0, 5.0)
     };
@@ -244,8 +281,14 @@ fn spawn_player(mut commands: Commands, mut spawn_point: ResMut<SpawnPoint>) {
             FallTracker::default(),
             AirSupply { current: air, max: 10.0 },
             Hunger { food_level, saturation, exhaustion: 0.0 },
-            JustJumped::default(),
-            Sneaking::default(),
+            // Movement state, nested to keep the bundle within Bevy's tuple limit
+            (
+                JustJumped::default(),
+                Sneaking::default(),
+                Flying::default(),
+                InWater::default(),
+                Climbing::default(),
+            ),
             armor,
             Transform::from_xyz(pos.x, pos.y, pos.z),
             Visibility::default(),
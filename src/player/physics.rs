@@ -1,25 +1,180 @@
 use bevy::prelude::*;
 
+use crate::block::model::collision_boxes;
+use crate::block::BlockState;
+use crate::controls::{Action, ActionInput};
+use crate::save::level::GameMode;
 use crate::world::chunk::CHUNK_SIZE;
+use crate::world::fluid::{flow_vector, state_at};
 use crate::world::manager::ChunkDataStore;
+use crate::world::WorldHeight;
 
-use super::{Player, Velocity, OnGround, Health, FallTracker, AirSupply, Hunger, PendingExhaustion, JustJumped, Sneaking, PLAYER_EYE_HEIGHT};
+use super::{Player, Velocity, OnGround, Health, FallTracker, AirSupply, Hunger, PendingExhaustion, JustJumped, Sneaking, Flying, InWater, Climbing, PlayerPitch, PLAYER_EYE_HEIGHT};
 
 const GRAVITY: f32 = 20.0;
 const JUMP_VELOCITY: f32 = 7.4;
 
+/// Water pulls down far more gently than air, and drags on vertical speed so the
+/// player sinks no faster than `MAX_SINK_SPEED`.
+const WATER_GRAVITY: f32 = 4.0;
+const WATER_DRAG: f32 = 3.0;
+const MAX_SINK_SPEED: f32 = 2.0;
+/// Holding jump swims up, holding sneak swims down, in blocks per second.
+const SWIM_UP_SPEED: f32 = 3.0;
+const SWIM_DOWN_SPEED: f32 = 3.0;
+const SWIM_ACCELERATION: f32 = 12.0;
+/// Upward kick when swimming against a ledge, enough to climb out onto it.
+const LEDGE_EXIT_VELOCITY: f32 = 6.5;
+/// How fast flowing water carries the player along, in blocks per second, and how
+/// quickly it brings them up to that speed.
+const CURRENT_SPEED: f32 = 1.4;
+const CURRENT_ACCELERATION: f32 = 60.0;
+
+/// Climbing a ladder or vines is slow going up and no faster than this coming down.
+const CLIMB_SPEED: f32 = 2.35;
+const CLIMB_DESCENT_SPEED: f32 = 3.0;
+
+/// Work out how far the player is in water before anything moves them.
+pub fn detect_water(
+    store: Res<ChunkDataStore>,
+    mut query: Query<(&Transform, &mut InWater), With<Player>>,
+) {
+    for (tf, mut in_water) in &mut query {
+        let (min, max) = player_bounds(tf.translation);
+        let (lo, hi) = (min.floor().as_ivec3(), max.ceil().as_ivec3() - IVec3::ONE);
+        let mut body = false;
+        for x in lo.x..=hi.x {
+            for y in lo.y..=hi.y {
+                for z in lo.z..=hi.z {
+                    body |= get_block_at(&store, x, y, z).is_liquid();
+                }
+            }
+        }
+        let eye = (tf.translation + Vec3::Y * PLAYER_EYE_HEIGHT).floor().as_ivec3();
+        let feet = tf.translation.floor().as_ivec3();
+        *in_water = InWater {
+            body,
+            eyes: get_block_at(&store, eye.x, eye.y, eye.z).is_liquid(),
+            current: flow_vector(feet, |p| state_at(&store, p)),
+        };
+    }
+}
+
 pub fn apply_gravity(
     time: Res<Time>,
-    mut query: Query<(&mut Velocity, &OnGround), With<Player>>,
+    mut query: Query<(&mut Velocity, &OnGround, &Flying, &InWater), With<Player>>,
 ) {
     let dt = time.delta_secs();
-    for (mut vel, on_ground) in &mut query {
-        if !on_ground.0 {
+    for (mut vel, on_ground, flying, in_water) in &mut query {
+        if on_ground.0 || flying.0 {
+            continue;
+        }
+        if in_water.body {
+            vel.0.y -= WATER_GRAVITY * dt;
+            vel.0.y -= vel.0.y * (WATER_DRAG * dt).min(1.0);
+            vel.0.y = vel.0.y.max(-MAX_SINK_SPEED);
+        } else {
             vel.0.y -= GRAVITY * dt;
         }
     }
 }
 
+/// Swim in water: jump rises and climbs out over ledges, sneak sinks, sprinting under
+/// water swims where the player looks, and the current carries them along.
+pub fn swim(
+    time: Res<Time>,
+    input: ActionInput,
+    dead: Res<crate::ui::death_screen::PlayerDead>,
+    store: Res<ChunkDataStore>,
+    mut query: Query<(&Transform, &mut Velocity, &InWater, &Flying, &PlayerPitch), With<Player>>,
+) {
+    if dead.0 {
+        return;
+    }
+    let dt = time.delta_secs();
+    for (tf, mut vel, in_water, flying, pitch) in &mut query {
+        if !in_water.body || flying.0 {
+            continue;
+        }
+        let horizontal = Vec3::new(vel.0.x, 0.0, vel.0.z);
+
+        if input.pressed(Action::Jump) {
+            let target = vel.0.y.max(SWIM_UP_SPEED);
+            vel.0.y = (vel.0.y + SWIM_ACCELERATION * dt).min(target);
+            if ledge_ahead(&store, tf.translation, horizontal) {
+                vel.0.y = vel.0.y.max(LEDGE_EXIT_VELOCITY);
+            }
+        } else if input.pressed(Action::Sneak) {
+            vel.0.y = (vel.0.y - SWIM_ACCELERATION * dt).max(-SWIM_DOWN_SPEED);
+        } else if in_water.eyes && input.pressed(Action::Sprint) && horizontal.length() > super::SWIM_SPEED {
+            // Sprint-swimming dives and surfaces along the view
+            let speed = horizontal.length();
+            vel.0.x *= pitch.0.cos();
+            vel.0.z *= pitch.0.cos();
+            vel.0.y = speed * pitch.0.sin();
+        }
+
+        vel.0 = drift_with_current(vel.0, in_water.current, dt);
+    }
+}
+
+/// Carry a velocity along with flowing water: the part of it along the current is pulled
+/// up to the current's own speed, and never slowed down. Bounded however many frames it
+/// runs without `player_movement` resetting the velocity, as while a screen is open.
+fn drift_with_current(vel: Vec3, current: Vec3, dt: f32) -> Vec3 {
+    let drift = current * CURRENT_SPEED;
+    let speed = drift.length();
+    if speed == 0.0 {
+        return vel;
+    }
+    let dir = drift / speed;
+    let gap = speed - vel.dot(dir);
+    if gap <= 0.0 {
+        return vel;
+    }
+    vel + dir * gap.min(CURRENT_ACCELERATION * dt)
+}
+
+/// Climb ladders and vines the player is in: jump or walking forward climbs, sneaking
+/// holds on, and otherwise they slide down at a steady speed.
+pub fn climb(
+    input: ActionInput,
+    dead: Res<crate::ui::death_screen::PlayerDead>,
+    store: Res<ChunkDataStore>,
+    game_mode: Res<GameMode>,
+    mut query: Query<(&Transform, &mut Velocity, &Flying, &mut Climbing), With<Player>>,
+) {
+    for (tf, mut vel, flying, mut climbing) in &mut query {
+        let feet = tf.translation.floor().as_ivec3();
+        climbing.0 = !dead.0
+            && !flying.0
+            && !game_mode.is_noclip()
+            && get_block_at(&store, feet.x, feet.y, feet.z).is_climbable();
+        if !climbing.0 {
+            continue;
+        }
+
+        if input.pressed(Action::Jump) || input.movement().y > 0.0 {
+            vel.0.y = CLIMB_SPEED;
+        } else if input.pressed(Action::Sneak) {
+            vel.0.y = 0.0;
+        } else {
+            vel.0.y = vel.0.y.max(-CLIMB_DESCENT_SPEED);
+        }
+    }
+}
+
+/// Whether the player is swimming into a block at waist height with room to stand
+/// on top of it.
+fn ledge_ahead(store: &ChunkDataStore, pos: Vec3, horizontal: Vec3) -> bool {
+    let Some(dir) = horizontal.try_normalize() else {
+        return false;
+    };
+    let front = pos + dir * (PLAYER_HALF_WIDTH + 0.2);
+    let (x, y, z) = (front.x.floor() as i32, (pos.y + 0.5).floor() as i32, front.z.floor() as i32);
+    is_block_solid(store, x, y, z) && !is_block_solid(store, x, y + 1, z) && !is_block_solid(store, x, y + 2, z)
+}
+
 pub fn apply_velocity(
     time: Res<Time>,
     mut query: Query<(&mut Transform, &Velocity), With<Player>>,
@@ -32,58 +187,52 @@ pub fn apply_velocity(
 
 pub fn ground_collision(
     store: Res<ChunkDataStore>,
+    game_mode: Res<GameMode>,
     mut query: Query<(&mut Transform, &mut Velocity, &mut OnGround), With<Player>>,
 ) {
     for (mut tf, mut vel, mut on_ground) in &mut query {
-        let feet_y = tf.translation.y;
-        let check_y = (feet_y - 0.01).floor() as i32;
-
-        // Check all blocks the player AABB overlaps on X/Z
-        let min_bx = (tf.translation.x - PLAYER_HALF_WIDTH).floor() as i32;
-        let max_bx = (tf.translation.x + PLAYER_HALF_WIDTH - 0.001).floor() as i32;
-        let min_bz = (tf.translation.z - PLAYER_HALF_WIDTH).floor() as i32;
-        let max_bz = (tf.translation.z + PLAYER_HALF_WIDTH - 0.001).floor() as i32;
-
-        let mut solid_below = false;
-        for bx in min_bx..=max_bx {
-            for bz in min_bz..=max_bz {
-                if is_block_solid(&store, bx, check_y, bz) {
-                    solid_below = true;
-                    break;
-                }
-            }
-            if solid_below { break; }
+        // Spectators fly through everything
+        if game_mode.is_noclip() {
+            on_ground.0 = false;
+            continue;
         }
-
-        if solid_below && vel.0.y <= 0.0 {
-            let landing_y = (check_y + 1) as f32;
-            if feet_y <= landing_y + 0.01 {
+        let pos = tf.translation;
+        let feet_y = pos.y;
+        let check_y = (feet_y - 0.01).floor();
+
+        // Highest box top in the block row under the feet, across the player's footprint
+        let support = colliding_boxes(
+            &store,
+            Vec3::new(pos.x - PLAYER_HALF_WIDTH, check_y, pos.z - PLAYER_HALF_WIDTH),
+            Vec3::new(pos.x + PLAYER_HALF_WIDTH, check_y + 1.0, pos.z + PLAYER_HALF_WIDTH),
+        )
+        .into_iter()
+        .map(|(_, max)| max.y)
+        .reduce(f32::max);
+
+        match support {
+            Some(landing_y) if vel.0.y <= 0.0 && feet_y <= landing_y + 0.01 => {
                 tf.translation.y = landing_y;
                 vel.0.y = 0.0;
                 on_ground.0 = true;
-            } else {
-                on_ground.0 = false;
             }
-        } else {
-            on_ground.0 = false;
+            _ => on_ground.0 = false,
         }
 
         // Also check head collision (hitting ceiling while jumping)
         if vel.0.y > 0.0 {
-            let head_y = tf.translation.y + PLAYER_HEIGHT_FULL;
-            let head_check = head_y.floor() as i32;
-            let mut hit_ceiling = false;
-            for bx in min_bx..=max_bx {
-                for bz in min_bz..=max_bz {
-                    if is_block_solid(&store, bx, head_check, bz) {
-                        hit_ceiling = true;
-                        break;
-                    }
-                }
-                if hit_ceiling { break; }
-            }
-            if hit_ceiling {
-                tf.translation.y = head_check as f32 - PLAYER_HEIGHT_FULL - 0.001;
+            let pos = tf.translation;
+            let head_y = pos.y + PLAYER_HEIGHT_FULL;
+            let ceiling = colliding_boxes(
+                &store,
+                Vec3::new(pos.x - PLAYER_HALF_WIDTH, head_y.floor(), pos.z - PLAYER_HALF_WIDTH),
+                Vec3::new(pos.x + PLAYER_HALF_WIDTH, head_y, pos.z + PLAYER_HALF_WIDTH),
+            )
+            .into_iter()
+            .map(|(min, _)| min.y)
+            .reduce(f32::min);
+            if let Some(ceiling_y) = ceiling {
+                tf.translation.y = ceiling_y - PLAYER_HEIGHT_FULL - 0.001;
                 vel.0.y = 0.0;
             }
         }
@@ -92,15 +241,15 @@ pub fn ground_collision(
 }
 
 pub fn jump(
-    input: Res<ButtonInput<KeyCode>>,
+    input: ActionInput,
     dead: Res<crate::ui::death_screen::PlayerDead>,
-    mut query: Query<(&mut Velocity, &OnGround, &mut JustJumped), With<Player>>,
+    mut query: Query<(&mut Velocity, &OnGround, &mut JustJumped, &Flying), With<Player>>,
 ) {
     if dead.0 {
         return;
     }
-    for (mut vel, on_ground, mut just_jumped) in &mut query {
-        if input.just_pressed(KeyCode::Space) && on_ground.0 {
+    for (mut vel, on_ground, mut just_jumped, flying) in &mut query {
+        if input.just_pressed(Action::Jump) && on_ground.0 && !flying.0 {
             vel.0.y = JUMP_VELOCITY;
             just_jumped.0 = true;
         }
@@ -108,10 +257,17 @@ pub fn jump(
 }
 
 pub fn track_fall(
-    mut query: Query<(&Transform, &OnGround, &mut FallTracker, &mut Health), With<Player>>,
+    game_mode: Res<GameMode>,
+    mut query: Query<(&Transform, &OnGround, &Flying, &InWater, &Climbing, &mut FallTracker, &mut Health), With<Player>>,
     mut fall_audio: bevy::ecs::message::MessageWriter<crate::audio::FallDamageAudio>,
 ) {
-    for (tf, on_ground, mut tracker, mut health) in &mut query {
+    for (tf, on_ground, flying, in_water, climbing, mut tracker, mut health) in &mut query {
+        // A fall only counts from where flight ended or the player let go of a ladder,
+        // and water breaks it
+        if flying.0 || in_water.body || climbing.0 || !game_mode.is_vulnerable() {
+            tracker.fall_start_y = None;
+            continue;
+        }
         if !on_ground.0 {
             if tracker.fall_start_y.is_none() {
                 tracker.fall_start_y = Some(tf.translation.y);
@@ -157,12 +313,18 @@ pub fn hunger_system(
     mut timers: ResMut<HungerTimers>,
     mut pending: ResMut<PendingExhaustion>,
     mut query: Query<(&mut Hunger, &mut Health, &Velocity, &OnGround, &mut JustJumped), With<Player>>,
-    input: Res<ButtonInput<KeyCode>>,
+    input: ActionInput,
     dead: Res<crate::ui::death_screen::PlayerDead>,
+    game_mode: Res<GameMode>,
 ) {
     if dead.0 {
         return;
     }
+    // Food only runs down for players who can take damage
+    if !game_mode.is_vulnerable() {
+        pending.0 = 0.0;
+        return;
+    }
     let dt = time.delta_secs();
 
     for (mut hunger, mut health, velocity, on_ground, mut just_jumped) in &mut query {
@@ -175,7 +337,7 @@ pub fn hunger_system(
         // Accumulate exhaustion from sprinting (walking costs 0 in vanilla)
         let horizontal_speed = Vec3::new(velocity.0.x, 0.0, velocity.0.z).length();
         if horizontal_speed > 0.1 && on_ground.0 {
-            let is_sprinting = input.pressed(KeyCode::ControlLeft) && hunger.food_level > 6.0;
+            let is_sprinting = input.pressed(Action::Sprint) && hunger.food_level > 6.0;
             if is_sprinting {
                 hunger.exhaustion += 0.1 * horizontal_speed * dt;
             }
@@ -184,7 +346,7 @@ pub fn hunger_system(
         // Jump exhaustion (once per jump via JustJumped flag)
         if just_jumped.0 {
             just_jumped.0 = false;
-            let is_sprinting = input.pressed(KeyCode::ControlLeft) && hunger.food_level > 6.0;
+            let is_sprinting = input.pressed(Action::Sprint) && hunger.food_level > 6.0;
             hunger.exhaustion += if is_sprinting { 0.2 } else { 0.05 };
         }
 
@@ -241,10 +403,15 @@ pub fn handle_death(
 pub fn drowning(
     time: Res<Time>,
     store: Res<ChunkDataStore>,
+    game_mode: Res<GameMode>,
     mut query: Query<(&Transform, &mut AirSupply, &mut Health), With<Player>>,
 ) {
     let dt = time.delta_secs();
     for (tf, mut air, mut health) in &mut query {
+        if !game_mode.is_vulnerable() {
+            air.current = air.max;
+            continue;
+        }
         let eye_y = tf.translation.y + PLAYER_EYE_HEIGHT;
         let block_x = tf.translation.x.floor() as i32;
         let block_y = eye_y.floor() as i32;
@@ -264,86 +431,98 @@ pub fn drowning(
 
 const PLAYER_HALF_WIDTH: f32 = 0.3;
 const PLAYER_HEIGHT_FULL: f32 = 1.8;
+/// Tallest ledge the player walks up onto without jumping (beds, farmland edges).
+const STEP_HEIGHT: f32 = 0.6;
 
 pub fn horizontal_collision(
     store: Res<ChunkDataStore>,
-    mut query: Query<(&mut Transform, &mut Velocity), With<Player>>,
+    game_mode: Res<GameMode>,
+    mut query: Query<(&mut Transform, &mut Velocity, &OnGround), With<Player>>,
 ) {
-    for (mut tf, mut vel) in &mut query {
-        let pos = tf.translation;
-        let min_by = pos.y.floor() as i32;
-        let max_by = (pos.y + PLAYER_HEIGHT_FULL - 0.01).floor() as i32;
-
-        // Resolve X axis — check all Z blocks the AABB spans
-        let min_bz = (pos.z - PLAYER_HALF_WIDTH).floor() as i32;
-        let max_bz = (pos.z + PLAYER_HALF_WIDTH - 0.001).floor() as i32;
-
-        // Check -X face
-        let neg_x_block = (pos.x - PLAYER_HALF_WIDTH).floor() as i32;
-        let center_bx = pos.x.floor() as i32;
-        if neg_x_block < center_bx {
-            'neg_x: for by in min_by..=max_by {
-                for bz in min_bz..=max_bz {
-                    if is_block_solid(&store, neg_x_block, by, bz) {
-                        tf.translation.x = (neg_x_block + 1) as f32 + PLAYER_HALF_WIDTH + 0.001;
-                        vel.0.x = 0.0;
-                        break 'neg_x;
-                    }
-                }
-            }
+    if game_mode.is_noclip() {
+        return;
+    }
+    for (mut tf, mut vel, on_ground) in &mut query {
+        // Resolve X first, then Z from the updated X position
+        for axis in [0, 2] {
+            resolve_horizontal(&store, &mut tf.translation, &mut vel.0, axis, on_ground.0);
         }
-        // Check +X face
-        let pos_x_block = (tf.translation.x + PLAYER_HALF_WIDTH).floor() as i32;
-        let center_bx_new = tf.translation.x.floor() as i32;
-        if pos_x_block > center_bx_new {
-            'pos_x: for by in min_by..=max_by {
-                for bz in min_bz..=max_bz {
-                    if is_block_solid(&store, pos_x_block, by, bz) {
-                        tf.translation.x = pos_x_block as f32 - PLAYER_HALF_WIDTH - 0.001;
-                        vel.0.x = 0.0;
-                        break 'pos_x;
-                    }
-                }
+    }
+}
+
+/// The player's bounding box with its feet at `pos`.
+fn player_bounds(pos: Vec3) -> (Vec3, Vec3) {
+    (
+        Vec3::new(pos.x - PLAYER_HALF_WIDTH, pos.y, pos.z - PLAYER_HALF_WIDTH),
+        Vec3::new(pos.x + PLAYER_HALF_WIDTH, pos.y + PLAYER_HEIGHT_FULL, pos.z + PLAYER_HALF_WIDTH),
+    )
+}
+
+/// Push the player out of block boxes along one horizontal axis (0 = X, 2 = Z). Boxes
+/// low enough are stepped up onto instead when on the ground with room above. On the
+/// X pass, boxes overlapped less deeply along Z are left for the Z pass, so sliding
+/// along a wall doesn't snag at block edges.
+fn resolve_horizontal(store: &ChunkDataStore, pos: &mut Vec3, vel: &mut Vec3, axis: usize, on_ground: bool) {
+    let (min, max) = player_bounds(*pos);
+    let hits = colliding_boxes(store, min, max);
+    if hits.is_empty() {
+        return;
+    }
+
+    if on_ground {
+        let top = hits.iter().map(|(_, b_max)| b_max.y).fold(f32::MIN, f32::max);
+        if top - pos.y <= STEP_HEIGHT {
+            let stepped = Vec3::new(pos.x, top, pos.z);
+            let (step_min, step_max) = player_bounds(stepped);
+            if colliding_boxes(store, step_min, step_max).is_empty() {
+                *pos = stepped;
+                return;
             }
         }
+    }
 
-        // Resolve Z axis (use updated X position)
-        let updated_x = tf.translation.x;
-        let min_bx = (updated_x - PLAYER_HALF_WIDTH).floor() as i32;
-        let max_bx = (updated_x + PLAYER_HALF_WIDTH - 0.001).floor() as i32;
-
-        // Check -Z face
-        let neg_z_block = (tf.translation.z - PLAYER_HALF_WIDTH).floor() as i32;
-        let center_bz = tf.translation.z.floor() as i32;
-        if neg_z_block < center_bz {
-            'neg_z: for by in min_by..=max_by {
-                for bx in min_bx..=max_bx {
-                    if is_block_solid(&store, bx, by, neg_z_block) {
-                        tf.translation.z = (neg_z_block + 1) as f32 + PLAYER_HALF_WIDTH + 0.001;
-                        vel.0.z = 0.0;
-                        break 'neg_z;
-                    }
-                }
-            }
+    let other = 2 - axis;
+    for (b_min, b_max) in hits {
+        let depth = |i: usize| (max[i] - b_min[i]).min(b_max[i] - min[i]);
+        if axis == 0 && depth(axis) > depth(other) {
+            continue;
         }
-        // Check +Z face
-        let pos_z_block = (tf.translation.z + PLAYER_HALF_WIDTH).floor() as i32;
-        let center_bz_new = tf.translation.z.floor() as i32;
-        if pos_z_block > center_bz_new {
-            'pos_z: for by in min_by..=max_by {
-                for bx in min_bx..=max_bx {
-                    if is_block_solid(&store, bx, by, pos_z_block) {
-                        tf.translation.z = pos_z_block as f32 - PLAYER_HALF_WIDTH - 0.001;
-                        vel.0.z = 0.0;
-                        break 'pos_z;
+        if (b_min[axis] + b_max[axis]) / 2.0 > pos[axis] {
+            pos[axis] = pos[axis].min(b_min[axis] - PLAYER_HALF_WIDTH - 0.001);
+        } else {
+            pos[axis] = pos[axis].max(b_max[axis] + PLAYER_HALF_WIDTH + 0.001);
+        }
+        vel[axis] = 0.0;
+    }
+}
+
+/// World-space collision boxes of the blocks that overlap the box `min..max`
+/// (touching isn't overlapping).
+fn colliding_boxes(store: &ChunkDataStore, min: Vec3, max: Vec3) -> Vec<(Vec3, Vec3)> {
+    let lo = min.floor().as_ivec3();
+    let hi = max.ceil().as_ivec3() - IVec3::ONE;
+    let mut boxes = Vec::new();
+    for x in lo.x..=hi.x {
+        for y in lo.y..=hi.y {
+            for z in lo.z..=hi.z {
+                let origin = Vec3::new(x as f32, y as f32, z as f32);
+                for b in collision_boxes(get_block_state_at(store, x, y, z)) {
+                    let (b_min, b_max) = (origin + b.min, origin + b.max);
+                    if b_min.cmplt(max).all() && b_max.cmpgt(min).all() {
+                        boxes.push((b_min, b_max));
                     }
                 }
             }
         }
     }
+    boxes
 }
 
 fn get_block_at(store: &ChunkDataStore, x: i32, y: i32, z: i32) -> crate::block::BlockType {
+    get_block_state_at(store, x, y, z).block()
+}
+
+fn get_block_state_at(store: &ChunkDataStore, x: i32, y: i32, z: i32) -> BlockState {
     let size = CHUNK_SIZE as i32;
     let cx = x.div_euclid(size);
     let cy = y.div_euclid(size);
@@ -351,14 +530,14 @@ fn get_block_at(store: &ChunkDataStore, x: i32, y: i32, z: i32) -> crate::block:
     let chunk_pos = IVec3::new(cx, cy, cz);
 
     let Some(chunk) = store.chunks.get(&chunk_pos) else {
-        return crate::block::BlockType::Air;
+        return BlockState::AIR;
     };
 
     let lx = x.rem_euclid(size) as usize;
     let ly = y.rem_euclid(size) as usize;
     let lz = z.rem_euclid(size) as usize;
 
-    chunk.get(lx, ly, lz)
+    chunk.get_state(lx, ly, lz)
 }
 
 /// When sneaking on ground, prevent the player from walking off block edges.
@@ -425,15 +604,21 @@ pub fn sneak_edge_protection(
     }
 }
 
-/// Deal 4 damage every 0.5 seconds when the player falls below Y=-10 (into the void).
+/// Deal 4 damage every 0.5 seconds when the player falls 10 blocks below the world floor (into the void).
 pub fn void_damage(
     time: Res<Time>,
     mut timer: Local<f32>,
+    height: Res<WorldHeight>,
+    game_mode: Res<GameMode>,
     mut query: Query<(&Transform, &mut Health), With<Player>>,
 ) {
+    if !game_mode.is_vulnerable() {
+        *timer = 0.0;
+        return;
+    }
     let dt = time.delta_secs();
     for (tf, mut health) in &mut query {
-        if tf.translation.y < -10.0 {
+        if tf.translation.y < (height.min_y - 10) as f32 {
             *timer += dt;
             while *timer >= 0.5 {
                 *timer -= 0.5;
@@ -460,5 +645,52 @@ fn is_block_solid(store: &ChunkDataStore, x: i32, y: i32, z: i32) -> bool {
     let ly = y.rem_euclid(size) as usize;
     let lz = z.rem_euclid(size) as usize;
 
-    chunk.get(lx, ly, lz).is_solid()
+    chunk.get_state(lx, ly, lz).is_solid()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn current_drift_stays_bounded_without_movement_input() {
+        let mut app = App::new();
+        app.add_plugins(bevy::input::InputPlugin)
+            .init_resource::<Time>()
+            .init_resource::<crate::controls::Bindings>()
+            .init_resource::<crate::controls::GamepadCursor>()
+            .init_resource::<crate::ui::death_screen::PlayerDead>()
+            .init_resource::<ChunkDataStore>()
+            .add_systems(Update, swim);
+        let current = Vec3::new(0.6, -0.2, 0.8);
+        let player = app
+            .world_mut()
+            .spawn((
+                Player,
+                Transform::default(),
+                Velocity::default(),
+                InWater { body: true, eyes: true, current },
+                Flying(false),
+                PlayerPitch(0.0),
+            ))
+            .id();
+
+        // Nothing resets the velocity, as with the inventory open in flowing water
+        for _ in 0..600 {
+            app.world_mut().resource_mut::<Time>().advance_by(Duration::from_millis(16));
+            app.update();
+        }
+        let vel = app.world().get::<Velocity>(player).unwrap().0;
+        assert!(vel.length() <= CURRENT_SPEED * current.length() + 1e-4, "drifting at {vel}");
+        assert!(vel.dot(current) > 0.0, "carried along the current");
+    }
+
+    #[test]
+    fn current_never_slows_a_faster_swimmer() {
+        let fast = Vec3::new(4.0, 0.0, 0.0);
+        assert_eq!(drift_with_current(fast, Vec3::X, 0.016), fast);
+        let still = drift_with_current(Vec3::ZERO, Vec3::Z, 0.5);
+        assert!((still - Vec3::Z * CURRENT_SPEED).length() < 1e-5);
+    }
 }
@@ -0,0 +1,142 @@
+pub mod pack;
+
+use bevy::prelude::*;
+use bevy::asset::io::file::FileAssetReader;
+use bevy::asset::RenderAssetUsages;
+use bevy::ecs::message::{Message, MessageWriter};
+use bevy::image::{CompressedImageFormats, ImageSampler, ImageType};
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use pack::{
+    discover_packs, pack_fingerprint, stack_tiles, stitch_atlas, Texture, TileAnimation, DEFAULT_PACK_DIR, PACKS_DIR,
+};
+
+/// How often pack directories are checked for changed files.
+const WATCH_INTERVAL_SECONDS: f32 = 1.0;
+
+/// The block texture atlas, stitched from the active resource packs. The image asset is
+/// replaced in place when packs change, so every handle to it stays valid.
+#[derive(Resource)]
+pub struct BlockAtlas {
+    pub image: Handle<Image>,
+    /// Pixel size of one tile; larger than 16 with high-resolution packs.
+    pub tile_size: u32,
+    /// Tiles with animated textures, for the block materials.
+    pub animations: Vec<TileAnimation>,
+}
+
+/// Sent after the atlas image has been rebuilt, for anything derived from it.
+#[derive(Message)]
+pub struct AtlasRebuilt {
+    pub tile_size: u32,
+}
+
+/// Files the current atlas was built from, polled for changes.
+#[derive(Resource)]
+struct PackWatch {
+    timer: Timer,
+    fingerprint: Vec<(PathBuf, Option<SystemTime>, u64)>,
+}
+
+/// Read and decode a PNG texture into RGBA8.
+fn decode_png(path: &Path) -> Option<Texture> {
+    let bytes = std::fs::read(path).ok()?;
+    let image = match Image::from_buffer(
+        &bytes,
+        ImageType::Extension("png"),
+        CompressedImageFormats::NONE,
+        true,
+        ImageSampler::Default,
+        RenderAssetUsages::default(),
+    ) {
+        Ok(image) => image,
+        Err(e) => {
+            warn!("Skipping texture {}: {}", path.display(), e);
+            return None;
+        }
+    };
+    let image = image.convert(TextureFormat::Rgba8UnormSrgb)?;
+    Some(Texture { width: image.width(), height: image.height(), pixels: image.data? })
+}
+
+/// The built-in pack's directory and the installed packs' directory. Both sit under the
+/// root the asset server loads from (the manifest directory under cargo, otherwise the
+/// executable's), so starting the game from another directory still finds them.
+fn pack_dirs() -> (PathBuf, PathBuf) {
+    let root = FileAssetReader::get_base_path();
+    (root.join(DEFAULT_PACK_DIR), root.join(PACKS_DIR))
+}
+
+/// Stitch the atlas from the packs currently installed.
+fn build_atlas_image() -> (Image, u32, Vec<TileAnimation>) {
+    let (default_dir, packs_dir) = pack_dirs();
+    let packs = discover_packs(&default_dir, &packs_dir);
+    let names: Vec<&str> = packs.iter().map(|p| p.manifest.name.as_str()).collect();
+    info!("[PACKS] Stacking resource packs {:?}", names);
+
+    let stitched = stitch_atlas(&stack_tiles(&packs, decode_png));
+    let atlas = stitched.texture;
+    let tile_size = atlas.width / crate::block::atlas::ATLAS_TILES as u32;
+    let image = Image::new(
+        Extent3d { width: atlas.width, height: atlas.height, depth_or_array_layers: 1 },
+        TextureDimension::D2,
+        atlas.pixels,
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::default(),
+    );
+    (image, tile_size, stitched.animations)
+}
+
+fn current_fingerprint() -> Vec<(PathBuf, Option<SystemTime>, u64)> {
+    let (default_dir, packs_dir) = pack_dirs();
+    pack_fingerprint(&default_dir, &packs_dir)
+}
+
+/// Build the atlas before anything that draws blocks is set up.
+fn setup_block_atlas(mut commands: Commands, mut images: ResMut<Assets<Image>>) {
+    let (image, tile_size, animations) = build_atlas_image();
+    commands.insert_resource(BlockAtlas { image: images.add(image), tile_size, animations });
+    commands.insert_resource(PackWatch {
+        timer: Timer::from_seconds(WATCH_INTERVAL_SECONDS, TimerMode::Repeating),
+        fingerprint: current_fingerprint(),
+    });
+}
+
+/// Restitch the atlas when a pack is added, removed or edited.
+fn reload_changed_packs(
+    time: Res<Time>,
+    mut watch: ResMut<PackWatch>,
+    mut atlas: ResMut<BlockAtlas>,
+    mut images: ResMut<Assets<Image>>,
+    mut rebuilt: MessageWriter<AtlasRebuilt>,
+) {
+    if !watch.timer.tick(time.delta()).just_finished() {
+        return;
+    }
+    let fingerprint = current_fingerprint();
+    if fingerprint == watch.fingerprint {
+        return;
+    }
+    watch.fingerprint = fingerprint;
+
+    let (image, tile_size, animations) = build_atlas_image();
+    if let Err(e) = images.insert(&atlas.image, image) {
+        warn!("Failed to replace block atlas: {}", e);
+        return;
+    }
+    atlas.tile_size = tile_size;
+    atlas.animations = animations;
+    rebuilt.write(AtlasRebuilt { tile_size });
+}
+
+pub struct ResourcePackPlugin;
+
+impl Plugin for ResourcePackPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_message::<AtlasRebuilt>()
+            .add_systems(PreStartup, setup_block_atlas)
+            .add_systems(Update, reload_changed_packs);
+    }
+}
@@ -0,0 +1,520 @@
+use bevy::prelude::*;
+use serde::Deserialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use crate::block::atlas::{tile_tint, ATLAS_TILES, ATLAS_TILE_COUNT, TILE_SOURCES};
+
+/// Directory holding one subdirectory per installed resource pack, beside the built-in one.
+pub const PACKS_DIR: &str = "resourcepacks";
+
+/// The built-in pack, always at the bottom of the stack: the asset server's own folder.
+pub const DEFAULT_PACK_DIR: &str = "assets";
+
+const MANIFEST_FILE: &str = "pack.json";
+
+/// Where block textures live inside a pack.
+const BLOCK_TEXTURES_DIR: &str = "textures/blocks";
+
+/// Tile size of the atlas when no pack supplies any texture.
+const DEFAULT_TILE_SIZE: u32 = 16;
+
+/// Stands in for tiles no pack supplies.
+const MISSING_COLOR: [u8; 4] = [255, 0, 255, 255];
+
+/// Appended to a texture's file name for its animation metadata, as in Minecraft packs
+/// (`water.png.mcmeta`).
+const ANIMATION_SUFFIX: &str = ".mcmeta";
+
+/// Animation frame times are in game ticks.
+const TICKS_PER_SECOND: f32 = 20.0;
+
+/// Longest cycle `animation_cycle` reports: an hour, past which an f32 clock in seconds
+/// gets too coarse to step frames smoothly.
+const MAX_ANIMATION_CYCLE_TICKS: u64 = 72_000;
+
+/// A pack's `pack.json`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct PackManifest {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    /// Packs with higher priority stack on top, overriding textures of lower ones.
+    #[serde(default)]
+    pub priority: i32,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResourcePack {
+    pub dir: PathBuf,
+    pub manifest: PackManifest,
+}
+
+impl ResourcePack {
+    fn load(dir: &Path) -> Option<Self> {
+        let json = fs::read_to_string(dir.join(MANIFEST_FILE)).ok()?;
+        match serde_json::from_str(&json) {
+            Ok(manifest) => Some(Self { dir: dir.to_path_buf(), manifest }),
+            Err(e) => {
+                warn!("Ignoring resource pack {}: bad {}: {}", dir.display(), MANIFEST_FILE, e);
+                None
+            }
+        }
+    }
+
+    fn texture_path(&self, file: &str) -> PathBuf {
+        self.dir.join(BLOCK_TEXTURES_DIR).join(file)
+    }
+
+    /// Animation metadata next to a texture, if the pack has any.
+    fn animation(&self, file: &str) -> Option<Animation> {
+        let path = self.texture_path(&format!("{file}{ANIMATION_SUFFIX}"));
+        let json = fs::read_to_string(&path).ok()?;
+        match serde_json::from_str::<AnimationFile>(&json) {
+            Ok(meta) => Some(meta.animation),
+            Err(e) => {
+                warn!("Ignoring animation {}: {}", path.display(), e);
+                None
+            }
+        }
+    }
+}
+
+/// A texture's `.mcmeta` file.
+#[derive(Deserialize)]
+struct AnimationFile {
+    animation: Animation,
+}
+
+/// How the frames of an animated texture play. Frames are stacked downwards in the
+/// texture, each as tall as it is wide.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Animation {
+    /// Ticks each frame shows for.
+    #[serde(default = "one_tick")]
+    pub frametime: u32,
+    /// Blend each frame into the next instead of cutting between them.
+    #[serde(default)]
+    pub interpolate: bool,
+    /// Frame order, as indices from the top; every frame in order when left out.
+    #[serde(default)]
+    pub frames: Option<Vec<u32>>,
+}
+
+fn one_tick() -> u32 {
+    1
+}
+
+/// Packs in stacking order, lowest first: the built-in pack, then every directory in
+/// `packs_dir` with a manifest, by priority (ties by directory name).
+pub fn discover_packs(default_dir: &Path, packs_dir: &Path) -> Vec<ResourcePack> {
+    let mut installed: Vec<ResourcePack> = fs::read_dir(packs_dir)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok())
+                .filter(|entry| entry.path().is_dir())
+                .filter_map(|entry| ResourcePack::load(&entry.path()))
+                .collect()
+        })
+        .unwrap_or_default();
+    installed.sort_by(|a, b| a.manifest.priority.cmp(&b.manifest.priority).then_with(|| a.dir.cmp(&b.dir)));
+
+    let mut packs: Vec<ResourcePack> = Vec::new();
+    match ResourcePack::load(default_dir) {
+        Some(pack) => packs.push(pack),
+        None if !default_dir.join(MANIFEST_FILE).exists() => {
+            warn!("No built-in resource pack: {} not found", default_dir.join(MANIFEST_FILE).display());
+        }
+        // A manifest that failed to parse has been reported already
+        None => {}
+    }
+    packs.extend(installed);
+    packs
+}
+
+/// Every file the atlas is built from, with its modification time and size. Changes
+/// when a pack is added or removed, or any of its manifest or textures is edited.
+pub fn pack_fingerprint(default_dir: &Path, packs_dir: &Path) -> Vec<(PathBuf, Option<SystemTime>, u64)> {
+    let mut dirs = vec![default_dir.to_path_buf()];
+    if let Ok(entries) = fs::read_dir(packs_dir) {
+        dirs.extend(entries.filter_map(|entry| entry.ok()).map(|entry| entry.path()));
+    }
+
+    let mut files = Vec::new();
+    for dir in dirs {
+        files.push(dir.join(MANIFEST_FILE));
+        if let Ok(entries) = fs::read_dir(dir.join(BLOCK_TEXTURES_DIR)) {
+            files.extend(entries.filter_map(|entry| entry.ok()).map(|entry| entry.path()));
+        }
+    }
+    files.sort();
+    files
+        .into_iter()
+        .filter_map(|path| {
+            let meta = fs::metadata(&path).ok()?;
+            Some((path, meta.modified().ok(), meta.len()))
+        })
+        .collect()
+}
+
+/// An RGBA8 image, rows top to bottom.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Texture {
+    pub width: u32,
+    pub height: u32,
+    pub pixels: Vec<u8>,
+}
+
+impl Texture {
+    fn filled(width: u32, height: u32, color: [u8; 4]) -> Self {
+        Self { width, height, pixels: color.repeat((width * height) as usize) }
+    }
+
+    fn pixel(&self, x: u32, y: u32) -> [u8; 4] {
+        let i = ((y * self.width + x) * 4) as usize;
+        [self.pixels[i], self.pixels[i + 1], self.pixels[i + 2], self.pixels[i + 3]]
+    }
+
+    fn set_pixel(&mut self, x: u32, y: u32, color: [u8; 4]) {
+        let i = ((y * self.width + x) * 4) as usize;
+        self.pixels[i..i + 4].copy_from_slice(&color);
+    }
+
+    /// Side of the square first frame. Animated textures stack their frames downwards.
+    fn frame_size(&self) -> u32 {
+        self.width.min(self.height)
+    }
+
+    /// Frames stacked in the texture.
+    fn frame_count(&self) -> u32 {
+        self.height / self.frame_size()
+    }
+}
+
+/// A tile's texture from the pack stack, with the animation from the same pack.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TileTexture {
+    pub texture: Texture,
+    pub animation: Option<Animation>,
+}
+
+impl TileTexture {
+    /// Sheet frames in the order they play. Textures without animation metadata show
+    /// only their first frame.
+    fn frame_order(&self) -> Vec<u32> {
+        let count = self.texture.frame_count();
+        match &self.animation {
+            Some(Animation { frames: Some(frames), .. }) => {
+                frames.iter().copied().filter(|&frame| frame < count).collect()
+            }
+            Some(_) => (0..count).collect(),
+            None => vec![0],
+        }
+    }
+}
+
+/// The texture for each atlas tile from the highest pack that has a readable one.
+/// `decode` reads and decodes a texture file, or returns `None` if it can't.
+pub fn stack_tiles(
+    packs: &[ResourcePack],
+    decode: impl Fn(&Path) -> Option<Texture>,
+) -> Vec<(u32, Option<TileTexture>)> {
+    TILE_SOURCES
+        .iter()
+        .map(|&(index, file)| {
+            let texture = packs.iter().rev().find_map(|pack| {
+                let path = pack.texture_path(file);
+                let texture = if path.exists() { decode(&path) } else { None }?;
+                Some(TileTexture { texture, animation: pack.animation(file) })
+            });
+            (index, texture)
+        })
+        .collect()
+}
+
+/// Where an animated tile's frames are in the atlas, and how they play.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TileAnimation {
+    /// The tile blocks are meshed with, holding the first frame.
+    pub tile: u32,
+    /// Tile holding the second frame; the rest follow it in order.
+    pub next_tile: u32,
+    pub frames: u32,
+    /// Seconds each frame shows for.
+    pub frame_time: f32,
+    pub interpolate: bool,
+}
+
+/// The stitched atlas image and the animated tiles in it.
+pub struct StitchedAtlas {
+    pub texture: Texture,
+    pub animations: Vec<TileAnimation>,
+}
+
+/// Stitch tile textures into the atlas grid. Tiles are as large as the largest texture's
+/// first frame, so high-resolution packs keep their detail; smaller textures are scaled up
+/// nearest-neighbor. Tiles no pack supplies are magenta.
+///
+/// An animated texture's first frame goes in its own tile and the rest in free tiles after
+/// the last one used, as far as they fit.
+pub fn stitch_atlas(tiles: &[(u32, Option<TileTexture>)]) -> StitchedAtlas {
+    let tile_size = tiles
+        .iter()
+        .filter_map(|(_, tile)| tile.as_ref().map(|tile| tile.texture.frame_size()))
+        .max()
+        .unwrap_or(DEFAULT_TILE_SIZE);
+    let grid = ATLAS_TILES as u32;
+    let mut atlas = Texture::filled(grid * tile_size, grid * tile_size, MISSING_COLOR);
+    let mut free_tile = tiles.iter().map(|&(index, _)| index + 1).max().unwrap_or(0);
+    let mut animations = Vec::new();
+
+    for (index, tile) in tiles {
+        let Some(tile) = tile else {
+            continue;
+        };
+        let tint = tile_tint(*index);
+        let order = tile.frame_order();
+        let (first, rest) = order.split_first().map_or((0, &[][..]), |(&first, rest)| (first, rest));
+        draw_frame(&mut atlas, *index, tile_size, &tile.texture, first, tint);
+        let (Some(animation), false) = (&tile.animation, rest.is_empty()) else {
+            continue;
+        };
+
+        let room = (ATLAS_TILE_COUNT as u32).saturating_sub(free_tile) as usize;
+        if rest.len() > room {
+            warn!("Atlas is out of tiles: tile {} keeps {} of its {} frames", index, room + 1, order.len());
+        }
+        let rest = &rest[..rest.len().min(room)];
+        if rest.is_empty() {
+            continue;
+        }
+        for (i, &frame) in rest.iter().enumerate() {
+            draw_frame(&mut atlas, free_tile + i as u32, tile_size, &tile.texture, frame, tint);
+        }
+        animations.push(TileAnimation {
+            tile: *index,
+            next_tile: free_tile,
+            frames: rest.len() as u32 + 1,
+            frame_time: animation.frametime.max(1) as f32 / TICKS_PER_SECOND,
+            interpolate: animation.interpolate,
+        });
+        free_tile += rest.len() as u32;
+    }
+    StitchedAtlas { texture: atlas, animations }
+}
+
+/// Draw one frame of a texture into an atlas tile, scaled to the tile size.
+/// Seconds after which every animated tile is back on its first frame together: the least
+/// common multiple of their cycles, counted in whole ticks. Capped at an hour; tiles whose
+/// cycle doesn't divide it then skip ahead once an hour.
+pub fn animation_cycle(animations: &[TileAnimation]) -> f64 {
+    let ticks = animations
+        .iter()
+        .map(|animation| (animation.frame_time * TICKS_PER_SECOND).round() as u64 * animation.frames as u64)
+        .filter(|&ticks| ticks > 0)
+        .try_fold(1, |cycle, ticks| {
+            let cycle = cycle / gcd(cycle, ticks) * ticks;
+            (cycle <= MAX_ANIMATION_CYCLE_TICKS).then_some(cycle)
+        })
+        .unwrap_or(MAX_ANIMATION_CYCLE_TICKS);
+    ticks as f64 / TICKS_PER_SECOND as f64
+}
+
+fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 { a } else { gcd(b, a % b) }
+}
+
+fn draw_frame(atlas: &mut Texture, tile: u32, tile_size: u32, texture: &Texture, frame: u32, tint: Option<(u8, u8, u8)>) {
+    let grid = ATLAS_TILES as u32;
+    let size = texture.frame_size();
+    let (x0, y0) = ((tile % grid) * tile_size, (tile / grid) * tile_size);
+    for dy in 0..tile_size {
+        for dx in 0..tile_size {
+            let mut pixel = texture.pixel(dx * size / tile_size, frame * size + dy * size / tile_size);
+            if let Some(tint) = tint {
+                pixel = tint_pixel(pixel, tint);
+            }
+            atlas.set_pixel(x0 + dx, y0 + dy, pixel);
+        }
+    }
+}
+
+fn tint_pixel(pixel: [u8; 4], tint: (u8, u8, u8)) -> [u8; 4] {
+    [
+        ((pixel[0] as u16 * tint.0 as u16) / 255) as u8,
+        ((pixel[1] as u16 * tint.1 as u16) / 255) as u8,
+        ((pixel[2] as u16 * tint.2 as u16) / 255) as u8,
+        pixel[3],
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("mineclaude-packs-{}-{}", std::process::id(), name));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    /// A pack whose "textures" are single bytes naming the pack, for a fake decoder.
+    fn write_pack(dir: &Path, name: &str, priority: i32, textures: &[&str], marker: u8) {
+        fs::create_dir_all(dir.join(BLOCK_TEXTURES_DIR)).unwrap();
+        let manifest = format!(r#"{{ "name": "{name}", "priority": {priority} }}"#);
+        fs::write(dir.join(MANIFEST_FILE), manifest).unwrap();
+        for file in textures {
+            fs::write(dir.join(BLOCK_TEXTURES_DIR).join(file), [marker]).unwrap();
+        }
+    }
+
+    fn decode_marker(path: &Path) -> Option<Texture> {
+        let marker = fs::read(path).ok()?[0];
+        Some(Texture::filled(16, 16, [marker, marker, marker, 255]))
+    }
+
+    #[test]
+    fn packs_stack_by_priority_over_the_default() {
+        let root = temp_dir("stack");
+        let default = root.join("default");
+        let packs = root.join("packs");
+        write_pack(&default, "Default", 100, &["stone.png", "dirt.png"], 1);
+        write_pack(&packs.join("b"), "High", 5, &["stone.png"], 3);
+        write_pack(&packs.join("a"), "Low", 0, &["stone.png", "sand.png"], 2);
+        fs::create_dir_all(packs.join("no-manifest")).unwrap();
+
+        let stack = discover_packs(&default, &packs);
+        let names: Vec<&str> = stack.iter().map(|p| p.manifest.name.as_str()).collect();
+        assert_eq!(names, ["Default", "Low", "High"]);
+
+        let tiles = stack_tiles(&stack, decode_marker);
+        let marker = |file: &str| {
+            let index = TILE_SOURCES.iter().find(|&&(_, f)| f == file).unwrap().0;
+            let (_, texture) = tiles.iter().find(|(i, _)| *i == index).unwrap();
+            texture.as_ref().map(|t| t.texture.pixels[0])
+        };
+        assert_eq!(marker("stone.png"), Some(3));
+        assert_eq!(marker("sand.png"), Some(2));
+        assert_eq!(marker("dirt.png"), Some(1));
+        assert_eq!(marker("gravel.png"), None);
+    }
+
+    fn still(texture: Texture) -> Option<TileTexture> {
+        Some(TileTexture { texture, animation: None })
+    }
+
+    /// A 16px animation of `frames` frames, each filled with its own index.
+    fn animated(frames: u32, animation: Animation) -> Option<TileTexture> {
+        let mut texture = Texture::filled(16, 16 * frames, [0, 0, 0, 255]);
+        for y in 0..16 * frames {
+            for x in 0..16 {
+                texture.set_pixel(x, y, [(y / 16) as u8, 0, 0, 255]);
+            }
+        }
+        Some(TileTexture { texture, animation: Some(animation) })
+    }
+
+    #[test]
+    fn atlas_takes_the_largest_tile_size() {
+        let small = Texture::filled(16, 16, [10, 20, 30, 255]);
+        // A 32px texture with two frames but no animation metadata; only the top one is used
+        let mut large = Texture::filled(32, 64, [0, 0, 0, 255]);
+        large.set_pixel(31, 31, [200, 100, 50, 255]);
+        large.set_pixel(0, 32, [1, 2, 3, 255]);
+
+        let StitchedAtlas { texture: atlas, animations } = stitch_atlas(&[(0, still(small)), (1, still(large)), (4, None)]);
+        assert!(animations.is_empty());
+        assert_eq!((atlas.width, atlas.height), (512, 512));
+        // Small textures are scaled up to fill their tile
+        assert_eq!(atlas.pixel(31, 31), [10, 20, 30, 255]);
+        assert_eq!(atlas.pixel(32 + 31, 31), [200, 100, 50, 255]);
+        assert_eq!(atlas.pixel(32, 0), [0, 0, 0, 255]);
+        assert_eq!(atlas.pixel(4 * 32, 0), MISSING_COLOR);
+    }
+
+    #[test]
+    fn tinted_tiles_are_recolored() {
+        let white = Texture::filled(16, 16, [255, 255, 255, 255]);
+        let atlas = stitch_atlas(&[(2, still(white))]).texture;
+        assert_eq!(atlas.pixel(2 * 16, 0), [124, 189, 107, 255]);
+        assert_eq!(atlas.width, 256);
+    }
+
+    #[test]
+    fn animation_frames_fill_free_tiles() {
+        let animation = Animation { frametime: 2, interpolate: true, frames: None };
+        let stitched = stitch_atlas(&[(4, animated(4, animation)), (5, still(Texture::filled(16, 16, [9; 4])))]);
+        assert_eq!(
+            stitched.animations,
+            [TileAnimation { tile: 4, next_tile: 6, frames: 4, frame_time: 0.1, interpolate: true }]
+        );
+        let frame_in = |tile: u32| stitched.texture.pixel(tile % 16 * 16, tile / 16 * 16)[0];
+        assert_eq!([frame_in(4), frame_in(6), frame_in(7), frame_in(8)], [0, 1, 2, 3]);
+        assert_eq!(frame_in(5), 9);
+    }
+
+    #[test]
+    fn animation_frame_order_and_overflow() {
+        // Listed frames play in their order, out-of-range ones are dropped
+        let animation = Animation { frametime: 1, interpolate: false, frames: Some(vec![2, 0, 7, 2]) };
+        let stitched = stitch_atlas(&[(0, animated(3, animation))]);
+        let frame_in = |tile: u32| stitched.texture.pixel(tile % 16 * 16, tile / 16 * 16)[0];
+        assert_eq!(stitched.animations[0].frames, 3);
+        assert_eq!([frame_in(0), frame_in(1), frame_in(2)], [2, 0, 2]);
+
+        // Frames past the end of the atlas are left out
+        let animation = Animation { frametime: 1, interpolate: false, frames: None };
+        let stitched = stitch_atlas(&[(250, animated(10, animation))]);
+        assert_eq!(stitched.animations[0].frames, 6);
+    }
+
+    #[test]
+    fn animation_cycle_fits_every_tile() {
+        let tile = |frames, frame_time| TileAnimation { tile: 0, next_tile: 1, frames, frame_time, interpolate: false };
+        assert_eq!(animation_cycle(&[]), 0.05);
+        // 4 frames of 2 ticks and 3 frames of 4 ticks line up every 24 ticks
+        assert_eq!(animation_cycle(&[tile(4, 0.1), tile(3, 0.2)]), 1.2);
+        // Cycles that only line up after hours are capped
+        let primes = [tile(1009, 0.05), tile(1013, 0.05)];
+        assert_eq!(animation_cycle(&primes), 3600.0);
+    }
+
+    #[test]
+    fn animation_metadata_comes_from_the_texture_pack() {
+        let root = temp_dir("animation");
+        let default = root.join("default");
+        let packs = root.join("packs");
+        write_pack(&default, "Default", 0, &["water.png"], 1);
+        let meta = r#"{ "animation": { "frametime": 4, "interpolate": true } }"#;
+        fs::write(default.join(BLOCK_TEXTURES_DIR).join("water.png.mcmeta"), meta).unwrap();
+
+        let water = |stack: &[ResourcePack]| {
+            let tiles = stack_tiles(stack, decode_marker);
+            tiles.into_iter().find(|(i, _)| *i == 17).unwrap().1.unwrap().animation
+        };
+        let expected = Animation { frametime: 4, interpolate: true, frames: None };
+        assert_eq!(water(&discover_packs(&default, &packs)), Some(expected));
+
+        // A pack replacing the texture without metadata replaces the animation too
+        write_pack(&packs.join("still"), "Still", 0, &["water.png"], 2);
+        assert_eq!(water(&discover_packs(&default, &packs)), None);
+    }
+
+    #[test]
+    fn fingerprint_changes_with_pack_files() {
+        let root = temp_dir("fingerprint");
+        let default = root.join("default");
+        let packs = root.join("packs");
+        write_pack(&default, "Default", 0, &["stone.png"], 1);
+        let before = pack_fingerprint(&default, &packs);
+
+        write_pack(&packs.join("new"), "New", 0, &["stone.png", "dirt.png"], 2);
+        let added = pack_fingerprint(&default, &packs);
+        assert_ne!(before, added);
+
+        fs::write(packs.join("new").join(BLOCK_TEXTURES_DIR).join("dirt.png"), [2, 2]).unwrap();
+        assert_ne!(pack_fingerprint(&default, &packs), added);
+    }
+}
@@ -0,0 +1,474 @@
+use bevy::prelude::Resource;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::world::chunk::CHUNK_SIZE;
+
+/// Directory holding one subdirectory per world.
+pub const SAVES_DIR: &str = "saves";
+
+const LEVEL_FILE: &str = "level.json";
+
+/// Per-world files from before worlds were named, when they lived directly in `saves/`.
+const LEGACY_FILES: [&str; 5] = ["player.json", "chests.json", "furnaces.json", "saplings.json", "crops.json"];
+
+/// Directory the pre-named-worlds layout kept chunks in; it becomes that world's directory.
+const LEGACY_WORLD_DIR: &str = "world";
+
+/// Vertical extent of new worlds: Y -64 up to (not including) 320.
+pub const DEFAULT_MIN_Y: i32 = -64;
+pub const DEFAULT_HEIGHT: i32 = 384;
+
+/// Vertical extent of worlds from before the height was configurable: Y 0 to 256.
+const LEGACY_MIN_Y: i32 = 0;
+const LEGACY_HEIGHT: i32 = 256;
+
+fn legacy_height() -> i32 {
+    LEGACY_HEIGHT
+}
+
+/// Tallest world that can be created, in blocks.
+pub const MAX_HEIGHT: i32 = 4096;
+
+/// Checks a world's vertical extent: the bottom and the height must both be whole chunks,
+/// the height at least one chunk and at most `MAX_HEIGHT`, and the bottom within
+/// `MAX_HEIGHT` of Y 0.
+pub fn check_height(min_y: i32, height: i32) -> Result<(), String> {
+    let size = CHUNK_SIZE as i32;
+    if min_y % size != 0 || height % size != 0 {
+        return Err(format!("world bottom {} and height {} must be multiples of {}", min_y, height, size));
+    }
+    if !(size..=MAX_HEIGHT).contains(&height) {
+        return Err(format!("world height {} must be between {} and {}", height, size, MAX_HEIGHT));
+    }
+    if min_y.abs() > MAX_HEIGHT {
+        return Err(format!("world bottom {} must be within {} of Y 0", min_y, MAX_HEIGHT));
+    }
+    Ok(())
+}
+
+/// How the player plays a world. Kept per world and applied while it's played.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, Resource)]
+pub enum GameMode {
+    /// Health, hunger and gathering every block by hand.
+    #[default]
+    Survival,
+    /// Flight, instant breaking and every item from the palette; nothing runs out or hurts.
+    Creative,
+    /// Survival without breaking or placing blocks, for playing through built maps.
+    Adventure,
+    /// Flying through walls to look around, touching nothing.
+    Spectator,
+}
+
+impl GameMode {
+    pub fn display_name(self) -> &'static str {
+        match self {
+            Self::Survival => "Survival",
+            Self::Creative => "Creative",
+            Self::Adventure => "Adventure",
+            Self::Spectator => "Spectator",
+        }
+    }
+
+    /// The mode after this one, for cycling through them.
+    pub fn next(self) -> Self {
+        match self {
+            Self::Survival => Self::Creative,
+            Self::Creative => Self::Adventure,
+            Self::Adventure => Self::Spectator,
+            Self::Spectator => Self::Survival,
+        }
+    }
+
+    /// Whether the player takes damage, gets hungry and runs out of air, and mobs go
+    /// after them.
+    pub fn is_vulnerable(self) -> bool {
+        matches!(self, Self::Survival | Self::Adventure)
+    }
+
+    /// Whether placing, eating and using items uses them up and wears tools down.
+    pub fn consumes_items(self) -> bool {
+        matches!(self, Self::Survival | Self::Adventure)
+    }
+
+    pub fn can_fly(self) -> bool {
+        matches!(self, Self::Creative | Self::Spectator)
+    }
+
+    /// Whether the player passes through blocks.
+    pub fn is_noclip(self) -> bool {
+        self == Self::Spectator
+    }
+
+    /// Whether the player can break and place blocks.
+    pub fn can_build(self) -> bool {
+        matches!(self, Self::Survival | Self::Creative)
+    }
+
+    /// Whether the player can use blocks, items and mobs at all.
+    pub fn can_interact(self) -> bool {
+        self != Self::Spectator
+    }
+
+    /// Whether blocks break at the first click, whatever the tool.
+    pub fn breaks_instantly(self) -> bool {
+        self == Self::Creative
+    }
+}
+
+/// Weather over a whole world.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Weather {
+    #[default]
+    Clear,
+    Rain,
+    Thunder,
+}
+
+/// World metadata stored in `saves/<world>/level.json`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LevelData {
+    /// Display name, shown in the world list. The directory name never changes.
+    pub name: String,
+    pub seed: u32,
+    /// World spawn, or `None` until the world has been entered for the first time.
+    #[serde(default)]
+    pub spawn: Option<[f32; 3]>,
+    pub time_of_day: f32,
+    #[serde(default)]
+    pub game_mode: GameMode,
+    #[serde(default)]
+    pub weather: Weather,
+    /// Seconds until the weather changes; 0 when the level has never been played.
+    #[serde(default)]
+    pub weather_time: f32,
+    /// Lowest block Y (the bedrock floor). Fixed when the world is created.
+    #[serde(default)]
+    pub min_y: i32,
+    /// Blocks from `min_y` to the build limit. Fixed when the world is created.
+    #[serde(default = "legacy_height")]
+    pub height: i32,
+    /// Unix time in seconds.
+    pub created: u64,
+    /// Unix time in seconds.
+    pub last_played: u64,
+}
+
+impl LevelData {
+    pub fn new(name: &str, seed: u32) -> Self {
+        let now = now_secs();
+        Self {
+            name: name.to_string(),
+            seed,
+            spawn: None,
+            time_of_day: 0.25,
+            game_mode: GameMode::default(),
+            weather: Weather::default(),
+            weather_time: 0.0,
+            min_y: DEFAULT_MIN_Y,
+            height: DEFAULT_HEIGHT,
+            created: now,
+            last_played: now,
+        }
+    }
+}
+
+/// A world directory under `saves/` and its metadata.
+#[derive(Debug, Clone)]
+pub struct WorldEntry {
+    pub dir_name: String,
+    pub level: LevelData,
+}
+
+pub fn now_secs() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Formats a Unix timestamp as `YYYY-MM-DD HH:MM` (UTC).
+pub fn format_date(secs: u64) -> String {
+    let days = (secs / 86_400) as i64;
+    let minutes = (secs % 86_400) / 60;
+    // Civil-from-days (Howard Hinnant's algorithm)
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    format!("{:04}-{:02}-{:02} {:02}:{:02}", year, month, day, minutes / 60, minutes % 60)
+}
+
+pub fn load_level(world_dir: &Path) -> Option<LevelData> {
+    let data = fs::read_to_string(world_dir.join(LEVEL_FILE)).ok()?;
+    serde_json::from_str(&data).ok()
+}
+
+pub fn save_level(world_dir: &Path, level: &LevelData) -> Result<(), Box<dyn std::error::Error>> {
+    fs::create_dir_all(world_dir)?;
+    let json = serde_json::to_string_pretty(level)?;
+    fs::write(world_dir.join(LEVEL_FILE), json)?;
+    Ok(())
+}
+
+/// All worlds in `saves_dir`, most recently played first.
+pub fn list_worlds(saves_dir: &Path) -> Vec<WorldEntry> {
+    let Ok(entries) = fs::read_dir(saves_dir) else {
+        return Vec::new();
+    };
+    let mut worlds: Vec<WorldEntry> = entries
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            let dir_name = path.file_name()?.to_str()?.to_string();
+            let level = load_level(&path)?;
+            Some(WorldEntry { dir_name, level })
+        })
+        .collect();
+    worlds.sort_by_key(|w| std::cmp::Reverse(w.level.last_played));
+    worlds
+}
+
+/// Directory name for a new world: the display name with unsafe characters replaced,
+/// plus a numeric suffix if that directory is taken.
+fn unique_dir_name(saves_dir: &Path, name: &str) -> String {
+    let sanitized: String = name
+        .trim()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == ' ' || c == '-' || c == '_' { c } else { '_' })
+        .collect();
+    let base = if sanitized.is_empty() { "World".to_string() } else { sanitized };
+    let mut candidate = base.clone();
+    let mut n = 2;
+    while saves_dir.join(&candidate).exists() {
+        candidate = format!("{} ({})", base, n);
+        n += 1;
+    }
+    candidate
+}
+
+/// Creates a world directory with a fresh level file. Returns the directory name.
+/// Creates a world reaching from `min_y` up `height` blocks (see `check_height`).
+/// Returns the new directory name.
+pub fn create_world(
+    saves_dir: &Path,
+    name: &str,
+    seed: u32,
+    min_y: i32,
+    height: i32,
+) -> Result<String, Box<dyn std::error::Error>> {
+    check_height(min_y, height)?;
+    let dir_name = unique_dir_name(saves_dir, name);
+    save_level(&saves_dir.join(&dir_name), &LevelData { min_y, height, ..LevelData::new(name, seed) })?;
+    Ok(dir_name)
+}
+
+pub fn rename_world(saves_dir: &Path, dir_name: &str, new_name: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let world_dir = saves_dir.join(dir_name);
+    let mut level = load_level(&world_dir).ok_or("missing level file")?;
+    level.name = new_name.trim().to_string();
+    save_level(&world_dir, &level)
+}
+
+pub fn set_game_mode(saves_dir: &Path, dir_name: &str, mode: GameMode) -> Result<(), Box<dyn std::error::Error>> {
+    let world_dir = saves_dir.join(dir_name);
+    let mut level = load_level(&world_dir).ok_or("missing level file")?;
+    level.game_mode = mode;
+    save_level(&world_dir, &level)
+}
+
+/// Copies a world, naming the copy "<name> (Copy)". Returns the new directory name.
+pub fn duplicate_world(saves_dir: &Path, dir_name: &str) -> Result<String, Box<dyn std::error::Error>> {
+    let source = saves_dir.join(dir_name);
+    let mut level = load_level(&source).ok_or("missing level file")?;
+    level.name = format!("{} (Copy)", level.name);
+    let new_dir_name = unique_dir_name(saves_dir, &level.name);
+    copy_dir(&source, &saves_dir.join(&new_dir_name))?;
+    save_level(&saves_dir.join(&new_dir_name), &level)?;
+    Ok(new_dir_name)
+}
+
+pub fn delete_world(saves_dir: &Path, dir_name: &str) -> io::Result<()> {
+    fs::remove_dir_all(saves_dir.join(dir_name))
+}
+
+fn copy_dir(from: &Path, to: &Path) -> io::Result<()> {
+    fs::create_dir_all(to)?;
+    for entry in fs::read_dir(from)? {
+        let entry = entry?;
+        let target = to.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_dir(&entry.path(), &target)?;
+        } else {
+            fs::copy(entry.path(), target)?;
+        }
+    }
+    Ok(())
+}
+
+/// Converts the old single-world layout (`saves/*.json` plus chunks in `saves/world/`)
+/// into a named world in `saves/world/`. Old saves never stored their seed, so the
+/// level gets `seed`, the default the game used to load them with.
+/// Returns whether anything was migrated.
+pub fn migrate_legacy_layout(saves_dir: &Path, seed: u32) -> io::Result<bool> {
+    let world_dir = saves_dir.join(LEGACY_WORLD_DIR);
+    let legacy_files: Vec<PathBuf> = LEGACY_FILES
+        .iter()
+        .map(|name| saves_dir.join(name))
+        .filter(|path| path.exists())
+        .collect();
+    let has_legacy_chunks = world_dir.exists() && load_level(&world_dir).is_none();
+    if legacy_files.is_empty() && !has_legacy_chunks {
+        return Ok(false);
+    }
+
+    fs::create_dir_all(&world_dir)?;
+    for path in legacy_files {
+        if let Some(name) = path.file_name() {
+            fs::rename(&path, world_dir.join(name))?;
+        }
+    }
+    if load_level(&world_dir).is_none() {
+        let level = LevelData {
+            min_y: LEGACY_MIN_Y,
+            height: LEGACY_HEIGHT,
+            ..LevelData::new("World", seed)
+        };
+        save_level(&world_dir, &level).map_err(|e| io::Error::other(e.to_string()))?;
+    }
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_saves(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("mineclaude-level-{}-{}", std::process::id(), name));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn format_date_known_values() {
+        assert_eq!(format_date(0), "1970-01-01 00:00");
+        assert_eq!(format_date(951_782_400 + 3_660), "2000-02-29 01:01");
+        assert_eq!(format_date(1_735_689_599), "2024-12-31 23:59");
+    }
+
+    #[test]
+    fn level_files_without_height_are_legacy_height() {
+        let json = r#"{"name":"Old","seed":1,"time_of_day":0.5,"created":0,"last_played":0}"#;
+        let level: LevelData = serde_json::from_str(json).unwrap();
+        assert_eq!((level.min_y, level.height), (LEGACY_MIN_Y, LEGACY_HEIGHT));
+        let new = LevelData::new("New", 1);
+        assert_eq!((new.min_y, new.height), (DEFAULT_MIN_Y, DEFAULT_HEIGHT));
+    }
+
+    #[test]
+    fn weather_round_trips_and_defaults_to_clear() {
+        let json = r#"{"name":"Old","seed":1,"time_of_day":0.5,"created":0,"last_played":0}"#;
+        let level: LevelData = serde_json::from_str(json).unwrap();
+        assert_eq!((level.weather, level.weather_time), (Weather::Clear, 0.0));
+
+        let stormy = LevelData { weather: Weather::Thunder, weather_time: 90.0, ..LevelData::new("Storm", 1) };
+        let saved: LevelData = serde_json::from_str(&serde_json::to_string(&stormy).unwrap()).unwrap();
+        assert_eq!(saved, stormy);
+    }
+
+    #[test]
+    fn game_modes_round_trip_and_default_to_survival() {
+        let json = r#"{"name":"Old","seed":1,"time_of_day":0.5,"created":0,"last_played":0}"#;
+        let level: LevelData = serde_json::from_str(json).unwrap();
+        assert_eq!(level.game_mode, GameMode::Survival);
+
+        let mut mode = GameMode::Survival;
+        for _ in 0..4 {
+            mode = mode.next();
+            let level = LevelData { game_mode: mode, ..LevelData::new("Mode", 1) };
+            let saved: LevelData = serde_json::from_str(&serde_json::to_string(&level).unwrap()).unwrap();
+            assert_eq!(saved.game_mode, mode);
+        }
+        assert_eq!(mode, GameMode::Survival);
+
+        assert!(!GameMode::Creative.is_vulnerable() && GameMode::Adventure.is_vulnerable());
+        assert!(!GameMode::Adventure.can_build() && !GameMode::Spectator.can_interact());
+    }
+
+    #[test]
+    fn create_rename_duplicate_delete() {
+        let saves = temp_saves("crud");
+        let a = create_world(&saves, "My World", 7, DEFAULT_MIN_Y, DEFAULT_HEIGHT).unwrap();
+        let b = create_world(&saves, "My World", 8, DEFAULT_MIN_Y, DEFAULT_HEIGHT).unwrap();
+        assert_ne!(a, b);
+
+        rename_world(&saves, &a, "Renamed").unwrap();
+        assert_eq!(load_level(&saves.join(&a)).unwrap().name, "Renamed");
+        set_game_mode(&saves, &a, GameMode::Creative).unwrap();
+        assert_eq!(load_level(&saves.join(&a)).unwrap().game_mode, GameMode::Creative);
+
+        fs::write(saves.join(&b).join("player.json"), "{}").unwrap();
+        let copy = duplicate_world(&saves, &b).unwrap();
+        assert_eq!(load_level(&saves.join(&copy)).unwrap().name, "My World (Copy)");
+        assert_eq!(load_level(&saves.join(&copy)).unwrap().seed, 8);
+        assert!(saves.join(&copy).join("player.json").exists());
+
+        delete_world(&saves, &a).unwrap();
+        let names: Vec<String> = list_worlds(&saves).into_iter().map(|w| w.dir_name).collect();
+        assert!(!names.contains(&a));
+        assert_eq!(names.len(), 2);
+        let _ = fs::remove_dir_all(&saves);
+    }
+
+    #[test]
+    fn worlds_are_created_with_whole_chunk_heights_only() {
+        let saves = temp_saves("height");
+        let dir = create_world(&saves, "Deep", 1, -128, 512).unwrap();
+        let level = load_level(&saves.join(&dir)).unwrap();
+        assert_eq!((level.min_y, level.height), (-128, 512));
+
+        for (min_y, height) in [(-60, 384), (-64, 390), (0, 0), (0, -16), (0, MAX_HEIGHT + 16), (-MAX_HEIGHT - 16, 256)] {
+            assert!(check_height(min_y, height).is_err(), "{min_y} {height}");
+            assert!(create_world(&saves, "Bad", 1, min_y, height).is_err());
+        }
+        assert!(check_height(DEFAULT_MIN_Y, DEFAULT_HEIGHT).is_ok());
+        assert!(check_height(LEGACY_MIN_Y, LEGACY_HEIGHT).is_ok());
+        // Refused worlds leave nothing behind
+        assert_eq!(list_worlds(&saves).len(), 1);
+        let _ = fs::remove_dir_all(&saves);
+    }
+
+    #[test]
+    fn unsafe_names_get_safe_directories() {
+        let saves = temp_saves("names");
+        let dir = create_world(&saves, "../a/b", 1, DEFAULT_MIN_Y, DEFAULT_HEIGHT).unwrap();
+        assert!(!dir.contains('/') && !dir.contains('.'));
+        assert_eq!(load_level(&saves.join(&dir)).unwrap().name, "../a/b");
+        let _ = fs::remove_dir_all(&saves);
+    }
+
+    #[test]
+    fn legacy_layout_becomes_named_world() {
+        let saves = temp_saves("legacy");
+        fs::write(saves.join("player.json"), "{}").unwrap();
+        fs::create_dir_all(saves.join("world")).unwrap();
+        fs::write(saves.join("world").join("r.0.0.region"), []).unwrap();
+
+        assert!(migrate_legacy_layout(&saves, 42).unwrap());
+        assert!(saves.join("world").join("player.json").exists());
+        assert!(!saves.join("player.json").exists());
+        let level = load_level(&saves.join("world")).unwrap();
+        assert_eq!(level.seed, 42);
+        assert_eq!((level.min_y, level.height), (LEGACY_MIN_Y, LEGACY_HEIGHT));
+        assert!(!migrate_legacy_layout(&saves, 42).unwrap());
+        let _ = fs::remove_dir_all(&saves);
+    }
+}
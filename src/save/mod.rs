@@ -1,11 +1,18 @@
+pub mod level;
 pub mod persistence;
+pub mod region;
 
 use bevy::prelude::*;
 
+use crate::controls::{Action, ActionInput};
 use crate::inventory::chest::ChestStore;
 use crate::inventory::furnace::Furnaces;
 use crate::inventory::inventory::Inventory;
+use crate::lighting::day_night::DayNightCycle;
+use crate::lighting::weather::WeatherState;
 use crate::player::{AirSupply, ArmorSlots, Health, Hunger, Player, PlayerPitch, PlayerYaw, SpawnPoint};
+use crate::save::level::GameMode;
+use crate::world::{WorldHeight, WorldSeed};
 use crate::world::manager::{CropTracker, SaplingTracker};
 
 pub struct SavePlugin;
@@ -19,10 +26,29 @@ impl Plugin for SavePlugin {
             60.0,
             TimerMode::Repeating,
         )))
+        .init_resource::<GameMode>()
+        .add_systems(Startup, load_active_level.before(crate::world::manager::setup_world))
         .add_systems(Update, (auto_save_system, manual_save_system));
     }
 }
 
+/// Applies the active world's seed, height, time of day, weather and game mode before the world starts generating.
+fn load_active_level(
+    mut world_seed: ResMut<WorldSeed>,
+    mut world_height: ResMut<WorldHeight>,
+    mut cycle: ResMut<DayNightCycle>,
+    mut weather: ResMut<WeatherState>,
+    mut game_mode: ResMut<GameMode>,
+) {
+    if let Some(level) = persistence::load_active_level() {
+        world_seed.0 = level.seed;
+        *world_height = WorldHeight::of_level(&level);
+        cycle.time_of_day = level.time_of_day;
+        *weather = WeatherState::load(level.weather, level.weather_time);
+        *game_mode = level.game_mode;
+    }
+}
+
 fn save_all(
     store: ResMut<crate::world::manager::ChunkDataStore>,
     player_query: &Query<
@@ -35,8 +61,12 @@ fn save_all(
     furnaces: &Res<Furnaces>,
     sapling_tracker: &Res<SaplingTracker>,
     crop_tracker: &Res<CropTracker>,
+    cycle: &Res<DayNightCycle>,
+    weather: &Res<WeatherState>,
+    game_mode: &Res<GameMode>,
 ) {
     persistence::save_modified_chunks(store);
+    save_level_state(spawn_point, cycle, weather, game_mode);
     save_player_state(player_query, inventory, spawn_point);
     if let Err(e) = persistence::save_chests(chest_store) {
         warn!("Failed to save chests: {}", e);
@@ -52,6 +82,27 @@ fn save_all(
     }
 }
 
+/// Updates the active world's level file with the current spawn, time, weather, game mode and play date.
+fn save_level_state(
+    spawn_point: &Res<SpawnPoint>,
+    cycle: &Res<DayNightCycle>,
+    weather: &Res<WeatherState>,
+    game_mode: &Res<GameMode>,
+) {
+    let Some(mut level) = persistence::load_active_level() else {
+        return;
+    };
+    level.spawn = Some(spawn_point.0.to_array());
+    level.time_of_day = cycle.time_of_day;
+    level.weather = weather.weather;
+    level.weather_time = weather.remaining;
+    level.game_mode = **game_mode;
+    level.last_played = level::now_secs();
+    if let Err(e) = persistence::save_active_level(&level) {
+        warn!("Failed to save level: {}", e);
+    }
+}
+
 fn save_player_state(
     player_query: &Query<
         (&Transform, &PlayerYaw, &PlayerPitch, &Health, &AirSupply, &Hunger, &ArmorSlots),
@@ -97,15 +148,18 @@ fn auto_save_system(
     furnaces: Res<Furnaces>,
     sapling_tracker: Res<SaplingTracker>,
     crop_tracker: Res<CropTracker>,
+    cycle: Res<DayNightCycle>,
+    weather: Res<WeatherState>,
+    game_mode: Res<GameMode>,
 ) {
     timer.0.tick(time.delta());
     if timer.0.just_finished() {
-        save_all(store, &player_query, &inventory, &spawn_point, &chest_store, &furnaces, &sapling_tracker, &crop_tracker);
+        save_all(store, &player_query, &inventory, &spawn_point, &chest_store, &furnaces, &sapling_tracker, &crop_tracker, &cycle, &weather, &game_mode);
     }
 }
 
 fn manual_save_system(
-    keys: Res<ButtonInput<KeyCode>>,
+    actions: ActionInput,
     store: ResMut<crate::world::manager::ChunkDataStore>,
     player_query: Query<
         (&Transform, &PlayerYaw, &PlayerPitch, &Health, &AirSupply, &Hunger, &ArmorSlots),
@@ -117,8 +171,11 @@ fn manual_save_system(
     furnaces: Res<Furnaces>,
     sapling_tracker: Res<SaplingTracker>,
     crop_tracker: Res<CropTracker>,
+    cycle: Res<DayNightCycle>,
+    weather: Res<WeatherState>,
+    game_mode: Res<GameMode>,
 ) {
-    if keys.pressed(KeyCode::ControlLeft) && keys.just_pressed(KeyCode::KeyS) {
-        save_all(store, &player_query, &inventory, &spawn_point, &chest_store, &furnaces, &sapling_tracker, &crop_tracker);
+    if actions.just_pressed(Action::QuickSave) {
+        save_all(store, &player_query, &inventory, &spawn_point, &chest_store, &furnaces, &sapling_tracker, &crop_tracker, &cycle, &weather, &game_mode);
     }
 }
@@ -1,47 +1,152 @@
 use bevy::prelude::*;
-use flate2::read::GzDecoder;
-use flate2::write::GzEncoder;
-use flate2::Compression;
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 use std::fs;
-use std::io::{Read, Write};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, LazyLock, Mutex, RwLock};
 
 use crate::inventory::chest::{ChestData, ChestStore};
 use crate::inventory::furnace::{FurnaceData, Furnaces};
 use crate::inventory::item::Item;
 use crate::world::chunk::Chunk;
-use crate::world::manager::{ChunkDataStore, CropTracker, SaplingTracker};
+use crate::world::manager::{BlockTimer, ChunkDataStore, CropTracker, SaplingTracker};
 
-fn chunk_path(pos: IVec3) -> PathBuf {
-    PathBuf::from(format!(
-        "saves/world/chunk_{}_{}_{}.bin",
-        pos.x, pos.y, pos.z
-    ))
+use crate::world::{WorldHeight, WorldSeed};
+
+use super::level::{self, LevelData, SAVES_DIR};
+use super::region::{self, region_coords, region_path, RegionFile};
+
+/// Directory of the world being played, or `None` before any world exists on disk
+/// (the title-screen backdrop world is never saved). Chosen lazily on first use: the
+/// most recently played world, after converting any old single-world save.
+static ACTIVE_WORLD: LazyLock<RwLock<Option<PathBuf>>> = LazyLock::new(|| {
+    let saves = Path::new(SAVES_DIR);
+    if let Err(e) = level::migrate_legacy_layout(saves, WorldSeed::default().0) {
+        warn!("Failed to migrate old save layout: {}", e);
+    }
+    let dir = level::list_worlds(saves).first().map(|w| saves.join(&w.dir_name));
+    if let Some(dir) = &dir {
+        migrate_legacy_chunks(dir);
+    }
+    RwLock::new(dir)
+});
+
+/// Directory of the active world, if there is one.
+pub fn active_world() -> Option<PathBuf> {
+    ACTIVE_WORLD.read().expect("ACTIVE_WORLD lock poisoned").clone()
 }
 
-pub fn save_chunk(pos: IVec3, chunk: &Chunk) -> Result<(), Box<dyn std::error::Error>> {
-    let path = chunk_path(pos);
-    if let Some(parent) = path.parent() {
-        fs::create_dir_all(parent)?;
+/// Switches saving and loading to the world in `saves/<dir_name>`.
+pub fn set_active_world(dir_name: &str) {
+    close_regions();
+    let dir = Path::new(SAVES_DIR).join(dir_name);
+    migrate_legacy_chunks(&dir);
+    *ACTIVE_WORLD.write().expect("ACTIVE_WORLD lock poisoned") = Some(dir);
+}
+
+/// Stops saving to any world. Call before deleting the active world's directory.
+pub fn clear_active_world() {
+    close_regions();
+    *ACTIVE_WORLD.write().expect("ACTIVE_WORLD lock poisoned") = None;
+}
+
+/// Path of a per-world file in the active world.
+fn world_file(name: &str) -> Option<PathBuf> {
+    active_world().map(|dir| dir.join(name))
+}
+
+pub fn load_active_level() -> Option<LevelData> {
+    level::load_level(&active_world()?)
+}
+
+pub fn save_active_level(data: &LevelData) -> Result<(), Box<dyn std::error::Error>> {
+    match active_world() {
+        Some(dir) => level::save_level(&dir, data),
+        None => Ok(()),
+    }
+}
+
+/// A region file, opened on first use. `None` until then, or while there's no file yet.
+type SharedRegion = Arc<Mutex<Option<RegionFile>>>;
+
+/// Region files opened so far, kept open so each chunk load doesn't re-read a header.
+/// Each has its own lock, so chunk loads on the task pool and saves on the main thread
+/// only wait on each other within one region; the map's lock is held just for lookups.
+static OPEN_REGIONS: LazyLock<Mutex<HashMap<IVec2, SharedRegion>>> = LazyLock::new(Default::default);
+
+/// Runs `f` on the region file holding `pos`. With `create` unset, returns `Ok(None)`
+/// instead of creating a missing file.
+fn with_region<R>(
+    pos: IVec3,
+    create: bool,
+    f: impl FnOnce(&mut RegionFile) -> std::io::Result<R>,
+) -> std::io::Result<Option<R>> {
+    let Some(world_dir) = active_world() else {
+        return Ok(None);
+    };
+    let region_pos = region_coords(pos);
+    let shared = OPEN_REGIONS
+        .lock()
+        .expect("OPEN_REGIONS lock poisoned")
+        .entry(region_pos)
+        .or_default()
+        .clone();
+
+    let mut region = shared.lock().expect("region lock poisoned");
+    if region.is_none() {
+        let path = region_path(&world_dir, region_pos);
+        *region = match RegionFile::open(&path)? {
+            Some(opened) => Some(opened),
+            None if create => {
+                let height = world_height(&world_dir);
+                Some(RegionFile::create(&path, height.min_chunk_y(), height.chunk_layers() as u32)?)
+            }
+            None => return Ok(None),
+        };
     }
+    let region = region.as_mut().expect("region was just opened");
+    f(region).map(Some)
+}
+
+/// Closes all open region files. Call before deleting or replacing a world on disk.
+pub fn close_regions() {
+    OPEN_REGIONS.lock().expect("OPEN_REGIONS lock poisoned").clear();
+}
 
-    let encoded = bincode::serialize(chunk)?;
-    let mut encoder = GzEncoder::new(Vec::new(), Compression::fast());
-    encoder.write_all(&encoded)?;
-    let compressed = encoder.finish()?;
-    fs::write(path, compressed)?;
+/// Vertical extent of the world in `world_dir`, from its level file.
+fn world_height(world_dir: &Path) -> WorldHeight {
+    level::load_level(world_dir).map(|level| WorldHeight::of_level(&level)).unwrap_or_default()
+}
+
+/// Converts chunks saved one file per chunk (older saves) into region files.
+fn migrate_legacy_chunks(world_dir: &Path) {
+    let height = world_height(world_dir);
+    match region::migrate_legacy_chunks(world_dir, height.min_chunk_y(), height.chunk_layers() as u32) {
+        Ok(0) => {}
+        Ok(count) => info!("Migrated {} chunks to region files", count),
+        Err(e) => warn!("Failed to migrate chunk files: {}", e),
+    }
+}
+
+/// Compression happens before the region is locked and decompression after, so the
+/// lock only covers file access.
+pub fn save_chunk(pos: IVec3, chunk: &Chunk) -> Result<(), Box<dyn std::error::Error>> {
+    let payload = region::compress_chunk(&bincode::serialize(chunk)?)?;
+    with_region(pos, true, |region| region.write_raw(pos, payload))?;
     Ok(())
 }
 
 pub fn load_chunk(pos: IVec3) -> Option<Chunk> {
-    let path = chunk_path(pos);
-    let data = fs::read(&path).ok()?;
-    let mut decoder = GzDecoder::new(&data[..]);
-    let mut decompressed = Vec::new();
-    decoder.read_to_end(&mut decompressed).ok()?;
-    bincode::deserialize(&decompressed).ok()
+    let data = with_region(pos, false, |region| region.read_raw(pos))
+        .and_then(|payload| payload.flatten().map(|p| region::decompress_chunk(&p)).transpose());
+    let data = match data {
+        Ok(data) => data?,
+        Err(e) => {
+            warn!("Failed to read chunk {:?}: {}", pos, e);
+            return None;
+        }
+    };
+    bincode::deserialize(&data).ok()
 }
 
 pub fn save_modified_chunks(mut store: ResMut<ChunkDataStore>) {
@@ -82,31 +187,35 @@ pub struct PlayerSaveData {
     pub armor_slots: Option<Vec<Option<(Item, u8, u16)>>>,
 }
 
-const PLAYER_SAVE_PATH: &str = "saves/player.json";
+const PLAYER_SAVE_FILE: &str = "player.json";
 
 pub fn save_player(data: &PlayerSaveData) -> Result<(), Box<dyn std::error::Error>> {
-    let dir = PathBuf::from("saves");
-    if !dir.exists() {
-        fs::create_dir_all(&dir)?;
+    let Some(path) = world_file(PLAYER_SAVE_FILE) else {
+        return Ok(());
+    };
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
     }
     let json = serde_json::to_string_pretty(data)?;
-    fs::write(PLAYER_SAVE_PATH, json)?;
+    fs::write(path, json)?;
     Ok(())
 }
 
 pub fn load_player() -> Option<PlayerSaveData> {
-    let data = fs::read_to_string(PLAYER_SAVE_PATH).ok()?;
+    let data = fs::read_to_string(world_file(PLAYER_SAVE_FILE)?).ok()?;
     serde_json::from_str(&data).ok()
 }
 
 // --- Chest persistence ---
 
-const CHESTS_SAVE_PATH: &str = "saves/chests.json";
+const CHESTS_SAVE_FILE: &str = "chests.json";
 
 pub fn save_chests(store: &ChestStore) -> Result<(), Box<dyn std::error::Error>> {
-    let dir = PathBuf::from("saves");
-    if !dir.exists() {
-        fs::create_dir_all(&dir)?;
+    let Some(path) = world_file(CHESTS_SAVE_FILE) else {
+        return Ok(());
+    };
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
     }
     let entries: Vec<([i32; 3], &ChestData)> = store
         .data
@@ -114,12 +223,15 @@ pub fn save_chests(store: &ChestStore) -> Result<(), Box<dyn std::error::Error>>
         .map(|(pos, data)| ([pos.x, pos.y, pos.z], data))
         .collect();
     let json = serde_json::to_string_pretty(&entries)?;
-    fs::write(CHESTS_SAVE_PATH, json)?;
+    fs::write(path, json)?;
     Ok(())
 }
 
 pub fn load_chests() -> ChestStore {
-    let data = match fs::read_to_string(CHESTS_SAVE_PATH) {
+    let Some(path) = world_file(CHESTS_SAVE_FILE) else {
+        return ChestStore::default();
+    };
+    let data = match fs::read_to_string(path) {
         Ok(d) => d,
         Err(_) => return ChestStore::default(),
     };
@@ -139,12 +251,14 @@ pub fn load_chests() -> ChestStore {
 
 // --- Furnace persistence ---
 
-const FURNACES_SAVE_PATH: &str = "saves/furnaces.json";
+const FURNACES_SAVE_FILE: &str = "furnaces.json";
 
 pub fn save_furnaces(furnaces: &Furnaces) -> Result<(), Box<dyn std::error::Error>> {
-    let dir = PathBuf::from("saves");
-    if !dir.exists() {
-        fs::create_dir_all(&dir)?;
+    let Some(path) = world_file(FURNACES_SAVE_FILE) else {
+        return Ok(());
+    };
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
     }
     let entries: Vec<([i32; 3], &FurnaceData)> = furnaces
         .data
@@ -152,12 +266,15 @@ pub fn save_furnaces(furnaces: &Furnaces) -> Result<(), Box<dyn std::error::Erro
         .map(|(pos, data)| ([pos.x, pos.y, pos.z], data))
         .collect();
     let json = serde_json::to_string_pretty(&entries)?;
-    fs::write(FURNACES_SAVE_PATH, json)?;
+    fs::write(path, json)?;
     Ok(())
 }
 
 pub fn load_furnaces() -> Furnaces {
-    let data = match fs::read_to_string(FURNACES_SAVE_PATH) {
+    let Some(path) = world_file(FURNACES_SAVE_FILE) else {
+        return Furnaces::default();
+    };
+    let data = match fs::read_to_string(path) {
         Ok(d) => d,
         Err(_) => return Furnaces::default(),
     };
@@ -177,12 +294,14 @@ pub fn load_furnaces() -> Furnaces {
 
 // --- Sapling tracker persistence ---
 
-const SAPLINGS_SAVE_PATH: &str = "saves/saplings.json";
+const SAPLINGS_SAVE_FILE: &str = "saplings.json";
 
 pub fn save_saplings(tracker: &SaplingTracker) -> Result<(), Box<dyn std::error::Error>> {
-    let dir = PathBuf::from("saves");
-    if !dir.exists() {
-        fs::create_dir_all(&dir)?;
+    let Some(path) = world_file(SAPLINGS_SAVE_FILE) else {
+        return Ok(());
+    };
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
     }
     let entries: Vec<([i32; 3], f32)> = tracker
         .saplings
@@ -190,12 +309,15 @@ pub fn save_saplings(tracker: &SaplingTracker) -> Result<(), Box<dyn std::error:
         .map(|(pos, time)| ([pos.x, pos.y, pos.z], *time))
         .collect();
     let json = serde_json::to_string_pretty(&entries)?;
-    fs::write(SAPLINGS_SAVE_PATH, json)?;
+    fs::write(path, json)?;
     Ok(())
 }
 
 pub fn load_saplings() -> SaplingTracker {
-    let data = match fs::read_to_string(SAPLINGS_SAVE_PATH) {
+    let Some(path) = world_file(SAPLINGS_SAVE_FILE) else {
+        return SaplingTracker::default();
+    };
+    let data = match fs::read_to_string(path) {
         Ok(d) => d,
         Err(_) => return SaplingTracker::default(),
     };
@@ -218,25 +340,30 @@ pub fn load_saplings() -> SaplingTracker {
 
 // --- Crop tracker persistence ---
 
-const CROPS_SAVE_PATH: &str = "saves/crops.json";
+const CROPS_SAVE_FILE: &str = "crops.json";
 
 pub fn save_crops(tracker: &CropTracker) -> Result<(), Box<dyn std::error::Error>> {
-    let dir = PathBuf::from("saves");
-    if !dir.exists() {
-        fs::create_dir_all(&dir)?;
+    let Some(path) = world_file(CROPS_SAVE_FILE) else {
+        return Ok(());
+    };
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
     }
     let entries: Vec<([i32; 3], f32)> = tracker
         .crops
         .iter()
-        .map(|(pos, time)| ([pos.x, pos.y, pos.z], *time))
+        .map(|(pos, timer)| ([pos.x, pos.y, pos.z], timer.remaining))
         .collect();
     let json = serde_json::to_string_pretty(&entries)?;
-    fs::write(CROPS_SAVE_PATH, json)?;
+    fs::write(path, json)?;
     Ok(())
 }
 
 pub fn load_crops() -> CropTracker {
-    let data = match fs::read_to_string(CROPS_SAVE_PATH) {
+    let Some(path) = world_file(CROPS_SAVE_FILE) else {
+        return CropTracker::default();
+    };
+    let data = match fs::read_to_string(path) {
         Ok(d) => d,
         Err(_) => return CropTracker::default(),
     };
@@ -249,7 +376,8 @@ pub fn load_crops() -> CropTracker {
     };
     let mut crops = HashMap::new();
     for ([x, y, z], time) in entries {
-        crops.insert(IVec3::new(x, y, z), time);
+        let pos = IVec3::new(x, y, z);
+        crops.insert(pos, BlockTimer::new(pos, time));
     }
     CropTracker {
         crops,
@@ -0,0 +1,482 @@
+use bevy::prelude::*;
+use flate2::read::{GzDecoder, ZlibDecoder};
+use flate2::write::ZlibEncoder;
+use flate2::Compression;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+/// Width and depth of a region in chunks. A region file holds every chunk layer of a
+/// 32×32 column of chunks.
+pub const REGION_SIZE: i32 = 32;
+
+/// Chunk payloads are stored in whole sectors of this many bytes.
+const SECTOR_SIZE: usize = 512;
+
+const MAGIC: &[u8; 4] = b"MCRG";
+const VERSION: u32 = 1;
+
+/// Magic, version, min layer, layer count.
+const PREAMBLE_BYTES: usize = 16;
+
+/// Each header entry is a sector offset and a byte length, both `u32`.
+const ENTRY_BYTES: usize = 8;
+
+/// First byte of every chunk payload: how the rest of it is compressed.
+const COMPRESSION_ZLIB: u8 = 1;
+
+/// Sectors taken by the preamble and entry table of a file with `slots` chunk slots.
+fn header_sectors(slots: usize) -> usize {
+    (PREAMBLE_BYTES + slots * ENTRY_BYTES).div_ceil(SECTOR_SIZE)
+}
+
+/// Region containing a chunk position.
+pub fn region_coords(chunk_pos: IVec3) -> IVec2 {
+    IVec2::new(chunk_pos.x.div_euclid(REGION_SIZE), chunk_pos.z.div_euclid(REGION_SIZE))
+}
+
+pub fn region_path(dir: &Path, region: IVec2) -> PathBuf {
+    dir.join(format!("r.{}.{}.region", region.x, region.y))
+}
+
+/// One region file: a header with an (offset, length) entry per chunk slot, followed by
+/// individually compressed chunk payloads aligned to sectors. Sectors freed when a chunk
+/// is rewritten are reused by later writes.
+pub struct RegionFile {
+    file: File,
+    /// Lowest chunk Y stored in this file.
+    min_layer: i32,
+    /// Number of chunk layers (Y values) this file has slots for.
+    layers: u32,
+    /// (first sector, byte length) per slot; a zero offset means the chunk isn't stored.
+    entries: Vec<(u32, u32)>,
+    /// Which sectors are occupied by the header or a chunk payload.
+    used: Vec<bool>,
+}
+
+impl RegionFile {
+    /// Creates a new, empty region file covering chunk layers `min_layer..min_layer + layers`.
+    pub fn create(path: &Path, min_layer: i32, layers: u32) -> io::Result<Self> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(path)?;
+        let slots = (REGION_SIZE * REGION_SIZE) as usize * layers as usize;
+        let mut region = Self {
+            file,
+            min_layer,
+            layers,
+            entries: vec![(0, 0); slots],
+            used: Vec::new(),
+        };
+        let header_sectors = header_sectors(slots);
+        region.used = vec![true; header_sectors];
+
+        let mut header = vec![0u8; header_sectors * SECTOR_SIZE];
+        header[0..4].copy_from_slice(MAGIC);
+        header[4..8].copy_from_slice(&VERSION.to_le_bytes());
+        header[8..12].copy_from_slice(&min_layer.to_le_bytes());
+        header[12..16].copy_from_slice(&layers.to_le_bytes());
+        region.file.write_all(&header)?;
+        Ok(region)
+    }
+
+    /// Opens an existing region file, or returns `None` if there isn't one.
+    pub fn open(path: &Path) -> io::Result<Option<Self>> {
+        let mut file = match OpenOptions::new().read(true).write(true).open(path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e),
+        };
+
+        let mut preamble = [0u8; PREAMBLE_BYTES];
+        file.read_exact(&mut preamble)?;
+        if &preamble[0..4] != MAGIC {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "not a region file"));
+        }
+        let version = u32::from_le_bytes(preamble[4..8].try_into().unwrap());
+        if version != VERSION {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("unsupported region version {}", version),
+            ));
+        }
+        let min_layer = i32::from_le_bytes(preamble[8..12].try_into().unwrap());
+        let layers = u32::from_le_bytes(preamble[12..16].try_into().unwrap());
+
+        let slots = (REGION_SIZE * REGION_SIZE) as usize * layers as usize;
+        let mut table = vec![0u8; slots * ENTRY_BYTES];
+        file.read_exact(&mut table)?;
+
+        let mut region = Self {
+            file,
+            min_layer,
+            layers,
+            entries: Vec::with_capacity(slots),
+            used: Vec::new(),
+        };
+        let header_sectors = header_sectors(slots);
+        let file_sectors = (region.file.metadata()?.len() as usize).div_ceil(SECTOR_SIZE);
+        region.used = vec![false; file_sectors.max(header_sectors)];
+        region.used[..header_sectors].fill(true);
+
+        for entry in table.chunks_exact(ENTRY_BYTES) {
+            let offset = u32::from_le_bytes(entry[0..4].try_into().unwrap());
+            let length = u32::from_le_bytes(entry[4..8].try_into().unwrap());
+            let start = offset as usize;
+            let end = start + (length as usize).div_ceil(SECTOR_SIZE);
+            // Drop entries pointing into the header or past the end of the file
+            if offset == 0 || start < header_sectors || end > region.used.len() {
+                region.entries.push((0, 0));
+                continue;
+            }
+            region.used[start..end].fill(true);
+            region.entries.push((offset, length));
+        }
+        Ok(Some(region))
+    }
+
+    /// Whether this file has a slot for the chunk's Y layer.
+    pub fn covers(&self, chunk_pos: IVec3) -> bool {
+        chunk_pos.y >= self.min_layer && chunk_pos.y < self.min_layer + self.layers as i32
+    }
+
+    fn slot(&self, chunk_pos: IVec3) -> io::Result<usize> {
+        if !self.covers(chunk_pos) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("chunk layer {} outside region layers", chunk_pos.y),
+            ));
+        }
+        let lx = chunk_pos.x.rem_euclid(REGION_SIZE) as usize;
+        let lz = chunk_pos.z.rem_euclid(REGION_SIZE) as usize;
+        let ly = (chunk_pos.y - self.min_layer) as usize;
+        Ok((ly * REGION_SIZE as usize + lz) * REGION_SIZE as usize + lx)
+    }
+
+    /// Reads and decompresses a chunk's payload, or `None` if it hasn't been stored.
+    pub fn read_chunk(&mut self, chunk_pos: IVec3) -> io::Result<Option<Vec<u8>>> {
+        self.read_raw(chunk_pos)?.map(|payload| decompress_chunk(&payload)).transpose()
+    }
+
+    /// Reads a chunk's payload as stored, still compressed (see `decompress_chunk`).
+    pub fn read_raw(&mut self, chunk_pos: IVec3) -> io::Result<Option<Vec<u8>>> {
+        if !self.covers(chunk_pos) {
+            return Ok(None);
+        }
+        let (offset, length) = self.entries[self.slot(chunk_pos)?];
+        if offset == 0 {
+            return Ok(None);
+        }
+
+        let mut payload = vec![0u8; length as usize];
+        self.file.seek(SeekFrom::Start(offset as u64 * SECTOR_SIZE as u64))?;
+        self.file.read_exact(&mut payload)?;
+        Ok(Some(payload))
+    }
+
+    /// Compresses and stores a chunk's payload.
+    pub fn write_chunk(&mut self, chunk_pos: IVec3, data: &[u8]) -> io::Result<()> {
+        self.write_raw(chunk_pos, compress_chunk(data)?)
+    }
+
+    /// Stores an already compressed payload (see `compress_chunk`), reusing free sectors
+    /// where it fits. The chunk's previous copy is only freed once the new one is written.
+    pub fn write_raw(&mut self, chunk_pos: IVec3, mut payload: Vec<u8>) -> io::Result<()> {
+        let slot = self.slot(chunk_pos)?;
+        let length = payload.len() as u32;
+        let sectors = payload.len().div_ceil(SECTOR_SIZE);
+        payload.resize(sectors * SECTOR_SIZE, 0);
+
+        // Write the new copy into free sectors and point the header at it before the old
+        // copy is released, so a failed write never loses a chunk that was stored
+        let start = self.allocate(sectors);
+        if let Err(e) = self.write_sectors(slot, start, length, &payload) {
+            self.used[start..start + sectors].fill(false);
+            return Err(e);
+        }
+
+        let (old_offset, old_length) = std::mem::replace(&mut self.entries[slot], (start as u32, length));
+        if old_offset != 0 {
+            let old_start = old_offset as usize;
+            self.used[old_start..old_start + (old_length as usize).div_ceil(SECTOR_SIZE)].fill(false);
+        }
+
+        // Trim free sectors left at the end of the file
+        let last_used = self.used.iter().rposition(|&u| u).unwrap_or(0);
+        if last_used + 1 < self.used.len() {
+            self.used.truncate(last_used + 1);
+            self.file.set_len((self.used.len() * SECTOR_SIZE) as u64)?;
+        }
+        Ok(())
+    }
+
+    /// Writes a sector-padded payload at `start`, then the slot's header entry pointing at it.
+    fn write_sectors(&mut self, slot: usize, start: usize, length: u32, payload: &[u8]) -> io::Result<()> {
+        self.file.seek(SeekFrom::Start((start * SECTOR_SIZE) as u64))?;
+        self.file.write_all(payload)?;
+
+        let mut entry = [0u8; ENTRY_BYTES];
+        entry[0..4].copy_from_slice(&(start as u32).to_le_bytes());
+        entry[4..8].copy_from_slice(&length.to_le_bytes());
+        self.file.seek(SeekFrom::Start((PREAMBLE_BYTES + slot * ENTRY_BYTES) as u64))?;
+        self.file.write_all(&entry)
+    }
+
+    /// Marks and returns the first run of `count` free sectors, growing the file if needed.
+    fn allocate(&mut self, count: usize) -> usize {
+        let mut run = 0;
+        for i in 0..self.used.len() {
+            if self.used[i] {
+                run = 0;
+                continue;
+            }
+            run += 1;
+            if run == count {
+                let start = i + 1 - count;
+                self.used[start..=i].fill(true);
+                return start;
+            }
+        }
+        // Extend past the end, reusing any free sectors already at the tail
+        let start = self.used.len() - run;
+        self.used.resize(start + count, true);
+        self.used[start..].fill(true);
+        start
+    }
+
+    /// Size of the file in sectors (header plus payloads, including free gaps).
+    pub fn sector_count(&self) -> usize {
+        self.used.len()
+    }
+}
+
+/// Compresses a chunk's data into a payload for `RegionFile::write_raw`. Kept apart
+/// from the file so callers can compress without holding on to it.
+pub fn compress_chunk(data: &[u8]) -> io::Result<Vec<u8>> {
+    let mut encoder = ZlibEncoder::new(vec![COMPRESSION_ZLIB], Compression::fast());
+    encoder.write_all(data)?;
+    encoder.finish()
+}
+
+/// Decompresses a payload read with `RegionFile::read_raw`.
+pub fn decompress_chunk(payload: &[u8]) -> io::Result<Vec<u8>> {
+    match payload.first() {
+        Some(&COMPRESSION_ZLIB) => {
+            let mut data = Vec::new();
+            ZlibDecoder::new(&payload[1..]).read_to_end(&mut data)?;
+            Ok(data)
+        }
+        _ => Err(io::Error::new(io::ErrorKind::InvalidData, "unknown chunk compression")),
+    }
+}
+
+/// Moves chunks saved in the old one-gzip-file-per-chunk format (`chunk_x_y_z.bin`)
+/// in `dir` into region files, deleting each old file once it has been copied.
+/// Returns how many chunks were migrated.
+pub fn migrate_legacy_chunks(dir: &Path, min_layer: i32, layers: u32) -> io::Result<usize> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(0),
+        Err(e) => return Err(e),
+    };
+
+    let mut regions: std::collections::HashMap<IVec2, RegionFile> = std::collections::HashMap::new();
+    let mut migrated = 0;
+    for entry in entries {
+        let path = entry?.path();
+        let Some(pos) = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(parse_legacy_chunk_name)
+        else {
+            continue;
+        };
+
+        let mut data = Vec::new();
+        if let Err(e) = GzDecoder::new(File::open(&path)?).read_to_end(&mut data) {
+            warn!("Skipping unreadable chunk file {:?}: {}", path, e);
+            continue;
+        }
+
+        let region_pos = region_coords(pos);
+        let region = match regions.entry(region_pos) {
+            std::collections::hash_map::Entry::Occupied(e) => e.into_mut(),
+            std::collections::hash_map::Entry::Vacant(e) => {
+                let path = region_path(dir, region_pos);
+                let region = match RegionFile::open(&path)? {
+                    Some(region) => region,
+                    None => RegionFile::create(&path, min_layer, layers)?,
+                };
+                e.insert(region)
+            }
+        };
+        if !region.covers(pos) {
+            warn!("Skipping chunk {:?} outside the world height", pos);
+            continue;
+        }
+        region.write_chunk(pos, &data)?;
+        fs::remove_file(&path)?;
+        migrated += 1;
+    }
+    Ok(migrated)
+}
+
+/// Parses `chunk_x_y_z.bin` into a chunk position.
+fn parse_legacy_chunk_name(name: &str) -> Option<IVec3> {
+    let coords = name.strip_prefix("chunk_")?.strip_suffix(".bin")?;
+    let mut parts = coords.split('_').map(|p| p.parse::<i32>().ok());
+    let pos = IVec3::new(parts.next()??, parts.next()??, parts.next()??);
+    if parts.next().is_some() {
+        return None;
+    }
+    Some(pos)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("mineclaude-region-{}-{}", std::process::id(), name));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn region_coords_floor_negative_chunks() {
+        assert_eq!(region_coords(IVec3::new(0, 5, 31)), IVec2::new(0, 0));
+        assert_eq!(region_coords(IVec3::new(-1, 0, 32)), IVec2::new(-1, 1));
+        assert_eq!(region_coords(IVec3::new(-33, 0, -32)), IVec2::new(-2, -1));
+    }
+
+    #[test]
+    fn write_read_roundtrip_survives_reopen() {
+        let dir = temp_dir("roundtrip");
+        let path = region_path(&dir, IVec2::ZERO);
+        let a = IVec3::new(3, 2, 7);
+        let b = IVec3::new(31, 15, 0);
+        {
+            let mut region = RegionFile::create(&path, 0, 16).unwrap();
+            region.write_chunk(a, &[1, 2, 3]).unwrap();
+            region.write_chunk(b, &vec![9u8; 5000]).unwrap();
+        }
+        let mut region = RegionFile::open(&path).unwrap().unwrap();
+        assert_eq!(region.read_chunk(a).unwrap(), Some(vec![1, 2, 3]));
+        assert_eq!(region.read_chunk(b).unwrap(), Some(vec![9u8; 5000]));
+        assert_eq!(region.read_chunk(IVec3::new(0, 0, 0)).unwrap(), None);
+        assert_eq!(region.read_chunk(IVec3::new(0, 16, 0)).unwrap(), None);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn writes_after_reopen_keep_the_header_intact() {
+        let dir = temp_dir("reopen-write");
+        let path = region_path(&dir, IVec2::ZERO);
+        // Slot 74, whose entry lies in the header's second sector
+        let a = IVec3::new(10, 0, 2);
+        let b = IVec3::new(20, 11, 4);
+        {
+            let mut region = RegionFile::create(&path, 0, 16).unwrap();
+            region.write_chunk(a, &noise_bytes(3000, 1)).unwrap();
+        }
+        {
+            let mut region = RegionFile::open(&path).unwrap().unwrap();
+            region.write_chunk(b, &noise_bytes(2000, 2)).unwrap();
+        }
+        let mut region = RegionFile::open(&path).unwrap().unwrap();
+        assert_eq!(region.read_chunk(a).unwrap(), Some(noise_bytes(3000, 1)));
+        assert_eq!(region.read_chunk(b).unwrap(), Some(noise_bytes(2000, 2)));
+        assert_eq!(region.read_chunk(IVec3::new(0, 0, 0)).unwrap(), None);
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    /// Incompressible bytes so the payload size is predictable.
+    fn noise_bytes(len: usize, seed: u32) -> Vec<u8> {
+        let mut state = seed.wrapping_mul(2654435761).max(1);
+        (0..len)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 17;
+                state ^= state << 5;
+                state as u8
+            })
+            .collect()
+    }
+
+    #[test]
+    fn rewritten_chunks_reuse_sectors() {
+        let dir = temp_dir("reuse");
+        let path = region_path(&dir, IVec2::ZERO);
+        let mut region = RegionFile::create(&path, 0, 16).unwrap();
+        let a = IVec3::new(0, 0, 0);
+        let b = IVec3::new(1, 0, 0);
+
+        region.write_chunk(a, &noise_bytes(4 * SECTOR_SIZE, 1)).unwrap();
+        region.write_chunk(b, &noise_bytes(SECTOR_SIZE, 2)).unwrap();
+        let size = region.sector_count();
+
+        // Shrinking `a` frees sectors once its new copy is written; growing `b` back into
+        // them and moving `a` into the rest lets the file shrink to its old size
+        region.write_chunk(a, &noise_bytes(SECTOR_SIZE, 3)).unwrap();
+        region.write_chunk(b, &noise_bytes(2 * SECTOR_SIZE, 4)).unwrap();
+        region.write_chunk(a, &noise_bytes(SECTOR_SIZE, 5)).unwrap();
+        assert!(region.sector_count() <= size);
+
+        assert_eq!(region.read_chunk(a).unwrap(), Some(noise_bytes(SECTOR_SIZE, 5)));
+        assert_eq!(region.read_chunk(b).unwrap(), Some(noise_bytes(2 * SECTOR_SIZE, 4)));
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn rewrites_leave_the_stored_copy_alone_until_replaced() {
+        let dir = temp_dir("rewrite");
+        let path = region_path(&dir, IVec2::ZERO);
+        let mut region = RegionFile::create(&path, 0, 16).unwrap();
+        let a = IVec3::new(2, 1, 2);
+
+        region.write_chunk(a, &noise_bytes(SECTOR_SIZE, 1)).unwrap();
+        let slot = region.slot(a).unwrap();
+        let (first, _) = region.entries[slot];
+        // Same size, so it would fit in place, but goes next to the old copy instead
+        region.write_chunk(a, &noise_bytes(SECTOR_SIZE, 2)).unwrap();
+        let (second, _) = region.entries[slot];
+        assert_ne!(first, second);
+        assert!(!region.used[first as usize], "old copy freed once replaced");
+
+        let mut reopened = RegionFile::open(&path).unwrap().unwrap();
+        assert_eq!(reopened.read_chunk(a).unwrap(), Some(noise_bytes(SECTOR_SIZE, 2)));
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn legacy_chunk_files_are_migrated() {
+        use flate2::write::GzEncoder;
+
+        let dir = temp_dir("migrate");
+        let pos = IVec3::new(-40, 3, 5);
+        let legacy = dir.join("chunk_-40_3_5.bin");
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::fast());
+        encoder.write_all(&[7, 7, 7]).unwrap();
+        fs::write(&legacy, encoder.finish().unwrap()).unwrap();
+
+        assert_eq!(migrate_legacy_chunks(&dir, 0, 16).unwrap(), 1);
+        assert!(!legacy.exists());
+        let mut region = RegionFile::open(&region_path(&dir, region_coords(pos))).unwrap().unwrap();
+        assert_eq!(region.read_chunk(pos).unwrap(), Some(vec![7, 7, 7]));
+        let _ = fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn legacy_names_parse() {
+        assert_eq!(parse_legacy_chunk_name("chunk_1_-2_3.bin"), Some(IVec3::new(1, -2, 3)));
+        assert_eq!(parse_legacy_chunk_name("chunk_1_2.bin"), None);
+        assert_eq!(parse_legacy_chunk_name("r.0.0.region"), None);
+    }
+}
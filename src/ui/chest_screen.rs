@@ -1,6 +1,7 @@
 use bevy::prelude::*;
 use bevy::window::{CursorGrabMode, CursorOptions, PrimaryWindow};
 
+use crate::controls::{Action, ActionInput};
 use crate::inventory::chest::{ChestOpen, ChestStore, CHEST_SLOTS};
 use crate::inventory::inventory::{Inventory, INVENTORY_COLS, INVENTORY_SLOTS};
 use crate::ui::inventory_screen::CursorItem;
@@ -27,6 +28,7 @@ pub struct ChestInvSlotCount(usize);
 
 pub fn toggle_chest(
     keys: Res<ButtonInput<KeyCode>>,
+    actions: ActionInput,
     mut chest_open: ResMut<ChestOpen>,
     mut cursor_q: Query<&mut CursorOptions, With<PrimaryWindow>>,
     mut cursor_item: ResMut<CursorItem>,
@@ -36,7 +38,7 @@ pub fn toggle_chest(
         return;
     }
 
-    let close = keys.just_pressed(KeyCode::Escape) || keys.just_pressed(KeyCode::KeyE);
+    let close = keys.just_pressed(KeyCode::Escape) || actions.just_pressed(Action::Inventory);
     if !close {
         return;
     }
@@ -113,6 +113,8 @@ pub fn item_placeholder_color(item: Item) -> Color {
         Item::DiamondHelmet | Item::DiamondChestplate | Item::DiamondLeggings | Item::DiamondBoots => {
             Color::srgb(0.3, 0.9, 0.85)
         }
+        Item::Bucket => Color::srgb(0.75, 0.75, 0.8),
+        Item::WaterBucket => Color::srgb(0.25, 0.45, 0.85),
         Item::Block(_) => Color::NONE,
     }
 }
@@ -146,6 +148,7 @@ pub fn block_color(block: BlockType) -> Color {
         BlockType::Snow => Color::srgb(0.95, 0.95, 0.95),
         BlockType::Clay => Color::srgb(0.6, 0.6, 0.65),
         BlockType::Sandstone => Color::srgb(0.8, 0.75, 0.5),
+        BlockType::Deepslate => Color::srgb(0.3, 0.3, 0.33),
         BlockType::BirchLog => Color::srgb(0.85, 0.8, 0.75),
         BlockType::BirchLeaves => Color::srgb(0.35, 0.6, 0.25),
         BlockType::CraftingTable => Color::srgb(0.6, 0.45, 0.25),
@@ -154,15 +157,14 @@ pub fn block_color(block: BlockType) -> Color {
         BlockType::TallGrass => Color::srgb(0.25, 0.55, 0.18),
         BlockType::Chest => Color::srgb(0.6, 0.45, 0.2),
         BlockType::Bed => Color::srgb(0.7, 0.2, 0.2),
-        BlockType::DoorBottom | BlockType::DoorTop
-        | BlockType::DoorBottomOpen | BlockType::DoorTopOpen => Color::srgb(0.6, 0.45, 0.25),
+        BlockType::Door => Color::srgb(0.6, 0.45, 0.25),
         BlockType::OakSapling => Color::srgb(0.2, 0.5, 0.15),
         BlockType::BirchSapling => Color::srgb(0.35, 0.6, 0.25),
         BlockType::Farmland => Color::srgb(0.4, 0.25, 0.1),
-        BlockType::WheatStage0 => Color::srgb(0.3, 0.55, 0.15),
-        BlockType::WheatStage1 => Color::srgb(0.4, 0.6, 0.15),
-        BlockType::WheatStage2 => Color::srgb(0.6, 0.65, 0.15),
-        BlockType::WheatStage3 => Color::srgb(0.8, 0.75, 0.2),
+        BlockType::WheatCrop => Color::srgb(0.3, 0.55, 0.15),
+        BlockType::Fire => Color::srgb(0.9, 0.5, 0.1),
+        BlockType::Ladder => Color::srgb(0.55, 0.43, 0.25),
+        BlockType::Vines => Color::srgb(0.25, 0.5, 0.12),
         BlockType::Air => Color::NONE,
     }
 }
@@ -269,6 +271,7 @@ pub struct SlotQueries<'w, 's> {
     pub furnace_inv_slots: Query<'w, 's, &'static super::furnace_screen::FurnaceInvSlot>,
     pub chest_slots: Query<'w, 's, &'static super::chest_screen::ChestSlot>,
     pub chest_inv_slots: Query<'w, 's, &'static super::chest_screen::ChestInvSlot>,
+    pub palette_slots: Query<'w, 's, &'static super::creative_screen::PaletteSlot>,
 }
 
 /// SystemParam bundle for data resources used by the tooltip system.
@@ -398,6 +401,10 @@ pub fn update_slot_hover_tooltip(
                     found_name = Some(item.display_name().to_string());
                 }
             }
+            // Creative palette slots
+            if let Ok(slot) = slots.palette_slots.get(child) {
+                found_name = Some(slot.0.display_name().to_string());
+            }
 
             if found_name.is_some() {
                 break;
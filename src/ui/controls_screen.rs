@@ -0,0 +1,325 @@
+use bevy::ecs::hierarchy::ChildSpawnerCommands;
+use bevy::prelude::*;
+use bevy::ui::FocusPolicy;
+
+use crate::controls::bindings::InputButton;
+use crate::controls::{Action, Binding, Bindings};
+
+/// The controls screen, opened from the pause menu, and the action waiting for a new
+/// binding after its button was clicked.
+#[derive(Resource, Default)]
+pub struct ControlsScreen {
+    pub open: bool,
+    pub waiting: Option<Action>,
+}
+
+#[derive(Component)]
+pub(crate) struct ControlsUiRoot;
+
+#[derive(Component)]
+pub(crate) enum ControlsButton {
+    Bind(Action),
+    Reset,
+    Done,
+}
+
+/// Text on an action's binding button.
+#[derive(Component)]
+pub(crate) struct BindingLabel(Action);
+
+const ROWS_PER_COLUMN: usize = 11;
+const ACTION_LABEL_WIDTH: f32 = 170.0;
+const BINDING_WIDTH: f32 = 140.0;
+const ROW_HEIGHT: f32 = 28.0;
+const BUTTON_WIDTH: f32 = 200.0;
+const BUTTON_HEIGHT: f32 = 40.0;
+
+const CONFLICT_COLOR: Color = Color::srgb(1.0, 0.35, 0.35);
+const WAITING_COLOR: Color = Color::srgb(1.0, 1.0, 0.4);
+
+fn binding_text(screen: &ControlsScreen, bindings: &Bindings, action: Action) -> (String, Color) {
+    if screen.waiting == Some(action) {
+        ("> ? <".to_string(), WAITING_COLOR)
+    } else if bindings.conflicts(action).is_empty() {
+        (bindings.get(action).label(), Color::WHITE)
+    } else {
+        (bindings.get(action).label(), CONFLICT_COLOR)
+    }
+}
+
+pub fn spawn_controls_ui(
+    mut commands: Commands,
+    screen: Res<ControlsScreen>,
+    bindings: Res<Bindings>,
+    existing: Query<Entity, With<ControlsUiRoot>>,
+) {
+    if !screen.is_changed() || !screen.open || !existing.is_empty() {
+        return;
+    }
+
+    commands
+        .spawn((
+            ControlsUiRoot,
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(12.0),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.85)),
+            // Over the pause menu, which stays underneath without taking clicks
+            FocusPolicy::Block,
+            ZIndex(101),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new("Controls"),
+                TextColor(Color::WHITE),
+                TextFont {
+                    font_size: 36.0,
+                    ..default()
+                },
+            ));
+            parent.spawn((
+                Text::new("Click a binding, then press a key or mouse button. Esc cancels."),
+                TextColor(Color::srgb(0.7, 0.7, 0.7)),
+                TextFont {
+                    font_size: 14.0,
+                    ..default()
+                },
+                Node {
+                    margin: UiRect::bottom(Val::Px(8.0)),
+                    ..default()
+                },
+            ));
+
+            parent
+                .spawn(Node {
+                    flex_direction: FlexDirection::Row,
+                    column_gap: Val::Px(32.0),
+                    ..default()
+                })
+                .with_children(|columns| {
+                    for actions in Action::ALL.chunks(ROWS_PER_COLUMN) {
+                        columns
+                            .spawn(Node {
+                                flex_direction: FlexDirection::Column,
+                                row_gap: Val::Px(6.0),
+                                ..default()
+                            })
+                            .with_children(|column| {
+                                for &action in actions {
+                                    spawn_action_row(column, &screen, &bindings, action);
+                                }
+                            });
+                    }
+                });
+
+            parent
+                .spawn(Node {
+                    flex_direction: FlexDirection::Row,
+                    column_gap: Val::Px(12.0),
+                    margin: UiRect::top(Val::Px(12.0)),
+                    ..default()
+                })
+                .with_children(|buttons| {
+                    spawn_button(buttons, "Reset to Defaults", ControlsButton::Reset);
+                    spawn_button(buttons, "Done", ControlsButton::Done);
+                });
+        });
+}
+
+fn spawn_action_row(parent: &mut ChildSpawnerCommands, screen: &ControlsScreen, bindings: &Bindings, action: Action) {
+    let (text, color) = binding_text(screen, bindings, action);
+    parent
+        .spawn(Node {
+            flex_direction: FlexDirection::Row,
+            align_items: AlignItems::Center,
+            ..default()
+        })
+        .with_children(|row| {
+            row.spawn((
+                Text::new(action.display_name()),
+                TextColor(Color::WHITE),
+                TextFont {
+                    font_size: 15.0,
+                    ..default()
+                },
+                Node {
+                    width: Val::Px(ACTION_LABEL_WIDTH),
+                    ..default()
+                },
+            ));
+            row.spawn((
+                ControlsButton::Bind(action),
+                Node {
+                    width: Val::Px(BINDING_WIDTH),
+                    height: Val::Px(ROW_HEIGHT),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    border: UiRect::all(Val::Px(2.0)),
+                    ..default()
+                },
+                BorderColor::all(Color::srgba(0.6, 0.6, 0.6, 0.8)),
+                BackgroundColor(Color::srgba(0.2, 0.2, 0.2, 0.9)),
+                Interaction::default(),
+            ))
+            .with_children(|btn| {
+                btn.spawn((
+                    BindingLabel(action),
+                    Text::new(text),
+                    TextColor(color),
+                    TextFont {
+                        font_size: 15.0,
+                        ..default()
+                    },
+                ));
+            });
+        });
+}
+
+fn spawn_button(parent: &mut ChildSpawnerCommands, label: &str, button: ControlsButton) {
+    parent
+        .spawn((
+            button,
+            Node {
+                width: Val::Px(BUTTON_WIDTH),
+                height: Val::Px(BUTTON_HEIGHT),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                border: UiRect::all(Val::Px(2.0)),
+                ..default()
+            },
+            BorderColor::all(Color::srgba(0.6, 0.6, 0.6, 0.8)),
+            BackgroundColor(Color::srgba(0.2, 0.2, 0.2, 0.9)),
+            Interaction::default(),
+        ))
+        .with_children(|btn| {
+            btn.spawn((
+                Text::new(label),
+                TextColor(Color::WHITE),
+                TextFont {
+                    font_size: 18.0,
+                    ..default()
+                },
+            ));
+        });
+}
+
+pub fn despawn_controls_ui(
+    mut commands: Commands,
+    screen: Res<ControlsScreen>,
+    query: Query<Entity, With<ControlsUiRoot>>,
+) {
+    if !screen.is_changed() || screen.open {
+        return;
+    }
+
+    for entity in &query {
+        commands.entity(entity).despawn();
+    }
+}
+
+pub fn controls_button_interaction(
+    mut screen: ResMut<ControlsScreen>,
+    mut bindings: ResMut<Bindings>,
+    button_q: Query<(&Interaction, &ControlsButton), Changed<Interaction>>,
+) {
+    for (interaction, button) in &button_q {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+
+        match button {
+            ControlsButton::Bind(action) => screen.waiting = Some(*action),
+            ControlsButton::Reset => {
+                screen.waiting = None;
+                bindings.set_if_neq(Bindings::default());
+            }
+            ControlsButton::Done => {
+                screen.open = false;
+                screen.waiting = None;
+            }
+        }
+    }
+}
+
+/// Bind the waiting action to the next key or mouse button pressed. Holding Ctrl makes a
+/// Ctrl binding, so Ctrl on its own binds when it's released without another key.
+/// Runs before the buttons, so the click that starts waiting isn't taken as the binding.
+pub fn capture_binding(
+    keys: Res<ButtonInput<KeyCode>>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    mut screen: ResMut<ControlsScreen>,
+    mut bindings: ResMut<Bindings>,
+) {
+    let Some(action) = screen.waiting else {
+        return;
+    };
+    let is_ctrl = |key: KeyCode| matches!(key, KeyCode::ControlLeft | KeyCode::ControlRight);
+    let ctrl = keys.pressed(KeyCode::ControlLeft) || keys.pressed(KeyCode::ControlRight);
+
+    let pressed_key = keys
+        .get_just_pressed()
+        .copied()
+        .find(|&key| key != KeyCode::Escape && !is_ctrl(key) && Binding::is_bindable_key(key))
+        .map(InputButton::Key);
+    let pressed_mouse =
+        mouse.get_just_pressed().copied().find(|&b| Binding::is_bindable_mouse(b)).map(InputButton::Mouse);
+    let binding = match pressed_key.or(pressed_mouse) {
+        Some(button) => Binding { button, ctrl },
+        None => match keys.get_just_released().copied().find(|&key| is_ctrl(key)) {
+            Some(key) if !ctrl => Binding::key(key),
+            _ => return,
+        },
+    };
+
+    bindings.set(action, binding);
+    screen.waiting = None;
+}
+
+/// Escape stops waiting for a binding, or goes back to the pause menu.
+pub fn close_controls_on_escape(keys: Res<ButtonInput<KeyCode>>, mut screen: ResMut<ControlsScreen>) {
+    if !screen.open || !keys.just_pressed(KeyCode::Escape) {
+        return;
+    }
+    if screen.waiting.is_some() {
+        screen.waiting = None;
+    } else {
+        screen.open = false;
+    }
+}
+
+pub fn update_binding_labels(
+    screen: Res<ControlsScreen>,
+    bindings: Res<Bindings>,
+    mut label_q: Query<(&BindingLabel, &mut Text, &mut TextColor)>,
+) {
+    if !screen.is_changed() && !bindings.is_changed() {
+        return;
+    }
+    for (label, mut text, mut color) in &mut label_q {
+        let (new_text, new_color) = binding_text(&screen, &bindings, label.0);
+        text.0 = new_text;
+        color.0 = new_color;
+    }
+}
+
+pub fn controls_button_hover(
+    mut button_q: Query<
+        (&Interaction, &mut BackgroundColor),
+        (Changed<Interaction>, With<ControlsButton>),
+    >,
+) {
+    for (interaction, mut bg) in &mut button_q {
+        *bg = match interaction {
+            Interaction::Hovered => BackgroundColor(Color::srgba(0.35, 0.35, 0.35, 0.9)),
+            Interaction::Pressed => BackgroundColor(Color::srgba(0.15, 0.15, 0.15, 0.9)),
+            Interaction::None => BackgroundColor(Color::srgba(0.2, 0.2, 0.2, 0.9)),
+        };
+    }
+}
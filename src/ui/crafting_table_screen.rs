@@ -1,6 +1,7 @@
 use bevy::prelude::*;
 use bevy::window::{CursorGrabMode, CursorOptions, PrimaryWindow};
 
+use crate::controls::{Action, ActionInput};
 use crate::inventory::crafting::{self, CraftingTableGrid, CraftingTableOpen, CRAFTING_TABLE_SIZE};
 use crate::inventory::inventory::{Inventory, INVENTORY_COLS, INVENTORY_SLOTS};
 use crate::ui::inventory_screen::CursorItem;
@@ -30,6 +31,7 @@ pub struct CraftingTableInvSlotCount(usize);
 
 pub fn toggle_crafting_table(
     keys: Res<ButtonInput<KeyCode>>,
+    actions: ActionInput,
     mut ct_open: ResMut<CraftingTableOpen>,
     mut cursor_q: Query<&mut CursorOptions, With<PrimaryWindow>>,
     mut ct_grid: ResMut<CraftingTableGrid>,
@@ -42,7 +44,7 @@ pub fn toggle_crafting_table(
     }
 
     let toggle = (ct_open.0 && keys.just_pressed(KeyCode::Escape))
-        || (ct_open.0 && keys.just_pressed(KeyCode::KeyE));
+        || (ct_open.0 && actions.just_pressed(Action::Inventory));
 
     if !toggle {
         return;
@@ -0,0 +1,181 @@
+use bevy::prelude::*;
+
+use crate::inventory::inventory::{Inventory, INVENTORY_COLS, INVENTORY_SLOTS};
+use crate::inventory::item::Item;
+use crate::save::level::GameMode;
+use super::UiAtlas;
+use super::common::*;
+use super::inventory_screen::{CursorItem, InventoryOpen, InventorySlot, InventorySlotCount, InventoryUiRoot};
+
+/// Palette slots per row.
+const PALETTE_COLS: usize = 13;
+
+/// A palette slot handing out stacks of its item.
+#[derive(Component)]
+pub(crate) struct PaletteSlot(pub(crate) Item);
+
+/// In creative the inventory key opens the item palette instead of the inventory:
+/// every item, above the hotbar. It shares the inventory's root marker, so it closes
+/// the same way, and its hotbar slots are inventory slots.
+pub fn spawn_creative_ui(
+    mut commands: Commands,
+    inventory_open: Res<InventoryOpen>,
+    game_mode: Res<GameMode>,
+    inventory: Res<Inventory>,
+    atlas: Res<UiAtlas>,
+    existing: Query<Entity, With<InventoryUiRoot>>,
+) {
+    if !inventory_open.is_changed() || !inventory_open.0 || *game_mode != GameMode::Creative || !existing.is_empty() {
+        return;
+    }
+
+    let items = Item::all();
+
+    commands
+        .spawn((
+            InventoryUiRoot,
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(16.0),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.6)),
+        ))
+        .with_children(|parent| {
+            // === Palette ===
+            parent
+                .spawn(Node {
+                    display: Display::Flex,
+                    flex_direction: FlexDirection::Column,
+                    row_gap: Val::Px(SLOT_GAP),
+                    padding: UiRect::all(Val::Px(8.0)),
+                    ..default()
+                })
+                .with_children(|grid| {
+                    grid.spawn((
+                        Text::new("Creative"),
+                        TextColor(Color::WHITE),
+                        TextFont { font_size: 18.0, ..default() },
+                        Node { margin: UiRect::bottom(Val::Px(8.0)), ..default() },
+                    ));
+
+                    for row in items.chunks(PALETTE_COLS) {
+                        grid.spawn(Node {
+                            display: Display::Flex,
+                            flex_direction: FlexDirection::Row,
+                            column_gap: Val::Px(SLOT_GAP),
+                            ..default()
+                        })
+                        .with_children(|row_node| {
+                            for &item in row {
+                                let data = Some((item, 1, 0));
+                                row_node
+                                    .spawn((
+                                        Node {
+                                            width: Val::Px(SLOT_SIZE),
+                                            height: Val::Px(SLOT_SIZE),
+                                            border: UiRect::all(Val::Px(BORDER_WIDTH)),
+                                            justify_content: JustifyContent::Center,
+                                            align_items: AlignItems::Center,
+                                            ..default()
+                                        },
+                                        BorderColor::all(Color::srgba(0.4, 0.4, 0.4, 0.5)),
+                                        BackgroundColor(Color::srgba(0.1, 0.1, 0.1, 0.7)),
+                                        Interaction::default(),
+                                    ))
+                                    .with_children(|sp| {
+                                        sp.spawn((
+                                            PaletteSlot(item),
+                                            Node {
+                                                width: Val::Px(INNER_SIZE),
+                                                height: Val::Px(INNER_SIZE),
+                                                ..default()
+                                            },
+                                            slot_image(&atlas, &data),
+                                            slot_bg(&data),
+                                        ));
+                                    });
+                            }
+                        });
+                    }
+                });
+
+            // === Hotbar ===
+            parent
+                .spawn(Node {
+                    display: Display::Flex,
+                    flex_direction: FlexDirection::Row,
+                    column_gap: Val::Px(SLOT_GAP),
+                    ..default()
+                })
+                .with_children(|row_node| {
+                    for slot_idx in INVENTORY_SLOTS - INVENTORY_COLS..INVENTORY_SLOTS {
+                        let data = inventory.slots[slot_idx];
+                        row_node
+                            .spawn((
+                                Node {
+                                    width: Val::Px(SLOT_SIZE),
+                                    height: Val::Px(SLOT_SIZE),
+                                    border: UiRect::all(Val::Px(BORDER_WIDTH)),
+                                    justify_content: JustifyContent::Center,
+                                    align_items: AlignItems::Center,
+                                    ..default()
+                                },
+                                BorderColor::all(Color::srgba(0.8, 0.8, 0.3, 0.7)),
+                                BackgroundColor(Color::srgba(0.1, 0.1, 0.1, 0.7)),
+                                Interaction::default(),
+                            ))
+                            .with_children(|sp| {
+                                sp.spawn((
+                                    InventorySlot(slot_idx),
+                                    Node {
+                                        width: Val::Px(INNER_SIZE),
+                                        height: Val::Px(INNER_SIZE),
+                                        justify_content: JustifyContent::End,
+                                        align_items: AlignItems::End,
+                                        ..default()
+                                    },
+                                    slot_image(&atlas, &data),
+                                    slot_bg(&data),
+                                ))
+                                .with_children(|bp| {
+                                    bp.spawn((
+                                        InventorySlotCount(slot_idx),
+                                        Text::new(count_text(&data)),
+                                        TextColor(Color::WHITE),
+                                        TextFont { font_size: 11.0, ..default() },
+                                    ));
+                                });
+                            });
+                    }
+                });
+        });
+}
+
+/// Clicking a palette slot picks up a full stack of its item; clicking one while
+/// holding something throws the held stack away.
+pub fn palette_slot_interaction(
+    mut cursor_item: ResMut<CursorItem>,
+    palette_slot_q: Query<&PaletteSlot>,
+    interaction_q: Query<(&Interaction, &Children), Changed<Interaction>>,
+) {
+    for (interaction, children) in &interaction_q {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        for child in children.iter() {
+            if let Ok(slot) = palette_slot_q.get(child) {
+                cursor_item.0 = match cursor_item.0 {
+                    Some(_) => None,
+                    None => Some((slot.0, slot.0.max_stack(), slot.0.max_durability())),
+                };
+                return;
+            }
+        }
+    }
+}
@@ -3,10 +3,14 @@ use bevy::image::Image;
 use bevy::asset::RenderAssetUsages;
 use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
 
+use crate::block::atlas::BiomeTint;
 use crate::block::BlockType;
+use crate::controls::{Action, ActionInput};
 use crate::player::Player;
 use crate::world::chunk::{Chunk, CHUNK_SIZE};
-use crate::world::generation::generate_chunk;
+use crate::world::colormap::biome_color;
+use crate::world::generation::{generate_chunk, sample_climate, Biome};
+use crate::world::WorldHeight;
 
 /// Size of the debug map in world blocks (and pixels).
 const MAP_SIZE: usize = 128;
@@ -97,52 +101,12 @@ pub(crate) struct DebugMapText;
 #[derive(Resource)]
 pub(crate) struct DebugMapImageHandle(Handle<Image>);
 
-fn block_color(block: BlockType) -> [u8; 4] {
-    match block {
-        BlockType::Air => [0, 0, 0, 255],
-        BlockType::Stone => [128, 128, 128, 255],
-        BlockType::Dirt => [139, 90, 43, 255],
-        BlockType::Grass => [76, 153, 0, 255],
-        BlockType::Cobblestone => [120, 120, 120, 255],
-        BlockType::Planks => [180, 140, 80, 255],
-        BlockType::Sand => [237, 201, 175, 255],
-        BlockType::Gravel => [150, 140, 130, 255],
-        BlockType::OakLog | BlockType::BirchLog => [101, 67, 33, 255],
-        BlockType::OakLeaves | BlockType::BirchLeaves => [34, 139, 34, 255],
-        BlockType::Glass => [200, 220, 255, 255],
-        BlockType::CoalOre => [64, 64, 64, 255],
-        BlockType::IronOre => [180, 140, 100, 255],
-        BlockType::GoldOre => [255, 215, 0, 255],
-        BlockType::DiamondOre => [0, 255, 255, 255],
-        BlockType::Bedrock => [32, 32, 32, 255],
-        BlockType::Water => [30, 80, 200, 255],
-        BlockType::CraftingTable => [160, 120, 60, 255],
-        BlockType::Furnace => [100, 100, 100, 255],
-        BlockType::Torch => [255, 200, 50, 255],
-        BlockType::Snow => [240, 240, 255, 255],
-        BlockType::Clay => [160, 165, 175, 255],
-        BlockType::Sandstone => [220, 200, 150, 255],
-        BlockType::TallGrass => [50, 130, 50, 255],
-        BlockType::Chest => [160, 120, 50, 255],
-        BlockType::Bed => [180, 50, 50, 255],
-        BlockType::DoorBottom | BlockType::DoorTop
-        | BlockType::DoorBottomOpen | BlockType::DoorTopOpen => [160, 120, 60, 255],
-        BlockType::OakSapling => [34, 139, 34, 255],
-        BlockType::BirchSapling => [50, 160, 50, 255],
-        BlockType::Farmland => [100, 65, 25, 255],
-        BlockType::WheatStage0 => [80, 140, 40, 255],
-        BlockType::WheatStage1 => [100, 155, 40, 255],
-        BlockType::WheatStage2 => [155, 165, 40, 255],
-        BlockType::WheatStage3 => [200, 190, 50, 255],
-    }
-}
-
-/// Toggle the debug map open/closed with F4.
+/// Toggle the debug map open/closed (F4 by default).
 pub fn toggle_debug_map(
-    keys: Res<ButtonInput<KeyCode>>,
+    actions: ActionInput,
     mut state: ResMut<DebugMapState>,
 ) {
-    if keys.just_pressed(KeyCode::F4) {
+    if actions.just_pressed(Action::DebugMap) {
         state.open = !state.open;
         if state.open {
             state.dirty = true;
@@ -154,17 +118,18 @@ pub fn toggle_debug_map(
 pub fn debug_map_input(
     keys: Res<ButtonInput<KeyCode>>,
     mut state: ResMut<DebugMapState>,
+    height: Res<WorldHeight>,
 ) {
     if !state.open {
         return;
     }
 
     if keys.just_pressed(KeyCode::ArrowUp) {
-        state.y_level = (state.y_level + 1).min(255);
+        state.y_level = (state.y_level + 1).min(height.max_y() - 1);
         state.dirty = true;
     }
     if keys.just_pressed(KeyCode::ArrowDown) {
-        state.y_level = (state.y_level - 1).max(0);
+        state.y_level = (state.y_level - 1).max(height.min_y);
         state.dirty = true;
     }
     if keys.just_pressed(KeyCode::Tab) {
@@ -274,6 +239,7 @@ pub fn despawn_debug_map_ui(
 pub fn regenerate_debug_map(
     mut state: ResMut<DebugMapState>,
     player_q: Query<&Transform, With<Player>>,
+    height: Res<WorldHeight>,
     handle_res: Option<Res<DebugMapImageHandle>>,
     mut images: ResMut<Assets<Image>>,
 ) {
@@ -310,9 +276,9 @@ pub fn regenerate_debug_map(
     let cx_count = (chunk_x_max - chunk_x_min + 1) as usize;
     let cz_count = (chunk_z_max - chunk_z_min + 1) as usize;
 
-    // Chunk range in Y: we need 0..16 (world Y 0..255)
-    let chunk_y_min = 0i32;
-    let chunk_y_max = 15i32;
+    // Chunk range in Y: the whole world height
+    let chunk_y_min = height.min_chunk_y();
+    let chunk_y_max = height.max_chunk_y() - 1;
     let cy_count = (chunk_y_max - chunk_y_min + 1) as usize;
 
     // Only regenerate chunks if center changed significantly
@@ -333,7 +299,7 @@ pub fn regenerate_debug_map(
                 let mut x_row: Vec<Chunk> = Vec::with_capacity(cx_count);
                 for cx_idx in 0..cx_count {
                     let cx = chunk_x_min + cx_idx as i32;
-                    x_row.push(generate_chunk(IVec3::new(cx, cy, cz)));
+                    x_row.push(generate_chunk(IVec3::new(cx, cy, cz), *height));
                 }
                 z_row.push(x_row);
             }
@@ -347,9 +313,9 @@ pub fn regenerate_debug_map(
             for px in 0..MAP_SIZE {
                 let wx = origin_x + px as i32;
                 // Scan from top down to find first non-air block
-                let mut found_height = 0i32;
-                for wy in (0..=255).rev() {
-                    let cy_idx = (wy / CHUNK_SIZE as i32 - chunk_y_min) as usize;
+                let mut found_height = height.min_y;
+                for wy in (height.min_y..height.max_y()).rev() {
+                    let cy_idx = (wy.div_euclid(CHUNK_SIZE as i32) - chunk_y_min) as usize;
                     if cy_idx >= cy_count {
                         continue;
                     }
@@ -412,7 +378,7 @@ pub fn regenerate_debug_map(
                         chunk_x_min, state.chunk_y_min, chunk_z_min,
                         cx_count, cy_count, cz_count,
                     );
-                    block_color(block)
+                    block.map_color()
                 }
                 DebugMapMode::Heightmap => {
                     let h = state.heightmap[pz][px];
@@ -443,21 +409,16 @@ pub fn regenerate_debug_map(
                     }
                 }
                 DebugMapMode::Biome => {
-                    // Approximate biome from heightmap and surface block
-                    // Check what the surface block is at this column
-                    let h = state.heightmap[pz][px];
-                    let surface_block = sample_block(
-                        &state.chunks, wx, h, wz,
-                        chunk_x_min, state.chunk_y_min, chunk_z_min,
-                        cx_count, cy_count, cz_count,
-                    );
-                    match surface_block {
-                        BlockType::Sand | BlockType::Sandstone => [237, 201, 100, 255], // desert yellow
-                        BlockType::Grass | BlockType::TallGrass => [50, 180, 50, 255], // plains green
-                        BlockType::Water => [30, 80, 200, 255],
-                        BlockType::Snow => [230, 230, 255, 255],
-                        _ => [100, 160, 80, 255], // generic green
-                    }
+                    // The colormaps chunks are tinted with: water over oceans,
+                    // foliage in forests, grass elsewhere
+                    let climate = sample_climate(wx, wz);
+                    let tint = match Biome::from_climate(climate) {
+                        Biome::Ocean => BiomeTint::Water,
+                        Biome::Forest | Biome::BirchForest | Biome::Swamp => BiomeTint::Foliage,
+                        _ => BiomeTint::Grass,
+                    };
+                    let (r, g, b) = biome_color(tint, climate);
+                    [r, g, b, 255]
                 }
             };
 
@@ -480,9 +441,7 @@ fn sample_block(
     chunk_x_min: i32, chunk_y_min: i32, chunk_z_min: i32,
     cx_count: usize, cy_count: usize, cz_count: usize,
 ) -> BlockType {
-    if wy < 0 || wy > 255 {
-        return BlockType::Air;
-    }
+    // Out-of-range indices wrap to huge values and fail the bounds check below
     let cy_idx = (wy.div_euclid(CHUNK_SIZE as i32) - chunk_y_min) as usize;
     let cz_idx = (wz.div_euclid(CHUNK_SIZE as i32) - chunk_z_min) as usize;
     let cx_idx = (wx.div_euclid(CHUNK_SIZE as i32) - chunk_x_min) as usize;
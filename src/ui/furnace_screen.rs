@@ -2,6 +2,7 @@ use bevy::ecs::hierarchy::ChildSpawnerCommands;
 use bevy::prelude::*;
 use bevy::window::{CursorGrabMode, CursorOptions, PrimaryWindow};
 
+use crate::controls::{Action, ActionInput};
 use crate::inventory::furnace::{FurnaceOpen, Furnaces};
 use crate::inventory::inventory::{Inventory, INVENTORY_COLS, INVENTORY_SLOTS};
 use crate::inventory::item::Item;
@@ -47,12 +48,13 @@ pub struct FurnaceInvSlotCount(usize);
 
 pub fn toggle_furnace(
     keys: Res<ButtonInput<KeyCode>>,
+    actions: ActionInput,
     mut furnace_open: ResMut<FurnaceOpen>,
     mut cursor_q: Query<&mut CursorOptions, With<PrimaryWindow>>,
     mut cursor_item: ResMut<CursorItem>,
     mut inventory: ResMut<Inventory>,
 ) {
-    if !keys.just_pressed(KeyCode::Escape) {
+    if !keys.just_pressed(KeyCode::Escape) && !actions.just_pressed(Action::Inventory) {
         return;
     }
 
@@ -0,0 +1,63 @@
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+
+use crate::controls::gamepad::{stick_response, CURSOR_CURVE, CURSOR_SPEED, MOVE_DEADZONE};
+use crate::controls::GamepadCursor;
+use crate::inventory::chest::ChestOpen;
+use crate::inventory::crafting::CraftingTableOpen;
+use crate::inventory::furnace::FurnaceOpen;
+use super::inventory_screen::InventoryOpen;
+
+/// Mouse buttons held down on behalf of a controller: (left, right).
+#[derive(Default)]
+pub(crate) struct HeldClicks(bool, bool);
+
+/// While an inventory, chest, furnace or crafting-table screen is open, a controller's
+/// left stick moves the mouse pointer, A clicks and X right-clicks. It moves the real
+/// pointer and presses the real buttons, so the screens' slot handling needs no changes.
+/// Runs between input handling and UI focus, so clicks land in the frame they're made.
+pub fn gamepad_cursor(
+    time: Res<Time>,
+    gamepads: Query<&Gamepad>,
+    mut window_q: Query<&mut Window, With<PrimaryWindow>>,
+    mut mouse: ResMut<ButtonInput<MouseButton>>,
+    mut cursor: ResMut<GamepadCursor>,
+    mut held: Local<HeldClicks>,
+    inventory_open: Res<InventoryOpen>,
+    ct_open: Res<CraftingTableOpen>,
+    furnace_open: Res<FurnaceOpen>,
+    chest_open: Res<ChestOpen>,
+) {
+    let screen_open = inventory_open.0 || ct_open.0 || furnace_open.0.is_some() || chest_open.0.is_some();
+    cursor.active = screen_open && !gamepads.is_empty();
+    let pressed = |button| cursor.active && gamepads.iter().any(|g| g.pressed(button));
+    hold_click(&mut mouse, MouseButton::Left, &mut held.0, pressed(GamepadButton::South));
+    hold_click(&mut mouse, MouseButton::Right, &mut held.1, pressed(GamepadButton::West));
+    if !cursor.active {
+        return;
+    }
+
+    let stick: Vec2 = gamepads.iter().map(|g| stick_response(g.left_stick(), MOVE_DEADZONE, CURSOR_CURVE)).sum();
+    if stick == Vec2::ZERO {
+        return;
+    }
+    let Ok(mut window) = window_q.single_mut() else {
+        return;
+    };
+    let size = window.size();
+    let position = window.cursor_position().unwrap_or(size / 2.0);
+    // Window y points down, stick y up
+    let moved = position + Vec2::new(stick.x, -stick.y) * CURSOR_SPEED * time.delta_secs();
+    window.set_cursor_position(Some(moved.clamp(Vec2::ZERO, size)));
+}
+
+/// Press or release a mouse button for the controller. Only a press the controller made
+/// is released, so it never cuts a real click short.
+fn hold_click(mouse: &mut ButtonInput<MouseButton>, button: MouseButton, holding: &mut bool, pressed: bool) {
+    if pressed && !*holding {
+        mouse.press(button);
+    } else if !pressed && *holding {
+        mouse.release(button);
+    }
+    *holding = pressed;
+}
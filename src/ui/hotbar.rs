@@ -4,6 +4,7 @@ use bevy::prelude::*;
 use crate::block::BlockType;
 use crate::block::atlas::texture_index;
 use crate::block::Face;
+use crate::controls::{Action, ActionInput};
 use crate::inventory::inventory::{Inventory, INVENTORY_COLS, INVENTORY_SLOTS};
 use crate::inventory::item::Item;
 use super::UiAtlas;
@@ -178,33 +179,27 @@ pub fn spawn_hotbar(mut commands: Commands, atlas: Res<UiAtlas>, inventory: Res<
 }
 
 pub fn hotbar_input(
-    keys: Res<ButtonInput<KeyCode>>,
+    actions: ActionInput,
     scroll: Res<AccumulatedMouseScroll>,
+    gamepads: Query<&Gamepad>,
     mut hotbar: ResMut<HotbarState>,
 ) {
-    let number_keys = [
-        KeyCode::Digit1,
-        KeyCode::Digit2,
-        KeyCode::Digit3,
-        KeyCode::Digit4,
-        KeyCode::Digit5,
-        KeyCode::Digit6,
-        KeyCode::Digit7,
-        KeyCode::Digit8,
-        KeyCode::Digit9,
-    ];
-
-    for (i, key) in number_keys.iter().enumerate() {
-        if keys.just_pressed(*key) {
+    for (i, action) in Action::HOTBAR.into_iter().enumerate() {
+        if actions.just_pressed(action) {
             hotbar.selected_slot = i;
             return;
         }
     }
 
-    let scroll_y = scroll.delta.y;
-    if scroll_y.abs() > 0.0 {
+    // Scrolling up or the left bumper steps left, scrolling down or the right bumper right
+    let mut step = if scroll.delta.y != 0.0 { -scroll.delta.y.signum() as i32 } else { 0 };
+    for gamepad in &gamepads {
+        step += gamepad.just_pressed(GamepadButton::RightTrigger) as i32;
+        step -= gamepad.just_pressed(GamepadButton::LeftTrigger) as i32;
+    }
+    if step != 0 {
         let current = hotbar.selected_slot as i32;
-        let next = (current - scroll_y.signum() as i32).rem_euclid(HOTBAR_SLOTS as i32);
+        let next = (current + step.signum()).rem_euclid(HOTBAR_SLOTS as i32);
         hotbar.selected_slot = next as usize;
     }
 }
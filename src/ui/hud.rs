@@ -1,7 +1,11 @@
 use bevy::diagnostic::{DiagnosticsStore, FrameTimeDiagnosticsPlugin};
 use bevy::prelude::*;
 
+use crate::controls::{Action, ActionInput};
 use crate::player::{Player, PlayerYaw, PlayerPitch, Health, AirSupply, Hunger, ArmorSlots};
+use crate::save::level::GameMode;
+use crate::world::light::light_levels_at;
+use crate::world::manager::{CaveCulling, ChunkDataStore};
 
 #[derive(Component)]
 pub struct DebugText;
@@ -9,6 +13,9 @@ pub struct DebugText;
 #[derive(Component)]
 pub struct HeartIcon(pub usize);
 
+#[derive(Component)]
+pub struct HealthBar;
+
 pub fn spawn_crosshair(mut commands: Commands) {
     // Vertical bar
     commands.spawn((
@@ -69,19 +76,23 @@ pub fn spawn_health_bar(mut commands: Commands) {
     let half_hotbar = (9.0 * 40.0 + 8.0 * 2.0) / 2.0; // hotbar total width / 2
 
     commands
-        .spawn(Node {
-            position_type: PositionType::Absolute,
-            bottom: Val::Px(56.0),
-            left: Val::Percent(50.0),
-            margin: UiRect {
-                left: Val::Px(-half_hotbar),
+        .spawn((
+            HealthBar,
+            Node {
+                position_type: PositionType::Absolute,
+                bottom: Val::Px(56.0),
+                left: Val::Percent(50.0),
+                margin: UiRect {
+                    left: Val::Px(-half_hotbar),
+                    ..default()
+                },
+                display: Display::Flex,
+                flex_direction: FlexDirection::Row,
+                column_gap: Val::Px(HEART_GAP),
                 ..default()
             },
-            display: Display::Flex,
-            flex_direction: FlexDirection::Row,
-            column_gap: Val::Px(HEART_GAP),
-            ..default()
-        })
+            Visibility::default(),
+        ))
         .with_children(|parent| {
             for i in 0..NUM_HEARTS {
                 parent.spawn((
@@ -99,12 +110,23 @@ pub fn spawn_health_bar(mut commands: Commands) {
 
 pub fn update_health_bar(
     player_q: Query<&Health, With<Player>>,
+    game_mode: Res<GameMode>,
+    mut bar_q: Query<&mut Visibility, With<HealthBar>>,
     mut heart_q: Query<(&HeartIcon, &mut BackgroundColor)>,
 ) {
     let Ok(health) = player_q.single() else {
         return;
     };
 
+    // Only shown in modes where the player can be hurt
+    for mut vis in &mut bar_q {
+        *vis = if game_mode.is_vulnerable() {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+    }
+
     for (heart, mut bg) in &mut heart_q {
         let threshold = (heart.0 as f32 + 1.0) * 2.0;
         if health.current >= threshold {
@@ -117,10 +139,22 @@ pub fn update_health_bar(
     }
 }
 
+/// Toggle cave culling (F7 by default), to compare frame times with and without it.
+pub fn toggle_cave_culling(actions: ActionInput, mut culling: ResMut<CaveCulling>) {
+    if actions.just_pressed(Action::CaveCulling) {
+        culling.enabled = !culling.enabled;
+        culling.dirty = true;
+    }
+}
+
 pub fn update_debug_text(
     diagnostics: Res<DiagnosticsStore>,
     player_q: Query<(&Transform, &PlayerYaw, &PlayerPitch), With<Player>>,
     mut text_q: Query<&mut Text, With<DebugText>>,
+    store: Option<Res<ChunkDataStore>>,
+    culling: Res<CaveCulling>,
+    // Last frame time seen with cave culling off and on
+    mut frame_times: Local<[Option<f64>; 2]>,
 ) {
     let Ok(mut text) = text_q.single_mut() else {
         return;
@@ -130,6 +164,12 @@ pub fn update_debug_text(
         .get(&FrameTimeDiagnosticsPlugin::FPS)
         .and_then(|d| d.smoothed())
         .unwrap_or(0.0);
+    if let Some(frame_time) = diagnostics
+        .get(&FrameTimeDiagnosticsPlugin::FRAME_TIME)
+        .and_then(|d| d.smoothed())
+    {
+        frame_times[culling.enabled as usize] = Some(frame_time);
+    }
 
     let (pos_str, facing_str) = if let Ok((tf, yaw, pitch)) = player_q.single() {
         let pos = tf.translation;
@@ -146,10 +186,15 @@ pub fn update_debug_text(
             _ => "?",
         };
 
+        let (sky_light, block_light) = store
+            .as_ref()
+            .map(|s| light_levels_at(s, pos.floor().as_ivec3()))
+            .unwrap_or((0, 0));
+
         (
             format!(
-                "XYZ: {:.1} / {:.1} / {:.1}\nChunk: {} {} {}",
-                pos.x, pos.y, pos.z, chunk_x, chunk_y, chunk_z
+                "XYZ: {:.1} / {:.1} / {:.1}\nChunk: {} {} {}\nLight: {} sky, {} block",
+                pos.x, pos.y, pos.z, chunk_x, chunk_y, chunk_z, sky_light, block_light
             ),
             format!("Facing: {} ({:.1} / {:.1})", facing, yaw_deg, pitch.0.to_degrees()),
         )
@@ -157,7 +202,30 @@ pub fn update_debug_text(
         (String::new(), String::new())
     };
 
-    **text = format!("FPS: {:.0}\n{}\n{}", fps, pos_str, facing_str);
+    let mem_str = match store.as_ref() {
+        Some(s) if !s.chunks.is_empty() => {
+            let total: usize = s.chunks.values().map(|c| c.memory_bytes()).sum();
+            format!(
+                "Chunks: {} loaded, {} B/chunk ({:.1} MiB)",
+                s.chunks.len(),
+                total / s.chunks.len(),
+                total as f32 / (1024.0 * 1024.0)
+            )
+        }
+        _ => String::new(),
+    };
+
+    let ms = |time: Option<f64>| time.map_or("-".to_string(), |t| format!("{:.1} ms", t));
+    let cull_str = format!(
+        "Cave culling (F7): {}, {} / {} chunks drawn\nFrame time: {} culled, {} unculled",
+        if culling.enabled { "on" } else { "off" },
+        culling.drawn,
+        culling.meshed,
+        ms(frame_times[1]),
+        ms(frame_times[0])
+    );
+
+    **text = format!("FPS: {:.0}\n{}\n{}\n{}\n{}", fps, pos_str, facing_str, mem_str, cull_str);
 }
 
 const BUBBLE_SIZE: f32 = 12.0;
@@ -211,6 +279,7 @@ pub fn spawn_air_bar(mut commands: Commands) {
 
 pub fn update_air_bar(
     player_q: Query<&AirSupply, With<Player>>,
+    game_mode: Res<GameMode>,
     mut bar_q: Query<&mut Visibility, With<AirBar>>,
     mut bubble_q: Query<(&AirBubble, &mut BackgroundColor)>,
 ) {
@@ -222,7 +291,7 @@ pub fn update_air_bar(
 
     // Hide bar when air is full
     for mut vis in &mut bar_q {
-        *vis = if ratio >= 1.0 {
+        *vis = if ratio >= 1.0 || !game_mode.is_vulnerable() {
             Visibility::Hidden
         } else {
             Visibility::Inherited
@@ -254,24 +323,31 @@ const EMPTY_DRUMSTICK_COLOR: Color = Color::srgb(0.15, 0.1, 0.05);
 #[derive(Component)]
 pub struct DrumstickIcon(pub usize);
 
+#[derive(Component)]
+pub struct HungerBar;
+
 pub fn spawn_hunger_bar(mut commands: Commands) {
     let total_width = NUM_DRUMSTICKS as f32 * DRUMSTICK_SIZE + (NUM_DRUMSTICKS as f32 - 1.0) * DRUMSTICK_GAP;
     let half_hotbar = (9.0 * 40.0 + 8.0 * 2.0) / 2.0; // hotbar total width / 2
 
     commands
-        .spawn(Node {
-            position_type: PositionType::Absolute,
-            bottom: Val::Px(56.0),
-            left: Val::Percent(50.0),
-            margin: UiRect {
-                left: Val::Px(half_hotbar - total_width),
+        .spawn((
+            HungerBar,
+            Node {
+                position_type: PositionType::Absolute,
+                bottom: Val::Px(56.0),
+                left: Val::Percent(50.0),
+                margin: UiRect {
+                    left: Val::Px(half_hotbar - total_width),
+                    ..default()
+                },
+                display: Display::Flex,
+                flex_direction: FlexDirection::Row,
+                column_gap: Val::Px(DRUMSTICK_GAP),
                 ..default()
             },
-            display: Display::Flex,
-            flex_direction: FlexDirection::Row,
-            column_gap: Val::Px(DRUMSTICK_GAP),
-            ..default()
-        })
+            Visibility::default(),
+        ))
         .with_children(|parent| {
             for i in 0..NUM_DRUMSTICKS {
                 parent.spawn((
@@ -289,12 +365,22 @@ pub fn spawn_hunger_bar(mut commands: Commands) {
 
 pub fn update_hunger_bar(
     player_q: Query<&Hunger, With<Player>>,
+    game_mode: Res<GameMode>,
+    mut bar_q: Query<&mut Visibility, With<HungerBar>>,
     mut drumstick_q: Query<(&DrumstickIcon, &mut BackgroundColor)>,
 ) {
     let Ok(hunger) = player_q.single() else {
         return;
     };
 
+    for mut vis in &mut bar_q {
+        *vis = if game_mode.is_vulnerable() {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+    }
+
     // Each drumstick represents 2 food points
     for (drumstick, mut bg) in &mut drumstick_q {
         let threshold = (drumstick.0 as f32 + 1.0) * 2.0;
@@ -361,6 +447,7 @@ pub fn spawn_armor_bar(mut commands: Commands) {
 
 pub fn update_armor_bar(
     player_q: Query<&ArmorSlots, With<Player>>,
+    game_mode: Res<GameMode>,
     mut bar_q: Query<&mut Visibility, With<ArmorBar>>,
     mut shield_q: Query<(&ShieldIcon, &mut BackgroundColor)>,
 ) {
@@ -372,7 +459,7 @@ pub fn update_armor_bar(
 
     // Hide bar when armor is 0
     for mut vis in &mut bar_q {
-        *vis = if total > 0.0 {
+        *vis = if total > 0.0 && game_mode.is_vulnerable() {
             Visibility::Inherited
         } else {
             Visibility::Hidden
@@ -1,6 +1,7 @@
 use bevy::prelude::*;
 use bevy::window::{CursorGrabMode, CursorOptions, PrimaryWindow};
 
+use crate::controls::{Action, ActionInput};
 use crate::inventory::crafting::{self, CraftingGrid, CRAFTING_GRID_SIZE};
 use crate::inventory::inventory::{Inventory, INVENTORY_COLS, INVENTORY_SLOTS};
 use crate::inventory::item::Item;
@@ -21,7 +22,7 @@ pub(crate) struct InventoryUiRoot;
 pub(crate) struct InventorySlot(pub(crate) usize);
 
 #[derive(Component)]
-pub(crate) struct InventorySlotCount(usize);
+pub(crate) struct InventorySlotCount(pub(crate) usize);
 
 #[derive(Component)]
 pub(crate) struct CraftingSlot(pub(crate) usize);
@@ -88,6 +89,7 @@ pub fn spawn_cursor_item_display(mut commands: Commands, atlas: Res<UiAtlas>) {
 
 pub fn toggle_inventory(
     keys: Res<ButtonInput<KeyCode>>,
+    actions: ActionInput,
     mut inventory_open: ResMut<InventoryOpen>,
     mut cursor_q: Query<&mut CursorOptions, With<PrimaryWindow>>,
     mut crafting_grid: ResMut<CraftingGrid>,
@@ -102,7 +104,7 @@ pub fn toggle_inventory(
         return;
     }
 
-    let toggle = keys.just_pressed(KeyCode::KeyE)
+    let toggle = actions.just_pressed(Action::Inventory)
         || (inventory_open.0 && keys.just_pressed(KeyCode::Escape));
 
     if !toggle {
@@ -138,8 +140,10 @@ pub fn spawn_inventory_ui(
     atlas: Res<UiAtlas>,
     existing: Query<Entity, With<InventoryUiRoot>>,
     armor_q: Query<&crate::player::ArmorSlots, With<crate::player::Player>>,
+    game_mode: Res<crate::save::level::GameMode>,
 ) {
-    if !inventory_open.is_changed() || !inventory_open.0 || !existing.is_empty() {
+    // Creative opens the item palette instead (creative_screen)
+    if !inventory_open.is_changed() || !inventory_open.0 || !existing.is_empty() || *game_mode == crate::save::level::GameMode::Creative {
         return;
     }
 
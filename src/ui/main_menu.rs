@@ -1,18 +1,25 @@
 use bevy::ecs::hierarchy::ChildSpawnerCommands;
-use bevy::ecs::message::MessageWriter;
+use bevy::ecs::message::{Message, MessageReader, MessageWriter};
+use bevy::input::keyboard::{Key, KeyboardInput};
 use bevy::prelude::*;
 use bevy::window::{CursorGrabMode, CursorOptions, PrimaryWindow};
+use std::path::Path;
 
+use crate::entity::falling_block::FallingBlock;
 use crate::entity::mob::Mob;
 use crate::inventory::chest::ChestStore;
 use crate::inventory::crafting::{CraftingGrid, CraftingTableGrid};
 use crate::inventory::furnace::Furnaces;
 use crate::inventory::inventory::Inventory;
-use crate::player::{Player, SpawnPoint, Health, AirSupply, Velocity, OnGround, FallTracker, PlayerYaw, PlayerPitch, ArmorSlots, Hunger};
+use crate::lighting::day_night::DayNightCycle;
+use crate::lighting::weather::WeatherState;
+use crate::player::{Player, SpawnPoint, Health, AirSupply, Velocity, OnGround, FallTracker, PlayerYaw, PlayerPitch, ArmorSlots, Hunger, Flying};
+use crate::save::level::{self, GameMode, WorldEntry, DEFAULT_HEIGHT, DEFAULT_MIN_Y, SAVES_DIR};
+use crate::save::persistence;
 use crate::ui::inventory_screen::CursorItem;
-use crate::world::WorldSeed;
+use crate::world::{WorldHeight, WorldSeed};
 use crate::world::generation::set_world_seed;
-use crate::world::manager::{ChunkManager, ChunkDataStore, ChunkCoord, SaplingTracker, CropTracker};
+use crate::world::manager::{ChunkManager, ChunkDataStore, ChunkCoord, FarTerrain, FarTerrainTile, SaplingTracker, CropTracker, FireTracker};
 
 #[derive(Resource)]
 pub struct InMainMenu(pub bool);
@@ -23,30 +30,105 @@ impl Default for InMainMenu {
     }
 }
 
+/// Which page of the main menu is shown.
+#[derive(Resource, Default, PartialEq, Eq)]
+pub enum MenuScreen {
+    #[default]
+    Title,
+    WorldList,
+}
+
+/// Worlds on disk as shown in the world list, plus the selection and any rename in progress.
+#[derive(Resource, Default)]
+pub struct WorldList {
+    worlds: Vec<WorldEntry>,
+    selected: Option<usize>,
+    /// Text typed so far while renaming the selected world.
+    renaming: Option<String>,
+    /// Index into `NEW_WORLD_HEIGHTS` for the next world created.
+    new_height: usize,
+}
+
+impl WorldList {
+    fn refresh(&mut self) {
+        self.worlds = level::list_worlds(Path::new(SAVES_DIR));
+        self.selected = self.selected.filter(|&i| i < self.worlds.len());
+        self.renaming = None;
+    }
+
+    fn selected_dir(&self) -> Option<&str> {
+        self.selected.map(|i| self.worlds[i].dir_name.as_str())
+    }
+}
+
+/// Heights a new world can be created with: name, bottom Y and height in blocks.
+/// All are whole chunks and reach above sea level and the tallest mountains.
+const NEW_WORLD_HEIGHTS: [(&str, i32, i32); 3] = [
+    ("Normal", DEFAULT_MIN_Y, DEFAULT_HEIGHT),
+    ("Shallow", 0, 256),
+    ("Deep", -256, 640),
+];
+
+/// Sent to load a world from `saves/<dir_name>` and start playing it.
+#[derive(Message)]
+pub struct EnterWorld {
+    pub dir_name: String,
+}
+
 #[derive(Component)]
 pub(crate) struct MainMenuRoot;
 
-#[derive(Component)]
+#[derive(Component, PartialEq, Eq)]
 pub(crate) enum MainMenuButton {
-    NewWorld,
-    LoadWorld,
+    Singleplayer,
     Quit,
+    SelectWorld(usize),
+    PlaySelected,
+    CreateWorld,
+    RenameWorld,
+    /// Switches the selected world to the next game mode.
+    GameMode,
+    /// Switches to the next height for new worlds.
+    NewWorldHeight,
+    DuplicateWorld,
+    DeleteWorld,
+    Back,
 }
 
 const BUTTON_WIDTH: f32 = 220.0;
 const BUTTON_HEIGHT: f32 = 44.0;
 const BUTTON_GAP: f32 = 14.0;
+const WORLD_ROW_WIDTH: f32 = 460.0;
+const WORLD_ROW_HEIGHT: f32 = 54.0;
+const SMALL_BUTTON_WIDTH: f32 = 140.0;
 
-pub fn setup_main_menu(
-    mut commands: Commands,
-    mut cursor_q: Query<&mut CursorOptions, With<PrimaryWindow>>,
-) {
+const BUTTON_COLOR: Color = Color::srgba(0.15, 0.15, 0.2, 0.9);
+const BUTTON_HOVER_COLOR: Color = Color::srgba(0.3, 0.3, 0.4, 0.9);
+const BUTTON_PRESSED_COLOR: Color = Color::srgba(0.1, 0.1, 0.15, 0.9);
+const SELECTED_COLOR: Color = Color::srgba(0.25, 0.35, 0.55, 0.9);
+
+pub fn setup_main_menu(mut cursor_q: Query<&mut CursorOptions, With<PrimaryWindow>>) {
     if let Ok(mut cursor) = cursor_q.single_mut() {
         cursor.grab_mode = CursorGrabMode::None;
         cursor.visible = true;
     }
+}
 
-    let has_saves = std::path::Path::new("saves/world").exists();
+/// Respawns the menu UI whenever the page or the world list changes.
+pub fn rebuild_main_menu(
+    mut commands: Commands,
+    in_menu: Res<InMainMenu>,
+    screen: Res<MenuScreen>,
+    list: Res<WorldList>,
+    query: Query<Entity, With<MainMenuRoot>>,
+) {
+    if !in_menu.0 || !(in_menu.is_changed() || screen.is_changed() || list.is_changed()) {
+        return;
+    }
+
+    for entity in &query {
+        commands.entity(entity).despawn();
+    }
 
     commands
         .spawn((
@@ -64,37 +146,152 @@ pub fn setup_main_menu(
             BackgroundColor(Color::srgba(0.05, 0.05, 0.12, 0.95)),
             ZIndex(200),
         ))
-        .with_children(|parent| {
-            // Title
-            parent.spawn((
-                Text::new("MineClaude"),
-                TextColor(Color::WHITE),
-                TextFont {
-                    font_size: 52.0,
-                    ..default()
-                },
+        .with_children(|parent| match *screen {
+            MenuScreen::Title => spawn_title_screen(parent),
+            MenuScreen::WorldList => spawn_world_list(parent, &list),
+        });
+}
+
+fn spawn_title_screen(parent: &mut ChildSpawnerCommands) {
+    parent.spawn((
+        Text::new("MineClaude"),
+        TextColor(Color::WHITE),
+        TextFont {
+            font_size: 52.0,
+            ..default()
+        },
+        Node {
+            margin: UiRect::bottom(Val::Px(40.0)),
+            ..default()
+        },
+    ));
+
+    spawn_menu_button(parent, "Singleplayer", MainMenuButton::Singleplayer, BUTTON_WIDTH);
+    spawn_menu_button(parent, "Quit", MainMenuButton::Quit, BUTTON_WIDTH);
+}
+
+fn spawn_world_list(parent: &mut ChildSpawnerCommands, list: &WorldList) {
+    parent.spawn((
+        Text::new("Select World"),
+        TextColor(Color::WHITE),
+        TextFont {
+            font_size: 36.0,
+            ..default()
+        },
+        Node {
+            margin: UiRect::bottom(Val::Px(20.0)),
+            ..default()
+        },
+    ));
+
+    if list.worlds.is_empty() {
+        parent.spawn((
+            Text::new("No worlds yet"),
+            TextColor(Color::srgb(0.6, 0.6, 0.65)),
+            TextFont {
+                font_size: 18.0,
+                ..default()
+            },
+        ));
+    }
+
+    for (i, world) in list.worlds.iter().enumerate() {
+        let name = match (&list.renaming, list.selected == Some(i)) {
+            (Some(text), true) => format!("{}_", text),
+            _ => world.level.name.clone(),
+        };
+        let details = format!(
+            "{} - {} - last played {} - seed {}",
+            world.dir_name,
+            world.level.game_mode.display_name(),
+            level::format_date(world.level.last_played),
+            world.level.seed
+        );
+        let selected = list.selected == Some(i);
+        parent
+            .spawn((
+                MainMenuButton::SelectWorld(i),
                 Node {
-                    margin: UiRect::bottom(Val::Px(40.0)),
+                    width: Val::Px(WORLD_ROW_WIDTH),
+                    height: Val::Px(WORLD_ROW_HEIGHT),
+                    flex_direction: FlexDirection::Column,
+                    justify_content: JustifyContent::Center,
+                    padding: UiRect::horizontal(Val::Px(12.0)),
+                    border: UiRect::all(Val::Px(2.0)),
                     ..default()
                 },
-            ));
-
-            spawn_menu_button(parent, "New World", MainMenuButton::NewWorld);
+                BorderColor::all(Color::srgba(0.5, 0.5, 0.6, 0.8)),
+                BackgroundColor(if selected { SELECTED_COLOR } else { BUTTON_COLOR }),
+                Interaction::default(),
+            ))
+            .with_children(|row| {
+                row.spawn((
+                    Text::new(name),
+                    TextColor(Color::WHITE),
+                    TextFont {
+                        font_size: 20.0,
+                        ..default()
+                    },
+                ));
+                row.spawn((
+                    Text::new(details),
+                    TextColor(Color::srgb(0.65, 0.65, 0.7)),
+                    TextFont {
+                        font_size: 13.0,
+                        ..default()
+                    },
+                ));
+            });
+    }
 
-            if has_saves {
-                spawn_menu_button(parent, "Load World", MainMenuButton::LoadWorld);
-            }
+    parent
+        .spawn(Node {
+            flex_direction: FlexDirection::Row,
+            column_gap: Val::Px(BUTTON_GAP),
+            margin: UiRect::top(Val::Px(20.0)),
+            ..default()
+        })
+        .with_children(|row| {
+            spawn_menu_button(row, "Play", MainMenuButton::PlaySelected, SMALL_BUTTON_WIDTH);
+            spawn_menu_button(row, "Create New", MainMenuButton::CreateWorld, SMALL_BUTTON_WIDTH);
+            spawn_menu_button(row, "Rename", MainMenuButton::RenameWorld, SMALL_BUTTON_WIDTH);
+        });
+    parent
+        .spawn(Node {
+            flex_direction: FlexDirection::Row,
+            column_gap: Val::Px(BUTTON_GAP),
+            ..default()
+        })
+        .with_children(|row| {
+            spawn_menu_button(row, "Duplicate", MainMenuButton::DuplicateWorld, SMALL_BUTTON_WIDTH);
+            spawn_menu_button(row, "Delete", MainMenuButton::DeleteWorld, SMALL_BUTTON_WIDTH);
+            spawn_menu_button(row, "Back", MainMenuButton::Back, SMALL_BUTTON_WIDTH);
+        });
 
-            spawn_menu_button(parent, "Quit", MainMenuButton::Quit);
+    let label = match list.selected {
+        Some(i) => format!("Game Mode: {}", list.worlds[i].level.game_mode.display_name()),
+        None => "Game Mode".to_string(),
+    };
+    parent
+        .spawn(Node {
+            margin: UiRect::top(Val::Px(BUTTON_GAP)),
+            ..default()
+        })
+        .with_children(|row| {
+            spawn_menu_button(row, &label, MainMenuButton::GameMode, BUTTON_WIDTH);
         });
+
+    let (name, min_y, height) = NEW_WORLD_HEIGHTS[list.new_height];
+    let label = format!("New World Height: {} ({} to {})", name, min_y, min_y + height);
+    spawn_menu_button(parent, &label, MainMenuButton::NewWorldHeight, WORLD_ROW_WIDTH);
 }
 
-fn spawn_menu_button(parent: &mut ChildSpawnerCommands, label: &str, button: MainMenuButton) {
+fn spawn_menu_button(parent: &mut ChildSpawnerCommands, label: &str, button: MainMenuButton, width: f32) {
     parent
         .spawn((
             button,
             Node {
-                width: Val::Px(BUTTON_WIDTH),
+                width: Val::Px(width),
                 height: Val::Px(BUTTON_HEIGHT),
                 justify_content: JustifyContent::Center,
                 align_items: AlignItems::Center,
@@ -102,7 +299,7 @@ fn spawn_menu_button(parent: &mut ChildSpawnerCommands, label: &str, button: Mai
                 ..default()
             },
             BorderColor::all(Color::srgba(0.5, 0.5, 0.6, 0.8)),
-            BackgroundColor(Color::srgba(0.15, 0.15, 0.2, 0.9)),
+            BackgroundColor(BUTTON_COLOR),
             Interaction::default(),
         ))
         .with_children(|btn| {
@@ -118,16 +315,170 @@ fn spawn_menu_button(parent: &mut ChildSpawnerCommands, label: &str, button: Mai
 }
 
 pub fn main_menu_interaction(
-    mut in_menu: ResMut<InMainMenu>,
-    mut cursor_q: Query<&mut CursorOptions, With<PrimaryWindow>>,
     button_q: Query<(&Interaction, &MainMenuButton), Changed<Interaction>>,
+    mut screen: ResMut<MenuScreen>,
+    mut list: ResMut<WorldList>,
     mut exit: MessageWriter<AppExit>,
+    mut enter: MessageWriter<EnterWorld>,
+) {
+    let saves = Path::new(SAVES_DIR);
+    for (interaction, button) in &button_q {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+
+        match button {
+            MainMenuButton::Singleplayer => {
+                list.refresh();
+                *screen = MenuScreen::WorldList;
+            }
+            MainMenuButton::Quit => {
+                exit.write(AppExit::Success);
+            }
+            MainMenuButton::SelectWorld(i) => {
+                if list.selected == Some(*i) && list.renaming.is_none() {
+                    // Clicking the selected world again plays it
+                    enter.write(EnterWorld { dir_name: list.worlds[*i].dir_name.clone() });
+                } else {
+                    list.selected = Some(*i);
+                    list.renaming = None;
+                }
+            }
+            MainMenuButton::PlaySelected => {
+                if let Some(dir_name) = list.selected_dir() {
+                    enter.write(EnterWorld { dir_name: dir_name.to_string() });
+                }
+            }
+            MainMenuButton::CreateWorld => {
+                // Random seed from system time
+                let seed = std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .map(|d| d.as_millis() as u32)
+                    .unwrap_or(12345);
+                let (_, min_y, height) = NEW_WORLD_HEIGHTS[list.new_height];
+                match level::create_world(saves, "New World", seed, min_y, height) {
+                    Ok(dir_name) => {
+                        info!("[WORLD] Created world '{}' with seed {}", dir_name, seed);
+                        enter.write(EnterWorld { dir_name });
+                    }
+                    Err(e) => warn!("Failed to create world: {}", e),
+                }
+            }
+            MainMenuButton::RenameWorld => {
+                if let Some(i) = list.selected {
+                    list.renaming = Some(list.worlds[i].level.name.clone());
+                }
+            }
+            MainMenuButton::GameMode => {
+                let Some(i) = list.selected else {
+                    continue;
+                };
+                let dir_name = list.worlds[i].dir_name.clone();
+                let mode = list.worlds[i].level.game_mode.next();
+                if let Err(e) = level::set_game_mode(saves, &dir_name, mode) {
+                    warn!("Failed to set game mode of world '{}': {}", dir_name, e);
+                }
+                list.refresh();
+            }
+            MainMenuButton::NewWorldHeight => {
+                list.new_height = (list.new_height + 1) % NEW_WORLD_HEIGHTS.len();
+            }
+            MainMenuButton::DuplicateWorld => {
+                let Some(dir_name) = list.selected_dir().map(str::to_string) else {
+                    continue;
+                };
+                if let Err(e) = level::duplicate_world(saves, &dir_name) {
+                    warn!("Failed to duplicate world '{}': {}", dir_name, e);
+                }
+                list.refresh();
+            }
+            MainMenuButton::DeleteWorld => {
+                let Some(dir_name) = list.selected_dir().map(str::to_string) else {
+                    continue;
+                };
+                if persistence::active_world() == Some(saves.join(&dir_name)) {
+                    persistence::clear_active_world();
+                }
+                if let Err(e) = level::delete_world(saves, &dir_name) {
+                    warn!("Failed to delete world '{}': {}", dir_name, e);
+                }
+                list.selected = None;
+                list.refresh();
+            }
+            MainMenuButton::Back => {
+                list.renaming = None;
+                *screen = MenuScreen::Title;
+            }
+        }
+    }
+}
+
+/// Edits the selected world's name while renaming: typing appends, Backspace deletes,
+/// Enter saves and Escape cancels.
+pub fn world_rename_input(
+    mut keys: MessageReader<KeyboardInput>,
+    mut list: ResMut<WorldList>,
+) {
+    if list.renaming.is_none() {
+        keys.clear();
+        return;
+    }
+
+    for key in keys.read() {
+        if !key.state.is_pressed() {
+            continue;
+        }
+        match &key.logical_key {
+            Key::Enter => {
+                let (Some(text), Some(dir_name)) = (list.renaming.clone(), list.selected_dir().map(str::to_string)) else {
+                    continue;
+                };
+                if !text.trim().is_empty() {
+                    if let Err(e) = level::rename_world(Path::new(SAVES_DIR), &dir_name, &text) {
+                        warn!("Failed to rename world '{}': {}", dir_name, e);
+                    }
+                }
+                list.refresh();
+                return;
+            }
+            Key::Escape => {
+                list.renaming = None;
+                return;
+            }
+            Key::Backspace => {
+                if let Some(text) = list.renaming.as_mut() {
+                    text.pop();
+                }
+            }
+            Key::Space => {
+                if let Some(text) = list.renaming.as_mut() {
+                    text.push(' ');
+                }
+            }
+            Key::Character(chars) => {
+                if let Some(text) = list.renaming.as_mut() {
+                    text.extend(chars.chars().filter(|c| !c.is_control()));
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Switches saving to the chosen world, resets all live world state and reloads it
+/// from that world's files, then closes the menu.
+pub fn enter_world(
+    mut messages: MessageReader<EnterWorld>,
+    mut in_menu: ResMut<InMainMenu>,
+    mut cursor_q: Query<&mut CursorOptions, With<PrimaryWindow>>,
     mut world_seed: ResMut<WorldSeed>,
+    mut world_height: ResMut<WorldHeight>,
     mut chunk_manager: ResMut<ChunkManager>,
     mut chunk_store: ResMut<ChunkDataStore>,
+    mut far_terrain: ResMut<FarTerrain>,
     mut commands: Commands,
-    despawn_entities: Query<Entity, Or<(With<ChunkCoord>, With<Mob>)>>,
-    mut player_q: Query<(&mut Transform, &mut Velocity, &mut OnGround, &mut FallTracker, &mut Health, &mut AirSupply, &mut PlayerYaw, &mut PlayerPitch, &mut ArmorSlots, &mut Hunger), With<Player>>,
+    despawn_entities: Query<Entity, Or<(With<ChunkCoord>, With<FarTerrainTile>, With<Mob>, With<FallingBlock>)>>,
+    mut player_q: Query<(&mut Transform, &mut Velocity, &mut OnGround, &mut FallTracker, &mut Health, &mut AirSupply, &mut PlayerYaw, &mut PlayerPitch, &mut ArmorSlots, &mut Hunger, &mut Flying), With<Player>>,
     mut spawn_point: ResMut<SpawnPoint>,
     mut reset_resources: (
         ResMut<Inventory>,
@@ -137,86 +488,116 @@ pub fn main_menu_interaction(
         ResMut<CraftingTableGrid>,
         ResMut<CursorItem>,
     ),
-    mut sapling_tracker: ResMut<SaplingTracker>,
-    mut crop_tracker: ResMut<CropTracker>,
+    mut world_state: (
+        ResMut<SaplingTracker>,
+        ResMut<CropTracker>,
+        ResMut<DayNightCycle>,
+        ResMut<WeatherState>,
+        ResMut<FireTracker>,
+        ResMut<GameMode>,
+    ),
 ) {
-    for (interaction, button) in &button_q {
-        if *interaction != Interaction::Pressed {
-            continue;
-        }
+    let Some(message) = messages.read().last() else {
+        return;
+    };
 
-        match button {
-            MainMenuButton::NewWorld => {
-                // Delete existing saves for a fresh world
-                let _ = std::fs::remove_dir_all("saves");
+    persistence::set_active_world(&message.dir_name);
+    let Some(mut level) = persistence::load_active_level() else {
+        warn!("World '{}' has no level file", message.dir_name);
+        return;
+    };
 
-                // Generate a random seed from system time
-                let new_seed = std::time::SystemTime::now()
-                    .duration_since(std::time::UNIX_EPOCH)
-                    .map(|d| d.as_millis() as u32)
-                    .unwrap_or(12345);
-                world_seed.0 = new_seed;
-                set_world_seed(new_seed);
-                info!("[WORLD] New world with seed {}", new_seed);
+    world_seed.0 = level.seed;
+    *world_height = WorldHeight::of_level(&level);
+    set_world_seed(level.seed);
+    info!("[WORLD] Entering '{}' with seed {}", level.name, level.seed);
 
-                // Despawn all chunk entities and mob entities
-                for entity in &despawn_entities {
-                    commands.entity(entity).despawn();
-                }
+    // Despawn all chunk and far terrain entities, mobs and falling blocks
+    for entity in &despawn_entities {
+        commands.entity(entity).despawn();
+    }
 
-                // Clear chunk data
-                chunk_manager.loaded.clear();
-                chunk_store.chunks.clear();
-                chunk_store.modified.clear();
-
-                // Reset player inventory to default starter items
-                *reset_resources.0 = Inventory::default();
-
-                // Clear furnace, chest, crafting, and cursor state
-                reset_resources.1.data.clear();
-                reset_resources.2.data.clear();
-                *reset_resources.3 = CraftingGrid::default();
-                *reset_resources.4 = CraftingTableGrid::default();
-                reset_resources.5.0 = None;
-
-                // Clear growth trackers
-                *sapling_tracker = SaplingTracker::default();
-                *crop_tracker = CropTracker::default();
-
-                // Reset player to default spawn — use actual terrain height
-                let terrain_y = crate::world::generation::sample_terrain_height(0, 0);
-                *spawn_point = SpawnPoint(Vec3::new(0.0, (terrain_y + 1) as f32, 0.0));
-                if let Ok((mut transform, mut vel, mut on_ground, mut fall, mut health, mut air, mut yaw, mut pitch, mut armor, mut hunger)) = player_q.single_mut() {
-                    transform.translation = spawn_point.0;
-                    *vel = Velocity::default();
-                    on_ground.0 = false;
-                    fall.fall_start_y = None;
-                    *health = Health::default();
-                    *air = AirSupply::default();
-                    yaw.0 = 0.0;
-                    pitch.0 = 0.0;
-                    *armor = ArmorSlots::default();
-                    *hunger = Hunger::default();
-                }
+    // Clear chunk data
+    chunk_manager.loaded.clear();
+    chunk_manager.pending.clear();
+    chunk_manager.ready.clear();
+    chunk_store.chunks.clear();
+    chunk_store.modified.clear();
+    chunk_store.lights.clear();
+    chunk_store.visibility.clear();
+    chunk_store.light_dirty.clear();
+    chunk_store.fluid_ticks.clear();
+    chunk_store.gravity_checks.clear();
+    far_terrain.tiles.clear();
+    far_terrain.pending.clear();
+
+    // Load this world's inventory, containers and growth trackers
+    *reset_resources.0 = crate::inventory::load_saved_inventory();
+    *reset_resources.1 = persistence::load_furnaces();
+    *reset_resources.2 = persistence::load_chests();
+    *reset_resources.3 = CraftingGrid::default();
+    *reset_resources.4 = CraftingTableGrid::default();
+    reset_resources.5.0 = None;
+    *world_state.0 = persistence::load_saplings();
+    *world_state.1 = persistence::load_crops();
+    world_state.2.time_of_day = level.time_of_day;
+    *world_state.3 = WeatherState::load(level.weather, level.weather_time);
+    world_state.4.fires.clear();
+    *world_state.5 = level.game_mode;
+
+    // World spawn: from the level file, or dry land near the origin for a new world
+    let spawn = match level.spawn {
+        Some([x, y, z]) => Vec3::new(x, y, z),
+        None => crate::world::generation::find_spawn(),
+    };
+    *spawn_point = SpawnPoint(spawn);
 
-                in_menu.0 = false;
-                if let Ok(mut cursor) = cursor_q.single_mut() {
-                    cursor.grab_mode = CursorGrabMode::Locked;
-                    cursor.visible = false;
+    if let Ok((mut transform, mut vel, mut on_ground, mut fall, mut health, mut air, mut yaw, mut pitch, mut armor, mut hunger, mut flying)) = player_q.single_mut() {
+        *vel = Velocity::default();
+        flying.0 = level.game_mode.is_noclip();
+        on_ground.0 = false;
+        fall.fall_start_y = None;
+        *armor = ArmorSlots::default();
+        *hunger = Hunger::default();
+        *health = Health::default();
+        *air = AirSupply::default();
+        match persistence::load_player() {
+            Some(data) => {
+                if let (Some(sx), Some(sy), Some(sz)) = (data.spawn_x, data.spawn_y, data.spawn_z) {
+                    spawn_point.0 = Vec3::new(sx, sy, sz);
                 }
-            }
-            MainMenuButton::LoadWorld => {
-                in_menu.0 = false;
-                if let Ok(mut cursor) = cursor_q.single_mut() {
-                    cursor.grab_mode = CursorGrabMode::Locked;
-                    cursor.visible = false;
+                transform.translation = Vec3::from(data.position);
+                yaw.0 = data.yaw;
+                pitch.0 = data.pitch;
+                health.current = data.health;
+                air.current = data.air_supply;
+                hunger.food_level = data.food_level.unwrap_or(hunger.food_level);
+                hunger.saturation = data.saturation.unwrap_or(hunger.saturation);
+                if let Some(armor_data) = data.armor_slots {
+                    for (i, slot) in armor_data.into_iter().enumerate().take(4) {
+                        armor.slots[i] = slot;
+                    }
                 }
             }
-            MainMenuButton::Quit => {
-                exit.write(AppExit::Success);
+            None => {
+                transform.translation = spawn_point.0;
+                yaw.0 = 0.0;
+                pitch.0 = 0.0;
             }
         }
     }
+
+    level.spawn = Some(spawn_point.0.to_array());
+    level.last_played = level::now_secs();
+    if let Err(e) = persistence::save_active_level(&level) {
+        warn!("Failed to save level: {}", e);
+    }
+
+    in_menu.0 = false;
+    if let Ok(mut cursor) = cursor_q.single_mut() {
+        cursor.grab_mode = CursorGrabMode::Locked;
+        cursor.visible = false;
+    }
 }
 
 pub fn cleanup_main_menu(
@@ -235,15 +616,18 @@ pub fn cleanup_main_menu(
 
 pub fn main_menu_button_hover(
     mut button_q: Query<
-        (&Interaction, &mut BackgroundColor),
-        (Changed<Interaction>, With<MainMenuButton>),
+        (&Interaction, &MainMenuButton, &mut BackgroundColor),
+        Changed<Interaction>,
     >,
+    list: Res<WorldList>,
 ) {
-    for (interaction, mut bg) in &mut button_q {
+    for (interaction, button, mut bg) in &mut button_q {
+        let selected = matches!(button, MainMenuButton::SelectWorld(i) if list.selected == Some(*i));
         *bg = match interaction {
-            Interaction::Hovered => BackgroundColor(Color::srgba(0.3, 0.3, 0.4, 0.9)),
-            Interaction::Pressed => BackgroundColor(Color::srgba(0.1, 0.1, 0.15, 0.9)),
-            Interaction::None => BackgroundColor(Color::srgba(0.15, 0.15, 0.2, 0.9)),
+            Interaction::Hovered => BackgroundColor(BUTTON_HOVER_COLOR),
+            Interaction::Pressed => BackgroundColor(BUTTON_PRESSED_COLOR),
+            Interaction::None if selected => BackgroundColor(SELECTED_COLOR),
+            Interaction::None => BackgroundColor(BUTTON_COLOR),
         };
     }
 }
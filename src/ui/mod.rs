@@ -1,9 +1,12 @@
 pub mod chest_screen;
 pub mod common;
+pub mod controls_screen;
 pub mod crafting_table_screen;
+pub mod creative_screen;
 pub mod death_screen;
 pub mod debug_map;
 pub mod furnace_screen;
+pub mod gamepad_cursor;
 pub mod hotbar;
 pub mod hud;
 pub mod inventory_screen;
@@ -11,7 +14,13 @@ pub mod main_menu;
 pub mod pause_menu;
 
 use bevy::diagnostic::FrameTimeDiagnosticsPlugin;
+use bevy::ecs::message::MessageReader;
+use bevy::input::InputSystems;
 use bevy::prelude::*;
+use bevy::ui::UiSystems;
+
+use crate::block::atlas::ATLAS_TILES;
+use crate::resource_pack::{AtlasRebuilt, BlockAtlas};
 
 #[derive(Resource)]
 pub struct UiAtlas {
@@ -19,16 +28,34 @@ pub struct UiAtlas {
     pub layout: Handle<TextureAtlasLayout>,
 }
 
+/// Grid layout of the block atlas for a tile size in pixels.
+fn atlas_layout(tile_size: u32) -> TextureAtlasLayout {
+    let grid = ATLAS_TILES as u32;
+    TextureAtlasLayout::from_grid(UVec2::splat(tile_size), grid, grid, None, None)
+}
+
 fn setup_ui_atlas(
     mut commands: Commands,
-    asset_server: Res<AssetServer>,
+    atlas: Res<BlockAtlas>,
     mut layouts: ResMut<Assets<TextureAtlasLayout>>,
 ) {
-    let image = asset_server.load("textures/atlas.png");
-    let layout = layouts.add(TextureAtlasLayout::from_grid(
-        UVec2::new(16, 16), 16, 16, None, None,
-    ));
-    commands.insert_resource(UiAtlas { image, layout });
+    let layout = layouts.add(atlas_layout(atlas.tile_size));
+    commands.insert_resource(UiAtlas { image: atlas.image.clone(), layout });
+}
+
+/// Resize the atlas layout in place when the atlas is rebuilt with another tile size,
+/// so every item icon keeps pointing at its tile.
+fn rebuild_ui_atlas_layout(
+    mut rebuilt: MessageReader<AtlasRebuilt>,
+    atlas: Res<UiAtlas>,
+    mut layouts: ResMut<Assets<TextureAtlasLayout>>,
+) {
+    let Some(message) = rebuilt.read().last() else {
+        return;
+    };
+    if let Err(e) = layouts.insert(&atlas.layout, atlas_layout(message.tile_size)) {
+        warn!("Failed to resize UI atlas layout: {}", e);
+    }
 }
 
 pub struct UiPlugin;
@@ -41,6 +68,9 @@ impl Plugin for UiPlugin {
             .init_resource::<inventory_screen::CursorItem>()
             .init_resource::<pause_menu::PauseState>()
             .init_resource::<main_menu::InMainMenu>()
+            .init_resource::<main_menu::MenuScreen>()
+            .init_resource::<main_menu::WorldList>()
+            .add_message::<main_menu::EnterWorld>()
             .add_systems(Startup, (
                 setup_ui_atlas,
                 main_menu::setup_main_menu,
@@ -48,9 +78,13 @@ impl Plugin for UiPlugin {
             .add_systems(
                 Update,
                 (
+                    main_menu::rebuild_main_menu,
                     main_menu::main_menu_interaction,
-                    main_menu::cleanup_main_menu,
+                    main_menu::world_rename_input,
+                    main_menu::enter_world.after(main_menu::main_menu_interaction),
+                    main_menu::cleanup_main_menu.after(main_menu::enter_world),
                     main_menu::main_menu_button_hover,
+                    rebuild_ui_atlas_layout,
                 ),
             )
             .add_systems(
@@ -81,7 +115,12 @@ impl Plugin for UiPlugin {
                         .after(hotbar::hotbar_input)
                         .after(hotbar::sync_hotbar_from_inventory),
                     hotbar::update_item_name.after(hotbar::hotbar_input),
-                    inventory_screen::toggle_inventory,
+                    // Before the screens the inventory key also closes, so closing one
+                    // doesn't open the inventory in the same frame
+                    inventory_screen::toggle_inventory
+                        .before(furnace_screen::toggle_furnace)
+                        .before(crafting_table_screen::toggle_crafting_table)
+                        .before(chest_screen::toggle_chest),
                     inventory_screen::spawn_inventory_ui
                         .after(inventory_screen::toggle_inventory),
                     inventory_screen::despawn_inventory_ui
@@ -96,6 +135,14 @@ impl Plugin for UiPlugin {
                     common::update_slot_hover_tooltip,
                 ),
             )
+            .add_systems(
+                Update,
+                (
+                    creative_screen::spawn_creative_ui
+                        .after(inventory_screen::toggle_inventory),
+                    creative_screen::palette_slot_interaction,
+                ),
+            )
             .add_systems(
                 Update,
                 (
@@ -106,6 +153,34 @@ impl Plugin for UiPlugin {
                     pause_menu::pause_button_hover,
                 ),
             )
+            .add_systems(
+                PreUpdate,
+                gamepad_cursor::gamepad_cursor
+                    .after(InputSystems)
+                    .before(UiSystems::Focus),
+            )
+            .init_resource::<controls_screen::ControlsScreen>()
+            .add_systems(
+                Update,
+                (
+                    controls_screen::close_controls_on_escape
+                        .after(pause_menu::toggle_pause),
+                    controls_screen::capture_binding
+                        .before(controls_screen::controls_button_interaction),
+                    controls_screen::controls_button_interaction,
+                    controls_screen::controls_button_hover,
+                    controls_screen::spawn_controls_ui
+                        .after(controls_screen::controls_button_interaction)
+                        .after(pause_menu::pause_button_interaction),
+                    controls_screen::despawn_controls_ui
+                        .after(controls_screen::close_controls_on_escape)
+                        .after(controls_screen::controls_button_interaction),
+                    controls_screen::update_binding_labels
+                        .after(controls_screen::capture_binding)
+                        .after(controls_screen::controls_button_interaction)
+                        .after(controls_screen::close_controls_on_escape),
+                ),
+            )
             .add_systems(
                 Update,
                 (
@@ -174,6 +249,7 @@ impl Plugin for UiPlugin {
                         .after(debug_map::spawn_debug_map_ui),
                     debug_map::update_debug_map_text
                         .after(debug_map::regenerate_debug_map),
+                    hud::toggle_cave_culling,
                 ),
             );
     }
@@ -4,6 +4,7 @@ use bevy::prelude::*;
 use bevy::window::{CursorGrabMode, CursorOptions, PrimaryWindow};
 
 use crate::save::persistence;
+use crate::ui::controls_screen::ControlsScreen;
 use crate::ui::inventory_screen::InventoryOpen;
 use crate::ui::main_menu::InMainMenu;
 use crate::world::manager::ChunkDataStore;
@@ -18,6 +19,7 @@ pub(crate) struct PauseUiRoot;
 pub(crate) enum PauseButton {
     Resume,
     Save,
+    Controls,
     Quit,
 }
 
@@ -32,14 +34,15 @@ pub fn toggle_pause(
     in_menu: Res<InMainMenu>,
     furnace_open: Res<crate::inventory::furnace::FurnaceOpen>,
     dead: Res<crate::ui::death_screen::PlayerDead>,
+    controls: Res<ControlsScreen>,
     mut cursor_q: Query<&mut CursorOptions, With<PrimaryWindow>>,
 ) {
     if !keys.just_pressed(KeyCode::Escape) {
         return;
     }
 
-    // Don't toggle pause when main menu, inventory, furnace, or death screen is open
-    if in_menu.0 || inventory_open.0 || furnace_open.0.is_some() || dead.0 {
+    // Don't toggle pause when main menu, inventory, furnace, death or controls screen is open
+    if in_menu.0 || inventory_open.0 || furnace_open.0.is_some() || dead.0 || controls.open {
         return;
     }
 
@@ -104,6 +107,7 @@ pub fn spawn_pause_ui(
 
             spawn_button(parent, "Resume", PauseButton::Resume);
             spawn_button(parent, "Save", PauseButton::Save);
+            spawn_button(parent, "Controls", PauseButton::Controls);
             spawn_button(parent, "Quit", PauseButton::Quit);
         });
 }
@@ -152,6 +156,7 @@ pub fn despawn_pause_ui(
 
 pub fn pause_button_interaction(
     mut pause: ResMut<PauseState>,
+    mut controls: ResMut<ControlsScreen>,
     mut cursor_q: Query<&mut CursorOptions, With<PrimaryWindow>>,
     button_q: Query<(&Interaction, &PauseButton), Changed<Interaction>>,
     store: ResMut<ChunkDataStore>,
@@ -174,6 +179,9 @@ pub fn pause_button_interaction(
                 persistence::save_modified_chunks(store);
                 return; // store is moved
             }
+            PauseButton::Controls => {
+                controls.open = true;
+            }
             PauseButton::Quit => {
                 exit.write(AppExit::Success);
             }
@@ -1,16 +1,61 @@
-use crate::block::BlockType;
+use crate::block::{BlockState, BlockType};
 
 pub const CHUNK_SIZE: usize = 16;
 pub const CHUNK_VOLUME: usize = CHUNK_SIZE * CHUNK_SIZE * CHUNK_SIZE;
 
+/// Palette indices never shrink below this many bits, so a growing palette
+/// doesn't repack on every new block kind.
+const MIN_INDEX_BITS: u32 = 4;
+
+/// Block storage for one chunk.
+#[derive(Clone)]
+enum Storage {
+    /// Every block in the chunk has this packed state (all air, all stone, ...).
+    Uniform(u16),
+    /// Per-block indices into `palette`, `bits` wide, packed into `u64` words.
+    /// Indices never straddle a word boundary.
+    Paletted { palette: Vec<u16>, bits: u32, data: Vec<u64> },
+}
+
+/// Smallest index width that can address `len` palette entries.
+fn bits_for(len: usize) -> u32 {
+    (usize::BITS - (len.max(2) - 1).leading_zeros()).max(MIN_INDEX_BITS)
+}
+
+fn words_for(bits: u32) -> usize {
+    CHUNK_VOLUME.div_ceil(64 / bits as usize)
+}
+
+#[inline]
+fn read_index(data: &[u64], bits: u32, i: usize) -> usize {
+    let per_word = 64 / bits as usize;
+    let shift = (i % per_word) as u32 * bits;
+    ((data[i / per_word] >> shift) & ((1u64 << bits) - 1)) as usize
+}
+
+#[inline]
+fn write_index(data: &mut [u64], bits: u32, i: usize, value: usize) {
+    let per_word = 64 / bits as usize;
+    let shift = (i % per_word) as u32 * bits;
+    let mask = ((1u64 << bits) - 1) << shift;
+    let word = &mut data[i / per_word];
+    *word = (*word & !mask) | ((value as u64) << shift);
+}
+
+/// Packed block states (see `BlockState::to_raw`), in YZX order.
+/// Uniform chunks store a single value; mixed chunks store a palette of the
+/// states present plus bit-packed indices into it.
 #[derive(Clone)]
 pub struct Chunk {
-    blocks: [u8; CHUNK_VOLUME],
+    storage: Storage,
 }
 
+/// Serialized as little-endian `u16` states. Older saves stored one `u8` id per block
+/// and are still accepted.
 impl serde::Serialize for Chunk {
     fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
-        serializer.serialize_bytes(&self.blocks)
+        let bytes: Vec<u8> = (0..CHUNK_VOLUME).flat_map(|i| self.raw(i).to_le_bytes()).collect();
+        serializer.serialize_bytes(&bytes)
     }
 }
 
@@ -22,16 +67,20 @@ impl<'de> serde::Deserialize<'de> for Chunk {
             type Value = Chunk;
 
             fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-                write!(f, "a byte array of length {}", CHUNK_VOLUME)
+                write!(f, "a byte array of length {} or {}", CHUNK_VOLUME * 2, CHUNK_VOLUME)
             }
 
             fn visit_bytes<E: serde::de::Error>(self, v: &[u8]) -> Result<Chunk, E> {
-                if v.len() != CHUNK_VOLUME {
+                let blocks: Vec<u16> = if v.len() == CHUNK_VOLUME * 2 {
+                    v.chunks_exact(2)
+                        .map(|raw| BlockState::from_raw(u16::from_le_bytes([raw[0], raw[1]])).to_raw())
+                        .collect()
+                } else if v.len() == CHUNK_VOLUME {
+                    v.iter().map(|&id| BlockState::from_legacy_id(id).to_raw()).collect()
+                } else {
                     return Err(E::invalid_length(v.len(), &self));
-                }
-                let mut blocks = [0u8; CHUNK_VOLUME];
-                blocks.copy_from_slice(v);
-                Ok(Chunk { blocks })
+                };
+                Ok(Chunk::from_raw_states(&blocks))
             }
 
             fn visit_byte_buf<E: serde::de::Error>(self, v: Vec<u8>) -> Result<Chunk, E> {
@@ -46,7 +95,7 @@ impl<'de> serde::Deserialize<'de> for Chunk {
 impl Default for Chunk {
     fn default() -> Self {
         Self {
-            blocks: [0; CHUNK_VOLUME],
+            storage: Storage::Uniform(0),
         }
     }
 }
@@ -57,18 +106,122 @@ impl Chunk {
         y * CHUNK_SIZE * CHUNK_SIZE + z * CHUNK_SIZE + x
     }
 
+    /// Builds a chunk from `CHUNK_VOLUME` packed states with a minimal palette.
+    fn from_raw_states(blocks: &[u16]) -> Self {
+        let mut palette: Vec<u16> = Vec::new();
+        let indices: Vec<usize> = blocks
+            .iter()
+            .map(|&raw| match palette.iter().position(|&p| p == raw) {
+                Some(idx) => idx,
+                None => {
+                    palette.push(raw);
+                    palette.len() - 1
+                }
+            })
+            .collect();
+
+        if palette.len() == 1 {
+            return Self { storage: Storage::Uniform(palette[0]) };
+        }
+
+        let bits = bits_for(palette.len());
+        let mut data = vec![0u64; words_for(bits)];
+        for (i, &idx) in indices.iter().enumerate() {
+            write_index(&mut data, bits, i, idx);
+        }
+        Self { storage: Storage::Paletted { palette, bits, data } }
+    }
+
+    #[inline]
+    fn raw(&self, i: usize) -> u16 {
+        match &self.storage {
+            Storage::Uniform(raw) => *raw,
+            Storage::Paletted { palette, bits, data } => palette[read_index(data, *bits, i)],
+        }
+    }
+
+    fn set_raw(&mut self, i: usize, raw: u16) {
+        if let Storage::Uniform(current) = self.storage {
+            if current == raw {
+                return;
+            }
+            self.storage = Storage::Paletted {
+                palette: vec![current],
+                bits: MIN_INDEX_BITS,
+                data: vec![0; words_for(MIN_INDEX_BITS)],
+            };
+        }
+        let Storage::Paletted { palette, bits, data } = &mut self.storage else {
+            unreachable!();
+        };
+
+        let idx = match palette.iter().position(|&p| p == raw) {
+            Some(idx) => idx,
+            None => {
+                palette.push(raw);
+                let needed = bits_for(palette.len());
+                if needed > *bits {
+                    // Palette outgrew the index width: repack every index
+                    let mut wider = vec![0u64; words_for(needed)];
+                    for j in 0..CHUNK_VOLUME {
+                        write_index(&mut wider, needed, j, read_index(data, *bits, j));
+                    }
+                    *data = wider;
+                    *bits = needed;
+                }
+                palette.len() - 1
+            }
+        };
+        write_index(data, *bits, i, idx);
+    }
+
     #[inline]
     pub fn get(&self, x: usize, y: usize, z: usize) -> BlockType {
-        BlockType::from_id(self.blocks[Self::index(x, y, z)])
+        BlockType::from_id(self.raw(Self::index(x, y, z)) as u8)
     }
 
+    /// Sets the block in its default state.
     #[inline]
     pub fn set(&mut self, x: usize, y: usize, z: usize, block: BlockType) {
-        self.blocks[Self::index(x, y, z)] = block as u8;
+        self.set_state(x, y, z, BlockState::new(block));
+    }
+
+    #[inline]
+    pub fn get_state(&self, x: usize, y: usize, z: usize) -> BlockState {
+        BlockState::from_raw(self.raw(Self::index(x, y, z)))
+    }
+
+    #[inline]
+    pub fn set_state(&mut self, x: usize, y: usize, z: usize, state: BlockState) {
+        self.set_raw(Self::index(x, y, z), state.to_raw());
     }
 
     pub fn is_empty(&self) -> bool {
-        self.blocks.iter().all(|&b| b == 0)
+        match &self.storage {
+            Storage::Uniform(raw) => *raw == 0,
+            Storage::Paletted { .. } => (0..CHUNK_VOLUME).all(|i| self.raw(i) == 0),
+        }
+    }
+
+    /// Drops palette entries that are no longer used and collapses the chunk to a
+    /// single value if it became uniform. Palettes only grow on `set`, so call this
+    /// after bulk edits such as generation.
+    pub fn compact(&mut self) {
+        if let Storage::Paletted { .. } = self.storage {
+            let blocks: Vec<u16> = (0..CHUNK_VOLUME).map(|i| self.raw(i)).collect();
+            *self = Self::from_raw_states(&blocks);
+        }
+    }
+
+    /// Approximate bytes used by this chunk, including heap-allocated palette and indices.
+    pub fn memory_bytes(&self) -> usize {
+        let heap = match &self.storage {
+            Storage::Uniform(_) => 0,
+            Storage::Paletted { palette, data, .. } => {
+                palette.capacity() * std::mem::size_of::<u16>() + data.capacity() * std::mem::size_of::<u64>()
+            }
+        };
+        std::mem::size_of::<Self>() + heap
     }
 }
 
@@ -153,4 +306,81 @@ mod tests {
         assert_eq!(chunk.get(0, 1, 0), BlockType::Sand);
         assert_eq!(chunk.get(0, 0, 1), BlockType::Air);
     }
+
+    #[test]
+    fn set_state_keeps_properties() {
+        use crate::block::state::Facing;
+        let mut chunk = Chunk::default();
+        let furnace = BlockState::new(BlockType::Furnace).with_facing(Facing::East);
+        chunk.set_state(2, 3, 4, furnace);
+        assert_eq!(chunk.get(2, 3, 4), BlockType::Furnace);
+        assert_eq!(chunk.get_state(2, 3, 4), furnace);
+
+        // Plain set resets to the default state
+        chunk.set(2, 3, 4, BlockType::Furnace);
+        assert_eq!(chunk.get_state(2, 3, 4).facing(), Facing::North);
+    }
+
+    #[test]
+    fn serde_roundtrip_keeps_states() {
+        let mut chunk = Chunk::default();
+        let wheat = BlockState::new(BlockType::WheatCrop).with_age(2);
+        chunk.set_state(7, 1, 9, wheat);
+        chunk.set(0, 0, 0, BlockType::Stone);
+        let bytes = bincode::serialize(&chunk).unwrap();
+        let decoded: Chunk = bincode::deserialize(&bytes).unwrap();
+        assert_eq!(decoded.get_state(7, 1, 9), wheat);
+        assert_eq!(decoded.get(0, 0, 0), BlockType::Stone);
+    }
+
+    #[test]
+    fn legacy_byte_chunks_load() {
+        let mut legacy = vec![0u8; CHUNK_VOLUME];
+        legacy[Chunk::index(1, 0, 0)] = BlockType::Stone as u8;
+        legacy[Chunk::index(2, 0, 0)] = 37; // old WheatStage2
+        // bincode encodes a Vec<u8> the same way as a byte array
+        let bytes = bincode::serialize(&legacy).unwrap();
+        let decoded: Chunk = bincode::deserialize(&bytes).unwrap();
+        assert_eq!(decoded.get(1, 0, 0), BlockType::Stone);
+        assert_eq!(decoded.get_state(2, 0, 0).age(), 2);
+    }
+
+    #[test]
+    fn uniform_chunk_uses_no_heap() {
+        let mut chunk = Chunk::default();
+        assert_eq!(chunk.memory_bytes(), std::mem::size_of::<Chunk>());
+        // Writing the value already everywhere keeps the fast path
+        chunk.set(3, 3, 3, BlockType::Air);
+        assert_eq!(chunk.memory_bytes(), std::mem::size_of::<Chunk>());
+    }
+
+    #[test]
+    fn palette_grows_past_index_width() {
+        let mut chunk = Chunk::default();
+        // More distinct states than 4-bit indices can address forces a repack
+        for (i, age) in (0..=3u8).enumerate() {
+            let wheat = BlockState::new(BlockType::WheatCrop).with_age(age);
+            chunk.set_state(i, 0, 0, wheat);
+        }
+        for id in 1..=20u8 {
+            chunk.set(id as usize % CHUNK_SIZE, 1 + id as usize / CHUNK_SIZE, 0, BlockType::from_id(id));
+        }
+        for (i, age) in (0..=3u8).enumerate() {
+            assert_eq!(chunk.get_state(i, 0, 0).age(), age);
+        }
+        for id in 1..=20u8 {
+            assert_eq!(chunk.get(id as usize % CHUNK_SIZE, 1 + id as usize / CHUNK_SIZE, 0), BlockType::from_id(id));
+        }
+    }
+
+    #[test]
+    fn compact_collapses_uniform_chunk() {
+        let mut chunk = Chunk::default();
+        chunk.set(1, 2, 3, BlockType::Stone);
+        chunk.set(1, 2, 3, BlockType::Air);
+        assert!(chunk.memory_bytes() > std::mem::size_of::<Chunk>());
+        chunk.compact();
+        assert_eq!(chunk.memory_bytes(), std::mem::size_of::<Chunk>());
+        assert!(chunk.is_empty());
+    }
 }
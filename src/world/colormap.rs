@@ -0,0 +1,175 @@
+use bevy::prelude::*;
+
+use crate::block::atlas::{tile_tint, BiomeTint};
+use crate::world::chunk::CHUNK_SIZE;
+use crate::world::generation::{sample_climate, Climate};
+
+/// Corners of each colormap triangle, as in Minecraft's grass.png and foliage.png:
+/// cold, hot and dry, hot and wet. Temperate climates land near the middle, close to
+/// the plains colors baked into the atlas.
+const GRASS_COLORS: [(u8, u8, u8); 3] = [(128, 180, 151), (191, 183, 85), (71, 205, 51)];
+const FOLIAGE_COLORS: [(u8, u8, u8); 3] = [(96, 161, 123), (174, 164, 42), (26, 191, 0)];
+const WATER_COLORS: [(u8, u8, u8); 3] = [(61, 87, 214), (68, 175, 210), (50, 120, 180)];
+
+/// Columns on each side of a column corner that are blended into its tint, so colors
+/// change gradually over a few blocks at biome borders.
+const BLEND_RADIUS: i32 = 2;
+
+/// Weights of the cold, hot-dry and hot-wet corners for a climate. Humidity only spreads
+/// colors apart as it gets warmer, which makes the colormap a triangle.
+fn corner_weights(climate: Climate) -> [f32; 3] {
+    let temperature = ((climate.temperature as f32 + 1.0) / 2.0).clamp(0.0, 1.0);
+    let humidity = ((climate.humidity as f32 + 1.0) / 2.0).clamp(0.0, 1.0) * temperature;
+    [1.0 - temperature, temperature - humidity, humidity]
+}
+
+fn colormap(tint: BiomeTint) -> [(u8, u8, u8); 3] {
+    match tint {
+        BiomeTint::Grass => GRASS_COLORS,
+        BiomeTint::Foliage => FOLIAGE_COLORS,
+        BiomeTint::Water => WATER_COLORS,
+    }
+}
+
+/// A colormap's color for a climate.
+pub fn biome_color(tint: BiomeTint, climate: Climate) -> (u8, u8, u8) {
+    let weights = corner_weights(climate);
+    let channel = |pick: fn((u8, u8, u8)) -> u8| {
+        let value: f32 = colormap(tint).into_iter().zip(weights).map(|(color, w)| pick(color) as f32 * w).sum();
+        value.round().clamp(0.0, 255.0) as u8
+    };
+    (channel(|c| c.0), channel(|c| c.1), channel(|c| c.2))
+}
+
+/// Atlas tile standing for each colormap, whose baked tint is its plains color.
+fn baked_tile(tint: BiomeTint) -> u32 {
+    match tint {
+        BiomeTint::Grass => 2,
+        BiomeTint::Foliage => 10,
+        BiomeTint::Water => 17,
+    }
+}
+
+fn linear((r, g, b): (u8, u8, u8)) -> Vec3 {
+    let color = Color::srgba_u8(r, g, b, 255).to_linear();
+    Vec3::new(color.red, color.green, color.blue)
+}
+
+/// Multiplier turning a tile's baked plains color into the colormap's color for a
+/// climate, in linear color as the shader applies it.
+fn relative_tint(tint: BiomeTint, climate: Climate) -> Vec3 {
+    let baked = tile_tint(baked_tile(tint)).map_or(Vec3::ONE, linear);
+    linear(biome_color(tint, climate)) / baked.max(Vec3::splat(1e-4))
+}
+
+/// Biome tints across a chunk's columns, baked into its mesh vertices. Kept for every
+/// column corner, each blending the columns around it, relative to the plains colors
+/// baked into the atlas, so white leaves a tile as it is.
+pub struct ChunkTints {
+    /// Per corner, (CHUNK_SIZE + 1)² in rows along X, one tint per `BiomeTint`.
+    corners: Vec<[Vec3; 3]>,
+}
+
+impl Default for ChunkTints {
+    /// No biome tinting: every tile keeps its atlas color.
+    fn default() -> Self {
+        Self { corners: vec![[Vec3::ONE; 3]; (CHUNK_SIZE + 1) * (CHUNK_SIZE + 1)] }
+    }
+}
+
+impl ChunkTints {
+    /// Sample the climate around a chunk's columns.
+    pub fn sample(chunk_pos: IVec3) -> Self {
+        let origin = chunk_pos * CHUNK_SIZE as i32;
+        Self::from_climate(|x, z| sample_climate(origin.x + x, origin.z + z))
+    }
+
+    /// Build the tints from the climate at chunk-local columns, including ones just
+    /// outside the chunk.
+    fn from_climate(climate: impl Fn(i32, i32) -> Climate) -> Self {
+        let tints = [BiomeTint::Grass, BiomeTint::Foliage, BiomeTint::Water];
+        // Columns from -BLEND_RADIUS to CHUNK_SIZE + BLEND_RADIUS - 1 on both axes
+        let span = CHUNK_SIZE + 2 * BLEND_RADIUS as usize;
+        let mut columns = Vec::with_capacity(span * span);
+        for z in 0..span as i32 {
+            for x in 0..span as i32 {
+                let climate = climate(x - BLEND_RADIUS, z - BLEND_RADIUS);
+                columns.push(tints.map(|tint| relative_tint(tint, climate)));
+            }
+        }
+
+        // Corner (x, z) sits between columns x - 1 and x, so it averages the square
+        // of columns from x - BLEND_RADIUS to x + BLEND_RADIUS - 1
+        let blend = 2 * BLEND_RADIUS as usize;
+        let weight = 1.0 / (blend * blend) as f32;
+        let mut corners = Vec::with_capacity((CHUNK_SIZE + 1) * (CHUNK_SIZE + 1));
+        for z in 0..=CHUNK_SIZE {
+            for x in 0..=CHUNK_SIZE {
+                let mut sum = [Vec3::ZERO; 3];
+                for dz in 0..blend {
+                    for dx in 0..blend {
+                        let column = &columns[(z + dz) * span + x + dx];
+                        for (sum, tint) in sum.iter_mut().zip(column) {
+                            *sum += *tint;
+                        }
+                    }
+                }
+                corners.push(sum.map(|s| s * weight));
+            }
+        }
+        Self { corners }
+    }
+
+    fn corner(&self, x: usize, z: usize, tint: BiomeTint) -> Vec3 {
+        self.corners[z * (CHUNK_SIZE + 1) + x][tint as usize]
+    }
+
+    /// Tint at a chunk-local (x, z) position, blended between the nearest corners.
+    pub fn tint(&self, tint: BiomeTint, x: f32, z: f32) -> Vec3 {
+        let max = CHUNK_SIZE as f32;
+        let (x, z) = (x.clamp(0.0, max), z.clamp(0.0, max));
+        let (x0, z0) = ((x.floor() as usize).min(CHUNK_SIZE - 1), (z.floor() as usize).min(CHUNK_SIZE - 1));
+        let (fx, fz) = (x - x0 as f32, z - z0 as f32);
+        let near = self.corner(x0, z0, tint).lerp(self.corner(x0 + 1, z0, tint), fx);
+        let far = self.corner(x0, z0 + 1, tint).lerp(self.corner(x0 + 1, z0 + 1, tint), fx);
+        near.lerp(far, fz)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn climate(temperature: f64, humidity: f64) -> Climate {
+        Climate { temperature, humidity, continentalness: 0.0 }
+    }
+
+    #[test]
+    fn colormap_corners_and_plains() {
+        assert_eq!(biome_color(BiomeTint::Grass, climate(-1.0, 0.0)), GRASS_COLORS[0]);
+        assert_eq!(biome_color(BiomeTint::Grass, climate(1.0, -1.0)), GRASS_COLORS[1]);
+        assert_eq!(biome_color(BiomeTint::Foliage, climate(1.0, 1.0)), FOLIAGE_COLORS[2]);
+        // Humidity makes no difference in the cold
+        assert_eq!(biome_color(BiomeTint::Water, climate(-1.0, 1.0)), WATER_COLORS[0]);
+
+        // A temperate climate stays close to the plains colors in the atlas
+        let (r, g, b) = biome_color(BiomeTint::Grass, climate(0.0, 0.0));
+        let (pr, pg, pb) = tile_tint(2).unwrap();
+        assert!(r.abs_diff(pr) < 12 && g.abs_diff(pg) < 12 && b.abs_diff(pb) < 12);
+    }
+
+    #[test]
+    fn tints_blend_across_a_border() {
+        // Cold to the west of x = 8, hot to the east
+        let tints = ChunkTints::from_climate(|x, _| if x < 8 { climate(-1.0, 0.0) } else { climate(1.0, 0.0) });
+        let grass = |x: f32| tints.tint(BiomeTint::Grass, x, 4.0);
+        assert!((grass(0.0) - grass(5.0)).length() < 1e-5);
+        assert!((grass(11.0) - grass(16.0)).length() < 1e-5);
+        // The change is spread over the columns around the border
+        let steps: Vec<f32> = (6..=10).map(|x| grass(x as f32).x).collect();
+        assert!(steps.windows(2).all(|w| w[1] > w[0]));
+
+        // Untinted chunks leave tiles alone
+        assert_eq!(ChunkTints::default().tint(BiomeTint::Water, 3.5, 9.0), Vec3::ONE);
+    }
+}
@@ -0,0 +1,442 @@
+use bevy::prelude::*;
+use std::collections::{HashSet, VecDeque};
+
+use crate::block::state::MAX_FLUID_LEVEL;
+use crate::block::{BlockState, BlockType};
+use super::chunk::CHUNK_SIZE;
+use super::manager::ChunkDataStore;
+
+/// Seconds between fluid ticks.
+pub const FLUID_TICK_SECONDS: f32 = 0.25;
+
+/// Fluid ticks between a block changing and the water next to it reacting.
+const FLOW_DELAY: u64 = 1;
+
+/// Most scheduled updates processed in one fluid tick; the rest wait for the next.
+pub const MAX_FLUID_UPDATES_PER_TICK: usize = 1024;
+
+/// How far sideways flowing water looks for a drop to head towards.
+const SLOPE_FIND_DISTANCE: u8 = 4;
+
+/// Drop distance meaning "no drop within SLOPE_FIND_DISTANCE".
+const NO_DROP: u8 = SLOPE_FIND_DISTANCE + 1;
+
+const HORIZONTAL: [IVec3; 4] = [IVec3::X, IVec3::NEG_X, IVec3::Z, IVec3::NEG_Z];
+
+const NEIGHBORS: [IVec3; 6] = [
+    IVec3::X,
+    IVec3::NEG_X,
+    IVec3::Y,
+    IVec3::NEG_Y,
+    IVec3::Z,
+    IVec3::NEG_Z,
+];
+
+/// Scheduled-update queue for fluid blocks. Each position is queued at most once;
+/// updates run in the order they were scheduled once their tick comes up.
+#[derive(Default)]
+pub struct FluidTicks {
+    tick: u64,
+    queue: VecDeque<(u64, IVec3)>,
+    scheduled: HashSet<IVec3>,
+}
+
+impl FluidTicks {
+    /// Queue an update at `pos` for FLOW_DELAY ticks from now (no-op if already queued).
+    pub fn schedule(&mut self, pos: IVec3) {
+        if self.scheduled.insert(pos) {
+            self.queue.push_back((self.tick + FLOW_DELAY, pos));
+        }
+    }
+
+    /// Advance one tick and take up to `limit` updates that are now due.
+    pub fn advance(&mut self, limit: usize) -> Vec<IVec3> {
+        self.tick += 1;
+        let mut due = Vec::new();
+        while due.len() < limit {
+            match self.queue.front() {
+                Some(&(tick, pos)) if tick <= self.tick => {
+                    self.queue.pop_front();
+                    self.scheduled.remove(&pos);
+                    due.push(pos);
+                }
+                _ => break,
+            }
+        }
+        due
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.queue.clear();
+        self.scheduled.clear();
+    }
+}
+
+/// Block state at a world position, or `None` if its chunk isn't loaded.
+pub fn state_at(store: &ChunkDataStore, pos: IVec3) -> Option<BlockState> {
+    let size = CHUNK_SIZE as i32;
+    let chunk_pos = IVec3::new(pos.x.div_euclid(size), pos.y.div_euclid(size), pos.z.div_euclid(size));
+    let chunk = store.chunks.get(&chunk_pos)?;
+    Some(chunk.get_state(
+        pos.x.rem_euclid(size) as usize,
+        pos.y.rem_euclid(size) as usize,
+        pos.z.rem_euclid(size) as usize,
+    ))
+}
+
+/// Schedule updates for any fluid at or next to `pos` after the block there changed.
+pub fn schedule_around(store: &mut ChunkDataStore, pos: IVec3) {
+    for p in std::iter::once(pos).chain(NEIGHBORS.map(|dir| pos + dir)) {
+        if state_at(store, p).is_some_and(|s| s.block().is_liquid()) {
+            store.fluid_ticks.schedule(p);
+        }
+    }
+}
+
+/// Flowing water at the given level.
+fn flowing(level: u8) -> BlockState {
+    BlockState::new(BlockType::Water).with_fluid_level(level)
+}
+
+/// Water falling from the block above.
+fn falling() -> BlockState {
+    BlockState::new(BlockType::Water).with_falling(true)
+}
+
+/// Whether fluid can flow into a cell, washing away what's there.
+/// Unloaded cells (`None`) block flow so water never spills into missing chunks.
+fn can_flow_into(state: Option<BlockState>) -> bool {
+    let Some(state) = state else {
+        return false;
+    };
+    match state.block() {
+        BlockType::Air | BlockType::TallGrass => true,
+        BlockType::Water => !state.is_fluid_source(),
+        _ => false,
+    }
+}
+
+/// Level used when flowing out of a fluid block: falling water spreads like a source.
+fn effective_level(state: BlockState) -> u8 {
+    if state.is_falling() { 0 } else { state.fluid_level() }
+}
+
+/// Visible height of a fluid block's surface, from 0 to 1.
+pub fn fluid_height(state: BlockState) -> f32 {
+    if state.is_falling() {
+        1.0
+    } else {
+        (MAX_FLUID_LEVEL + 1 - state.fluid_level()) as f32 / (MAX_FLUID_LEVEL + 2) as f32
+    }
+}
+
+/// Direction the current pushes things in the fluid at `pos`: towards weaker flow and
+/// the open cells water spills into, and down in falling water. Zero in still water
+/// and outside fluid.
+pub fn flow_vector(pos: IVec3, get: impl Fn(IVec3) -> Option<BlockState>) -> Vec3 {
+    let Some(state) = get(pos).filter(|s| s.block().is_liquid()) else {
+        return Vec3::ZERO;
+    };
+    let level = effective_level(state) as f32;
+    let mut flow = Vec3::ZERO;
+    for dir in HORIZONTAL {
+        let neighbor = get(pos + dir);
+        let neighbor_level = match neighbor {
+            Some(n) if n.block().is_liquid() => effective_level(n) as f32,
+            // Water spills into open cells as if they held flow weaker than any
+            _ if can_flow_into(neighbor) => (MAX_FLUID_LEVEL + 1) as f32,
+            _ => continue,
+        };
+        flow += dir.as_vec3() * (neighbor_level - level);
+    }
+    if state.is_falling() {
+        flow.y -= 1.0;
+    }
+    flow.normalize_or_zero()
+}
+
+/// What a fluid block should become given its neighbors; `None` means it dries up.
+/// Sources never change. Flowing water is fed from above or from its lowest-level
+/// horizontal neighbor, and becomes a new source between two sources over solid
+/// ground or another source (the infinite-source rule).
+fn settle(pos: IVec3, state: BlockState, get: &impl Fn(IVec3) -> Option<BlockState>) -> Option<BlockState> {
+    if state.is_fluid_source() {
+        return Some(state);
+    }
+    if get(pos + IVec3::Y).is_some_and(|s| s.block().is_liquid()) {
+        return Some(falling());
+    }
+
+    let mut sources = 0;
+    let mut min_level: Option<u8> = None;
+    for dir in HORIZONTAL {
+        let Some(neighbor) = get(pos + dir) else {
+            continue;
+        };
+        if !neighbor.block().is_liquid() {
+            continue;
+        }
+        if neighbor.is_fluid_source() {
+            sources += 1;
+        }
+        let level = effective_level(neighbor);
+        min_level = Some(min_level.map_or(level, |m| m.min(level)));
+    }
+
+    let below = get(pos - IVec3::Y);
+    if sources >= 2 && below.is_some_and(|s| s.is_solid() || s.is_fluid_source()) {
+        return Some(BlockState::new(BlockType::Water));
+    }
+    let level = min_level? + 1;
+    (level <= MAX_FLUID_LEVEL).then(|| flowing(level))
+}
+
+/// Steps to the nearest drop from `pos` (reached by moving `from`), searching sideways
+/// through cells water can flow into, or NO_DROP if there is none in range.
+fn drop_distance(pos: IVec3, from: IVec3, depth: u8, get: &impl Fn(IVec3) -> Option<BlockState>) -> u8 {
+    let below = get(pos - IVec3::Y);
+    if can_flow_into(below) || below.is_some_and(|s| s.block().is_liquid()) {
+        return depth;
+    }
+    if depth >= SLOPE_FIND_DISTANCE {
+        return NO_DROP;
+    }
+    HORIZONTAL
+        .into_iter()
+        .filter(|&dir| dir != -from && can_flow_into(get(pos + dir)))
+        .map(|dir| drop_distance(pos + dir, dir, depth + 1, get))
+        .min()
+        .unwrap_or(NO_DROP)
+}
+
+/// Horizontal directions water spreads in: towards the nearest drop, or every open
+/// direction when there is no drop within SLOPE_FIND_DISTANCE.
+fn flow_directions(pos: IVec3, get: &impl Fn(IVec3) -> Option<BlockState>) -> Vec<IVec3> {
+    let mut best = u8::MAX;
+    let mut dirs = Vec::new();
+    for dir in HORIZONTAL {
+        if !can_flow_into(get(pos + dir)) {
+            continue;
+        }
+        let distance = drop_distance(pos + dir, dir, 1, get);
+        if distance < best {
+            best = distance;
+            dirs.clear();
+        }
+        if distance == best {
+            dirs.push(dir);
+        }
+    }
+    dirs
+}
+
+/// Run a scheduled update for the fluid at `pos`. Returns the blocks to change:
+/// the fluid itself settling or drying up, water falling into the cell below, and
+/// water spreading sideways one level weaker.
+pub fn fluid_tick(pos: IVec3, get: impl Fn(IVec3) -> Option<BlockState>) -> Vec<(IVec3, BlockState)> {
+    let Some(state) = get(pos) else {
+        return Vec::new();
+    };
+    if !state.block().is_liquid() {
+        return Vec::new();
+    }
+
+    let mut changes = Vec::new();
+    let state = match settle(pos, state, &get) {
+        Some(new) => {
+            if new != state {
+                changes.push((pos, new));
+            }
+            new
+        }
+        None => {
+            changes.push((pos, BlockState::AIR));
+            return changes;
+        }
+    };
+
+    // Fall first; only sources also spread sideways over a drop or onto other fluid
+    let below_pos = pos - IVec3::Y;
+    let below = get(below_pos);
+    if can_flow_into(below) && below != Some(falling()) {
+        changes.push((below_pos, falling()));
+    }
+    let over_fluid = below.is_some_and(|s| s.block().is_liquid()) || can_flow_into(below);
+    if over_fluid && !state.is_fluid_source() {
+        return changes;
+    }
+
+    let next = effective_level(state) + 1;
+    if next > MAX_FLUID_LEVEL {
+        return changes;
+    }
+    for dir in flow_directions(pos, &get) {
+        let target = pos + dir;
+        let Some(existing) = get(target) else {
+            continue;
+        };
+        // Don't weaken water that is already at least as strong
+        if existing.block().is_liquid() && (existing.is_falling() || existing.fluid_level() <= next) {
+            continue;
+        }
+        changes.push((target, flowing(next)));
+    }
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    /// A floor of stone at y = 0 spanning -8..=8, with everything above it air.
+    fn floor() -> HashMap<IVec3, BlockState> {
+        let mut world = HashMap::new();
+        for x in -8..=8 {
+            for z in -8..=8 {
+                world.insert(IVec3::new(x, 0, z), BlockState::new(BlockType::Stone));
+            }
+        }
+        world
+    }
+
+    /// Run scheduled updates until the water stops moving, like the world system does.
+    fn simulate(world: &mut HashMap<IVec3, BlockState>, start: IVec3) {
+        let inside = |p: IVec3| p.x.abs() <= 8 && p.z.abs() <= 8 && (0..8).contains(&p.y);
+        let mut ticks = FluidTicks::default();
+        ticks.schedule(start);
+        for _ in 0..200 {
+            if ticks.is_empty() {
+                return;
+            }
+            let due = ticks.advance(MAX_FLUID_UPDATES_PER_TICK);
+            let changes: Vec<_> = due
+                .into_iter()
+                .flat_map(|pos| {
+                    fluid_tick(pos, |p| {
+                        inside(p).then(|| world.get(&p).copied().unwrap_or(BlockState::AIR))
+                    })
+                })
+                .collect();
+            for (pos, state) in changes {
+                world.insert(pos, state);
+                for p in std::iter::once(pos).chain(NEIGHBORS.map(|d| pos + d)) {
+                    if world.get(&p).is_some_and(|s| s.block().is_liquid()) {
+                        ticks.schedule(p);
+                    }
+                }
+            }
+        }
+        panic!("water never settled");
+    }
+
+    fn water_at(world: &HashMap<IVec3, BlockState>, pos: IVec3) -> Option<BlockState> {
+        world.get(&pos).copied().filter(|s| s.block().is_liquid())
+    }
+
+    #[test]
+    fn ticks_run_in_order_once_due() {
+        let mut ticks = FluidTicks::default();
+        ticks.schedule(IVec3::X);
+        ticks.schedule(IVec3::Y);
+        ticks.schedule(IVec3::X);
+        assert_eq!(ticks.advance(10), vec![IVec3::X, IVec3::Y]);
+        assert!(ticks.is_empty());
+        ticks.schedule(IVec3::Z);
+        ticks.schedule(IVec3::X);
+        assert_eq!(ticks.advance(1), vec![IVec3::Z]);
+        assert_eq!(ticks.advance(1), vec![IVec3::X]);
+    }
+
+    #[test]
+    fn source_spreads_seven_blocks_on_flat_ground() {
+        let mut world = floor();
+        let source = IVec3::new(0, 1, 0);
+        world.insert(source, BlockState::new(BlockType::Water));
+        simulate(&mut world, source);
+
+        for d in 1..=MAX_FLUID_LEVEL as i32 {
+            let state = water_at(&world, source + IVec3::new(d, 0, 0)).expect("water should reach");
+            assert_eq!(state.fluid_level(), d as u8);
+        }
+        assert!(water_at(&world, source + IVec3::new(MAX_FLUID_LEVEL as i32 + 1, 0, 0)).is_none());
+    }
+
+    #[test]
+    fn water_falls_and_prefers_the_drop() {
+        let mut world = floor();
+        let source = IVec3::new(0, 2, 0);
+        world.insert(source, BlockState::new(BlockType::Water));
+        // Raised platform under the source with a hole two blocks east
+        for x in -8..=8 {
+            for z in -8..=8 {
+                world.insert(IVec3::new(x, 1, z), BlockState::new(BlockType::Stone));
+            }
+        }
+        world.insert(IVec3::new(2, 1, 0), BlockState::AIR);
+        simulate(&mut world, source);
+
+        assert!(water_at(&world, IVec3::new(1, 2, 0)).is_some());
+        assert!(water_at(&world, IVec3::new(-1, 2, 0)).is_none(), "flow should head for the hole");
+        assert!(water_at(&world, IVec3::new(2, 1, 0)).is_some_and(|s| s.is_falling()));
+    }
+
+    #[test]
+    fn flow_dries_up_when_source_removed() {
+        let mut world = floor();
+        let source = IVec3::new(0, 1, 0);
+        world.insert(source, BlockState::new(BlockType::Water));
+        simulate(&mut world, source);
+
+        world.insert(source, BlockState::AIR);
+        simulate(&mut world, source + IVec3::X);
+        assert!(world.values().all(|s| !s.block().is_liquid()));
+    }
+
+    #[test]
+    fn two_sources_make_a_third() {
+        let mut world = floor();
+        // A 3x1 trench walled in, sources at both ends
+        for x in -2..=2_i32 {
+            for z in -1..=1 {
+                if z != 0 || x.abs() == 2 {
+                    world.insert(IVec3::new(x, 1, z), BlockState::new(BlockType::Stone));
+                }
+            }
+        }
+        world.insert(IVec3::new(-1, 1, 0), BlockState::new(BlockType::Water));
+        world.insert(IVec3::new(1, 1, 0), BlockState::new(BlockType::Water));
+        simulate(&mut world, IVec3::new(-1, 1, 0));
+        assert!(water_at(&world, IVec3::new(0, 1, 0)).is_some_and(|s| s.is_fluid_source()));
+    }
+
+    #[test]
+    fn current_runs_downstream() {
+        let mut world = floor();
+        let source = IVec3::new(0, 1, 0);
+        world.insert(source, BlockState::new(BlockType::Water));
+        simulate(&mut world, source);
+        let get = |p: IVec3| Some(world.get(&p).copied().unwrap_or(BlockState::AIR));
+
+        // Flow east of the source heads further east
+        let flow = flow_vector(IVec3::new(3, 1, 0), get);
+        assert!(flow.x > 0.9 && flow.y == 0.0);
+        // A source surrounded by its own flow pushes nowhere in particular
+        assert_eq!(flow_vector(source, get), Vec3::ZERO);
+        assert_eq!(flow_vector(IVec3::new(0, 4, 0), get), Vec3::ZERO);
+        // Falling water drags things down
+        assert!(flow_vector(IVec3::ZERO, |p| Some(if p == IVec3::ZERO { falling() } else { BlockState::AIR })).y < 0.0);
+    }
+
+    #[test]
+    fn heights_fall_with_level() {
+        let source = fluid_height(BlockState::new(BlockType::Water));
+        let weakest = fluid_height(flowing(MAX_FLUID_LEVEL));
+        assert!(source < 1.0 && weakest > 0.0 && weakest < source);
+        assert_eq!(fluid_height(falling()), 1.0);
+    }
+}
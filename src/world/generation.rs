@@ -1,19 +1,36 @@
 use bevy::prelude::*;
 use noise::{Fbm, MultiFractal, NoiseFn, Perlin, SuperSimplex};
-use std::sync::RwLock;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, RwLock};
 use super::chunk::{Chunk, CHUNK_SIZE};
-use crate::block::BlockType;
+use super::WorldHeight;
+use crate::block::state::Facing;
+use crate::block::{BlockState, BlockType};
 
 const DEFAULT_SEED: u32 = 42;
 const SEA_LEVEL: i32 = 63;
 
-/// Terrain height range
-const BASE_HEIGHT: f64 = 64.0;
-const HEIGHT_AMPLITUDE: f64 = 30.0;
+/// Mountain tops at or above this height are capped with snow
+const SNOW_LINE: i32 = 105;
+
+/// Stone turns into deepslate below this height, blending over DEEPSLATE_BLEND blocks
+const DEEPSLATE_TOP: i32 = 0;
+const DEEPSLATE_BLEND: i32 = 8;
 
 /// Noise scales
 const TERRAIN_FREQUENCY: f64 = 0.005;
 const BIOME_FREQUENCY: f64 = 0.002;
+const CONTINENTAL_FREQUENCY: f64 = 0.0012;
+
+/// Biome height shaping is averaged over a (2 * BLEND_RADIUS + 1)^2 grid of biome samples
+/// BLEND_STEP blocks apart. The average is taken every BLEND_STEP columns and interpolated
+/// in between, so terrain eases from one biome's heights into the next.
+const BLEND_RADIUS: i32 = 4;
+const BLEND_STEP: i32 = 4;
+
+/// Chunk columns whose height and biome maps are kept for the column's other layers.
+/// Comfortably more than the columns within loading range.
+const COLUMN_CACHE_SIZE: usize = 2048;
 
 /// Cave thresholds
 pub const CHEESE_THRESHOLD: f64 = 0.45;
@@ -50,7 +67,10 @@ where
 /// Pre-computed noise generators for terrain generation.
 struct TerrainNoise {
     height: Fbm<Perlin>,
-    biome_temp: SuperSimplex,
+    /// Climate noise, together deciding the biome
+    temperature: SuperSimplex,
+    humidity: SuperSimplex,
+    continentalness: Fbm<Perlin>,
     /// Cheese caves — large chambers (Fbm for interesting shapes)
     cave_cheese: Fbm<Perlin>,
     /// Spaghetti caves — winding tunnels (two Perlin for zero-crossing intersection)
@@ -69,6 +89,8 @@ struct TerrainNoise {
     clay: Perlin,
     /// Tall grass scatter noise
     grass_scatter: Perlin,
+    /// Recently generated chunk columns' height and biome maps
+    columns: Mutex<ColumnCache>,
 }
 
 // SAFETY: Perlin, Fbm<Perlin>, SuperSimplex are all deterministic and read-only after creation.
@@ -83,7 +105,11 @@ impl TerrainNoise {
             .set_persistence(0.5)
             .set_lacunarity(2.0);
 
-        let biome_temp = SuperSimplex::new(seed.wrapping_add(1));
+        let temperature = SuperSimplex::new(seed.wrapping_add(1));
+        let humidity = SuperSimplex::new(seed.wrapping_add(2));
+        let continentalness = Fbm::<Perlin>::new(seed.wrapping_add(4))
+            .set_octaves(2)
+            .set_frequency(CONTINENTAL_FREQUENCY);
 
         let cave_cheese = Fbm::<Perlin>::new(seed.wrapping_add(10))
             .set_octaves(2)
@@ -110,7 +136,9 @@ impl TerrainNoise {
 
         Self {
             height,
-            biome_temp,
+            temperature,
+            humidity,
+            continentalness,
             cave_cheese,
             cave_spaghetti_a,
             cave_spaghetti_b,
@@ -121,30 +149,110 @@ impl TerrainNoise {
             gravel,
             clay,
             grass_scatter,
+            columns: Mutex::new(ColumnCache::default()),
+        }
+    }
+
+    /// Height and biome maps of a chunk column, given as (chunk x, chunk z). Computed once
+    /// and shared by every layer of the column while it stays in the cache.
+    fn column_maps(&self, column: IVec2) -> Arc<ColumnMaps> {
+        if let Some(maps) = self.columns.lock().expect("column cache lock poisoned").get(column) {
+            return maps;
         }
+        // Computed outside the lock so other columns generate meanwhile
+        let heights = self.sample_height_map(IVec3::new(column.x, 0, column.y));
+        let mut biomes = [[Biome::Plains; CHUNK_SIZE]; CHUNK_SIZE];
+        for (z, row) in biomes.iter_mut().enumerate() {
+            for (x, biome) in row.iter_mut().enumerate() {
+                *biome = self.sample_biome(column.x * CHUNK_SIZE as i32 + x as i32, column.y * CHUNK_SIZE as i32 + z as i32);
+            }
+        }
+        let maps = Arc::new(ColumnMaps { heights, biomes });
+        self.columns.lock().expect("column cache lock poisoned").insert(column, maps.clone());
+        maps
     }
 
-    /// Sample terrain height at a world (x, z) position.
+    /// Sample terrain height at a world (x, z) position, shaped by the surrounding biomes.
     fn sample_height(&self, world_x: i32, world_z: i32) -> i32 {
+        let (gx, gz) = (world_x.div_euclid(BLEND_STEP), world_z.div_euclid(BLEND_STEP));
+        // On the blend grid itself the shape needs no interpolation (far terrain samples there)
+        if world_x.rem_euclid(BLEND_STEP) == 0 && world_z.rem_euclid(BLEND_STEP) == 0 {
+            return self.height_from_shape(world_x, world_z, self.blended_shape(gx, gz));
+        }
+        let corners = [
+            self.blended_shape(gx, gz),
+            self.blended_shape(gx + 1, gz),
+            self.blended_shape(gx, gz + 1),
+            self.blended_shape(gx + 1, gz + 1),
+        ];
+        self.height_from_shape(world_x, world_z, HeightShape::interpolate(corners, world_x, world_z))
+    }
+
+    /// Terrain heights for a chunk's columns. Same result as `sample_height` per column,
+    /// but each blended grid point is computed once for the whole chunk.
+    fn sample_height_map(&self, chunk_pos: IVec3) -> [[i32; CHUNK_SIZE]; CHUNK_SIZE] {
+        const GRID: usize = CHUNK_SIZE / BLEND_STEP as usize + 1;
+        let gx0 = chunk_pos.x * CHUNK_SIZE as i32 / BLEND_STEP;
+        let gz0 = chunk_pos.z * CHUNK_SIZE as i32 / BLEND_STEP;
+        let mut grid = [[HeightShape::default(); GRID]; GRID];
+        for (i, row) in grid.iter_mut().enumerate() {
+            for (j, shape) in row.iter_mut().enumerate() {
+                *shape = self.blended_shape(gx0 + j as i32, gz0 + i as i32);
+            }
+        }
+
+        let mut height_map = [[0i32; CHUNK_SIZE]; CHUNK_SIZE];
+        for (z, row) in height_map.iter_mut().enumerate() {
+            for (x, height) in row.iter_mut().enumerate() {
+                let wx = chunk_pos.x * CHUNK_SIZE as i32 + x as i32;
+                let wz = chunk_pos.z * CHUNK_SIZE as i32 + z as i32;
+                let (i, j) = (z / BLEND_STEP as usize, x / BLEND_STEP as usize);
+                let corners = [grid[i][j], grid[i][j + 1], grid[i + 1][j], grid[i + 1][j + 1]];
+                *height = self.height_from_shape(wx, wz, HeightShape::interpolate(corners, wx, wz));
+            }
+        }
+        height_map
+    }
+
+    /// Tent-weighted average of the biome height shaping around blend grid point (gx, gz).
+    fn blended_shape(&self, gx: i32, gz: i32) -> HeightShape {
+        let mut base = 0.0;
+        let mut amplitude = 0.0;
+        let mut total = 0.0;
+        for dz in -BLEND_RADIUS..=BLEND_RADIUS {
+            for dx in -BLEND_RADIUS..=BLEND_RADIUS {
+                let weight = ((BLEND_RADIUS + 1 - dx.abs()) * (BLEND_RADIUS + 1 - dz.abs())) as f64;
+                let biome = self.sample_biome((gx + dx) * BLEND_STEP, (gz + dz) * BLEND_STEP);
+                base += biome.base_height() * weight;
+                amplitude += biome.height_amplitude() * weight;
+                total += weight;
+            }
+        }
+        HeightShape { base: base / total, amplitude: amplitude / total }
+    }
+
+    fn height_from_shape(&self, world_x: i32, world_z: i32, shape: HeightShape) -> i32 {
         let val = self.height.get([world_x as f64, world_z as f64]);
         // Fbm output roughly in [-1, 1]; map to height range
-        (BASE_HEIGHT + val * HEIGHT_AMPLITUDE) as i32
+        (shape.base + val * shape.amplitude) as i32
+    }
+
+    fn sample_climate(&self, world_x: i32, world_z: i32) -> Climate {
+        let (x, z) = (world_x as f64, world_z as f64);
+        Climate {
+            temperature: self.temperature.get([x * BIOME_FREQUENCY, z * BIOME_FREQUENCY]),
+            humidity: self.humidity.get([x * BIOME_FREQUENCY, z * BIOME_FREQUENCY]),
+            continentalness: self.continentalness.get([x, z]),
+        }
     }
 
-    /// Returns a temperature value in roughly [-1, 1].
-    /// Positive = warm (desert), negative = cool (plains).
-    fn sample_biome_temp(&self, world_x: i32, world_z: i32) -> f64 {
-        self.biome_temp
-            .get([world_x as f64 * BIOME_FREQUENCY, world_z as f64 * BIOME_FREQUENCY])
+    fn sample_biome(&self, world_x: i32, world_z: i32) -> Biome {
+        Biome::from_climate(self.sample_climate(world_x, world_z))
     }
 
     /// Returns true if this position should be carved out as a cave.
     /// Uses three cave types: cheese (chambers), spaghetti (tunnels), noodle (thin passages).
     fn is_cave(&self, wx: i32, wy: i32, wz: i32, terrain_height: i32) -> bool {
-        // No caves at bedrock
-        if wy <= 0 {
-            return false;
-        }
         let (x, y, z) = (wx as f64, wy as f64, wz as f64);
 
         // Cheese caves — large chambers, keep 4-block surface protection (giant holes look ugly)
@@ -207,10 +315,192 @@ impl TerrainNoise {
     }
 }
 
-#[derive(Clone, Copy)]
-enum Biome {
+/// Terrain height and biome of every block column in a chunk column.
+struct ColumnMaps {
+    heights: [[i32; CHUNK_SIZE]; CHUNK_SIZE],
+    biomes: [[Biome; CHUNK_SIZE]; CHUNK_SIZE],
+}
+
+/// The most recently used chunk columns' maps, up to `COLUMN_CACHE_SIZE` of them.
+#[derive(Default)]
+struct ColumnCache {
+    /// Maps and when they were last used, by chunk column.
+    columns: HashMap<IVec2, (Arc<ColumnMaps>, u64)>,
+    /// Counts lookups and inserts, to stamp each use.
+    clock: u64,
+}
+
+impl ColumnCache {
+    fn get(&mut self, column: IVec2) -> Option<Arc<ColumnMaps>> {
+        self.clock += 1;
+        let clock = self.clock;
+        self.columns.get_mut(&column).map(|(maps, used)| {
+            *used = clock;
+            maps.clone()
+        })
+    }
+
+    /// Adds a column's maps, dropping the least recently used column when full.
+    fn insert(&mut self, column: IVec2, maps: Arc<ColumnMaps>) {
+        if self.columns.len() >= COLUMN_CACHE_SIZE && !self.columns.contains_key(&column) {
+            let oldest = self.columns.iter().min_by_key(|(_, (_, used))| *used).map(|(&c, _)| c);
+            if let Some(oldest) = oldest {
+                self.columns.remove(&oldest);
+            }
+        }
+        self.clock += 1;
+        self.columns.insert(column, (maps, self.clock));
+    }
+}
+
+/// Base height and amplitude of the terrain, blended between biomes.
+#[derive(Clone, Copy, Default)]
+struct HeightShape {
+    base: f64,
+    amplitude: f64,
+}
+
+impl HeightShape {
+    /// Bilinear blend of the four blend grid points around (wx, wz), given in the order
+    /// (x0, z0), (x1, z0), (x0, z1), (x1, z1).
+    fn interpolate(corners: [HeightShape; 4], wx: i32, wz: i32) -> HeightShape {
+        let fx = wx.rem_euclid(BLEND_STEP) as f64 / BLEND_STEP as f64;
+        let fz = wz.rem_euclid(BLEND_STEP) as f64 / BLEND_STEP as f64;
+        let lerp = |f: fn(&HeightShape) -> f64| {
+            let near = f(&corners[0]) * (1.0 - fx) + f(&corners[1]) * fx;
+            let far = f(&corners[2]) * (1.0 - fx) + f(&corners[3]) * fx;
+            near * (1.0 - fz) + far * fz
+        };
+        HeightShape { base: lerp(|s| s.base), amplitude: lerp(|s| s.amplitude) }
+    }
+}
+
+/// Climate at a column, each value roughly in [-1, 1].
+#[derive(Clone, Copy, Debug)]
+pub struct Climate {
+    /// Cold (negative) to hot (positive).
+    pub temperature: f64,
+    /// Dry (negative) to wet (positive).
+    pub humidity: f64,
+    /// Deep ocean (negative) through coast to far inland (positive).
+    pub continentalness: f64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Biome {
+    Ocean,
+    Beach,
     Plains,
+    Forest,
+    BirchForest,
+    SnowyPlains,
+    Mountains,
     Desert,
+    /// Low, waterlogged land with vine-hung oaks.
+    Swamp,
+}
+
+impl Biome {
+    /// Pick the biome for a climate: continentalness decides ocean, coast and mountains,
+    /// then temperature and humidity decide among the inland biomes.
+    pub fn from_climate(climate: Climate) -> Biome {
+        let Climate { temperature, humidity, continentalness } = climate;
+        if continentalness < -0.25 {
+            Biome::Ocean
+        } else if continentalness < -0.15 {
+            Biome::Beach
+        } else if continentalness > 0.45 {
+            Biome::Mountains
+        } else if temperature < -0.35 {
+            Biome::SnowyPlains
+        } else if temperature > 0.35 && humidity < 0.0 {
+            Biome::Desert
+        } else if humidity > 0.2 {
+            if temperature > 0.1 {
+                Biome::BirchForest
+            } else if humidity > 0.55 && temperature > -0.1 {
+                Biome::Swamp
+            } else {
+                Biome::Forest
+            }
+        } else {
+            Biome::Plains
+        }
+    }
+
+    /// Terrain height the height noise is centered on.
+    fn base_height(self) -> f64 {
+        match self {
+            Biome::Ocean => 46.0,
+            Biome::Beach | Biome::Swamp => 63.0,
+            Biome::Plains | Biome::Desert => 67.0,
+            Biome::Forest | Biome::BirchForest | Biome::SnowyPlains => 70.0,
+            Biome::Mountains => 92.0,
+        }
+    }
+
+    /// How far the height noise moves the terrain from the base height.
+    fn height_amplitude(self) -> f64 {
+        match self {
+            Biome::Ocean => 12.0,
+            Biome::Beach | Biome::Swamp => 3.0,
+            Biome::Plains | Biome::Desert => 10.0,
+            Biome::Forest | Biome::BirchForest | Biome::SnowyPlains => 18.0,
+            Biome::Mountains => 60.0,
+        }
+    }
+
+    /// Top block of dry land.
+    fn surface_block(self, wy: i32) -> BlockType {
+        match self {
+            Biome::Ocean | Biome::Beach | Biome::Desert => BlockType::Sand,
+            Biome::SnowyPlains => BlockType::Snow,
+            Biome::Mountains if wy >= SNOW_LINE => BlockType::Snow,
+            Biome::Mountains => BlockType::Stone,
+            Biome::Plains | Biome::Forest | Biome::BirchForest | Biome::Swamp => BlockType::Grass,
+        }
+    }
+
+    /// The few blocks under the surface.
+    fn subsurface_block(self) -> BlockType {
+        match self {
+            Biome::Beach => BlockType::Sand,
+            Biome::Desert => BlockType::Sandstone,
+            Biome::Mountains => BlockType::Stone,
+            _ => BlockType::Dirt,
+        }
+    }
+
+    /// Top block below sea level.
+    fn underwater_block(self) -> BlockType {
+        match self {
+            Biome::Ocean => BlockType::Gravel,
+            Biome::Beach | Biome::Desert => BlockType::Sand,
+            _ => BlockType::Dirt,
+        }
+    }
+
+    /// Tree spacing and chance: one tree may grow per `spacing` x `spacing` grid cell,
+    /// when the cell's noise is above `threshold`. `None` for treeless biomes.
+    fn tree_grid(self) -> Option<(i32, f64)> {
+        match self {
+            Biome::Plains => Some((9, 0.25)),
+            Biome::Forest | Biome::BirchForest => Some((5, -0.2)),
+            Biome::SnowyPlains => Some((8, 0.2)),
+            Biome::Swamp => Some((7, -0.1)),
+            Biome::Ocean | Biome::Beach | Biome::Mountains | Biome::Desert => None,
+        }
+    }
+
+    /// Variety noise above which a tree is birch instead of oak.
+    fn birch_threshold(self) -> f64 {
+        match self {
+            Biome::BirchForest => f64::NEG_INFINITY,
+            Biome::Forest => 0.5,
+            Biome::SnowyPlains | Biome::Swamp => f64::INFINITY,
+            _ => 0.4,
+        }
+    }
 }
 
 /// Triangular distribution probability: peaks at `peak`, zero at `min` and `max`.
@@ -229,10 +519,31 @@ fn triangular_weight(y: i32, min: i32, max: i32, peak: i32) -> f64 {
     }
 }
 
+/// Deterministic per-block hash in [0, 1), for dithering block transitions.
+fn position_hash(wx: i32, wy: i32, wz: i32) -> f64 {
+    let mut h = (wx as u32)
+        .wrapping_mul(374_761_393)
+        .wrapping_add((wy as u32).wrapping_mul(668_265_263))
+        .wrapping_add((wz as u32).wrapping_mul(2_147_483_647));
+    h = (h ^ (h >> 13)).wrapping_mul(1_274_126_177);
+    h ^= h >> 16;
+    h as f64 / (u32::MAX as f64 + 1.0)
+}
+
+/// Whether stone at this height is deepslate: always below the blend band, and
+/// increasingly often further down within it.
+fn is_deepslate(wx: i32, wy: i32, wz: i32) -> bool {
+    if wy >= DEEPSLATE_TOP {
+        return false;
+    }
+    let depth = (DEEPSLATE_TOP - wy) as f64 / DEEPSLATE_BLEND as f64;
+    position_hash(wx, wy, wz) < depth
+}
+
 /// Determine which ore (if any) should replace stone at this position.
 fn determine_ore(noise: &TerrainNoise, wx: i32, wy: i32, wz: i32) -> Option<BlockType> {
-    // Diamond: Y 5-16, peak 8 (index 0)
-    let diamond_weight = triangular_weight(wy, 5, 16, 8);
+    // Diamond: Y 5-16, peak 8, plus a richer deep band at Y -60-0, peak -50 (index 0)
+    let diamond_weight = triangular_weight(wy, 5, 16, 8).max(triangular_weight(wy, -60, 0, -50));
     if diamond_weight > 0.0 {
         let density = noise.ore_density(wx, wy, wz, 0);
         if density > 1.0 - diamond_weight * 0.35 {
@@ -271,40 +582,72 @@ fn determine_ore(noise: &TerrainNoise, wx: i32, wy: i32, wz: i32) -> Option<Bloc
 }
 
 /// Generate a complete chunk at the given chunk position.
-pub fn generate_chunk(chunk_pos: IVec3) -> Chunk {
-    with_noise(|noise| generate_chunk_with_noise(chunk_pos, noise))
+pub fn generate_chunk(chunk_pos: IVec3, height: WorldHeight) -> Chunk {
+    with_noise(|noise| generate_chunk_with_noise(chunk_pos, height, noise))
 }
 
-/// Sample terrain height at a world (x, z) position without generating a full chunk.
-pub fn sample_terrain_height(world_x: i32, world_z: i32) -> i32 {
-    with_noise(|noise| noise.sample_height(world_x, world_z))
+/// Climate at a world (x, z) position.
+pub fn sample_climate(world_x: i32, world_z: i32) -> Climate {
+    with_noise(|noise| noise.sample_climate(world_x, world_z))
 }
 
-fn generate_chunk_with_noise(chunk_pos: IVec3, noise: &TerrainNoise) -> Chunk {
+/// The top of a column as seen from above, ignoring caves and trees: the highest
+/// block's Y and type. Below sea level that's the water surface.
+pub fn sample_surface(world_x: i32, world_z: i32) -> (i32, BlockType) {
+    with_noise(|noise| {
+        let height = noise.sample_height(world_x, world_z);
+        if height < SEA_LEVEL {
+            (SEA_LEVEL, BlockType::Water)
+        } else {
+            (height, noise.sample_biome(world_x, world_z).surface_block(height))
+        }
+    })
+}
+
+/// Where a new world's player first appears: on dry land, searching outward from
+/// the origin in case it lies in an ocean.
+pub fn find_spawn() -> Vec3 {
+    with_noise(|noise| {
+        for radius in (0..=1024).step_by(16) {
+            for (x, z) in ring(radius) {
+                let height = noise.sample_height(x, z);
+                if height >= SEA_LEVEL && noise.sample_biome(x, z) != Biome::Ocean {
+                    return Vec3::new(x as f32, (height + 1) as f32, z as f32);
+                }
+            }
+        }
+        Vec3::new(0.0, (noise.sample_height(0, 0) + 1) as f32, 0.0)
+    })
+}
+
+/// Points on the square ring `radius` blocks from the origin, 16 blocks apart.
+fn ring(radius: i32) -> Vec<(i32, i32)> {
+    if radius == 0 {
+        return vec![(0, 0)];
+    }
+    let mut points = Vec::new();
+    for i in (-radius..radius).step_by(16) {
+        points.push((i, -radius));
+        points.push((radius, i));
+        points.push((-i, radius));
+        points.push((-radius, -i));
+    }
+    points
+}
+
+fn generate_chunk_with_noise(chunk_pos: IVec3, height: WorldHeight, noise: &TerrainNoise) -> Chunk {
     let mut chunk = Chunk::default();
+    if chunk_pos.y < height.min_chunk_y() || chunk_pos.y >= height.max_chunk_y() {
+        return chunk;
+    }
 
     let world_x_base = chunk_pos.x * CHUNK_SIZE as i32;
     let world_y_base = chunk_pos.y * CHUNK_SIZE as i32;
     let world_z_base = chunk_pos.z * CHUNK_SIZE as i32;
 
-    // Pre-compute height map and biome for this chunk's XZ columns
-    let mut height_map = [[0i32; CHUNK_SIZE]; CHUNK_SIZE];
-    let mut biome_map = [[Biome::Plains; CHUNK_SIZE]; CHUNK_SIZE];
-
-    for z in 0..CHUNK_SIZE {
-        for x in 0..CHUNK_SIZE {
-            let wx = world_x_base + x as i32;
-            let wz = world_z_base + z as i32;
-            height_map[z][x] = noise.sample_height(wx, wz);
-
-            let temp = noise.sample_biome_temp(wx, wz);
-            biome_map[z][x] = if temp > 0.3 {
-                Biome::Desert
-            } else {
-                Biome::Plains
-            };
-        }
-    }
+    // Height map and biome for this chunk's XZ columns, shared with the layers above and below
+    let maps = noise.column_maps(IVec2::new(chunk_pos.x, chunk_pos.z));
+    let (height_map, biome_map) = (&maps.heights, &maps.biomes);
 
     // First pass: fill terrain
     for y in 0..CHUNK_SIZE {
@@ -314,9 +657,7 @@ fn generate_chunk_with_noise(chunk_pos: IVec3, noise: &TerrainNoise) -> Chunk {
                 let terrain_height = height_map[z][x];
                 let biome = biome_map[z][x];
 
-                let block = if wy < 0 {
-                    BlockType::Air
-                } else if wy == 0 {
+                let block = if wy == height.min_y {
                     BlockType::Bedrock
                 } else if wy > terrain_height {
                     // Above terrain: water if below sea level, else air
@@ -327,22 +668,16 @@ fn generate_chunk_with_noise(chunk_pos: IVec3, noise: &TerrainNoise) -> Chunk {
                     }
                 } else if wy == terrain_height && terrain_height >= SEA_LEVEL {
                     // Surface block (only when above water)
-                    match biome {
-                        Biome::Plains => BlockType::Grass,
-                        Biome::Desert => BlockType::Sand,
-                    }
+                    biome.surface_block(wy)
                 } else if wy > terrain_height - 4 && wy < terrain_height {
                     // Sub-surface layers (3-4 blocks deep)
-                    match biome {
-                        Biome::Plains => BlockType::Dirt,
-                        Biome::Desert => BlockType::Sandstone,
-                    }
+                    biome.subsurface_block()
                 } else if wy == terrain_height && terrain_height < SEA_LEVEL {
                     // Underwater surface
-                    match biome {
-                        Biome::Plains => BlockType::Dirt,
-                        Biome::Desert => BlockType::Sand,
-                    }
+                    biome.underwater_block()
+                } else if is_deepslate(x as i32 + world_x_base, wy, z as i32 + world_z_base) {
+                    // Deep layers below Y=0
+                    BlockType::Deepslate
                 } else {
                     // Deep underground: stone (with ore/cave pass later)
                     BlockType::Stone
@@ -362,11 +697,12 @@ fn generate_chunk_with_noise(chunk_pos: IVec3, noise: &TerrainNoise) -> Chunk {
                 let wz = world_z_base + z as i32;
                 let current = chunk.get(x, y, z);
 
-                // Cave carving — carves through stone, dirt, sand, grass, gravel, sandstone
+                // Cave carving — carves through stone, deepslate, dirt, sand, grass, gravel, sandstone
                 let terrain_height = height_map[z][x];
                 let is_carveable = matches!(
                     current,
                     BlockType::Stone
+                        | BlockType::Deepslate
                         | BlockType::Dirt
                         | BlockType::Grass
                         | BlockType::Sand
@@ -384,11 +720,13 @@ fn generate_chunk_with_noise(chunk_pos: IVec3, noise: &TerrainNoise) -> Chunk {
                         chunk.set(x, y, z, BlockType::Gravel);
                         continue;
                     }
+                }
 
-                    // Ore placement
-                    if let Some(ore) = determine_ore(noise, wx, wy, wz) {
-                        chunk.set(x, y, z, ore);
-                    }
+                // Ore placement
+                if (current == BlockType::Stone || current == BlockType::Deepslate)
+                    && let Some(ore) = determine_ore(noise, wx, wy, wz)
+                {
+                    chunk.set(x, y, z, ore);
                 }
 
                 // Clay patches near water (Y 60-63, replace sand/dirt)
@@ -403,134 +741,189 @@ fn generate_chunk_with_noise(chunk_pos: IVec3, noise: &TerrainNoise) -> Chunk {
         }
     }
 
-    // Third pass: place trees and vegetation (only if this chunk contains the surface)
-    place_trees(&mut chunk, noise, &height_map, &biome_map, chunk_pos);
-    place_tall_grass(&mut chunk, noise, &height_map, &biome_map, chunk_pos);
+    // Third pass: place trees and other features, then vegetation
+    place_features(&mut chunk, noise, chunk_pos);
+    place_tall_grass(&mut chunk, noise, height_map, biome_map, chunk_pos);
 
+    // Drop palette entries left behind by replaced blocks (stone -> ore, dirt -> clay, ...)
+    chunk.compact();
     chunk
 }
 
-/// Place trees on grass blocks in plains biome.
-fn place_trees(
-    chunk: &mut Chunk,
-    noise: &TerrainNoise,
-    height_map: &[[i32; CHUNK_SIZE]; CHUNK_SIZE],
-    biome_map: &[[Biome; CHUNK_SIZE]; CHUNK_SIZE],
-    chunk_pos: IVec3,
-) {
-    let world_x_base = chunk_pos.x * CHUNK_SIZE as i32;
-    let world_y_base = chunk_pos.y * CHUNK_SIZE as i32;
-    let world_z_base = chunk_pos.z * CHUNK_SIZE as i32;
+/// How far a feature may reach horizontally from the column it grows from. A chunk
+/// looks this far past its own columns for features that overhang into it.
+const MAX_FEATURE_REACH: i32 = 3;
+
+/// The chunk being generated, addressed in world coordinates. Features write through it
+/// without knowing which chunk they are rooted in; blocks outside the chunk are dropped,
+/// since each neighbor places the same feature again when it generates.
+struct FeatureWriter<'a> {
+    chunk: &'a mut Chunk,
+    /// World position of the chunk's (0, 0, 0) block.
+    origin: IVec3,
+}
 
-    // Use a simple hash-based check for tree placement to ensure spacing
-    for z in 2..CHUNK_SIZE - 2 {
-        for x in 2..CHUNK_SIZE - 2 {
-            let wx = world_x_base + x as i32;
-            let wz = world_z_base + z as i32;
-            let terrain_height = height_map[z][x];
+impl FeatureWriter<'_> {
+    fn local(&self, pos: IVec3) -> Option<(usize, usize, usize)> {
+        let local = pos - self.origin;
+        if local.cmplt(IVec3::ZERO).any() || local.cmpge(IVec3::splat(CHUNK_SIZE as i32)).any() {
+            return None;
+        }
+        Some((local.x as usize, local.y as usize, local.z as usize))
+    }
 
-            // Only place trees on plains grass above sea level
-            if !matches!(biome_map[z][x], Biome::Plains) {
-                continue;
-            }
-            if terrain_height < SEA_LEVEL {
-                continue;
-            }
+    fn set(&mut self, pos: IVec3, block: BlockType) {
+        if let Some((x, y, z)) = self.local(pos) {
+            self.chunk.set(x, y, z, block);
+        }
+    }
 
-            // Use noise-based spacing: sample at grid-snapped positions
-            let grid_x = wx.div_euclid(7);
-            let grid_z = wz.div_euclid(7);
-            let tree_noise = noise.ore.get([grid_x as f64 * 1.5, grid_z as f64 * 1.5]);
-            if tree_noise < 0.2 {
-                continue;
-            }
+    /// Place `block` only where there is air, so features never eat into terrain.
+    fn set_if_air(&mut self, pos: IVec3, block: BlockType) {
+        self.set_state_if_air(pos, BlockState::new(block));
+    }
 
-            // Only place tree if this is the "chosen" block within its grid cell
-            let cell_x = wx.rem_euclid(7);
-            let cell_z = wz.rem_euclid(7);
-            if cell_x != 3 || cell_z != 3 {
-                continue;
-            }
+    fn set_state_if_air(&mut self, pos: IVec3, state: BlockState) {
+        if let Some((x, y, z)) = self.local(pos)
+            && self.chunk.get(x, y, z) == BlockType::Air
+        {
+            self.chunk.set_state(x, y, z, state);
+        }
+    }
+}
 
-            // Surface must be in this chunk
-            let local_surface_y = terrain_height - world_y_base;
-            if local_surface_y < 0 || local_surface_y >= CHUNK_SIZE as i32 {
-                continue;
+/// Place every feature that reaches into this chunk, including those rooted in
+/// neighboring chunks. Features are decided purely from noise, so a feature crossing
+/// a border comes out the same on both sides whichever chunk generates first.
+fn place_features(chunk: &mut Chunk, noise: &TerrainNoise, chunk_pos: IVec3) {
+    let origin = chunk_pos * CHUNK_SIZE as i32;
+    let mut writer = FeatureWriter { chunk, origin };
+
+    // Walk columns in world order so overlapping features resolve the same way everywhere
+    for wz in origin.z - MAX_FEATURE_REACH..origin.z + CHUNK_SIZE as i32 + MAX_FEATURE_REACH {
+        for wx in origin.x - MAX_FEATURE_REACH..origin.x + CHUNK_SIZE as i32 + MAX_FEATURE_REACH {
+            if let Some(tree) = Tree::at(noise, wx, wz) {
+                tree.place(&mut writer);
             }
+        }
+    }
+}
 
-            // Verify the surface block is actually grass
-            if chunk.get(x, local_surface_y as usize, z) != BlockType::Grass {
-                continue;
-            }
+/// An oak or birch tree rooted on a grass or snow block.
+struct Tree {
+    /// The ground block the trunk stands on.
+    surface: IVec3,
+    log: BlockType,
+    leaves: BlockType,
+    trunk_height: i32,
+    /// Canopy radius: birch is narrower than oak.
+    radius: i32,
+    /// Swamp trees have vines hanging down the sides of their canopy.
+    vines: bool,
+}
+
+impl Tree {
+    /// The tree growing from column (wx, wz), if any. Trees grow above sea level in
+    /// wooded biomes, at most one per grid cell of the biome's tree spacing.
+    fn at(noise: &TerrainNoise, wx: i32, wz: i32) -> Option<Tree> {
+        let biome = noise.sample_biome(wx, wz);
+        let (spacing, threshold) = biome.tree_grid()?;
+
+        // Only the "chosen" block within its grid cell can hold a tree
+        if wx.rem_euclid(spacing) != spacing / 2 || wz.rem_euclid(spacing) != spacing / 2 {
+            return None;
+        }
+
+        // Use noise-based spacing: sample at grid-snapped positions
+        let grid_x = wx.div_euclid(spacing);
+        let grid_z = wz.div_euclid(spacing);
+        let tree_noise = noise.ore.get([grid_x as f64 * 1.5, grid_z as f64 * 1.5]);
+        if tree_noise < threshold {
+            return None;
+        }
 
-            // Determine tree type: ~30% birch, ~70% oak
-            // Use a secondary noise sample for deterministic variety
-            let variety_noise = noise.ore.get([wx as f64 * 0.7, wz as f64 * 0.7]);
-            let is_birch = variety_noise > 0.4; // roughly 30% of range maps to birch
+        let terrain_height = noise.sample_height(wx, wz);
+        if terrain_height < SEA_LEVEL {
+            return None;
+        }
+        // The surface is the biome's ground unless a cave opens right through it
+        if noise.is_cave(wx, terrain_height, wz, terrain_height) {
+            return None;
+        }
+
+        // Determine tree type from the biome's birch share
+        // Use a secondary noise sample for deterministic variety
+        let variety_noise = noise.ore.get([wx as f64 * 0.7, wz as f64 * 0.7]);
+        let is_birch = variety_noise > biome.birch_threshold();
+
+        let surface = IVec3::new(wx, terrain_height, wz);
+        let vines = biome == Biome::Swamp;
+        Some(if is_birch {
+            // Birch: taller trunk (5-7), thinner canopy
+            let h = 5 + ((tree_noise * 10.0) as i32).rem_euclid(3); // 5, 6, or 7
+            Tree { surface, log: BlockType::BirchLog, leaves: BlockType::BirchLeaves, trunk_height: h, radius: 1, vines }
+        } else {
+            // Oak: 5-6 blocks trunk
+            let h = 5 + ((tree_noise * 10.0) as i32).rem_euclid(2); // 5 or 6
+            Tree { surface, log: BlockType::OakLog, leaves: BlockType::OakLeaves, trunk_height: h, radius: 2, vines }
+        })
+    }
 
-            let (log_type, leaf_type, trunk_height) = if is_birch {
-                // Birch: taller trunk (5-7), thinner canopy
-                let h = 5 + ((tree_noise * 10.0) as i32 % 3); // 5, 6, or 7
-                (BlockType::BirchLog, BlockType::BirchLeaves, h)
+    fn place(&self, writer: &mut FeatureWriter) {
+        // Place trunk
+        for ty in 1..=self.trunk_height {
+            writer.set(self.surface + IVec3::Y * ty, self.log);
+        }
+
+        // Place leaves
+        let leaf_start = self.trunk_height - 2;
+        for ly_offset in leaf_start..=self.trunk_height + 1 {
+            // Narrower at top layers
+            let radius = if ly_offset >= self.trunk_height {
+                1.min(self.radius)
             } else {
-                // Oak: 5-6 blocks trunk
-                let h = 5 + ((tree_noise * 10.0) as i32 % 2); // 5 or 6
-                (BlockType::OakLog, BlockType::OakLeaves, h)
+                self.radius
             };
-
-            // Place trunk
-            for ty in 1..=trunk_height {
-                let ly = local_surface_y + ty;
-                if ly >= 0 && ly < CHUNK_SIZE as i32 {
-                    chunk.set(x, ly as usize, z, log_type);
+            for dz in -radius..=radius {
+                for dx in -radius..=radius {
+                    // Skip corners for rounder shape
+                    if dx.abs() == radius && dz.abs() == radius {
+                        continue;
+                    }
+                    // Don't overwrite trunk
+                    if dx == 0 && dz == 0 && ly_offset <= self.trunk_height {
+                        continue;
+                    }
+                    writer.set_if_air(self.surface + IVec3::new(dx, ly_offset, dz), self.leaves);
                 }
             }
+        }
 
-            // Place leaves: birch has narrower canopy (radius 1), oak has radius 2
-            let leaf_start = trunk_height - 2;
-            let max_radius: i32 = if is_birch { 1 } else { 2 };
-            for ly_offset in leaf_start..=trunk_height + 1 {
-                let ly = local_surface_y + ly_offset;
-                if ly < 0 || ly >= CHUNK_SIZE as i32 {
-                    continue;
-                }
-                // Narrower at top layers
-                let radius: i32 = if ly_offset >= trunk_height {
-                    1.min(max_radius)
-                } else {
-                    max_radius
-                };
-                for dz in -radius..=radius {
-                    for dx in -radius..=radius {
-                        // Skip corners for rounder shape
-                        if dx.abs() == radius && dz.abs() == radius {
-                            continue;
-                        }
-                        let lx = x as i32 + dx;
-                        let lz = z as i32 + dz;
-                        if lx < 0
-                            || lx >= CHUNK_SIZE as i32
-                            || lz < 0
-                            || lz >= CHUNK_SIZE as i32
-                        {
-                            continue;
-                        }
-                        // Don't overwrite trunk
-                        if dx == 0 && dz == 0 && ly_offset <= trunk_height {
-                            continue;
-                        }
-                        let current = chunk.get(lx as usize, ly as usize, lz as usize);
-                        if current == BlockType::Air {
-                            chunk.set(lx as usize, ly as usize, lz as usize, leaf_type);
-                        }
-                    }
+        if self.vines {
+            self.place_vines(writer);
+        }
+    }
+
+    /// Hang vines off the sides of the canopy's widest layer, facing out from the leaves,
+    /// each a few blocks long.
+    fn place_vines(&self, writer: &mut FeatureWriter) {
+        let top = self.surface + IVec3::Y * (self.trunk_height - 1);
+        for facing in [Facing::North, Facing::South, Facing::East, Facing::West] {
+            let out = facing.offset();
+            let across = IVec3::new(-out.z, 0, out.x);
+            // Beside each leaf along the side, leaving out the skipped corners
+            for step in 1 - self.radius..self.radius {
+                let start = top + out * (self.radius + 1) + across * step;
+                let length = (position_hash(start.x, start.y, start.z) * 5.0) as i32;
+                for dy in 0..length {
+                    writer.set_state_if_air(start - IVec3::Y * dy, BlockState::new(BlockType::Vines).with_facing(facing));
                 }
             }
         }
     }
 }
 
-/// Place tall grass on exposed grass blocks in plains biome (~20% coverage).
+/// Place tall grass on exposed grass blocks in grassy biomes (~20% coverage).
 fn place_tall_grass(
     chunk: &mut Chunk,
     noise: &TerrainNoise,
@@ -544,7 +937,7 @@ fn place_tall_grass(
 
     for z in 0..CHUNK_SIZE {
         for x in 0..CHUNK_SIZE {
-            if !matches!(biome_map[z][x], Biome::Plains) {
+            if !matches!(biome_map[z][x], Biome::Plains | Biome::Forest | Biome::BirchForest | Biome::Swamp) {
                 continue;
             }
 
@@ -582,3 +975,175 @@ fn place_tall_grass(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn block_at(chunks: &mut HashMap<IVec3, Chunk>, pos: IVec3) -> BlockType {
+        let size = CHUNK_SIZE as i32;
+        let chunk_pos = pos.div_euclid(IVec3::splat(size));
+        let local = pos.rem_euclid(IVec3::splat(size));
+        chunks
+            .entry(chunk_pos)
+            .or_insert_with(|| generate_chunk(chunk_pos, WorldHeight::default()))
+            .get(local.x as usize, local.y as usize, local.z as usize)
+    }
+
+    #[test]
+    fn climate_picks_biomes() {
+        let climate = |temperature, humidity, continentalness| Climate { temperature, humidity, continentalness };
+        assert_eq!(Biome::from_climate(climate(0.0, 0.0, -0.8)), Biome::Ocean);
+        assert_eq!(Biome::from_climate(climate(0.0, 0.0, -0.2)), Biome::Beach);
+        assert_eq!(Biome::from_climate(climate(0.0, 0.0, 0.8)), Biome::Mountains);
+        assert_eq!(Biome::from_climate(climate(-0.6, 0.0, 0.0)), Biome::SnowyPlains);
+        assert_eq!(Biome::from_climate(climate(0.6, -0.5, 0.0)), Biome::Desert);
+        assert_eq!(Biome::from_climate(climate(0.0, 0.5, 0.0)), Biome::Forest);
+        assert_eq!(Biome::from_climate(climate(0.3, 0.5, 0.0)), Biome::BirchForest);
+        assert_eq!(Biome::from_climate(climate(0.0, 0.7, 0.0)), Biome::Swamp);
+        assert_eq!(Biome::from_climate(climate(0.0, 0.0, 0.0)), Biome::Plains);
+    }
+
+    #[test]
+    fn heights_blend_between_biomes() {
+        // Walk a long line crossing several biomes: no cliff where one biome meets the next
+        let mut biomes = std::collections::HashSet::new();
+        let mut previous = Biome::from_climate(sample_climate(-2000, 0));
+        let mut previous_height = with_noise(|noise| noise.sample_height(-2000, 0));
+        for wx in -1999..2000 {
+            let biome = Biome::from_climate(sample_climate(wx, 0));
+            let height = with_noise(|noise| noise.sample_height(wx, 0));
+            if biome != previous {
+                assert!((height - previous_height).abs() <= 3, "step of {} at x={}", height - previous_height, wx);
+            }
+            biomes.insert(biome);
+            previous = biome;
+            previous_height = height;
+        }
+        assert!(biomes.len() >= 3, "only crossed {:?}", biomes);
+    }
+
+    #[test]
+    fn height_map_matches_single_columns() {
+        let chunk_pos = IVec3::new(-3, 4, 5);
+        let height_map = with_noise(|noise| noise.sample_height_map(chunk_pos));
+        for (z, row) in height_map.iter().enumerate() {
+            for (x, &height) in row.iter().enumerate() {
+                let wx = chunk_pos.x * CHUNK_SIZE as i32 + x as i32;
+                let wz = chunk_pos.z * CHUNK_SIZE as i32 + z as i32;
+                assert_eq!(height, with_noise(|noise| noise.sample_height(wx, wz)));
+            }
+        }
+    }
+
+    #[test]
+    fn column_maps_are_computed_once_per_column() {
+        let column = IVec2::new(7, -11);
+        let (first, again) = with_noise(|noise| (noise.column_maps(column), noise.column_maps(column)));
+        assert!(Arc::ptr_eq(&first, &again));
+        assert_eq!(first.heights, with_noise(|noise| noise.sample_height_map(IVec3::new(7, -2, -11))));
+        let (wx, wz) = (7 * CHUNK_SIZE as i32 + 3, -11 * CHUNK_SIZE as i32 + 9);
+        assert_eq!(first.biomes[9][3], with_noise(|noise| noise.sample_biome(wx, wz)));
+    }
+
+    #[test]
+    fn column_cache_drops_the_least_recently_used() {
+        let maps = Arc::new(ColumnMaps {
+            heights: [[0; CHUNK_SIZE]; CHUNK_SIZE],
+            biomes: [[Biome::Plains; CHUNK_SIZE]; CHUNK_SIZE],
+        });
+        let mut cache = ColumnCache::default();
+        for i in 0..COLUMN_CACHE_SIZE as i32 {
+            cache.insert(IVec2::new(i, 0), maps.clone());
+        }
+        // Using the first column keeps it; the second is now the oldest
+        assert!(cache.get(IVec2::new(0, 0)).is_some());
+        cache.insert(IVec2::new(-1, 0), maps.clone());
+        assert_eq!(cache.columns.len(), COLUMN_CACHE_SIZE);
+        assert!(cache.get(IVec2::new(0, 0)).is_some());
+        assert!(cache.get(IVec2::new(1, 0)).is_none());
+        assert!(cache.get(IVec2::new(-1, 0)).is_some());
+    }
+
+    #[test]
+    fn bedrock_floor_and_deepslate_follow_world_height() {
+        let deep = WorldHeight::new(-64, 384);
+        let floor = generate_chunk(IVec3::new(0, deep.min_chunk_y(), 0), deep);
+        for z in 0..CHUNK_SIZE {
+            for x in 0..CHUNK_SIZE {
+                assert_eq!(floor.get(x, 0, z), BlockType::Bedrock);
+                for y in 1..CHUNK_SIZE {
+                    assert_ne!(floor.get(x, y, z), BlockType::Stone);
+                }
+            }
+        }
+        assert!(generate_chunk(IVec3::new(0, deep.min_chunk_y() - 1, 0), deep).is_empty());
+
+        let legacy = WorldHeight::new(0, 256);
+        let floor = generate_chunk(IVec3::ZERO, legacy);
+        assert_eq!(floor.get(0, 0, 0), BlockType::Bedrock);
+        for y in 1..CHUNK_SIZE {
+            assert_ne!(floor.get(0, y, 0), BlockType::Deepslate);
+        }
+    }
+
+    #[test]
+    fn trees_continue_across_chunk_borders() {
+        let size = CHUNK_SIZE as i32;
+        let mut chunks = HashMap::new();
+        let mut crossing = 0;
+        for wz in -64..64 {
+            for wx in -64..64 {
+                let Some(tree) = with_noise(|noise| Tree::at(noise, wx, wz)) else {
+                    continue;
+                };
+                let top = tree.surface + IVec3::Y * tree.trunk_height;
+                let edge = top + IVec3::new(tree.radius, -1, 0);
+                if top.x.div_euclid(size) != edge.x.div_euclid(size) {
+                    crossing += 1;
+                }
+                assert_eq!(block_at(&mut chunks, top), tree.log, "trunk at {}", top);
+                assert_eq!(block_at(&mut chunks, edge), tree.leaves, "canopy at {}", edge);
+            }
+        }
+        assert!(crossing > 0, "no tree canopy crossed a chunk border");
+    }
+
+    #[test]
+    fn swamp_trees_hang_vines_off_their_canopy() {
+        let mut chunk = Chunk::default();
+        let tree = Tree {
+            surface: IVec3::new(8, 0, 8),
+            log: BlockType::OakLog,
+            leaves: BlockType::OakLeaves,
+            trunk_height: 5,
+            radius: 2,
+            vines: true,
+        };
+        tree.place(&mut FeatureWriter { chunk: &mut chunk, origin: IVec3::ZERO });
+
+        let mut vines = 0;
+        for y in 0..CHUNK_SIZE {
+            for z in 0..CHUNK_SIZE {
+                for x in 0..CHUNK_SIZE {
+                    let state = chunk.get_state(x, y, z);
+                    if state.block() != BlockType::Vines {
+                        continue;
+                    }
+                    vines += 1;
+                    // Facing away from the trunk, hanging under a vine that clings to leaves
+                    let out = state.facing().offset();
+                    let mut top = IVec3::new(x as i32, y as i32, z as i32);
+                    assert!((top - tree.surface).dot(out) > 0, "vine at {} faces the trunk", top);
+                    while top.y + 1 < CHUNK_SIZE as i32 && chunk.get_state(top.x as usize, top.y as usize + 1, top.z as usize) == state {
+                        top += IVec3::Y;
+                    }
+                    let wall = top - out;
+                    assert_eq!(chunk.get(wall.x as usize, wall.y as usize, wall.z as usize), BlockType::OakLeaves);
+                }
+            }
+        }
+        assert!(vines > 0);
+    }
+}
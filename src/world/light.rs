@@ -0,0 +1,473 @@
+use bevy::prelude::*;
+use std::collections::VecDeque;
+
+use crate::block::BlockType;
+use super::chunk::{Chunk, CHUNK_SIZE, CHUNK_VOLUME};
+use super::manager::ChunkDataStore;
+
+/// Brightest light level for both sky and block light.
+pub const MAX_LIGHT: u8 = 15;
+
+/// The six axis-aligned propagation directions.
+const DIRECTIONS: [IVec3; 6] = [
+    IVec3::X,
+    IVec3::NEG_X,
+    IVec3::Y,
+    IVec3::NEG_Y,
+    IVec3::Z,
+    IVec3::NEG_Z,
+];
+
+/// Which of the two independent light fields to read or write.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LightChannel {
+    /// Light coming down from the open sky. Scaled by time of day at render time.
+    Sky,
+    /// Light emitted by blocks such as torches.
+    Block,
+}
+
+/// Per-voxel light levels for one chunk, stored in the same YZX order as `Chunk`.
+/// Each byte packs sky light in the high nibble and block light in the low nibble.
+#[derive(Clone)]
+pub struct ChunkLight {
+    levels: [u8; CHUNK_VOLUME],
+}
+
+impl Default for ChunkLight {
+    fn default() -> Self {
+        Self {
+            levels: [0; CHUNK_VOLUME],
+        }
+    }
+}
+
+impl ChunkLight {
+    #[inline]
+    pub fn get(&self, channel: LightChannel, x: usize, y: usize, z: usize) -> u8 {
+        self.get_index(channel, Chunk::index(x, y, z))
+    }
+
+    #[inline]
+    pub fn set(&mut self, channel: LightChannel, x: usize, y: usize, z: usize, level: u8) {
+        self.set_index(channel, Chunk::index(x, y, z), level);
+    }
+
+    #[inline]
+    pub fn sky(&self, x: usize, y: usize, z: usize) -> u8 {
+        self.get(LightChannel::Sky, x, y, z)
+    }
+
+    #[inline]
+    pub fn block(&self, x: usize, y: usize, z: usize) -> u8 {
+        self.get(LightChannel::Block, x, y, z)
+    }
+
+    #[inline]
+    fn get_index(&self, channel: LightChannel, idx: usize) -> u8 {
+        match channel {
+            LightChannel::Sky => self.levels[idx] >> 4,
+            LightChannel::Block => self.levels[idx] & 0x0F,
+        }
+    }
+
+    #[inline]
+    fn set_index(&mut self, channel: LightChannel, idx: usize, level: u8) {
+        let level = level.min(MAX_LIGHT);
+        let packed = self.levels[idx];
+        self.levels[idx] = match channel {
+            LightChannel::Sky => (packed & 0x0F) | (level << 4),
+            LightChannel::Block => (packed & 0xF0) | level,
+        };
+    }
+}
+
+/// Split a world block position into its chunk position and index within that chunk.
+#[inline]
+fn split_pos(pos: IVec3) -> (IVec3, usize) {
+    let size = CHUNK_SIZE as i32;
+    let chunk_pos = IVec3::new(
+        pos.x.div_euclid(size),
+        pos.y.div_euclid(size),
+        pos.z.div_euclid(size),
+    );
+    let idx = Chunk::index(
+        pos.x.rem_euclid(size) as usize,
+        pos.y.rem_euclid(size) as usize,
+        pos.z.rem_euclid(size) as usize,
+    );
+    (chunk_pos, idx)
+}
+
+/// Block at a world position, or `None` if its chunk isn't loaded.
+fn block_at(store: &ChunkDataStore, pos: IVec3) -> Option<BlockType> {
+    let size = CHUNK_SIZE as i32;
+    let (chunk_pos, _) = split_pos(pos);
+    let chunk = store.chunks.get(&chunk_pos)?;
+    Some(chunk.get(
+        pos.x.rem_euclid(size) as usize,
+        pos.y.rem_euclid(size) as usize,
+        pos.z.rem_euclid(size) as usize,
+    ))
+}
+
+/// Light level at a world position, or `None` if its chunk has no light data yet.
+fn light_at(store: &ChunkDataStore, pos: IVec3, channel: LightChannel) -> Option<u8> {
+    let (chunk_pos, idx) = split_pos(pos);
+    store.lights.get(&chunk_pos).map(|light| light.get_index(channel, idx))
+}
+
+/// Write a light level and record which chunk meshes need rebuilding.
+/// Cells on a chunk border also dirty the neighbor, since its faces sample them.
+/// Writes inside `quiet` are not recorded (that chunk is about to be meshed anyway).
+fn set_light_at(
+    store: &mut ChunkDataStore,
+    pos: IVec3,
+    channel: LightChannel,
+    level: u8,
+    quiet: Option<IVec3>,
+) {
+    let (chunk_pos, idx) = split_pos(pos);
+    let Some(light) = store.lights.get_mut(&chunk_pos) else {
+        return;
+    };
+    light.set_index(channel, idx, level);
+
+    if quiet == Some(chunk_pos) {
+        return;
+    }
+    store.light_dirty.insert(chunk_pos);
+    let size = CHUNK_SIZE as i32;
+    let local = IVec3::new(
+        pos.x.rem_euclid(size),
+        pos.y.rem_euclid(size),
+        pos.z.rem_euclid(size),
+    );
+    for axis in 0..3 {
+        let mut offset = IVec3::ZERO;
+        if local[axis] == 0 {
+            offset[axis] = -1;
+        } else if local[axis] == size - 1 {
+            offset[axis] = 1;
+        } else {
+            continue;
+        }
+        store.light_dirty.insert(chunk_pos + offset);
+    }
+}
+
+/// Light level after moving one step in `dir` into `block`.
+/// Full sky light travels straight down through clear blocks without dimming.
+#[inline]
+fn attenuate(channel: LightChannel, level: u8, dir: IVec3, block: BlockType) -> u8 {
+    if block.blocks_light() {
+        return 0;
+    }
+    let filter = block.light_filter();
+    if channel == LightChannel::Sky && dir == IVec3::NEG_Y && level == MAX_LIGHT && filter == 0 {
+        return MAX_LIGHT;
+    }
+    level.saturating_sub(1 + filter)
+}
+
+/// Breadth-first flood fill outward from every queued position.
+fn spread(
+    store: &mut ChunkDataStore,
+    channel: LightChannel,
+    queue: &mut VecDeque<IVec3>,
+    quiet: Option<IVec3>,
+) {
+    while let Some(pos) = queue.pop_front() {
+        let Some(level) = light_at(store, pos, channel) else {
+            continue;
+        };
+        if level <= 1 {
+            continue;
+        }
+        for dir in DIRECTIONS {
+            let neighbor = pos + dir;
+            let Some(block) = block_at(store, neighbor) else {
+                continue;
+            };
+            let Some(current) = light_at(store, neighbor, channel) else {
+                continue;
+            };
+            let next = attenuate(channel, level, dir, block);
+            if next > current {
+                set_light_at(store, neighbor, channel, next, quiet);
+                queue.push_back(neighbor);
+            }
+        }
+    }
+}
+
+/// Breadth-first removal of light that depended on the queued (position, old level) pairs.
+/// Neighbors lit by some other source are pushed onto `relight` so `spread` can refill the gap.
+fn unspread(
+    store: &mut ChunkDataStore,
+    channel: LightChannel,
+    removals: &mut VecDeque<(IVec3, u8)>,
+    relight: &mut VecDeque<IVec3>,
+) {
+    while let Some((pos, level)) = removals.pop_front() {
+        for dir in DIRECTIONS {
+            let neighbor = pos + dir;
+            let Some(current) = light_at(store, neighbor, channel) else {
+                continue;
+            };
+            if current == 0 {
+                continue;
+            }
+            let sky_column = channel == LightChannel::Sky
+                && dir == IVec3::NEG_Y
+                && level == MAX_LIGHT
+                && current == MAX_LIGHT;
+            if current < level || sky_column {
+                set_light_at(store, neighbor, channel, 0, None);
+                removals.push_back((neighbor, current));
+                // Emitters keep their own light even when the light around them is removed
+                if channel == LightChannel::Block {
+                    let emission = block_at(store, neighbor).map_or(0, |b| b.light_emission());
+                    if emission > 0 {
+                        set_light_at(store, neighbor, channel, emission, None);
+                        relight.push_back(neighbor);
+                    }
+                }
+            } else {
+                relight.push_back(neighbor);
+            }
+        }
+    }
+}
+
+/// Compute initial sky and block light for a freshly loaded chunk.
+///
+/// Sky light is seeded per column from the chunk above (or the open sky when
+/// `open_sky` is set and nothing is loaded above), then both channels flood
+/// outward. Light already present in loaded neighbors is pulled in across the
+/// borders, and light from this chunk spills out into them.
+pub fn light_new_chunk(store: &mut ChunkDataStore, chunk_pos: IVec3, open_sky: bool) {
+    let Some(chunk) = store.chunks.get(&chunk_pos) else {
+        return;
+    };
+    let size = CHUNK_SIZE as i32;
+    let base = chunk_pos * size;
+    let above = store.lights.get(&(chunk_pos + IVec3::Y));
+
+    let mut light = ChunkLight::default();
+    let mut sky_queue = VecDeque::new();
+    let mut block_queue = VecDeque::new();
+
+    for z in 0..CHUNK_SIZE {
+        for x in 0..CHUNK_SIZE {
+            let mut level = match above {
+                Some(above) => above.sky(x, 0, z),
+                None if open_sky => MAX_LIGHT,
+                None => 0,
+            };
+            for y in (0..CHUNK_SIZE).rev() {
+                let block = chunk.get(x, y, z);
+                level = attenuate(LightChannel::Sky, level, IVec3::NEG_Y, block);
+                light.set(LightChannel::Sky, x, y, z, level);
+                let world = base + IVec3::new(x as i32, y as i32, z as i32);
+                if level > 1 {
+                    sky_queue.push_back(world);
+                }
+                let emission = block.light_emission();
+                if emission > 0 {
+                    light.set(LightChannel::Block, x, y, z, emission);
+                    block_queue.push_back(world);
+                }
+            }
+        }
+    }
+    store.lights.insert(chunk_pos, light);
+
+    // Pull light in from the border cells of already-lit neighbors
+    for dir in DIRECTIONS {
+        if !store.lights.contains_key(&(chunk_pos + dir)) {
+            continue;
+        }
+        for a in 0..size {
+            for b in 0..size {
+                let local = match dir {
+                    d if d.x != 0 => IVec3::new(if d.x > 0 { size } else { -1 }, a, b),
+                    d if d.y != 0 => IVec3::new(a, if d.y > 0 { size } else { -1 }, b),
+                    d => IVec3::new(a, b, if d.z > 0 { size } else { -1 }),
+                };
+                let world = base + local;
+                sky_queue.push_back(world);
+                block_queue.push_back(world);
+            }
+        }
+    }
+
+    spread(store, LightChannel::Sky, &mut sky_queue, Some(chunk_pos));
+    spread(store, LightChannel::Block, &mut block_queue, Some(chunk_pos));
+}
+
+/// Update both light fields after the block at `pos` changed from `old` to `new`.
+/// Removes light that passed through or came from the old block, then refills
+/// from the surrounding cells and any new emitter.
+pub fn update_light(store: &mut ChunkDataStore, pos: IVec3, old: BlockType, new: BlockType) {
+    if old.blocks_light() == new.blocks_light()
+        && old.light_filter() == new.light_filter()
+        && old.light_emission() == new.light_emission()
+    {
+        return;
+    }
+
+    for channel in [LightChannel::Sky, LightChannel::Block] {
+        let Some(level) = light_at(store, pos, channel) else {
+            return;
+        };
+        let mut removals = VecDeque::new();
+        let mut relight = VecDeque::new();
+        if level > 0 {
+            set_light_at(store, pos, channel, 0, None);
+            removals.push_back((pos, level));
+        }
+        unspread(store, channel, &mut removals, &mut relight);
+
+        match channel {
+            LightChannel::Block => {
+                let emission = new.light_emission();
+                if emission > 0 {
+                    set_light_at(store, pos, channel, emission, None);
+                    relight.push_back(pos);
+                }
+            }
+            LightChannel::Sky => {
+                // Nothing loaded above means this column is open to the sky
+                if !new.blocks_light() && block_at(store, pos + IVec3::Y).is_none() {
+                    set_light_at(store, pos, channel, MAX_LIGHT, None);
+                    relight.push_back(pos);
+                }
+            }
+        }
+        if !new.blocks_light() {
+            for dir in DIRECTIONS {
+                relight.push_back(pos + dir);
+            }
+        }
+        spread(store, channel, &mut relight, None);
+    }
+}
+
+/// Sky and block light at a world position. Unlit or unloaded positions read as full sky light.
+pub fn light_levels_at(store: &ChunkDataStore, pos: IVec3) -> (u8, u8) {
+    match (light_at(store, pos, LightChannel::Sky), light_at(store, pos, LightChannel::Block)) {
+        (Some(sky), Some(block)) => (sky, block),
+        _ => (MAX_LIGHT, 0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn store_with(chunks: &[IVec3], open_sky: bool) -> ChunkDataStore {
+        let mut store = ChunkDataStore::default();
+        for &pos in chunks {
+            store.chunks.insert(pos, Chunk::default());
+            light_new_chunk(&mut store, pos, open_sky);
+        }
+        store
+    }
+
+    fn place(store: &mut ChunkDataStore, pos: IVec3, block: BlockType) {
+        let (chunk_pos, _) = split_pos(pos);
+        let size = CHUNK_SIZE as i32;
+        let chunk = store.chunks.get_mut(&chunk_pos).unwrap();
+        let (x, y, z) = (
+            pos.x.rem_euclid(size) as usize,
+            pos.y.rem_euclid(size) as usize,
+            pos.z.rem_euclid(size) as usize,
+        );
+        let old = chunk.get(x, y, z);
+        chunk.set(x, y, z, block);
+        update_light(store, pos, old, block);
+    }
+
+    #[test]
+    fn open_sky_lights_empty_chunk_fully() {
+        let store = store_with(&[IVec3::ZERO], true);
+        assert_eq!(light_levels_at(&store, IVec3::new(3, 0, 12)), (MAX_LIGHT, 0));
+    }
+
+    #[test]
+    fn torch_light_falls_off_by_one_per_block() {
+        let mut store = store_with(&[IVec3::ZERO], false);
+        place(&mut store, IVec3::new(8, 8, 8), BlockType::Torch);
+        assert_eq!(light_levels_at(&store, IVec3::new(8, 8, 8)).1, 14);
+        assert_eq!(light_levels_at(&store, IVec3::new(9, 8, 8)).1, 13);
+        assert_eq!(light_levels_at(&store, IVec3::new(8, 8, 12)).1, 10);
+        assert_eq!(light_levels_at(&store, IVec3::new(10, 10, 8)).1, 10);
+    }
+
+    #[test]
+    fn removing_torch_clears_block_light() {
+        let mut store = store_with(&[IVec3::ZERO], false);
+        place(&mut store, IVec3::new(8, 8, 8), BlockType::Torch);
+        place(&mut store, IVec3::new(8, 8, 8), BlockType::Air);
+        for pos in [IVec3::new(8, 8, 8), IVec3::new(9, 8, 8), IVec3::new(2, 8, 8)] {
+            assert_eq!(light_levels_at(&store, pos).1, 0);
+        }
+    }
+
+    #[test]
+    fn opaque_block_shadows_sky_column() {
+        let mut store = store_with(&[IVec3::ZERO], true);
+        place(&mut store, IVec3::new(8, 15, 8), BlockType::Stone);
+        assert_eq!(light_levels_at(&store, IVec3::new(8, 15, 8)).0, 0);
+        // Below the block, light only arrives sideways
+        assert_eq!(light_levels_at(&store, IVec3::new(8, 14, 8)).0, MAX_LIGHT - 1);
+        assert_eq!(light_levels_at(&store, IVec3::new(8, 0, 8)).0, MAX_LIGHT - 1);
+
+        place(&mut store, IVec3::new(8, 15, 8), BlockType::Air);
+        assert_eq!(light_levels_at(&store, IVec3::new(8, 0, 8)).0, MAX_LIGHT);
+    }
+
+    #[test]
+    fn sealed_room_is_dark() {
+        let mut store = store_with(&[IVec3::ZERO], true);
+        for x in 0..CHUNK_SIZE as i32 {
+            for z in 0..CHUNK_SIZE as i32 {
+                place(&mut store, IVec3::new(x, 10, z), BlockType::Stone);
+            }
+        }
+        assert_eq!(light_levels_at(&store, IVec3::new(8, 5, 8)).0, 0);
+        assert_eq!(light_levels_at(&store, IVec3::new(8, 11, 8)).0, MAX_LIGHT);
+    }
+
+    #[test]
+    fn block_light_crosses_chunk_border() {
+        let mut store = store_with(&[IVec3::ZERO, IVec3::X], false);
+        store.light_dirty.clear();
+        place(&mut store, IVec3::new(15, 8, 8), BlockType::Torch);
+        assert_eq!(light_levels_at(&store, IVec3::new(16, 8, 8)).1, 13);
+        assert_eq!(light_levels_at(&store, IVec3::new(20, 8, 8)).1, 9);
+        assert!(store.light_dirty.contains(&IVec3::X));
+    }
+
+    #[test]
+    fn new_chunk_pulls_light_from_neighbors() {
+        let mut store = store_with(&[IVec3::ZERO], false);
+        place(&mut store, IVec3::new(15, 8, 8), BlockType::Torch);
+        store.chunks.insert(IVec3::X, Chunk::default());
+        light_new_chunk(&mut store, IVec3::X, false);
+        assert_eq!(light_levels_at(&store, IVec3::new(16, 8, 8)).1, 13);
+    }
+
+    #[test]
+    fn sky_light_dims_through_leaves_and_water() {
+        let mut store = store_with(&[IVec3::ZERO], true);
+        for x in 0..CHUNK_SIZE as i32 {
+            for z in 0..CHUNK_SIZE as i32 {
+                place(&mut store, IVec3::new(x, 12, z), BlockType::OakLeaves);
+            }
+        }
+        assert_eq!(light_levels_at(&store, IVec3::new(8, 12, 8)).0, MAX_LIGHT - 2);
+        assert_eq!(light_levels_at(&store, IVec3::new(8, 11, 8)).0, MAX_LIGHT - 3);
+    }
+}
@@ -0,0 +1,231 @@
+use bevy::prelude::*;
+use bevy::mesh::{Indices, PrimitiveTopology};
+use bevy::asset::RenderAssetUsages;
+
+use crate::world::chunk::CHUNK_SIZE;
+use crate::world::generation::sample_surface;
+
+/// Chunk columns along each side of a far-terrain tile.
+pub const TILE_CHUNKS: i32 = 4;
+/// Blocks between surface samples. Divides the chunk size, so each chunk column is a
+/// whole block of cells and can be cut out of its tile exactly.
+const STEP: i32 = 4;
+/// Cells along each side of a chunk column.
+const CHUNK_CELLS: usize = CHUNK_SIZE / STEP as usize;
+/// Cells along each side of a tile.
+const TILE_CELLS: usize = TILE_CHUNKS as usize * CHUNK_CELLS;
+/// Samples along each side of a tile, one per cell corner.
+const TILE_SAMPLES: usize = TILE_CELLS + 1;
+/// How far skirts hang below the edges they run along. They hide the gap between the
+/// coarse surface and the full-detail chunks next to it.
+const SKIRT_DEPTH: f32 = 12.0;
+
+/// Surface heights and colors of a tile, sampled at its cell corners row by row along +Z.
+pub struct TileSamples {
+    heights: Vec<i32>,
+    colors: Vec<[f32; 4]>,
+}
+
+impl TileSamples {
+    fn height(&self, x: usize, z: usize) -> i32 {
+        self.heights[z * TILE_SAMPLES + x]
+    }
+
+    fn color(&self, x: usize, z: usize) -> [f32; 4] {
+        self.colors[z * TILE_SAMPLES + x]
+    }
+
+    /// Lowest and highest sampled surface block in the chunk column at (cx, cz) of the tile.
+    pub fn column_range(&self, cx: usize, cz: usize) -> (i32, i32) {
+        let mut range = (i32::MAX, i32::MIN);
+        for z in cz * CHUNK_CELLS..=(cz + 1) * CHUNK_CELLS {
+            for x in cx * CHUNK_CELLS..=(cx + 1) * CHUNK_CELLS {
+                let height = self.height(x, z);
+                range = (range.0.min(height), range.1.max(height));
+            }
+        }
+        range
+    }
+}
+
+/// The tile a chunk column belongs to.
+pub fn tile_of(chunk_x: i32, chunk_z: i32) -> IVec2 {
+    IVec2::new(chunk_x.div_euclid(TILE_CHUNKS), chunk_z.div_euclid(TILE_CHUNKS))
+}
+
+/// World position of a tile's north-west corner, where its mesh is placed.
+pub fn tile_origin(tile: IVec2) -> Vec3 {
+    let size = TILE_CHUNKS * CHUNK_SIZE as i32;
+    Vec3::new((tile.x * size) as f32, 0.0, (tile.y * size) as f32)
+}
+
+/// Bit of a `build_tile_mesh` mask for the chunk column at (cx, cz) of the tile.
+pub fn column_bit(cx: i32, cz: i32) -> u16 {
+    1 << (cz * TILE_CHUNKS + cx)
+}
+
+/// Sample the generated surface over a tile. Slow enough to run on the async pool.
+pub fn sample_tile(tile: IVec2) -> TileSamples {
+    let origin = tile_origin(tile).as_ivec3();
+    let mut heights = Vec::with_capacity(TILE_SAMPLES * TILE_SAMPLES);
+    let mut colors = Vec::with_capacity(TILE_SAMPLES * TILE_SAMPLES);
+    for z in 0..TILE_SAMPLES as i32 {
+        for x in 0..TILE_SAMPLES as i32 {
+            let (height, block) = sample_surface(origin.x + x * STEP, origin.z + z * STEP);
+            let [r, g, b, a] = block.map_color();
+            let color = Color::srgba_u8(r, g, b, a).to_linear();
+            heights.push(height);
+            colors.push([color.red, color.green, color.blue, color.alpha]);
+        }
+    }
+    TileSamples { heights, colors }
+}
+
+/// Vertex buffers of a tile mesh being built.
+#[derive(Default)]
+struct TileMesh {
+    positions: Vec<[f32; 3]>,
+    normals: Vec<[f32; 3]>,
+    colors: Vec<[f32; 4]>,
+    indices: Vec<u32>,
+}
+
+impl TileMesh {
+    /// Add a flat-shaded quad, wound to face along `normal`.
+    fn push_quad(&mut self, corners: [Vec3; 4], normal: Vec3, color: [f32; 4]) {
+        let base = self.positions.len() as u32;
+        for corner in corners {
+            self.positions.push(corner.to_array());
+            self.normals.push(normal.to_array());
+            self.colors.push(color);
+        }
+        let facing = (corners[1] - corners[0]).cross(corners[2] - corners[0]).dot(normal) > 0.0;
+        let order = if facing { [0, 1, 2, 0, 2, 3] } else { [0, 2, 1, 0, 3, 2] };
+        self.indices.extend(order.map(|i| base + i));
+    }
+}
+
+/// Mesh of a tile's surface in tile-local coordinates, leaving out the chunk columns
+/// set in `covered` (drawn by full chunks instead). Each cell is a quad through its four
+/// corner samples, colored by its north-west one; skirts hang from every edge that
+/// borders a left-out column or another tile. `None` when every column is covered.
+pub fn build_tile_mesh(samples: &TileSamples, covered: u16) -> Option<Mesh> {
+    let drawn = |x: i32, z: i32| {
+        let cells = 0..TILE_CELLS as i32;
+        let (cx, cz) = (x / CHUNK_CELLS as i32, z / CHUNK_CELLS as i32);
+        cells.contains(&x) && cells.contains(&z) && (covered & column_bit(cx, cz)) == 0
+    };
+    // Top of the surface block at a sample
+    let corner = |x: usize, z: usize| {
+        Vec3::new((x as i32 * STEP) as f32, (samples.height(x, z) + 1) as f32, (z as i32 * STEP) as f32)
+    };
+
+    let mut mesh = TileMesh::default();
+    for z in 0..TILE_CELLS {
+        for x in 0..TILE_CELLS {
+            if !drawn(x as i32, z as i32) {
+                continue;
+            }
+            let color = samples.color(x, z);
+            let top = [corner(x, z), corner(x + 1, z), corner(x + 1, z + 1), corner(x, z + 1)];
+            let slope_x = (top[1].y - top[0].y + top[2].y - top[3].y) / (2 * STEP) as f32;
+            let slope_z = (top[3].y - top[0].y + top[2].y - top[1].y) / (2 * STEP) as f32;
+            mesh.push_quad(top, Vec3::new(-slope_x, 1.0, -slope_z).normalize(), color);
+
+            // Edges as (outward direction, the two top corners along it)
+            let edges = [
+                (IVec2::NEG_Y, top[0], top[1]),
+                (IVec2::X, top[1], top[2]),
+                (IVec2::Y, top[2], top[3]),
+                (IVec2::NEG_X, top[3], top[0]),
+            ];
+            for (dir, a, b) in edges {
+                if drawn(x as i32 + dir.x, z as i32 + dir.y) {
+                    continue;
+                }
+                let bottom = a.y.min(b.y) - SKIRT_DEPTH;
+                let skirt = [a, b, Vec3::new(b.x, bottom, b.z), Vec3::new(a.x, bottom, a.z)];
+                mesh.push_quad(skirt, Vec3::new(dir.x as f32, 0.0, dir.y as f32), color);
+            }
+        }
+    }
+
+    if mesh.indices.is_empty() {
+        return None;
+    }
+    Some(
+        Mesh::new(PrimitiveTopology::TriangleList, RenderAssetUsages::default())
+            .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, mesh.positions)
+            .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, mesh.normals)
+            .with_inserted_attribute(Mesh::ATTRIBUTE_COLOR, mesh.colors)
+            .with_inserted_indices(Indices::U32(mesh.indices)),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::mesh::VertexAttributeValues;
+
+    fn flat_tile(height: i32) -> TileSamples {
+        TileSamples {
+            heights: vec![height; TILE_SAMPLES * TILE_SAMPLES],
+            colors: vec![[1.0; 4]; TILE_SAMPLES * TILE_SAMPLES],
+        }
+    }
+
+    fn positions(mesh: &Mesh) -> Vec<Vec3> {
+        match mesh.attribute(Mesh::ATTRIBUTE_POSITION) {
+            Some(VertexAttributeValues::Float32x3(values)) => values.iter().map(|&p| Vec3::from_array(p)).collect(),
+            _ => panic!("tile mesh should have positions"),
+        }
+    }
+
+    #[test]
+    fn tiles_cover_negative_chunks() {
+        assert_eq!(tile_of(0, 3), IVec2::new(0, 0));
+        assert_eq!(tile_of(-1, -4), IVec2::new(-1, -1));
+        assert_eq!(tile_of(-5, 4), IVec2::new(-2, 1));
+        assert_eq!(tile_origin(IVec2::new(-1, 2)), Vec3::new(-64.0, 0.0, 128.0));
+    }
+
+    #[test]
+    fn samples_follow_the_generated_surface() {
+        let tile = IVec2::new(-3, 2);
+        let samples = sample_tile(tile);
+        let origin = tile_origin(tile).as_ivec3();
+        for (x, z) in [(0, 0), (5, 9), (TILE_CELLS, TILE_CELLS)] {
+            let (height, _) = sample_surface(origin.x + x as i32 * STEP, origin.z + z as i32 * STEP);
+            assert_eq!(samples.height(x, z), height);
+        }
+    }
+
+    #[test]
+    fn covered_columns_are_left_out() {
+        let samples = flat_tile(70);
+        assert!(build_tile_mesh(&samples, u16::MAX).is_none());
+
+        let mesh = build_tile_mesh(&samples, column_bit(1, 2)).unwrap();
+        let column_min = Vec3::new(16.0, 0.0, 32.0);
+        let column_max = Vec3::new(32.0, 0.0, 48.0);
+        let inside = |p: &Vec3| p.x > column_min.x && p.x < column_max.x && p.z > column_min.z && p.z < column_max.z;
+        assert!(!positions(&mesh).iter().any(inside));
+
+        let full = build_tile_mesh(&samples, 0).unwrap();
+        assert!(positions(&full).iter().any(inside));
+    }
+
+    #[test]
+    fn skirts_hang_around_covered_columns() {
+        let samples = flat_tile(70);
+        let mesh = build_tile_mesh(&samples, column_bit(0, 0)).unwrap();
+        let skirt_bottom = 71.0 - SKIRT_DEPTH;
+        // Along the covered column's east side, and along the tile's north edge
+        for z in [0.0, 8.0, 16.0] {
+            assert!(positions(&mesh).contains(&Vec3::new(16.0, skirt_bottom, z)));
+        }
+        assert!(positions(&mesh).contains(&Vec3::new(40.0, skirt_bottom, 0.0)));
+        // But not between two drawn cells
+        assert!(!positions(&mesh).contains(&Vec3::new(40.0, skirt_bottom, 40.0)));
+    }
+}
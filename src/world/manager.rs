@@ -1,24 +1,54 @@
 use bevy::prelude::*;
+use bevy::ecs::message::MessageReader;
 use bevy::tasks::{AsyncComputeTaskPool, Task, block_on, poll_once};
 use std::collections::{HashMap, HashSet};
-use crate::block::BlockType;
+use crate::block::{BlockState, BlockType};
+use crate::block::state::WHEAT_MAX_AGE;
+use crate::lighting::weather::{is_rained_on, Precipitation, WeatherState};
+use crate::resource_pack::{AtlasRebuilt, BlockAtlas};
+use crate::resource_pack::pack::animation_cycle;
 
 use super::chunk::{Chunk, CHUNK_SIZE};
-use super::coordinates::world_to_chunk_pos;
+use super::colormap::ChunkTints;
+use super::coordinates::{world_to_chunk_pos, world_to_local_pos};
 use super::generation::{generate_chunk, set_world_seed};
-use super::material::{AtlasTileMaterial, ChunkMaterialType};
-use super::meshing::{build_chunk_mesh, NeighborChunks};
-use super::WorldSeed;
+use super::lod::{build_tile_mesh, column_bit, sample_tile, tile_of, tile_origin, TileSamples, TILE_CHUNKS};
+use super::fluid::{fluid_tick, schedule_around, state_at, FluidTicks, FLUID_TICK_SECONDS, MAX_FLUID_UPDATES_PER_TICK};
+use super::light::{light_new_chunk, update_light, ChunkLight};
+use super::material::{
+    AtlasTileMaterial, ChunkMaterialType, FarTerrainMaterialType, FarTerrainShading, TileAnimations, WaterMaterialType,
+    WaterTintMaterial,
+};
+use super::meshing::{build_chunk_mesh, ChunkMeshes, NeighborChunks, NeighborLights, NEIGHBOR_OFFSETS};
+use super::visibility::{compute_visibility, visible_chunks, ChunkVisibility};
+use super::{WorldHeight, WorldSeed};
 
 const RENDER_DISTANCE: i32 = 16;
 const DESPAWN_DISTANCE: i32 = 18;
+/// Chunk layers kept loaded below the camera. Deeper chunks only load where sky light
+/// reaches down into them (ravines, ocean floors, open pits); columns above the camera
+/// are always loaded, since sky light is seeded from the top down.
+const DEPTH_RENDER_DISTANCE: i32 = 4;
+const DEPTH_DESPAWN_DISTANCE: i32 = 6;
+/// Most finished chunk loads inserted into the world per frame (lighting runs on the main thread).
 const MAX_LOADS_PER_FRAME: usize = 8;
-const WORLD_HEIGHT_CHUNKS: i32 = 16;
+/// Most chunk loads in flight on the async pool at once.
+const MAX_PENDING_LOADS: usize = 64;
+/// Far terrain is drawn out to this many chunks from the camera, beyond the loaded area.
+/// Kept within the camera's far plane.
+const FAR_TERRAIN_DISTANCE: i32 = 40;
+const FAR_TERRAIN_DROP_DISTANCE: i32 = 44;
+/// Most far-terrain tiles sampled on the async pool at once.
+const MAX_PENDING_TILES: usize = 8;
 
-/// Tracks which chunk positions have spawned entities.
+/// Tracks which chunk positions have spawned entities or are still loading.
 #[derive(Resource, Default)]
 pub struct ChunkManager {
     pub loaded: HashMap<IVec3, Entity>,
+    /// In-flight disk load or generation tasks. Dropping a task cancels it.
+    pub pending: HashMap<IVec3, Task<Chunk>>,
+    /// Finished loads waiting to be inserted into the world.
+    pub ready: HashMap<IVec3, Chunk>,
 }
 
 /// Stores generated chunk data for neighbor lookups and future use.
@@ -26,19 +56,83 @@ pub struct ChunkManager {
 pub struct ChunkDataStore {
     pub chunks: HashMap<IVec3, Chunk>,
     pub modified: HashSet<IVec3>,
+    /// Sky and block light for every loaded chunk (recomputed on load, never saved).
+    pub lights: HashMap<IVec3, ChunkLight>,
+    /// Chunks whose light changed since the last remesh pass.
+    pub light_dirty: HashSet<IVec3>,
+    /// Which faces of every meshed chunk see each other, for cave culling (recomputed
+    /// with the mesh, never saved).
+    pub visibility: HashMap<IVec3, ChunkVisibility>,
+    /// Pending fluid updates, queued whenever a block next to water changes.
+    pub fluid_ticks: FluidTicks,
+    /// Positions to check for unsupported sand or gravel, recorded whenever a block changes.
+    pub gravity_checks: HashSet<IVec3>,
 }
 
 /// Shared material handle for all chunk meshes (extended with atlas tiling shader).
 #[derive(Resource)]
 pub struct ChunkMaterial(pub Handle<ChunkMaterialType>);
 
+/// Shared material handle for the translucent fluid meshes of all chunks.
+#[derive(Resource)]
+pub struct WaterMaterial(pub Handle<WaterMaterialType>);
+
+/// Coarse surface tiles drawn around and beyond the loaded chunks. Inside the loaded
+/// area each tile leaves out the chunk columns whose full chunks are meshed.
+#[derive(Resource, Default)]
+pub struct FarTerrain {
+    pub tiles: HashMap<IVec2, FarTile>,
+    /// In-flight surface sampling tasks. Dropping a task cancels it.
+    pub pending: HashMap<IVec2, Task<TileSamples>>,
+}
+
+/// A sampled far-terrain tile and the entity drawing it.
+pub struct FarTile {
+    samples: TileSamples,
+    /// Chunk columns left out of the current mesh; `None` until the first mesh is built.
+    covered: Option<u16>,
+    entity: Option<Entity>,
+}
+
+/// Shared material handle for all far-terrain tiles.
+#[derive(Resource)]
+pub struct FarTerrainMaterial(pub Handle<FarTerrainMaterialType>);
+
+/// Marker for a far-terrain tile entity.
+#[derive(Component)]
+pub struct FarTerrainTile;
+
+/// Hides chunks the camera can't see into, such as caves under the surface, by walking
+/// the chunks' visibility from the camera's chunk.
+#[derive(Resource)]
+pub struct CaveCulling {
+    pub enabled: bool,
+    /// Set when a chunk's visibility changes, so the walk is redone.
+    pub dirty: bool,
+    /// The camera's chunk at the last walk.
+    camera_chunk: Option<IVec3>,
+    /// Chunk meshes drawn after culling, and before (every meshed chunk).
+    pub drawn: usize,
+    pub meshed: usize,
+}
+
+impl Default for CaveCulling {
+    fn default() -> Self {
+        Self { enabled: true, dirty: true, camera_chunk: None, drawn: 0, meshed: 0 }
+    }
+}
+
 /// Marker: this entity needs its mesh built.
 #[derive(Component)]
 pub struct NeedsMesh;
 
-/// Holds an in-flight async mesh task.
+/// Holds an in-flight async mesh task, which also works out the chunk's visibility.
+#[derive(Component)]
+pub struct MeshTask(Task<(ChunkMeshes, ChunkVisibility)>);
+
+/// The child entity drawing a chunk's translucent fluid mesh, if it has any fluid.
 #[derive(Component)]
-pub struct MeshTask(Task<Mesh>);
+pub struct FluidMesh(Entity);
 
 /// Marker for the chunk entity's position in chunk coordinates.
 #[derive(Component)]
@@ -67,67 +161,133 @@ pub const CROP_GROW_MIN: f32 = 20.0;
 /// Maximum time (seconds) before a crop stage advances.
 pub const CROP_GROW_MAX: f32 = 40.0;
 
+/// Countdown for a tracked crop or fire. The precipitation over its column is looked up
+/// once when tracking starts, since a world's climate never changes.
+#[derive(Clone, Copy, Debug)]
+pub struct BlockTimer {
+    pub remaining: f32,
+    pub precipitation: Precipitation,
+}
+
+impl BlockTimer {
+    pub fn new(pos: IVec3, remaining: f32) -> Self {
+        Self { remaining, precipitation: Precipitation::at(pos.as_vec3()) }
+    }
+
+    /// Count down `dt` seconds, `rain_multiplier` times faster while rained on.
+    /// Returns whether the timer has run out.
+    fn tick(&mut self, dt: f32, rained_on: bool, rain_multiplier: f32) -> bool {
+        self.remaining -= if rained_on { dt * rain_multiplier } else { dt };
+        self.remaining <= 0.0
+    }
+}
+
 /// Tracks planted crops and their growth timers.
 #[derive(Resource, Default)]
 pub struct CropTracker {
-    /// Maps world position -> remaining time until next growth stage.
-    pub crops: HashMap<IVec3, f32>,
+    /// Maps world position -> time until next growth stage.
+    pub crops: HashMap<IVec3, BlockTimer>,
     /// Chunk positions already scanned for crops (prevents re-scanning).
     pub scanned_chunks: HashSet<IVec3>,
 }
 
+/// Crops grow this many times faster while it rains on them.
+const RAIN_GROWTH_MULTIPLIER: f32 = 1.5;
+
+/// Minimum time (seconds) a fire burns before going out.
+const FIRE_BURN_MIN: f32 = 8.0;
+/// Maximum time (seconds) a fire burns before going out.
+const FIRE_BURN_MAX: f32 = 20.0;
+/// Fires burn out this many times faster while it rains on them.
+const RAIN_FIRE_MULTIPLIER: f32 = 4.0;
+
+/// Tracks burning fire blocks and how long each has left.
+#[derive(Resource, Default)]
+pub struct FireTracker {
+    /// Maps world position -> time until the fire goes out.
+    pub fires: HashMap<IVec3, BlockTimer>,
+}
+
 /// One-time setup: camera, light, and shared material.
 pub fn setup_world(
     mut commands: Commands,
-    asset_server: Res<AssetServer>,
+    atlas: Res<BlockAtlas>,
     mut materials: ResMut<Assets<ChunkMaterialType>>,
+    mut water_materials: ResMut<Assets<WaterMaterialType>>,
+    mut far_materials: ResMut<Assets<FarTerrainMaterialType>>,
     world_seed: Res<WorldSeed>,
 ) {
     // Initialize terrain noise with the world seed
     set_world_seed(world_seed.0);
 
     // Shared chunk material with atlas tiling shader for greedy meshing
-    let texture: Handle<Image> = asset_server.load("textures/atlas.png");
+    let texture = atlas.image.clone();
     let material = materials.add(bevy::pbr::ExtendedMaterial {
         base: StandardMaterial {
-            base_color_texture: Some(texture),
+            base_color_texture: Some(texture.clone()),
             perceptual_roughness: 1.0,
             reflectance: 0.1,
             alpha_mode: AlphaMode::Mask(0.5),
             ..default()
         },
-        extension: AtlasTileMaterial {},
+        extension: AtlasTileMaterial { animations: TileAnimations::new(&atlas.animations), ..default() },
     });
     commands.insert_resource(ChunkMaterial(material));
+
+    // Fluids are blended over the terrain and seen from both sides, so the
+    // surface still shows from underwater
+    let water_material = water_materials.add(bevy::pbr::ExtendedMaterial {
+        base: StandardMaterial {
+            base_color_texture: Some(texture),
+            perceptual_roughness: 1.0,
+            reflectance: 0.1,
+            alpha_mode: AlphaMode::Blend,
+            cull_mode: None,
+            double_sided: true,
+            ..default()
+        },
+        extension: WaterTintMaterial { animations: TileAnimations::new(&atlas.animations), ..default() },
+    });
+    commands.insert_resource(WaterMaterial(water_material));
+
+    // Far terrain takes its colors from the vertices, no texture
+    let far_material = far_materials.add(bevy::pbr::ExtendedMaterial {
+        base: StandardMaterial {
+            perceptual_roughness: 1.0,
+            reflectance: 0.1,
+            ..default()
+        },
+        extension: FarTerrainShading::default(),
+    });
+    commands.insert_resource(FarTerrainMaterial(far_material));
+    commands.init_resource::<FarTerrain>();
     commands.init_resource::<ChunkManager>();
     commands.init_resource::<ChunkDataStore>();
     commands.insert_resource(crate::save::persistence::load_saplings());
     commands.insert_resource(crate::save::persistence::load_crops());
 }
 
-/// Load/unload chunks based on camera position.
+/// Unload distant chunks and queue async disk loads / generation for chunks in range.
 pub fn update_chunk_loading(
     mut manager: ResMut<ChunkManager>,
     mut store: ResMut<ChunkDataStore>,
     mut tracker: ResMut<SaplingTracker>,
     mut crop_tracker: ResMut<CropTracker>,
-    camera: Query<&Transform, With<Camera3d>>,
+    camera: Query<&GlobalTransform, With<Camera3d>>,
+    height: Res<WorldHeight>,
     mut commands: Commands,
 ) {
     let Ok(cam_transform) = camera.single() else {
         return;
     };
-    let cam_chunk = world_to_chunk_pos(cam_transform.translation);
+    let cam_chunk = camera_chunk(cam_transform.translation(), *height);
+    drop_stale_loads(&mut manager, &store, cam_chunk);
 
     // Despawn distant chunks
     let to_despawn: Vec<IVec3> = manager
         .loaded
         .keys()
-        .filter(|pos| {
-            let dx = (pos.x - cam_chunk.x).abs();
-            let dz = (pos.z - cam_chunk.z).abs();
-            dx > DESPAWN_DISTANCE || dz > DESPAWN_DISTANCE
-        })
+        .filter(|&&pos| out_of_range(pos, cam_chunk, &store))
         .copied()
         .collect();
 
@@ -142,46 +302,138 @@ pub fn update_chunk_loading(
             store.modified.remove(&pos);
         }
         store.chunks.remove(&pos);
+        store.lights.remove(&pos);
+        store.visibility.remove(&pos);
         // Keep sapling/crop tracker entries in memory — they're tiny (IVec3 + f32 each).
         // Only mark as unscanned so re-scan on reload doesn't create duplicates via or_insert.
         tracker.scanned_chunks.remove(&pos);
         crop_tracker.scanned_chunks.remove(&pos);
     }
 
+    let in_flight = manager.pending.len() + manager.ready.len();
+    if in_flight >= MAX_PENDING_LOADS {
+        return;
+    }
+
     // Collect positions to load, sorted by distance (closest first)
-    let mut to_load: Vec<(IVec3, i32)> = Vec::new();
+    let mut to_load: Vec<(IVec3, (i32, i32))> = Vec::new();
     for x in (cam_chunk.x - RENDER_DISTANCE)..=(cam_chunk.x + RENDER_DISTANCE) {
         for z in (cam_chunk.z - RENDER_DISTANCE)..=(cam_chunk.z + RENDER_DISTANCE) {
-            let dx = (x - cam_chunk.x).abs();
-            let dz = (z - cam_chunk.z).abs();
-            if dx > RENDER_DISTANCE || dz > RENDER_DISTANCE {
-                continue;
-            }
-            for y in 0..WORLD_HEIGHT_CHUNKS {
+            for y in height.min_chunk_y()..height.max_chunk_y() {
                 let pos = IVec3::new(x, y, z);
-                if !manager.loaded.contains_key(&pos) {
-                    let dist_sq = dx * dx + dz * dz;
-                    to_load.push((pos, dist_sq));
+                let wanted = cam_chunk.y - y <= DEPTH_RENDER_DISTANCE || sky_reaches_below(&store, pos + IVec3::Y);
+                if wanted
+                    && !manager.loaded.contains_key(&pos)
+                    && !manager.pending.contains_key(&pos)
+                    && !manager.ready.contains_key(&pos)
+                {
+                    to_load.push((pos, load_priority(pos, cam_chunk)));
                 }
             }
         }
     }
-    to_load.sort_by_key(|&(_, d)| d);
+    to_load.sort_by_key(|&(_, priority)| priority);
+
+    // Disk reads and generation run on the async pool; results come back in apply_loaded_chunks
+    let task_pool = AsyncComputeTaskPool::get();
+    let height = *height;
+    for (pos, _) in to_load.into_iter().take(MAX_PENDING_LOADS - in_flight) {
+        let task = task_pool.spawn(async move {
+            crate::save::persistence::load_chunk(pos).unwrap_or_else(|| generate_chunk(pos, height))
+        });
+        manager.pending.insert(pos, task);
+    }
+}
+
+/// The camera's chunk, with its layer clamped into the world so flying above the build
+/// limit or falling into the void still measures depth from a real layer.
+fn camera_chunk(translation: Vec3, height: WorldHeight) -> IVec3 {
+    let chunk = world_to_chunk_pos(translation);
+    IVec3::new(chunk.x, chunk.y.clamp(height.min_chunk_y(), height.max_chunk_y() - 1), chunk.z)
+}
+
+/// Whether a chunk is far enough from the camera to be unloaded.
+fn out_of_range(pos: IVec3, cam_chunk: IVec3, store: &ChunkDataStore) -> bool {
+    let dx = (pos.x - cam_chunk.x).abs();
+    let dz = (pos.z - cam_chunk.z).abs();
+    let depth = cam_chunk.y - pos.y;
+    dx > DESPAWN_DISTANCE
+        || dz > DESPAWN_DISTANCE
+        || (depth > DEPTH_DESPAWN_DISTANCE && !sky_reaches_below(store, pos + IVec3::Y))
+}
+
+/// Cancel loads the camera has moved away from (dropping a task cancels it) and discard
+/// finished ones that were never inserted.
+fn drop_stale_loads(manager: &mut ChunkManager, store: &ChunkDataStore, cam_chunk: IVec3) {
+    manager.pending.retain(|&pos, _| !out_of_range(pos, cam_chunk, store));
+    manager.ready.retain(|&pos, _| !out_of_range(pos, cam_chunk, store));
+}
+
+/// Whether any sky light reaches the bottom layer of the loaded chunk at `pos`, so the
+/// open surface may continue into the chunk below it.
+fn sky_reaches_below(store: &ChunkDataStore, pos: IVec3) -> bool {
+    let Some(light) = store.lights.get(&pos) else {
+        return false;
+    };
+    (0..CHUNK_SIZE).any(|z| (0..CHUNK_SIZE).any(|x| light.sky(x, 0, z) > 0))
+}
+
+/// Load order: nearest first by 3D distance, counting only depth below the camera, and
+/// top-down among equals. Every chunk thus comes after the one above it, which sky light
+/// needs before the chunk can be lit.
+fn load_priority(pos: IVec3, cam_chunk: IVec3) -> (i32, i32) {
+    let dx = pos.x - cam_chunk.x;
+    let dz = pos.z - cam_chunk.z;
+    let depth = (cam_chunk.y - pos.y).max(0);
+    (dx * dx + dz * dz + depth * depth, -pos.y)
+}
+
+/// Collect finished load tasks and insert up to MAX_LOADS_PER_FRAME chunks, highest priority first.
+pub fn apply_loaded_chunks(
+    mut manager: ResMut<ChunkManager>,
+    mut store: ResMut<ChunkDataStore>,
+    mut tracker: ResMut<SaplingTracker>,
+    mut crop_tracker: ResMut<CropTracker>,
+    mut fires: ResMut<FireTracker>,
+    camera: Query<&GlobalTransform, With<Camera3d>>,
+    height: Res<WorldHeight>,
+    mut commands: Commands,
+) {
+    let Ok(cam_transform) = camera.single() else {
+        return;
+    };
+    let cam_chunk = camera_chunk(cam_transform.translation(), *height);
+
+    let ChunkManager { pending, ready, .. } = &mut *manager;
+    pending.retain(|&pos, task| match block_on(poll_once(task)) {
+        Some(chunk) => {
+            ready.insert(pos, chunk);
+            false
+        }
+        None => true,
+    });
+    if manager.ready.is_empty() {
+        return;
+    }
+
+    let mut candidates: Vec<IVec3> = manager.ready.keys().copied().collect();
+    candidates.sort_by_key(|&pos| load_priority(pos, cam_chunk));
 
-    // Spawn up to MAX_LOADS_PER_FRAME chunks
     let mut loaded = 0;
-    for (pos, _) in to_load {
+    for pos in candidates {
         if loaded >= MAX_LOADS_PER_FRAME {
             break;
         }
-        if manager.loaded.contains_key(&pos) {
+        if !can_apply(pos, &store, *height) {
             continue;
         }
+        let top = pos.y == height.max_chunk_y() - 1;
+        let Some(chunk) = manager.ready.remove(&pos) else {
+            continue;
+        };
 
-        // Try loading from disk first, otherwise generate
-        let chunk = crate::save::persistence::load_chunk(pos)
-            .unwrap_or_else(|| generate_chunk(pos));
         store.chunks.insert(pos, chunk);
+        light_new_chunk(&mut store, pos, top);
 
         // Spawn entity with NeedsMesh marker
         let world_pos = Vec3::new(
@@ -199,61 +451,133 @@ pub fn update_chunk_loading(
             .id();
         manager.loaded.insert(pos, entity);
 
-        // Scan newly loaded chunk for saplings and crops
-        if !tracker.scanned_chunks.contains(&pos) {
-            tracker.scanned_chunks.insert(pos);
-            if let Some(chunk) = store.chunks.get(&pos) {
-                let size = CHUNK_SIZE as i32;
-                let base_x = pos.x * size;
-                let base_y = pos.y * size;
-                let base_z = pos.z * size;
-                for y in 0..CHUNK_SIZE {
-                    for z in 0..CHUNK_SIZE {
-                        for x in 0..CHUNK_SIZE {
-                            let block = chunk.get(x, y, z);
-                            if block == BlockType::OakSapling || block == BlockType::BirchSapling {
-                                let world_pos = IVec3::new(
-                                    base_x + x as i32,
-                                    base_y + y as i32,
-                                    base_z + z as i32,
-                                );
-                                tracker.saplings.entry(world_pos).or_insert_with(|| {
-                                    SAPLING_GROW_MIN + rand::random::<f32>() * (SAPLING_GROW_MAX - SAPLING_GROW_MIN)
-                                });
-                            }
-                        }
+        if let Some(chunk) = store.chunks.get(&pos) {
+            scan_growables(pos, chunk, &mut tracker, &mut crop_tracker);
+            scan_fires(pos, chunk, &mut fires);
+        }
+
+        loaded += 1;
+    }
+}
+
+/// Sky light is seeded from the chunk above, so a finished load waits until that one is
+/// in, unless it is on the top layer.
+fn can_apply(pos: IVec3, store: &ChunkDataStore, height: WorldHeight) -> bool {
+    pos.y == height.max_chunk_y() - 1 || store.chunks.contains_key(&(pos + IVec3::Y))
+}
+
+/// Register saplings and unripe crops in a newly loaded chunk with their growth trackers.
+fn scan_growables(pos: IVec3, chunk: &Chunk, tracker: &mut SaplingTracker, crop_tracker: &mut CropTracker) {
+    let size = CHUNK_SIZE as i32;
+    let base_x = pos.x * size;
+    let base_y = pos.y * size;
+    let base_z = pos.z * size;
+    if !tracker.scanned_chunks.contains(&pos) {
+        tracker.scanned_chunks.insert(pos);
+        for y in 0..CHUNK_SIZE {
+            for z in 0..CHUNK_SIZE {
+                for x in 0..CHUNK_SIZE {
+                    let block = chunk.get(x, y, z);
+                    if block == BlockType::OakSapling || block == BlockType::BirchSapling {
+                        let world_pos = IVec3::new(
+                            base_x + x as i32,
+                            base_y + y as i32,
+                            base_z + z as i32,
+                        );
+                        tracker.saplings.entry(world_pos).or_insert_with(|| {
+                            SAPLING_GROW_MIN + rand::random::<f32>() * (SAPLING_GROW_MAX - SAPLING_GROW_MIN)
+                        });
                     }
                 }
             }
         }
-        if !crop_tracker.scanned_chunks.contains(&pos) {
-            crop_tracker.scanned_chunks.insert(pos);
-            if let Some(chunk) = store.chunks.get(&pos) {
-                let size = CHUNK_SIZE as i32;
-                let base_x = pos.x * size;
-                let base_y = pos.y * size;
-                let base_z = pos.z * size;
-                for y in 0..CHUNK_SIZE {
-                    for z in 0..CHUNK_SIZE {
-                        for x in 0..CHUNK_SIZE {
-                            let block = chunk.get(x, y, z);
-                            if matches!(block, BlockType::WheatStage0 | BlockType::WheatStage1 | BlockType::WheatStage2) {
-                                let world_pos = IVec3::new(
-                                    base_x + x as i32,
-                                    base_y + y as i32,
-                                    base_z + z as i32,
-                                );
-                                crop_tracker.crops.entry(world_pos).or_insert_with(|| {
-                                    CROP_GROW_MIN + rand::random::<f32>() * (CROP_GROW_MAX - CROP_GROW_MIN)
-                                });
-                            }
-                        }
+    }
+    if !crop_tracker.scanned_chunks.contains(&pos) {
+        crop_tracker.scanned_chunks.insert(pos);
+        for y in 0..CHUNK_SIZE {
+            for z in 0..CHUNK_SIZE {
+                for x in 0..CHUNK_SIZE {
+                    let state = chunk.get_state(x, y, z);
+                    if state.block() == BlockType::WheatCrop && state.age() < WHEAT_MAX_AGE {
+                        let world_pos = IVec3::new(
+                            base_x + x as i32,
+                            base_y + y as i32,
+                            base_z + z as i32,
+                        );
+                        crop_tracker.crops.entry(world_pos).or_insert_with(|| BlockTimer::new(world_pos, crop_grow_time()));
                     }
                 }
             }
         }
+    }
+}
 
-        loaded += 1;
+/// Give fires saved in a newly loaded chunk a time to burn out, so none burn forever.
+fn scan_fires(pos: IVec3, chunk: &Chunk, tracker: &mut FireTracker) {
+    if chunk.is_empty() {
+        return;
+    }
+    let base = pos * CHUNK_SIZE as i32;
+    for y in 0..CHUNK_SIZE {
+        for z in 0..CHUNK_SIZE {
+            for x in 0..CHUNK_SIZE {
+                if chunk.get(x, y, z) == BlockType::Fire {
+                    let world_pos = base + IVec3::new(x as i32, y as i32, z as i32);
+                    tracker.fires.entry(world_pos).or_insert_with(|| BlockTimer::new(world_pos, fire_burn_time()));
+                }
+            }
+        }
+    }
+}
+
+/// Update the chunk and water materials after the atlas image is rebuilt, so they're
+/// prepared again with the new texture and its animated tiles.
+pub fn refresh_atlas_materials(
+    mut rebuilt: MessageReader<AtlasRebuilt>,
+    atlas: Res<BlockAtlas>,
+    chunk_material: Res<ChunkMaterial>,
+    water_material: Res<WaterMaterial>,
+    mut materials: ResMut<Assets<ChunkMaterialType>>,
+    mut water_materials: ResMut<Assets<WaterMaterialType>>,
+) {
+    if rebuilt.read().count() == 0 {
+        return;
+    }
+    // Taking them mutably also marks them modified for the new image
+    let animations = TileAnimations::new(&atlas.animations);
+    if let Some(material) = materials.get_mut(&chunk_material.0) {
+        material.extension.animations = animations.clone();
+    }
+    if let Some(material) = water_materials.get_mut(&water_material.0) {
+        material.extension.animations = animations;
+    }
+}
+
+/// Keep the chunk and water materials' animation offset at the time Bevy has wrapped away
+/// from the shader clock, taken modulo the atlas' animation cycle. Shader time then runs on
+/// through the hourly wrap without animated tiles jumping frames. The offset only changes
+/// at a wrap or when the atlas is rebuilt, so the materials aren't re-uploaded every frame.
+pub fn sync_animation_clock(
+    time: Res<Time>,
+    atlas: Res<BlockAtlas>,
+    chunk_material: Res<ChunkMaterial>,
+    water_material: Res<WaterMaterial>,
+    mut materials: ResMut<Assets<ChunkMaterialType>>,
+    mut water_materials: ResMut<Assets<WaterMaterialType>>,
+) {
+    let wrapped_away = time.elapsed_secs_f64() - time.elapsed_secs_wrapped_f64();
+    let offset = (wrapped_away % animation_cycle(&atlas.animations)) as f32;
+    let Some(material) = materials.get(&chunk_material.0) else {
+        return;
+    };
+    if material.extension.animation_offset == offset {
+        return;
+    }
+    if let Some(material) = materials.get_mut(&chunk_material.0) {
+        material.extension.animation_offset = offset;
+    }
+    if let Some(material) = water_materials.get_mut(&water_material.0) {
+        material.extension.animation_offset = offset;
     }
 }
 
@@ -270,25 +594,27 @@ pub fn start_mesh_tasks(
             continue;
         };
         let chunk_clone = chunk.clone();
+        let light_clone = store.lights.get(&coord.0).cloned().unwrap_or_default();
 
-        // Look up the 6 neighbor chunks: [+X, -X, +Y, -Y, +Z, -Z]
-        let offsets = [
-            IVec3::X,   // +X (East)
-            IVec3::NEG_X, // -X (West)
-            IVec3::Y,   // +Y (Top)
-            IVec3::NEG_Y, // -Y (Bottom)
-            IVec3::Z,   // +Z (South)
-            IVec3::NEG_Z, // -Z (North)
-        ];
-        let neighbor_chunks: [Option<Chunk>; 6] = std::array::from_fn(|i| {
-            store.chunks.get(&(coord.0 + offsets[i])).cloned()
+        // Look up the 26 surrounding chunks, and light for the 6 sharing a face
+        let neighbor_chunks: [Option<Chunk>; 26] = std::array::from_fn(|i| {
+            store.chunks.get(&(coord.0 + NEIGHBOR_OFFSETS[i])).cloned()
+        });
+        let neighbor_light_data: [Option<ChunkLight>; 6] = std::array::from_fn(|i| {
+            store.lights.get(&(coord.0 + NEIGHBOR_OFFSETS[i])).cloned()
         });
 
+        let chunk_pos = coord.0;
         let task = task_pool.spawn(async move {
             let neighbors: NeighborChunks = std::array::from_fn(|i| {
                 neighbor_chunks[i].as_ref()
             });
-            build_chunk_mesh(&chunk_clone, &neighbors)
+            let neighbor_lights: NeighborLights = std::array::from_fn(|i| {
+                neighbor_light_data[i].as_ref()
+            });
+            let tints = ChunkTints::sample(chunk_pos);
+            let meshes = build_chunk_mesh(&chunk_clone, &neighbors, &light_clone, &neighbor_lights, &tints);
+            (meshes, compute_visibility(&chunk_clone))
         });
 
         commands
@@ -298,28 +624,276 @@ pub fn start_mesh_tasks(
     }
 }
 
-/// Poll completed mesh tasks and insert mesh + material components.
+/// Queue a remesh for every loaded chunk whose light changed since last frame.
+pub fn remesh_light_changes(
+    mut commands: Commands,
+    manager: Res<ChunkManager>,
+    mut store: ResMut<ChunkDataStore>,
+) {
+    if store.light_dirty.is_empty() {
+        return;
+    }
+    for pos in store.light_dirty.drain() {
+        if let Some(&entity) = manager.loaded.get(&pos) {
+            commands.entity(entity).insert(NeedsMesh);
+        }
+    }
+}
+
+/// Poll completed mesh tasks and insert mesh + material components. The fluid mesh
+/// goes on a child entity, spawned when a chunk first gets fluid and removed when it
+/// has none left.
 pub fn apply_mesh_results(
     mut commands: Commands,
     mut meshes: ResMut<Assets<Mesh>>,
     material: Res<ChunkMaterial>,
-    mut tasks: Query<(Entity, &mut MeshTask)>,
+    water_material: Res<WaterMaterial>,
+    mut store: ResMut<ChunkDataStore>,
+    mut culling: ResMut<CaveCulling>,
+    mut tasks: Query<(Entity, &ChunkCoord, &mut MeshTask, Option<&FluidMesh>)>,
+) {
+    for (entity, coord, mut task, fluid) in &mut tasks {
+        let Some((chunk_meshes, visibility)) = block_on(poll_once(&mut task.0)) else {
+            continue;
+        };
+        if store.visibility.insert(coord.0, visibility) != Some(visibility) {
+            culling.dirty = true;
+        }
+        commands
+            .entity(entity)
+            .remove::<MeshTask>()
+            .insert((
+                Mesh3d(meshes.add(chunk_meshes.opaque)),
+                MeshMaterial3d::<ChunkMaterialType>(material.0.clone()),
+            ));
+
+        match (chunk_meshes.translucent, fluid) {
+            (Some(mesh), Some(fluid)) => {
+                commands.entity(fluid.0).insert(Mesh3d(meshes.add(mesh)));
+            }
+            (Some(mesh), None) => {
+                let child = commands
+                    .spawn((
+                        Mesh3d(meshes.add(mesh)),
+                        MeshMaterial3d::<WaterMaterialType>(water_material.0.clone()),
+                        Transform::default(),
+                        Visibility::default(),
+                        ChildOf(entity),
+                    ))
+                    .id();
+                commands.entity(entity).insert(FluidMesh(child));
+            }
+            (None, Some(fluid)) => {
+                commands.entity(fluid.0).despawn();
+                commands.entity(entity).remove::<FluidMesh>();
+            }
+            (None, None) => {}
+        }
+    }
+}
+
+/// Show only the chunks reachable from the camera's chunk through open faces. Redone
+/// when the camera enters another chunk or a chunk's visibility changes.
+pub fn cull_hidden_chunks(
+    mut culling: ResMut<CaveCulling>,
+    manager: Res<ChunkManager>,
+    store: Res<ChunkDataStore>,
+    camera: Query<&GlobalTransform, With<Camera3d>>,
+    height: Res<WorldHeight>,
+    mut chunks: Query<(&ChunkCoord, &mut Visibility, Has<Mesh3d>)>,
 ) {
-    for (entity, mut task) in &mut tasks {
-        if let Some(mesh) = block_on(poll_once(&mut task.0)) {
-            commands
-                .entity(entity)
-                .remove::<MeshTask>()
-                .insert((
-                    Mesh3d(meshes.add(mesh)),
-                    MeshMaterial3d::<ChunkMaterialType>(material.0.clone()),
-                ));
+    let Ok(cam_transform) = camera.single() else {
+        return;
+    };
+    let cam_chunk = camera_chunk(cam_transform.translation(), *height);
+    if !culling.dirty && culling.camera_chunk == Some(cam_chunk) {
+        return;
+    }
+    culling.dirty = false;
+    culling.camera_chunk = Some(cam_chunk);
+
+    // Loaded chunks not meshed yet have nothing to hide behind
+    let visible = if culling.enabled {
+        visible_chunks(cam_chunk, |pos| {
+            manager
+                .loaded
+                .contains_key(&pos)
+                .then(|| store.visibility.get(&pos).copied().unwrap_or(ChunkVisibility::ALL))
+        })
+    } else {
+        None
+    };
+
+    let (mut drawn, mut meshed) = (0, 0);
+    for (coord, mut visibility, has_mesh) in &mut chunks {
+        let shown = visible.as_ref().is_none_or(|visible| visible.contains(&coord.0));
+        visibility.set_if_neq(if shown { Visibility::Inherited } else { Visibility::Hidden });
+        if has_mesh {
+            meshed += 1;
+            drawn += shown as usize;
         }
     }
+    culling.drawn = drawn;
+    culling.meshed = meshed;
+}
+
+/// Distance in chunks from the camera's column to the nearest column of a tile.
+fn tile_distance(tile: IVec2, cam_chunk: IVec3) -> i32 {
+    let min = tile * TILE_CHUNKS;
+    let max = min + IVec2::splat(TILE_CHUNKS - 1);
+    let dx = cam_chunk.x.clamp(min.x, max.x) - cam_chunk.x;
+    let dz = cam_chunk.z.clamp(min.y, max.y) - cam_chunk.z;
+    dx.abs().max(dz.abs())
+}
+
+/// Drop far-terrain tiles left behind and queue surface sampling for tiles in range,
+/// nearest first.
+pub fn update_far_terrain(
+    mut far: ResMut<FarTerrain>,
+    camera: Query<&GlobalTransform, With<Camera3d>>,
+    height: Res<WorldHeight>,
+    mut commands: Commands,
+) {
+    let Ok(cam_transform) = camera.single() else {
+        return;
+    };
+    let cam_chunk = camera_chunk(cam_transform.translation(), *height);
+
+    far.pending.retain(|&tile, _| tile_distance(tile, cam_chunk) <= FAR_TERRAIN_DROP_DISTANCE);
+    far.tiles.retain(|&tile, far_tile| {
+        let keep = tile_distance(tile, cam_chunk) <= FAR_TERRAIN_DROP_DISTANCE;
+        if !keep && let Some(entity) = far_tile.entity {
+            commands.entity(entity).despawn();
+        }
+        keep
+    });
+
+    if far.pending.len() >= MAX_PENDING_TILES {
+        return;
+    }
+    let center = tile_of(cam_chunk.x, cam_chunk.z);
+    let reach = FAR_TERRAIN_DISTANCE / TILE_CHUNKS + 1;
+    let mut to_sample: Vec<IVec2> = Vec::new();
+    for x in (center.x - reach)..=(center.x + reach) {
+        for z in (center.y - reach)..=(center.y + reach) {
+            let tile = IVec2::new(x, z);
+            if tile_distance(tile, cam_chunk) <= FAR_TERRAIN_DISTANCE
+                && !far.tiles.contains_key(&tile)
+                && !far.pending.contains_key(&tile)
+            {
+                to_sample.push(tile);
+            }
+        }
+    }
+    to_sample.sort_by_key(|&tile| tile_distance(tile, cam_chunk));
+
+    let task_pool = AsyncComputeTaskPool::get();
+    let free = MAX_PENDING_TILES - far.pending.len();
+    for tile in to_sample.into_iter().take(free) {
+        far.pending.insert(tile, task_pool.spawn(async move { sample_tile(tile) }));
+    }
+}
+
+/// Collect sampled far-terrain tiles and rebuild the mesh of any tile whose covered
+/// columns changed. Far terrain thus gives way to full chunks as soon as they're meshed,
+/// and fills back in when they unload.
+pub fn apply_far_terrain(
+    mut commands: Commands,
+    mut far: ResMut<FarTerrain>,
+    manager: Res<ChunkManager>,
+    meshed: Query<(), (With<ChunkCoord>, With<Mesh3d>)>,
+    camera: Query<&GlobalTransform, With<Camera3d>>,
+    height: Res<WorldHeight>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    material: Res<FarTerrainMaterial>,
+) {
+    let Ok(cam_transform) = camera.single() else {
+        return;
+    };
+    let cam_chunk = camera_chunk(cam_transform.translation(), *height);
+
+    let FarTerrain { tiles, pending } = &mut *far;
+    pending.retain(|&tile, task| match block_on(poll_once(task)) {
+        Some(samples) => {
+            tiles.insert(tile, FarTile { samples, covered: None, entity: None });
+            false
+        }
+        None => true,
+    });
+
+    for (&tile, far_tile) in tiles.iter_mut() {
+        let covered = covered_columns(tile, &far_tile.samples, cam_chunk, *height, &manager, &meshed);
+        if far_tile.covered == Some(covered) {
+            continue;
+        }
+        far_tile.covered = Some(covered);
+
+        match (build_tile_mesh(&far_tile.samples, covered), far_tile.entity) {
+            (Some(mesh), Some(entity)) => {
+                commands.entity(entity).insert(Mesh3d(meshes.add(mesh)));
+            }
+            (Some(mesh), None) => {
+                let entity = commands
+                    .spawn((
+                        FarTerrainTile,
+                        Mesh3d(meshes.add(mesh)),
+                        MeshMaterial3d(material.0.clone()),
+                        Transform::from_translation(tile_origin(tile)),
+                        Visibility::default(),
+                    ))
+                    .id();
+                far_tile.entity = Some(entity);
+            }
+            (None, Some(entity)) => {
+                commands.entity(entity).despawn();
+                far_tile.entity = None;
+            }
+            (None, None) => {}
+        }
+    }
+}
+
+/// Chunk columns of a tile drawn by full chunks: every layer the sampled surface passes
+/// through is loaded and meshed. Columns outside the loaded area are never covered.
+fn covered_columns(
+    tile: IVec2,
+    samples: &TileSamples,
+    cam_chunk: IVec3,
+    height: WorldHeight,
+    manager: &ChunkManager,
+    meshed: &Query<(), (With<ChunkCoord>, With<Mesh3d>)>,
+) -> u16 {
+    let mut covered = 0;
+    for cz in 0..TILE_CHUNKS {
+        for cx in 0..TILE_CHUNKS {
+            let column = tile * TILE_CHUNKS + IVec2::new(cx, cz);
+            if (column.x - cam_chunk.x).abs() > DESPAWN_DISTANCE || (column.y - cam_chunk.z).abs() > DESPAWN_DISTANCE {
+                continue;
+            }
+            let (low, high) = samples.column_range(cx as usize, cz as usize);
+            let bottom = low.div_euclid(CHUNK_SIZE as i32).max(height.min_chunk_y());
+            let top = high.div_euclid(CHUNK_SIZE as i32).min(height.max_chunk_y() - 1);
+            let drawn = (bottom..=top).all(|y| {
+                manager
+                    .loaded
+                    .get(&IVec3::new(column.x, y, column.y))
+                    .is_some_and(|&entity| meshed.contains(entity))
+            });
+            if drawn {
+                covered |= column_bit(cx, cz);
+            }
+        }
+    }
+    covered
 }
 
 /// Helper: read a block from the chunk data store at a world position.
 fn get_block_at(store: &ChunkDataStore, pos: IVec3) -> BlockType {
+    get_block_state_at(store, pos).block()
+}
+
+/// Helper: look up a block state in the chunk data store at a world position.
+fn get_block_state_at(store: &ChunkDataStore, pos: IVec3) -> BlockState {
     let size = CHUNK_SIZE as i32;
     let chunk_pos = IVec3::new(
         pos.x.div_euclid(size),
@@ -327,16 +901,21 @@ fn get_block_at(store: &ChunkDataStore, pos: IVec3) -> BlockType {
         pos.z.div_euclid(size),
     );
     let Some(chunk) = store.chunks.get(&chunk_pos) else {
-        return BlockType::Air;
+        return BlockState::AIR;
     };
     let lx = pos.x.rem_euclid(size) as usize;
     let ly = pos.y.rem_euclid(size) as usize;
     let lz = pos.z.rem_euclid(size) as usize;
-    chunk.get(lx, ly, lz)
+    chunk.get_state(lx, ly, lz)
 }
 
 /// Helper: set a block in the chunk data store at a world position.
-fn set_block_at(store: &mut ChunkDataStore, pos: IVec3, block: BlockType) {
+pub(crate) fn set_block_at(store: &mut ChunkDataStore, pos: IVec3, block: BlockType) {
+    set_block_state_at(store, pos, BlockState::new(block));
+}
+
+/// Helper: set a block state in the chunk data store at a world position.
+pub(crate) fn set_block_state_at(store: &mut ChunkDataStore, pos: IVec3, state: BlockState) {
     let size = CHUNK_SIZE as i32;
     let chunk_pos = IVec3::new(
         pos.x.div_euclid(size),
@@ -347,17 +926,39 @@ fn set_block_at(store: &mut ChunkDataStore, pos: IVec3, block: BlockType) {
         let lx = pos.x.rem_euclid(size) as usize;
         let ly = pos.y.rem_euclid(size) as usize;
         let lz = pos.z.rem_euclid(size) as usize;
-        chunk.set(lx, ly, lz, block);
+        let old = chunk.get(lx, ly, lz);
+        chunk.set_state(lx, ly, lz, state);
         store.modified.insert(chunk_pos);
+        update_light(store, pos, old, state.block());
+        schedule_around(store, pos);
+        store.gravity_checks.insert(pos);
+        store.gravity_checks.insert(pos + IVec3::Y);
     }
 }
 
 /// Helper: mark a world position's chunk (and boundary neighbors) for remeshing.
-fn mark_remesh(pos: IVec3, manager: &ChunkManager, commands: &mut Commands) {
+pub(crate) fn mark_remesh(pos: IVec3, manager: &ChunkManager, commands: &mut Commands) {
     let chunk_pos = world_to_chunk_pos(pos.as_vec3());
     if let Some(&entity) = manager.loaded.get(&chunk_pos) {
         commands.entity(entity).insert(NeedsMesh);
     }
+
+    // A block on a chunk boundary also shows in the neighbor's faces and AO, and one on a
+    // chunk edge or corner in the AO of the chunks diagonally across it
+    let local = world_to_local_pos(pos).as_ivec3();
+    let size = CHUNK_SIZE as i32;
+    let side = |c: i32| if c == 0 { -1 } else if c == size - 1 { 1 } else { 0 };
+    let sides = IVec3::new(side(local.x), side(local.y), side(local.z));
+    for offset in NEIGHBOR_OFFSETS {
+        // Only neighbors on the sides the block touches
+        let touches = (0..3).all(|axis| offset[axis] == 0 || offset[axis] == sides[axis]);
+        if !touches {
+            continue;
+        }
+        if let Some(&entity) = manager.loaded.get(&(chunk_pos + offset)) {
+            commands.entity(entity).insert(NeedsMesh);
+        }
+    }
 }
 
 /// System: tick sapling growth timers and attempt to grow mature saplings into trees.
@@ -504,53 +1105,52 @@ pub fn update_crop_growth(
     mut crop_tracker: ResMut<CropTracker>,
     mut store: ResMut<ChunkDataStore>,
     manager: Res<ChunkManager>,
+    weather: Res<WeatherState>,
     mut commands: Commands,
 ) {
     let dt = time.delta_secs();
 
     // Remove tracked crops that are no longer growing crops (broken/replaced/fully grown)
     crop_tracker.crops.retain(|pos, _| {
-        let block = get_block_at(&store, *pos);
-        matches!(block, BlockType::WheatStage0 | BlockType::WheatStage1 | BlockType::WheatStage2)
+        let state = get_block_state_at(&store, *pos);
+        state.block() == BlockType::WheatCrop && state.age() < WHEAT_MAX_AGE
     });
 
     // Tick timers and collect crops ready to advance
     let mut ready: Vec<IVec3> = Vec::new();
     for (pos, timer) in crop_tracker.crops.iter_mut() {
-        *timer -= dt;
-        if *timer <= 0.0 {
+        let rained_on = is_rained_on(&weather, &store, *pos, timer.precipitation);
+        if timer.tick(dt, rained_on, RAIN_GROWTH_MULTIPLIER) {
             ready.push(*pos);
         }
     }
 
     // Advance growth stage for each ready crop
     for pos in &ready {
-        let block = get_block_at(&store, *pos);
+        let state = get_block_state_at(&store, *pos);
 
         // Check that the block above is air (light requirement)
         let above = get_block_at(&store, *pos + IVec3::Y);
         if above != BlockType::Air {
             // Can't grow, reset timer
             if let Some(timer) = crop_tracker.crops.get_mut(pos) {
-                *timer = CROP_GROW_MIN + rand::random::<f32>() * (CROP_GROW_MAX - CROP_GROW_MIN);
+                timer.remaining = crop_grow_time();
             }
             continue;
         }
 
-        let next_stage = match block {
-            BlockType::WheatStage0 => BlockType::WheatStage1,
-            BlockType::WheatStage1 => BlockType::WheatStage2,
-            BlockType::WheatStage2 => BlockType::WheatStage3,
-            _ => continue,
-        };
+        if state.block() != BlockType::WheatCrop {
+            continue;
+        }
+        let next_stage = state.with_age(state.age() + 1);
 
-        set_block_at(&mut store, *pos, next_stage);
+        set_block_state_at(&mut store, *pos, next_stage);
         mark_remesh(*pos, &manager, &mut commands);
 
         // If not yet fully grown, reset timer for next stage
-        if next_stage != BlockType::WheatStage3 {
+        if next_stage.age() < WHEAT_MAX_AGE {
             if let Some(timer) = crop_tracker.crops.get_mut(pos) {
-                *timer = CROP_GROW_MIN + rand::random::<f32>() * (CROP_GROW_MAX - CROP_GROW_MIN);
+                timer.remaining = crop_grow_time();
             }
         } else {
             // Fully grown, remove from tracker
@@ -558,3 +1158,189 @@ pub fn update_crop_growth(
         }
     }
 }
+
+pub(crate) fn crop_grow_time() -> f32 {
+    CROP_GROW_MIN + rand::random::<f32>() * (CROP_GROW_MAX - CROP_GROW_MIN)
+}
+
+fn fire_burn_time() -> f32 {
+    FIRE_BURN_MIN + rand::random::<f32>() * (FIRE_BURN_MAX - FIRE_BURN_MIN)
+}
+
+/// Start a fire at `pos` if it's air resting on a solid block. Returns whether it caught.
+pub(crate) fn ignite(
+    store: &mut ChunkDataStore,
+    tracker: &mut FireTracker,
+    manager: &ChunkManager,
+    commands: &mut Commands,
+    pos: IVec3,
+) -> bool {
+    if get_block_at(store, pos) != BlockType::Air || !get_block_state_at(store, pos - IVec3::Y).is_solid() {
+        return false;
+    }
+    set_block_at(store, pos, BlockType::Fire);
+    tracker.fires.insert(pos, BlockTimer::new(pos, fire_burn_time()));
+    mark_remesh(pos, manager, commands);
+    true
+}
+
+/// System: tick fire timers and put out fires that have burned down, lost the block
+/// they stood on, or been rained out.
+pub fn update_fires(
+    time: Res<Time>,
+    mut tracker: ResMut<FireTracker>,
+    mut store: ResMut<ChunkDataStore>,
+    manager: Res<ChunkManager>,
+    weather: Res<WeatherState>,
+    mut commands: Commands,
+) {
+    let dt = time.delta_secs();
+
+    // Forget fires that were broken, replaced or unloaded
+    tracker.fires.retain(|pos, _| get_block_at(&store, *pos) == BlockType::Fire);
+
+    let mut out: Vec<IVec3> = Vec::new();
+    for (pos, timer) in tracker.fires.iter_mut() {
+        let rained_on = is_rained_on(&weather, &store, *pos, timer.precipitation);
+        if timer.tick(dt, rained_on, RAIN_FIRE_MULTIPLIER) || !get_block_state_at(&store, *pos - IVec3::Y).is_solid() {
+            out.push(*pos);
+        }
+    }
+
+    for pos in out {
+        tracker.fires.remove(&pos);
+        set_block_at(&mut store, pos, BlockType::Air);
+        mark_remesh(pos, &manager, &mut commands);
+    }
+}
+
+/// Run due fluid updates on a fixed tick, applying flow changes and remeshing.
+pub fn update_fluids(
+    time: Res<Time>,
+    mut elapsed: Local<f32>,
+    mut store: ResMut<ChunkDataStore>,
+    manager: Res<ChunkManager>,
+    mut commands: Commands,
+) {
+    // Don't try to catch up on missed ticks after a long frame
+    *elapsed = (*elapsed + time.delta_secs()).min(FLUID_TICK_SECONDS * 2.0);
+    if *elapsed < FLUID_TICK_SECONDS || store.fluid_ticks.is_empty() {
+        return;
+    }
+    *elapsed -= FLUID_TICK_SECONDS;
+
+    // Every update in a tick sees the world as it was at the start of the tick
+    let due = store.fluid_ticks.advance(MAX_FLUID_UPDATES_PER_TICK);
+    let changes: Vec<(IVec3, BlockState)> = due
+        .into_iter()
+        .flat_map(|pos| fluid_tick(pos, |p| state_at(&store, p)))
+        .collect();
+
+    let mut remesh = HashSet::new();
+    for (pos, state) in changes {
+        if state_at(&store, pos) == Some(state) {
+            continue;
+        }
+        set_block_state_at(&mut store, pos, state);
+        // Water surfaces on chunk borders slope towards the neighbors' water, including the
+        // chunks diagonally across a corner, so remesh those too
+        remesh.insert(world_to_chunk_pos(pos.as_vec3()));
+        for dir in NEIGHBOR_OFFSETS.into_iter().filter(|o| o.y == 0 || (o.x == 0 && o.z == 0)) {
+            remesh.insert(world_to_chunk_pos((pos + dir).as_vec3()));
+        }
+    }
+    for chunk_pos in remesh {
+        if let Some(&entity) = manager.loaded.get(&chunk_pos) {
+            commands.entity(entity).insert(NeedsMesh);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::light::LightChannel;
+    use bevy::tasks::TaskPool;
+
+    #[test]
+    fn loads_nearest_first_and_top_down() {
+        let cam = IVec3::new(0, 4, 0);
+        let mut order = vec![
+            IVec3::new(5, 4, 0),
+            IVec3::new(0, 2, 0),
+            IVec3::new(1, 4, 1),
+            IVec3::new(0, 3, 0),
+            IVec3::new(0, 4, 0),
+            IVec3::new(0, 6, 0),
+        ];
+        order.sort_by_key(|&pos| load_priority(pos, cam));
+        // Chunks above the camera cost nothing for their height, so the column loads top-down
+        // before spreading out
+        assert_eq!(
+            order,
+            [
+                IVec3::new(0, 6, 0),
+                IVec3::new(0, 4, 0),
+                IVec3::new(0, 3, 0),
+                IVec3::new(1, 4, 1),
+                IVec3::new(0, 2, 0),
+                IVec3::new(5, 4, 0),
+            ]
+        );
+    }
+
+    #[test]
+    fn loads_out_of_range_are_dropped() {
+        let pool = AsyncComputeTaskPool::get_or_init(TaskPool::new);
+        let mut manager = ChunkManager::default();
+        let mut store = ChunkDataStore::default();
+        let cam = IVec3::new(0, 4, 0);
+        let near = IVec3::new(2, 4, -3);
+        let far = IVec3::new(DESPAWN_DISTANCE + 1, 4, 0);
+        let deep = IVec3::new(0, 4 - DEPTH_DESPAWN_DISTANCE - 1, 0);
+        let open_pit = IVec3::new(1, 4 - DEPTH_DESPAWN_DISTANCE - 1, 0);
+        // Sky light reaches into the pit from the chunk above, so it stays wanted
+        let mut light = ChunkLight::default();
+        light.set(LightChannel::Sky, 0, 0, 0, 15);
+        store.lights.insert(open_pit + IVec3::Y, light);
+
+        for pos in [near, far, deep, open_pit] {
+            manager.pending.insert(pos, pool.spawn(async { Chunk::default() }));
+            manager.ready.insert(pos, Chunk::default());
+        }
+        drop_stale_loads(&mut manager, &store, cam);
+
+        let wanted = HashSet::from([near, open_pit]);
+        assert_eq!(manager.pending.keys().copied().collect::<HashSet<_>>(), wanted);
+        assert_eq!(manager.ready.keys().copied().collect::<HashSet<_>>(), wanted);
+    }
+
+    #[test]
+    fn rain_speeds_up_crops_and_puts_out_fires() {
+        let mut crop = BlockTimer { remaining: 3.0, precipitation: Precipitation::Rain };
+        let mut dry_crop = crop;
+        assert!(!crop.tick(1.0, true, RAIN_GROWTH_MULTIPLIER));
+        assert!(!dry_crop.tick(1.0, false, RAIN_GROWTH_MULTIPLIER));
+        assert_eq!(crop.remaining, 3.0 - RAIN_GROWTH_MULTIPLIER);
+        assert_eq!(dry_crop.remaining, 2.0);
+
+        // A fire that would burn for a while is soon out in the rain
+        let mut fire = BlockTimer { remaining: FIRE_BURN_MIN, precipitation: Precipitation::Rain };
+        let rain_seconds = FIRE_BURN_MIN / RAIN_FIRE_MULTIPLIER;
+        assert!(!fire.tick(rain_seconds * 0.9, true, RAIN_FIRE_MULTIPLIER));
+        assert!(fire.tick(rain_seconds * 0.2, true, RAIN_FIRE_MULTIPLIER));
+    }
+
+    #[test]
+    fn chunks_wait_for_the_one_above() {
+        let height = WorldHeight::default();
+        let mut store = ChunkDataStore::default();
+        let top = IVec3::new(0, height.max_chunk_y() - 1, 0);
+        let below = top - IVec3::Y;
+        assert!(can_apply(top, &store, height));
+        assert!(!can_apply(below, &store, height));
+        store.chunks.insert(top, Chunk::default());
+        assert!(can_apply(below, &store, height));
+        assert!(!can_apply(below - IVec3::Y, &store, height));
+    }
+}
@@ -1,19 +1,172 @@
 use bevy::prelude::*;
-use bevy::pbr::{ExtendedMaterial, MaterialExtension};
-use bevy::render::render_resource::AsBindGroup;
+use bevy::mesh::MeshVertexBufferLayoutRef;
+use bevy::pbr::{ExtendedMaterial, MaterialExtension, MaterialExtensionKey, MaterialExtensionPipeline};
+use bevy::render::render_resource::{
+    AsBindGroup, RenderPipelineDescriptor, ShaderType, SpecializedMeshPipelineError,
+};
 use bevy::shader::ShaderRef;
 
+use crate::block::atlas::ATLAS_TILE_COUNT;
+use crate::resource_pack::pack::TileAnimation;
+use crate::world::meshing::ATTRIBUTE_TINT;
+
+/// Shader location of `ATTRIBUTE_TINT`, past the ones Bevy's mesh pipeline uses.
+const TINT_SHADER_LOCATION: u32 = 10;
+
+/// Vertex shader shared by the chunk materials, passing the biome tint through.
+const CHUNK_VERTEX_SHADER: &str = "shaders/chunk_vertex.wgsl";
+
+/// Add the biome tint to the vertex attributes Bevy's mesh pipeline reads.
+fn add_tint_attribute(
+    descriptor: &mut RenderPipelineDescriptor,
+    layout: &MeshVertexBufferLayoutRef,
+) -> Result<(), SpecializedMeshPipelineError> {
+    let tint = layout.0.get_layout(&[ATTRIBUTE_TINT.at_shader_location(TINT_SHADER_LOCATION)])?;
+    descriptor.vertex.buffers[0].attributes.extend(tint.attributes);
+    Ok(())
+}
+
+/// Frame animation of every atlas tile, laid out as `atlas_animation.wgsl` reads it: the
+/// tile holding the second frame, the frame count (0 for still tiles), seconds per frame,
+/// and 1 to blend between frames.
+#[derive(Clone, ShaderType)]
+pub struct TileAnimations {
+    pub tiles: [Vec4; ATLAS_TILE_COUNT],
+}
+
+impl TileAnimations {
+    pub fn new(animations: &[TileAnimation]) -> Self {
+        let mut tiles = [Vec4::ZERO; ATLAS_TILE_COUNT];
+        for animation in animations {
+            tiles[animation.tile as usize] = Vec4::new(
+                animation.next_tile as f32,
+                animation.frames as f32,
+                animation.frame_time,
+                if animation.interpolate { 1.0 } else { 0.0 },
+            );
+        }
+        Self { tiles }
+    }
+}
+
+impl Default for TileAnimations {
+    fn default() -> Self {
+        Self::new(&[])
+    }
+}
+
 /// Material extension that tiles atlas textures correctly for greedy-meshed quads.
 /// Uses UV_1 to pass the tile origin so the fragment shader can wrap UVs within
-/// the atlas tile boundaries.
-#[derive(Asset, AsBindGroup, TypePath, Clone, Default)]
-pub struct AtlasTileMaterial {}
+/// the atlas tile boundaries, and swap in the current frame of animated tiles.
+/// Also shades chunks from the voxel light baked into vertex colors instead of
+/// scene lights, and multiplies in the biome tint baked into each vertex.
+#[derive(Asset, AsBindGroup, TypePath, Clone)]
+pub struct AtlasTileMaterial {
+    /// Brightness multiplier for sky light: 1.0 at noon, dim at night.
+    /// Driven by the day/night cycle.
+    #[uniform(100)]
+    pub daylight: f32,
+    /// Animated tiles, whose frames the shader steps through over time.
+    #[uniform(101)]
+    pub animations: TileAnimations,
+    /// Seconds added to the shader's time, which Bevy wraps every hour, to keep it counting
+    /// in whole animation cycles across the wrap. Set by `sync_animation_clock`.
+    #[uniform(102)]
+    pub animation_offset: f32,
+}
+
+impl Default for AtlasTileMaterial {
+    fn default() -> Self {
+        Self { daylight: 1.0, animations: TileAnimations::default(), animation_offset: 0.0 }
+    }
+}
 
 impl MaterialExtension for AtlasTileMaterial {
+    fn vertex_shader() -> ShaderRef {
+        CHUNK_VERTEX_SHADER.into()
+    }
+
     fn fragment_shader() -> ShaderRef {
         "shaders/atlas_tile.wgsl".into()
     }
+
+    fn specialize(
+        _pipeline: &MaterialExtensionPipeline,
+        descriptor: &mut RenderPipelineDescriptor,
+        layout: &MeshVertexBufferLayoutRef,
+        _key: MaterialExtensionKey<Self>,
+    ) -> Result<(), SpecializedMeshPipelineError> {
+        add_tint_attribute(descriptor, layout)
+    }
 }
 
 /// Type alias for the chunk material: StandardMaterial extended with atlas tiling.
 pub type ChunkMaterialType = ExtendedMaterial<StandardMaterial, AtlasTileMaterial>;
+
+/// Material extension for the translucent fluid mesh: tints and fades water by the
+/// column depth baked into vertex color alpha and the biome tint, lit from the same voxel
+/// light as chunks.
+#[derive(Asset, AsBindGroup, TypePath, Clone)]
+pub struct WaterTintMaterial {
+    /// Brightness multiplier for sky light, kept in step with `AtlasTileMaterial`.
+    #[uniform(100)]
+    pub daylight: f32,
+    /// Animated tiles, as for `AtlasTileMaterial`.
+    #[uniform(101)]
+    pub animations: TileAnimations,
+    /// Shader time offset, as for `AtlasTileMaterial`.
+    #[uniform(102)]
+    pub animation_offset: f32,
+}
+
+impl Default for WaterTintMaterial {
+    fn default() -> Self {
+        Self { daylight: 1.0, animations: TileAnimations::default(), animation_offset: 0.0 }
+    }
+}
+
+impl MaterialExtension for WaterTintMaterial {
+    fn vertex_shader() -> ShaderRef {
+        CHUNK_VERTEX_SHADER.into()
+    }
+
+    fn fragment_shader() -> ShaderRef {
+        "shaders/water.wgsl".into()
+    }
+
+    fn specialize(
+        _pipeline: &MaterialExtensionPipeline,
+        descriptor: &mut RenderPipelineDescriptor,
+        layout: &MeshVertexBufferLayoutRef,
+        _key: MaterialExtensionKey<Self>,
+    ) -> Result<(), SpecializedMeshPipelineError> {
+        add_tint_attribute(descriptor, layout)
+    }
+}
+
+/// Type alias for the water material: a blended StandardMaterial extended with depth tinting.
+pub type WaterMaterialType = ExtendedMaterial<StandardMaterial, WaterTintMaterial>;
+
+/// Material extension for far terrain: shades the surface colors baked into vertex
+/// colors like chunk faces in full sky light, dimmed by the same daylight factor.
+#[derive(Asset, AsBindGroup, TypePath, Clone)]
+pub struct FarTerrainShading {
+    /// Brightness multiplier for sky light, kept in step with `AtlasTileMaterial`.
+    #[uniform(100)]
+    pub daylight: f32,
+}
+
+impl Default for FarTerrainShading {
+    fn default() -> Self {
+        Self { daylight: 1.0 }
+    }
+}
+
+impl MaterialExtension for FarTerrainShading {
+    fn fragment_shader() -> ShaderRef {
+        "shaders/far_terrain.wgsl".into()
+    }
+}
+
+/// Type alias for the far terrain material: a StandardMaterial extended with flat shading.
+pub type FarTerrainMaterialType = ExtendedMaterial<StandardMaterial, FarTerrainShading>;
@@ -1,14 +1,66 @@
 use bevy::prelude::*;
-use bevy::mesh::{Indices, PrimitiveTopology};
+use bevy::mesh::{Indices, MeshVertexAttribute, PrimitiveTopology, VertexFormat};
 use bevy::asset::RenderAssetUsages;
 
-use crate::block::atlas::{face_uvs_tiled, tile_uvs, texture_index};
-use crate::block::{BlockType, Face};
+use crate::block::atlas::{face_uvs, face_uvs_tiled, tile_at, tile_biome_tint, tile_uvs};
+use crate::block::model::{block_model, BlockModel, MODEL_FACES};
+use crate::block::{BlockState, BlockType, Face};
 use crate::world::chunk::{Chunk, CHUNK_SIZE};
+use crate::world::colormap::ChunkTints;
+use crate::world::fluid::fluid_height;
+use crate::world::light::{ChunkLight, MAX_LIGHT};
 
-/// Optional neighbor chunk data for cross-chunk face culling.
-/// Order: [+X, -X, +Y, -Y, +Z, -Z] matching [East, West, Top, Bottom, South, North].
-pub type NeighborChunks<'a> = [Option<&'a Chunk>; 6];
+/// Optional neighbor chunk data for cross-chunk face culling and ambient occlusion,
+/// in `NEIGHBOR_OFFSETS` order.
+pub type NeighborChunks<'a> = [Option<&'a Chunk>; 26];
+
+/// Optional neighbor light data for lighting faces on chunk borders.
+/// The six face neighbors of `NeighborChunks`; faces never look into the others.
+pub type NeighborLights<'a> = [Option<&'a ChunkLight>; 6];
+
+/// Chunk offsets of the `NeighborChunks` entries. The six face neighbors come first:
+/// [+X, -X, +Y, -Y, +Z, -Z] matching [East, West, Top, Bottom, South, North]. Then the
+/// twelve edge and eight corner neighbors, whose cells only darken corners of faces at
+/// the chunk's edges.
+pub const NEIGHBOR_OFFSETS: [IVec3; 26] = [
+    IVec3::new(1, 0, 0),
+    IVec3::new(-1, 0, 0),
+    IVec3::new(0, 1, 0),
+    IVec3::new(0, -1, 0),
+    IVec3::new(0, 0, 1),
+    IVec3::new(0, 0, -1),
+    IVec3::new(1, 1, 0),
+    IVec3::new(1, -1, 0),
+    IVec3::new(-1, 1, 0),
+    IVec3::new(-1, -1, 0),
+    IVec3::new(1, 0, 1),
+    IVec3::new(1, 0, -1),
+    IVec3::new(-1, 0, 1),
+    IVec3::new(-1, 0, -1),
+    IVec3::new(0, 1, 1),
+    IVec3::new(0, 1, -1),
+    IVec3::new(0, -1, 1),
+    IVec3::new(0, -1, -1),
+    IVec3::new(1, 1, 1),
+    IVec3::new(1, 1, -1),
+    IVec3::new(1, -1, 1),
+    IVec3::new(1, -1, -1),
+    IVec3::new(-1, 1, 1),
+    IVec3::new(-1, 1, -1),
+    IVec3::new(-1, -1, 1),
+    IVec3::new(-1, -1, -1),
+];
+
+/// The two meshes built for a chunk: alpha-masked terrain, and fluids drawn
+/// afterwards with blending. `translucent` is `None` when the chunk has no fluid.
+pub struct ChunkMeshes {
+    pub opaque: Mesh,
+    pub translucent: Option<Mesh>,
+}
+
+/// Biome tint multiplied into each vertex's texture by the chunk shaders; white leaves
+/// the atlas color as it is. Read at shader location 10 (see `chunk_vertex.wgsl`).
+pub const ATTRIBUTE_TINT: MeshVertexAttribute = MeshVertexAttribute::new("Tint", 988_540_917, VertexFormat::Float32x3);
 
 /// Describes a face direction for the sweeping algorithm.
 struct FaceDir {
@@ -43,16 +95,20 @@ fn should_emit_face(block: BlockType, neighbor: BlockType) -> bool {
     if block == BlockType::Air {
         return false;
     }
-    // Non-cube blocks (torch, tall grass, saplings) are not rendered as cubes
-    if block.is_non_cube() {
+    // Blocks with models (torches, plants, beds, doors) get their own pass
+    if block.has_model() {
+        return false;
+    }
+    // Liquids get their own pass with sloped surfaces
+    if block.is_liquid() {
         return false;
     }
     // Non-solid, non-transparent blocks generate nothing (only Air matches this)
     if !block.is_solid() && !block.is_transparent() {
         return false;
     }
-    // Face toward air is always visible
-    if neighbor == BlockType::Air {
+    // Face toward air, or a model that doesn't fill its cell, is always visible
+    if neighbor == BlockType::Air || neighbor.has_model() {
         return true;
     }
     // Solid opaque face visible through a transparent neighbor
@@ -66,28 +122,24 @@ fn should_emit_face(block: BlockType, neighbor: BlockType) -> bool {
     false
 }
 
-/// Gets the block at position `[axis, u, v]` remapped to `[x, y, z]`.
+/// Gets the block state at position `[axis, u, v]` remapped to `[x, y, z]`.
 #[inline]
-fn get_block_from_pos(chunk: &Chunk, axis: usize, u_axis: usize, v_axis: usize, a: usize, u: usize, v: usize) -> BlockType {
+fn get_block_from_pos(chunk: &Chunk, axis: usize, u_axis: usize, v_axis: usize, a: usize, u: usize, v: usize) -> BlockState {
     let mut pos = [0usize; 3];
     pos[axis] = a;
     pos[u_axis] = u;
     pos[v_axis] = v;
-    chunk.get(pos[0], pos[1], pos[2])
+    chunk.get_state(pos[0], pos[1], pos[2])
 }
 
-/// Gets the neighbor block for a face. If the neighbor is outside the chunk,
-/// uses the neighbor chunk data if available, otherwise returns Air.
+/// Locates the cell on the other side of a face: `None` for the chunk index when the
+/// cell is inside this chunk, otherwise the index into the neighbor arrays.
 #[inline]
-fn get_neighbor_block(
-    chunk: &Chunk,
-    neighbors: &NeighborChunks,
-    face_dir: &FaceDir,
-    slice: usize,
-    u: usize,
-    v: usize,
-) -> BlockType {
+fn neighbor_cell(face_dir: &FaceDir, slice: usize, u: usize, v: usize) -> (Option<usize>, [usize; 3]) {
     let size = CHUNK_SIZE;
+    let mut pos = [0usize; 3];
+    pos[face_dir.u_axis] = u;
+    pos[face_dir.v_axis] = v;
     if face_dir.back_face {
         // Neighbor is at slice - 1 along the axis
         if slice == 0 {
@@ -98,17 +150,11 @@ fn get_neighbor_block(
                 Face::North => 5,  // -Z
                 _ => unreachable!(),
             };
-            if let Some(neighbor_chunk) = neighbors[neighbor_idx] {
-                let mut pos = [0usize; 3];
-                pos[face_dir.axis] = size - 1;
-                pos[face_dir.u_axis] = u;
-                pos[face_dir.v_axis] = v;
-                neighbor_chunk.get(pos[0], pos[1], pos[2])
-            } else {
-                BlockType::Air
-            }
+            pos[face_dir.axis] = size - 1;
+            (Some(neighbor_idx), pos)
         } else {
-            get_block_from_pos(chunk, face_dir.axis, face_dir.u_axis, face_dir.v_axis, slice - 1, u, v)
+            pos[face_dir.axis] = slice - 1;
+            (None, pos)
         }
     } else {
         // Neighbor is at slice + 1 along the axis
@@ -119,34 +165,142 @@ fn get_neighbor_block(
                 Face::South => 4, // +Z
                 _ => unreachable!(),
             };
-            if let Some(neighbor_chunk) = neighbors[neighbor_idx] {
-                let mut pos = [0usize; 3];
-                pos[face_dir.axis] = 0;
-                pos[face_dir.u_axis] = u;
-                pos[face_dir.v_axis] = v;
-                neighbor_chunk.get(pos[0], pos[1], pos[2])
-            } else {
-                BlockType::Air
-            }
+            pos[face_dir.axis] = 0;
+            (Some(neighbor_idx), pos)
         } else {
-            get_block_from_pos(chunk, face_dir.axis, face_dir.u_axis, face_dir.v_axis, slice + 1, u, v)
+            pos[face_dir.axis] = slice + 1;
+            (None, pos)
         }
     }
 }
 
-/// Build a chunk mesh using greedy meshing with face culling.
+/// Gets the neighbor block for a face. If the neighbor is outside the chunk,
+/// uses the neighbor chunk data if available, otherwise returns Air.
+#[inline]
+fn get_neighbor_block(
+    chunk: &Chunk,
+    neighbors: &NeighborChunks,
+    face_dir: &FaceDir,
+    slice: usize,
+    u: usize,
+    v: usize,
+) -> BlockType {
+    match neighbor_cell(face_dir, slice, u, v) {
+        (None, pos) => chunk.get(pos[0], pos[1], pos[2]),
+        (Some(idx), pos) => match neighbors[idx] {
+            Some(neighbor_chunk) => neighbor_chunk.get(pos[0], pos[1], pos[2]),
+            None => BlockType::Air,
+        },
+    }
+}
+
+/// Gets the light shining onto a face (the light of the cell it faces), packed as
+/// `sky << 4 | block`. Faces toward an unloaded neighbor get full sky light.
+#[inline]
+fn get_face_light(
+    light: &ChunkLight,
+    neighbor_lights: &NeighborLights,
+    face_dir: &FaceDir,
+    slice: usize,
+    u: usize,
+    v: usize,
+) -> u8 {
+    let (source, pos) = neighbor_cell(face_dir, slice, u, v);
+    let source = match source {
+        None => Some(light),
+        Some(idx) => neighbor_lights[idx],
+    };
+    match source {
+        Some(l) => (l.sky(pos[0], pos[1], pos[2]) << 4) | l.block(pos[0], pos[1], pos[2]),
+        None => MAX_LIGHT << 4,
+    }
+}
+
+/// Ambient occlusion level of a vertex with nothing around it. Levels run from
+/// 0 (a corner between two walls) up to this.
+const MAX_AO: u8 = 3;
+
+/// Whether a block is an opaque full cube, which darkens the corners of faces next
+/// to it and hides model faces against it.
+#[inline]
+fn occludes(state: Option<BlockState>) -> bool {
+    state.is_some_and(BlockState::is_opaque_cube)
+}
+
+/// Ambient occlusion of one vertex from the two cells along its edges and the cell
+/// diagonally between them. Two occluding sides hide the corner cell entirely.
+#[inline]
+fn vertex_ao(side1: bool, side2: bool, corner: bool) -> u8 {
+    if side1 && side2 {
+        0
+    } else {
+        MAX_AO - side1 as u8 - side2 as u8 - corner as u8
+    }
+}
+
+/// Ambient occlusion at the four corners of a face, in (u, v) order
+/// `[(0, 0), (1, 0), (1, 1), (0, 1)]`, from the cells around the one the face looks
+/// into, which may be in any of the chunks around this one. Cells in unloaded chunks
+/// don't occlude.
+fn face_ao(
+    chunk: &Chunk,
+    neighbors: &NeighborChunks,
+    face_dir: &FaceDir,
+    slice: usize,
+    u: usize,
+    v: usize,
+) -> [u8; 4] {
+    let layer = if face_dir.back_face { slice as i32 - 1 } else { slice as i32 + 1 };
+    let occluded = |du: i32, dv: i32| {
+        let mut pos = [0i32; 3];
+        pos[face_dir.axis] = layer;
+        pos[face_dir.u_axis] = u as i32 + du;
+        pos[face_dir.v_axis] = v as i32 + dv;
+        occludes(state_at_local(chunk, neighbors, pos[0], pos[1], pos[2]))
+    };
+    [(-1, -1), (1, -1), (1, 1), (-1, 1)]
+        .map(|(du, dv)| vertex_ao(occluded(du, 0), occluded(0, dv), occluded(du, dv)))
+}
+
+/// Triangle indices for a quad whose four vertices (from `base`) run counter-clockwise.
+/// The quad is split along the diagonal joining its more occluded pair of corners, so
+/// the occlusion gradient looks the same whichever way the quad is oriented.
+fn quad_indices(base: u32, ao: [u8; 4], flip_winding: bool) -> [u32; 6] {
+    let [a, b, c, d] = if ao[0] + ao[2] > ao[1] + ao[3] { [1, 2, 3, 0] } else { [0, 1, 2, 3] };
+    let tris = if flip_winding { [a, c, b, a, d, c] } else { [a, b, c, a, c, d] };
+    tris.map(|i| base + i)
+}
+
+/// Converts packed light into the vertex color read by the chunk shader:
+/// red = sky light, green = block light, blue = ambient occlusion, all normalized to 0..1.
+#[inline]
+fn light_color(packed: u8, ao: u8) -> [f32; 4] {
+    let max = MAX_LIGHT as f32;
+    [(packed >> 4) as f32 / max, (packed & 0x0F) as f32 / max, ao as f32 / MAX_AO as f32, 1.0]
+}
+
+/// Build a chunk's meshes using greedy meshing with face culling.
 ///
 /// Takes a reference to the chunk and optional neighbor chunks for cross-chunk
-/// face culling. Returns a Bevy `Mesh` with positions, normals, UVs, and indices.
-pub fn build_chunk_mesh(chunk: &Chunk, neighbors: &NeighborChunks) -> Mesh {
+/// face culling, plus the matching light data and the biome tints of its columns.
+/// Returns Bevy `Mesh`es with positions, normals, UVs, light and ambient occlusion baked
+/// into vertex colors, biome tints, and indices; fluids go into a separate translucent mesh.
+pub fn build_chunk_mesh(
+    chunk: &Chunk,
+    neighbors: &NeighborChunks,
+    light: &ChunkLight,
+    neighbor_lights: &NeighborLights,
+    tints: &ChunkTints,
+) -> ChunkMeshes {
     let mut positions: Vec<[f32; 3]> = Vec::new();
     let mut normals: Vec<[f32; 3]> = Vec::new();
     let mut uvs: Vec<[f32; 2]> = Vec::new();
     let mut uv1s: Vec<[f32; 2]> = Vec::new();
+    let mut colors: Vec<[f32; 4]> = Vec::new();
     let mut indices: Vec<u32> = Vec::new();
 
     if chunk.is_empty() {
-        return empty_mesh();
+        return ChunkMeshes { opaque: empty_mesh(), translucent: None };
     }
 
     let size = CHUNK_SIZE;
@@ -156,16 +310,21 @@ pub fn build_chunk_mesh(chunk: &Chunk, neighbors: &NeighborChunks) -> Mesh {
 
         // Sweep slices along the main axis
         for slice in 0..size {
-            // Build 2D mask: which block type (or None) has a visible face here
-            let mut mask = [[BlockType::Air; CHUNK_SIZE]; CHUNK_SIZE];
+            // Build 2D mask: which block state (or Air) has a visible face here, the packed
+            // light on that face and its corner occlusion. Only identical faces are merged.
+            let mut mask = [[(BlockState::AIR, 0u8, [0u8; 4]); CHUNK_SIZE]; CHUNK_SIZE];
 
             for v in 0..size {
                 for u in 0..size {
                     let block = get_block_from_pos(chunk, face_dir.axis, face_dir.u_axis, face_dir.v_axis, slice, u, v);
                     let neighbor = get_neighbor_block(chunk, neighbors, face_dir, slice, u, v);
 
-                    if should_emit_face(block, neighbor) {
-                        mask[v][u] = block;
+                    if should_emit_face(block.block(), neighbor) {
+                        mask[v][u] = (
+                            block,
+                            get_face_light(light, neighbor_lights, face_dir, slice, u, v),
+                            face_ao(chunk, neighbors, face_dir, slice, u, v),
+                        );
                     }
                 }
             }
@@ -174,21 +333,22 @@ pub fn build_chunk_mesh(chunk: &Chunk, neighbors: &NeighborChunks) -> Mesh {
             for v in 0..size {
                 let mut u = 0;
                 while u < size {
-                    let block = mask[v][u];
-                    if block == BlockType::Air {
+                    let cell = mask[v][u];
+                    let (block, face_light, ao) = cell;
+                    if block.block() == BlockType::Air {
                         u += 1;
                         continue;
                     }
 
                     // Transparent blocks (glass, water, leaves) must not be merged —
                     // merging removes internal faces between adjacent transparent blocks.
-                    let (w, h) = if block.is_transparent() {
-                        mask[v][u] = BlockType::Air;
+                    let (w, h) = if block.block().is_transparent() {
+                        mask[v][u] = (BlockState::AIR, 0, [0; 4]);
                         (1, 1)
                     } else {
                         // Greedy merge: expand width along u-axis
                         let mut w = 1;
-                        while u + w < size && mask[v][u + w] == block {
+                        while u + w < size && mask[v][u + w] == cell {
                             w += 1;
                         }
 
@@ -196,7 +356,7 @@ pub fn build_chunk_mesh(chunk: &Chunk, neighbors: &NeighborChunks) -> Mesh {
                         let mut h = 1;
                         'outer: while v + h < size {
                             for du in 0..w {
-                                if mask[v + h][u + du] != block {
+                                if mask[v + h][u + du] != cell {
                                     break 'outer;
                                 }
                             }
@@ -206,7 +366,7 @@ pub fn build_chunk_mesh(chunk: &Chunk, neighbors: &NeighborChunks) -> Mesh {
                         // Clear the merged region from the mask
                         for dv in 0..h {
                             for du in 0..w {
-                                mask[v + dv][u + du] = BlockType::Air;
+                                mask[v + dv][u + du] = (BlockState::AIR, 0, [0; 4]);
                             }
                         }
                         (w, h)
@@ -218,6 +378,7 @@ pub fn build_chunk_mesh(chunk: &Chunk, neighbors: &NeighborChunks) -> Mesh {
                         &mut normals,
                         &mut uvs,
                         &mut uv1s,
+                        &mut colors,
                         &mut indices,
                         face_dir,
                         slice,
@@ -227,6 +388,8 @@ pub fn build_chunk_mesh(chunk: &Chunk, neighbors: &NeighborChunks) -> Mesh {
                         h,
                         normal,
                         block,
+                        face_light,
+                        ao,
                     );
 
                     u += w;
@@ -235,39 +398,108 @@ pub fn build_chunk_mesh(chunk: &Chunk, neighbors: &NeighborChunks) -> Mesh {
         }
     }
 
-    // Second pass: cross-billboard meshes for non-cube blocks (torch, tallgrass, saplings, wheat)
+    // Second pass: block models (torches, plants, beds, doors, farmland)
     for y in 0..size {
         for z in 0..size {
             for x in 0..size {
-                let block = chunk.get(x, y, z);
-                if !block.is_non_cube() {
+                let state = chunk.get_state(x, y, z);
+                let Some(model) = block_model(state) else {
                     continue;
-                }
-
-                emit_cross_billboard(
+                };
+                emit_model(
                     &mut positions,
                     &mut normals,
                     &mut uvs,
                     &mut uv1s,
+                    &mut colors,
                     &mut indices,
-                    x as f32,
-                    y as f32,
-                    z as f32,
-                    block,
+                    chunk,
+                    neighbors,
+                    light,
+                    neighbor_lights,
+                    [x as i32, y as i32, z as i32],
+                    state,
+                    model,
                 );
             }
         }
     }
 
-    if positions.is_empty() {
-        return empty_mesh();
+    let opaque = assemble_mesh(positions, normals, uvs, uv1s, colors, indices, tints);
+
+    // Third pass: fluids, with surfaces sloped between neighboring flow levels,
+    // into their own mesh so they can be blended over the terrain behind them
+    let mut positions: Vec<[f32; 3]> = Vec::new();
+    let mut normals: Vec<[f32; 3]> = Vec::new();
+    let mut uvs: Vec<[f32; 2]> = Vec::new();
+    let mut uv1s: Vec<[f32; 2]> = Vec::new();
+    let mut colors: Vec<[f32; 4]> = Vec::new();
+    let mut indices: Vec<u32> = Vec::new();
+    for y in 0..size {
+        for z in 0..size {
+            for x in 0..size {
+                let state = chunk.get_state(x, y, z);
+                if !state.block().is_liquid() {
+                    continue;
+                }
+                emit_fluid_block(
+                    &mut positions,
+                    &mut normals,
+                    &mut uvs,
+                    &mut uv1s,
+                    &mut colors,
+                    &mut indices,
+                    chunk,
+                    neighbors,
+                    light,
+                    neighbor_lights,
+                    [x as i32, y as i32, z as i32],
+                    state,
+                );
+            }
+        }
     }
 
+    let translucent = if positions.is_empty() {
+        None
+    } else {
+        Some(assemble_mesh(positions, normals, uvs, uv1s, colors, indices, tints))
+    };
+
+    ChunkMeshes { opaque, translucent }
+}
+
+/// Biome tint of each vertex from the colormap of the tile it shows, at its column;
+/// white for tiles without one. Greedy quads only get tints at their corners, which is
+/// enough for climate that changes over hundreds of blocks.
+fn vertex_tints(positions: &[[f32; 3]], uv1s: &[[f32; 2]], tints: &ChunkTints) -> Vec<[f32; 3]> {
+    positions
+        .iter()
+        .zip(uv1s)
+        .map(|(p, &origin)| match tile_biome_tint(tile_at(origin)) {
+            Some(tint) => tints.tint(tint, p[0], p[2]).to_array(),
+            None => [1.0; 3],
+        })
+        .collect()
+}
+
+fn assemble_mesh(
+    positions: Vec<[f32; 3]>,
+    normals: Vec<[f32; 3]>,
+    uvs: Vec<[f32; 2]>,
+    uv1s: Vec<[f32; 2]>,
+    colors: Vec<[f32; 4]>,
+    indices: Vec<u32>,
+    tints: &ChunkTints,
+) -> Mesh {
+    let vertex_tints = vertex_tints(&positions, &uv1s, tints);
     Mesh::new(PrimitiveTopology::TriangleList, RenderAssetUsages::default())
         .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, positions)
         .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, normals)
         .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, uvs)
         .with_inserted_attribute(Mesh::ATTRIBUTE_UV_1, uv1s)
+        .with_inserted_attribute(Mesh::ATTRIBUTE_COLOR, colors)
+        .with_inserted_attribute(ATTRIBUTE_TINT, vertex_tints)
         .with_inserted_indices(Indices::U32(indices))
 }
 
@@ -278,6 +510,7 @@ fn emit_quad(
     normals: &mut Vec<[f32; 3]>,
     uvs: &mut Vec<[f32; 2]>,
     uv1s: &mut Vec<[f32; 2]>,
+    colors: &mut Vec<[f32; 4]>,
     indices: &mut Vec<u32>,
     face_dir: &FaceDir,
     slice: usize,
@@ -286,7 +519,9 @@ fn emit_quad(
     width: usize,
     height: usize,
     normal: [f32; 3],
-    block: BlockType,
+    block: BlockState,
+    light: u8,
+    ao: [u8; 4],
 ) {
     let base_index = positions.len() as u32;
 
@@ -362,96 +597,100 @@ fn emit_quad(
 
     // UV_1: tile origin [u_min, v_min] — same for all 4 vertices of this quad.
     // The fragment shader uses this to wrap tiling UVs within the correct atlas tile.
-    let [tile_u_min, tile_v_min, _, _] = tile_uvs(texture_index(block, face_dir.face));
+    let [tile_u_min, tile_v_min, _, _] = tile_uvs(block.texture_index(face_dir.face));
     let tile_origin = [tile_u_min, tile_v_min];
     uv1s.push(tile_origin);
     uv1s.push(tile_origin);
     uv1s.push(tile_origin);
     uv1s.push(tile_origin);
 
+    // Vertex color: baked sky/block light, uniform across the merged quad, and the
+    // corner occlusion, reordered from (u, v) corner order to match the vertices.
+    let vertex_ao = if face_dir.back_face {
+        [ao[0], ao[3], ao[2], ao[1]]
+    } else {
+        ao
+    };
+    for corner_ao in vertex_ao {
+        colors.push(light_color(light, corner_ao));
+    }
+
     // Two triangles per quad — flip winding for faces where the default
     // u_axis/v_axis ordering produces a clockwise winding instead of CCW.
-    if face_dir.flip_winding {
-        indices.push(base_index);
-        indices.push(base_index + 2);
-        indices.push(base_index + 1);
-        indices.push(base_index);
-        indices.push(base_index + 3);
-        indices.push(base_index + 2);
-    } else {
-        indices.push(base_index);
-        indices.push(base_index + 1);
-        indices.push(base_index + 2);
-        indices.push(base_index);
-        indices.push(base_index + 2);
-        indices.push(base_index + 3);
+    indices.extend_from_slice(&quad_indices(base_index, vertex_ao, face_dir.flip_winding));
+}
+
+/// Corners of one face of a box (in block units), as seen from outside:
+/// bottom-left, bottom-right, top-right, top-left. Counter-clockwise from outside.
+fn box_face_corners(min: Vec3, max: Vec3, face: Face) -> [Vec3; 4] {
+    let (x0, y0, z0) = (min.x, min.y, min.z);
+    let (x1, y1, z1) = (max.x, max.y, max.z);
+    let corners = match face {
+        Face::South => [[x0, y0, z1], [x1, y0, z1], [x1, y1, z1], [x0, y1, z1]],
+        Face::North => [[x1, y0, z0], [x0, y0, z0], [x0, y1, z0], [x1, y1, z0]],
+        Face::East => [[x1, y0, z1], [x1, y0, z0], [x1, y1, z0], [x1, y1, z1]],
+        Face::West => [[x0, y0, z0], [x0, y0, z1], [x0, y1, z1], [x0, y1, z0]],
+        Face::Top => [[x0, y1, z1], [x1, y1, z1], [x1, y1, z0], [x0, y1, z0]],
+        Face::Bottom => [[x0, y0, z0], [x1, y0, z0], [x1, y0, z1], [x0, y0, z1]],
+    };
+    corners.map(Vec3::from_array)
+}
+
+/// Whether a box face lies on the edge of its cell, where a neighboring cube hides it.
+fn on_cell_edge(min: Vec3, max: Vec3, face: Face) -> bool {
+    match face {
+        Face::Top => max.y >= 1.0,
+        Face::Bottom => min.y <= 0.0,
+        Face::North => min.z <= 0.0,
+        Face::South => max.z >= 1.0,
+        Face::East => max.x >= 1.0,
+        Face::West => min.x <= 0.0,
     }
 }
 
-/// Emit a cross-billboard (X-shaped) mesh for non-cube blocks like torches, tallgrass, saplings.
-/// Two diagonal quads intersecting at the block center, each rendered double-sided.
-fn emit_cross_billboard(
+/// Atlas UVs (bottom-left, bottom-right, top-right, top-left) for a region
+/// `[u_min, v_min, u_max, v_max]` of a tile, given in 0..1 of the tile.
+fn region_uvs(tile_index: u32, region: [f32; 4]) -> [[f32; 2]; 4] {
+    let [u_min, v_min, u_max, _] = tile_uvs(tile_index);
+    let size = u_max - u_min;
+    let at = |u: f32, v: f32| [u_min + u * size, v_min + v * size];
+    let [u0, v0, u1, v1] = region;
+    [at(u0, v1), at(u1, v1), at(u1, v0), at(u0, v0)]
+}
+
+/// Emit a block's model. Box faces are lit from the cell they face and skipped on the
+/// cell edge against a full cube; planes are drawn from both sides, lit from the
+/// block's own cell with an upward normal so they shade evenly.
+#[allow(clippy::too_many_arguments)]
+fn emit_model(
     positions: &mut Vec<[f32; 3]>,
     normals: &mut Vec<[f32; 3]>,
     uvs: &mut Vec<[f32; 2]>,
     uv1s: &mut Vec<[f32; 2]>,
+    colors: &mut Vec<[f32; 4]>,
     indices: &mut Vec<u32>,
-    bx: f32,
-    by: f32,
-    bz: f32,
-    block: BlockType,
+    chunk: &Chunk,
+    neighbors: &NeighborChunks,
+    light: &ChunkLight,
+    neighbor_lights: &NeighborLights,
+    [x, y, z]: [i32; 3],
+    state: BlockState,
+    model: &BlockModel,
 ) {
-    let tex_idx = texture_index(block, Face::South);
-    let tile = tile_uvs(tex_idx);
-    let tile_origin = [tile[0], tile[1]];
-
-    // UV corners: bottom-left, bottom-right, top-right, top-left
-    let uv_bl = [tile[0], tile[3]];
-    let uv_br = [tile[2], tile[3]];
-    let uv_tr = [tile[2], tile[1]];
-    let uv_tl = [tile[0], tile[1]];
-
-    // Inset so cross doesn't poke out of block boundaries
-    let inset = 0.15;
-    let lo = inset;
-    let hi = 1.0 - inset;
-
-    // Uniform upward normal for all billboard faces — gives even lighting from above
-    let normal = [0.0, 1.0, 0.0_f32];
-
-    // Quad 1 vertices: diagonal from (lo,0,lo) to (hi,1,hi)
-    let q1 = [
-        [bx + lo, by,       bz + lo], // bottom-left
-        [bx + hi, by,       bz + hi], // bottom-right
-        [bx + hi, by + 1.0, bz + hi], // top-right
-        [bx + lo, by + 1.0, bz + lo], // top-left
-    ];
-
-    // Quad 2 vertices: diagonal from (hi,0,lo) to (lo,1,hi)
-    let q2 = [
-        [bx + hi, by,       bz + lo], // bottom-left
-        [bx + lo, by,       bz + hi], // bottom-right
-        [bx + lo, by + 1.0, bz + hi], // top-right
-        [bx + hi, by + 1.0, bz + lo], // top-left
-    ];
-
-    let front_uvs = [uv_bl, uv_br, uv_tr, uv_tl];
-    let back_uvs = [uv_br, uv_bl, uv_tl, uv_tr]; // horizontally mirrored for back face
-
-    // Helper: emit one side of a quad (front or back)
-    // Front face: 0,1,2, 0,2,3 (CCW)
-    // Back face:  0,2,1, 0,3,2 (CW = reversed)
-    let mut emit_side = |verts: &[[f32; 3]; 4], face_uvs: &[[f32; 2]; 4], front: bool| {
+    let origin = Vec3::new(x as f32, y as f32, z as f32);
+    let mut emit = |verts: [Vec3; 4], face_uvs: [[f32; 2]; 4], normal: [f32; 3], tile: u32, packed_light: u8, front: bool| {
         let base = positions.len() as u32;
-        for &v in verts {
-            positions.push(v);
+        let [tile_u, tile_v, _, _] = tile_uvs(tile);
+        let color = light_color(packed_light, MAX_AO);
+        for v in verts {
+            positions.push((origin + v).to_array());
         }
+        uvs.extend_from_slice(&face_uvs);
         for _ in 0..4 {
             normals.push(normal);
-            uv1s.push(tile_origin);
+            uv1s.push([tile_u, tile_v]);
+            colors.push(color);
         }
-        uvs.extend_from_slice(face_uvs);
-
         if front {
             indices.extend_from_slice(&[base, base + 1, base + 2, base, base + 2, base + 3]);
         } else {
@@ -459,13 +698,238 @@ fn emit_cross_billboard(
         }
     };
 
-    // Quad 1: front and back
-    emit_side(&q1, &front_uvs, true);
-    emit_side(&q1, &back_uvs, false);
+    for model_box in &model.boxes {
+        for (face, region) in MODEL_FACES.iter().zip(model_box.faces) {
+            let Some(region) = region else {
+                continue;
+            };
+            let normal = face.normal();
+            let [nx, ny, nz] = normal.map(|n| n as i32);
+            let (ax, ay, az) = (x + nx, y + ny, z + nz);
+            if on_cell_edge(model_box.min, model_box.max, *face)
+                && occludes(state_at_local(chunk, neighbors, ax, ay, az))
+            {
+                continue;
+            }
+            let tile = state.texture_index(*face);
+            emit(
+                box_face_corners(model_box.min, model_box.max, *face),
+                region_uvs(tile, region),
+                normal,
+                tile,
+                light_at_local(light, neighbor_lights, ax, ay, az),
+                true,
+            );
+        }
+    }
+
+    let own_light = light_at_local(light, neighbor_lights, x, y, z);
+    for plane in &model.planes {
+        let tile = state.texture_index(plane.texture);
+        let front_uvs = region_uvs(tile, plane.uv);
+        // Horizontally mirrored for the back face
+        let [bl, br, tr, tl] = front_uvs;
+        let back_uvs = [br, bl, tl, tr];
+        emit(plane.corners, front_uvs, [0.0, 1.0, 0.0], tile, own_light, true);
+        emit(plane.corners, back_uvs, [0.0, 1.0, 0.0], tile, own_light, false);
+    }
+}
+
+/// Index into `NeighborChunks` for a cell up to one block outside the chunk, `None` for
+/// the chunk itself, and the cell's coordinates within that chunk.
+#[inline]
+fn local_cell(x: i32, y: i32, z: i32) -> (Option<usize>, [usize; 3]) {
+    let size = CHUNK_SIZE as i32;
+    let side = |c: i32| if c < 0 { -1 } else if c >= size { 1 } else { 0 };
+    let offset = IVec3::new(side(x), side(y), side(z));
+    let idx = NEIGHBOR_OFFSETS.iter().position(|&o| o == offset);
+    let wrap = |c: i32| c.rem_euclid(size) as usize;
+    (idx, [wrap(x), wrap(y), wrap(z)])
+}
+
+/// Block state at local coordinates that may be one block outside the chunk.
+/// `None` when the cell is in an unloaded neighbor.
+#[inline]
+fn state_at_local(chunk: &Chunk, neighbors: &NeighborChunks, x: i32, y: i32, z: i32) -> Option<BlockState> {
+    match local_cell(x, y, z) {
+        (None, [lx, ly, lz]) => Some(chunk.get_state(lx, ly, lz)),
+        (Some(idx), [lx, ly, lz]) => neighbors[idx].map(|c| c.get_state(lx, ly, lz)),
+    }
+}
 
-    // Quad 2: front and back
-    emit_side(&q2, &front_uvs, true);
-    emit_side(&q2, &back_uvs, false);
+/// Packed light at local coordinates that may be one block outside the chunk.
+/// Unloaded neighbors, and the edge and corner ones light isn't passed for, get full
+/// sky light, like `get_face_light`.
+#[inline]
+fn light_at_local(light: &ChunkLight, neighbor_lights: &NeighborLights, x: i32, y: i32, z: i32) -> u8 {
+    let (cell, [lx, ly, lz]) = local_cell(x, y, z);
+    let source = match cell {
+        None => Some(light),
+        Some(idx) => neighbor_lights.get(idx).copied().flatten(),
+    };
+    match source {
+        Some(l) => (l.sky(lx, ly, lz) << 4) | l.block(lx, ly, lz),
+        None => MAX_LIGHT << 4,
+    }
+}
+
+/// Height of a fluid surface at the block corner (cx, cz): the average over the cells
+/// sharing that corner, where fluid counts its own height, open cells count as zero and
+/// solid cells are skipped. Full height if any of the fluid cells has fluid above it.
+fn fluid_corner_height(chunk: &Chunk, neighbors: &NeighborChunks, cx: i32, y: i32, cz: i32) -> f32 {
+    let mut total = 0.0;
+    let mut count = 0;
+    for (x, z) in [(cx - 1, cz - 1), (cx, cz - 1), (cx - 1, cz), (cx, cz)] {
+        let Some(state) = state_at_local(chunk, neighbors, x, y, z) else {
+            continue;
+        };
+        if state.block().is_liquid() {
+            if state_at_local(chunk, neighbors, x, y + 1, z).is_some_and(|s| s.block().is_liquid()) {
+                return 1.0;
+            }
+            total += fluid_height(state);
+            count += 1;
+        } else if !state.is_solid() {
+            count += 1;
+        }
+    }
+    if count == 0 { 0.0 } else { total / count as f32 }
+}
+
+/// Water blocks counted by the depth tint; deeper columns are tinted the same.
+const MAX_TINT_DEPTH: i32 = 8;
+
+/// How far the fluid column goes down from (and including) a fluid block, as 0..1 of
+/// `MAX_TINT_DEPTH`. Continues into the chunk below when it is loaded.
+fn fluid_depth(chunk: &Chunk, neighbors: &NeighborChunks, x: usize, y: i32, z: usize) -> f32 {
+    let mut depth = 0;
+    while depth < MAX_TINT_DEPTH {
+        let cy = y - depth;
+        let state = if cy >= 0 {
+            Some(chunk.get_state(x, cy as usize, z))
+        } else {
+            neighbors[3].map(|below| below.get_state(x, (cy + CHUNK_SIZE as i32) as usize, z))
+        };
+        if !state.is_some_and(|s| s.block().is_liquid()) {
+            break;
+        }
+        depth += 1;
+    }
+    depth as f32 / MAX_TINT_DEPTH as f32
+}
+
+/// Whether a fluid face toward `neighbor` can be seen: not toward more fluid, and not
+/// toward a block that fully hides it. Unloaded neighbors count as air.
+fn fluid_face_visible(neighbor: Option<BlockState>) -> bool {
+    match neighbor {
+        None => true,
+        Some(n) => !n.block().is_liquid() && (n.block().is_transparent() || !n.is_solid()),
+    }
+}
+
+/// Emit the visible faces of one fluid block. The top surface follows the corner heights
+/// so flowing water slopes down towards lower levels; side faces stop at the same heights.
+/// The vertex color alpha carries the column depth for the water shader's tint.
+#[allow(clippy::too_many_arguments)]
+fn emit_fluid_block(
+    positions: &mut Vec<[f32; 3]>,
+    normals: &mut Vec<[f32; 3]>,
+    uvs: &mut Vec<[f32; 2]>,
+    uv1s: &mut Vec<[f32; 2]>,
+    colors: &mut Vec<[f32; 4]>,
+    indices: &mut Vec<u32>,
+    chunk: &Chunk,
+    neighbors: &NeighborChunks,
+    light: &ChunkLight,
+    neighbor_lights: &NeighborLights,
+    [x, y, z]: [i32; 3],
+    state: BlockState,
+) {
+    let (fx, fy, fz) = (x as f32, y as f32, z as f32);
+    let depth = fluid_depth(chunk, neighbors, x as usize, y, z as usize);
+    // Corner heights indexed by [dx][dz]
+    let heights = [
+        [fluid_corner_height(chunk, neighbors, x, y, z), fluid_corner_height(chunk, neighbors, x, y, z + 1)],
+        [fluid_corner_height(chunk, neighbors, x + 1, y, z), fluid_corner_height(chunk, neighbors, x + 1, y, z + 1)],
+    ];
+
+    let mut emit = |face: Face, verts: [[f32; 3]; 4], top_heights: Option<[f32; 2]>, cell: [i32; 3]| {
+        let base = positions.len() as u32;
+        let [bl, br, mut tr, mut tl] = face_uvs(state, face);
+        // Side faces only show the part of the texture up to the water line
+        if let Some([right, left]) = top_heights {
+            tr[1] = br[1] - (br[1] - tr[1]) * right;
+            tl[1] = bl[1] - (bl[1] - tl[1]) * left;
+        }
+        let [tile_u, tile_v, _, _] = tile_uvs(state.texture_index(face));
+        let mut color = light_color(light_at_local(light, neighbor_lights, cell[0], cell[1], cell[2]), MAX_AO);
+        color[3] = depth;
+        positions.extend_from_slice(&verts);
+        uvs.extend_from_slice(&[bl, br, tr, tl]);
+        for _ in 0..4 {
+            normals.push(face.normal());
+            uv1s.push([tile_u, tile_v]);
+            colors.push(color);
+        }
+        indices.extend_from_slice(&[base, base + 1, base + 2, base, base + 2, base + 3]);
+    };
+
+    // Top: skipped under more fluid, where the surface is a full block anyway
+    let above = state_at_local(chunk, neighbors, x, y + 1, z);
+    if !above.is_some_and(|s| s.block().is_liquid()) {
+        emit(
+            Face::Top,
+            [
+                [fx, fy + heights[0][0], fz],
+                [fx, fy + heights[0][1], fz + 1.0],
+                [fx + 1.0, fy + heights[1][1], fz + 1.0],
+                [fx + 1.0, fy + heights[1][0], fz],
+            ],
+            None,
+            [x, y + 1, z],
+        );
+    }
+
+    if fluid_face_visible(state_at_local(chunk, neighbors, x, y - 1, z)) {
+        emit(
+            Face::Bottom,
+            [
+                [fx, fy, fz],
+                [fx + 1.0, fy, fz],
+                [fx + 1.0, fy, fz + 1.0],
+                [fx, fy, fz + 1.0],
+            ],
+            None,
+            [x, y - 1, z],
+        );
+    }
+
+    // Sides, each as (face, neighbor offset, left corner, right corner) seen from outside
+    let sides = [
+        (Face::East, [1, 0], [1, 1], [1, 0]),
+        (Face::West, [-1, 0], [0, 0], [0, 1]),
+        (Face::South, [0, 1], [0, 1], [1, 1]),
+        (Face::North, [0, -1], [1, 0], [0, 0]),
+    ];
+    for (face, [ox, oz], [lx, lz], [rx, rz]) in sides {
+        if !fluid_face_visible(state_at_local(chunk, neighbors, x + ox, y, z + oz)) {
+            continue;
+        }
+        let left_h = heights[lx][lz];
+        let right_h = heights[rx][rz];
+        let (lx, lz, rx, rz) = (fx + lx as f32, fz + lz as f32, fx + rx as f32, fz + rz as f32);
+        emit(
+            face,
+            [
+                [lx, fy, lz],
+                [rx, fy, rz],
+                [rx, fy + right_h, rz],
+                [lx, fy + left_h, lz],
+            ],
+            Some([right_h, left_h]),
+            [x + ox, y, z + oz],
+        );
+    }
 }
 
 fn empty_mesh() -> Mesh {
@@ -474,5 +938,218 @@ fn empty_mesh() -> Mesh {
         .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, Vec::<[f32; 3]>::new())
         .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, Vec::<[f32; 2]>::new())
         .with_inserted_attribute(Mesh::ATTRIBUTE_UV_1, Vec::<[f32; 2]>::new())
+        .with_inserted_attribute(Mesh::ATTRIBUTE_COLOR, Vec::<[f32; 4]>::new())
+        .with_inserted_attribute(ATTRIBUTE_TINT, Vec::<[f32; 3]>::new())
         .with_inserted_indices(Indices::U32(Vec::new()))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::mesh::VertexAttributeValues;
+
+    const NO_NEIGHBORS: NeighborChunks<'static> = [None; 26];
+
+    fn top_face_ao(chunk: &Chunk, neighbors: &NeighborChunks, x: usize, y: usize, z: usize) -> [u8; 4] {
+        let top = &FACE_DIRS[2];
+        face_ao(chunk, neighbors, top, y, x, z)
+    }
+
+    /// Ambient occlusion level of every up-facing vertex at height `y`, keyed by (x, z).
+    fn top_vertex_ao(mesh: &Mesh, y: f32) -> Vec<([f32; 2], u8)> {
+        let Some(VertexAttributeValues::Float32x3(positions)) = mesh.attribute(Mesh::ATTRIBUTE_POSITION) else {
+            panic!("mesh has no positions");
+        };
+        let Some(VertexAttributeValues::Float32x3(normals)) = mesh.attribute(Mesh::ATTRIBUTE_NORMAL) else {
+            panic!("mesh has no normals");
+        };
+        let Some(VertexAttributeValues::Float32x4(colors)) = mesh.attribute(Mesh::ATTRIBUTE_COLOR) else {
+            panic!("mesh has no colors");
+        };
+        positions
+            .iter()
+            .zip(normals)
+            .zip(colors)
+            .filter(|((p, n), _)| p[1] == y && n[1] == 1.0)
+            .map(|((p, _), c)| ([p[0], p[2]], (c[2] * MAX_AO as f32).round() as u8))
+            .collect()
+    }
+
+    #[test]
+    fn open_face_is_unoccluded() {
+        let mut chunk = Chunk::default();
+        chunk.set(5, 5, 5, BlockType::Stone);
+        assert_eq!(top_face_ao(&chunk, &NO_NEIGHBORS, 5, 5, 5), [MAX_AO; 4]);
+    }
+
+    #[test]
+    fn neighboring_blocks_darken_shared_corners() {
+        let mut chunk = Chunk::default();
+        chunk.set(5, 5, 5, BlockType::Stone);
+        // One block up and east of the face: darkens the two +X corners
+        chunk.set(6, 6, 5, BlockType::Stone);
+        assert_eq!(top_face_ao(&chunk, &NO_NEIGHBORS, 5, 5, 5), [3, 2, 2, 3]);
+
+        // A second wall to the south closes the (+X, +Z) corner off completely
+        chunk.set(5, 6, 6, BlockType::Stone);
+        assert_eq!(top_face_ao(&chunk, &NO_NEIGHBORS, 5, 5, 5), [3, 2, 0, 2]);
+
+        // A lone diagonal block only touches one corner
+        let mut chunk = Chunk::default();
+        chunk.set(5, 5, 5, BlockType::Stone);
+        chunk.set(4, 6, 4, BlockType::Stone);
+        assert_eq!(top_face_ao(&chunk, &NO_NEIGHBORS, 5, 5, 5), [2, 3, 3, 3]);
+    }
+
+    #[test]
+    fn transparent_blocks_do_not_occlude() {
+        let mut chunk = Chunk::default();
+        chunk.set(5, 5, 5, BlockType::Stone);
+        chunk.set(6, 6, 5, BlockType::Glass);
+        chunk.set(5, 6, 6, BlockType::Water);
+        assert_eq!(top_face_ao(&chunk, &NO_NEIGHBORS, 5, 5, 5), [MAX_AO; 4]);
+    }
+
+    #[test]
+    fn occlusion_reads_neighbor_chunks() {
+        let mut chunk = Chunk::default();
+        chunk.set(CHUNK_SIZE - 1, 5, 5, BlockType::Stone);
+        let mut east = Chunk::default();
+        east.set(0, 6, 5, BlockType::Stone);
+        let mut neighbors = NO_NEIGHBORS;
+        neighbors[0] = Some(&east);
+        assert_eq!(top_face_ao(&chunk, &neighbors, CHUNK_SIZE - 1, 5, 5), [3, 2, 2, 3]);
+    }
+
+    #[test]
+    fn occlusion_reads_edge_and_corner_chunks() {
+        // A face in the chunk's top corner, at the (+X, +Z) edge of its top face
+        let last = CHUNK_SIZE - 1;
+        let mut chunk = Chunk::default();
+        chunk.set(last, last, last, BlockType::Stone);
+        let neighbor_at = |offset: IVec3| NEIGHBOR_OFFSETS.iter().position(|&o| o == offset).unwrap();
+
+        // Only the chunk diagonally above and to the east and south holds the corner cell
+        let mut corner = Chunk::default();
+        corner.set(0, 0, 0, BlockType::Stone);
+        let mut neighbors = NO_NEIGHBORS;
+        neighbors[neighbor_at(IVec3::new(1, 1, 1))] = Some(&corner);
+        assert_eq!(top_face_ao(&chunk, &neighbors, last, last, last), [3, 3, 2, 3]);
+
+        // Side cells across the chunk's top edges darken both corners they touch
+        let mut east_above = Chunk::default();
+        east_above.set(0, 0, last, BlockType::Stone);
+        let mut south_above = Chunk::default();
+        south_above.set(last, 0, 0, BlockType::Stone);
+        neighbors[neighbor_at(IVec3::new(1, 1, 0))] = Some(&east_above);
+        neighbors[neighbor_at(IVec3::new(0, 1, 1))] = Some(&south_above);
+        assert_eq!(top_face_ao(&chunk, &neighbors, last, last, last), [3, 2, 0, 2]);
+    }
+
+    #[test]
+    fn quads_split_along_more_occluded_diagonal() {
+        // One dark corner: the diagonal runs through it
+        assert_eq!(quad_indices(0, [0, 3, 3, 3], false), [0, 1, 2, 0, 2, 3]);
+        assert_eq!(quad_indices(0, [3, 0, 3, 3], false), [1, 2, 3, 1, 3, 0]);
+        // Winding flips keep the same diagonal
+        assert_eq!(quad_indices(4, [3, 0, 3, 3], true), [5, 7, 6, 5, 4, 7]);
+    }
+
+    #[test]
+    fn greedy_merge_stops_at_occlusion_changes() {
+        let mut chunk = Chunk::default();
+        for x in 0..CHUNK_SIZE {
+            for z in 0..CHUNK_SIZE {
+                chunk.set(x, 0, z, BlockType::Stone);
+            }
+        }
+        let light = ChunkLight::default();
+        let no_lights: NeighborLights = [None; 6];
+
+        // A flat floor is a single quad with no occlusion
+        let mesh = build_chunk_mesh(&chunk, &NO_NEIGHBORS, &light, &no_lights, &ChunkTints::default()).opaque;
+        let floor = top_vertex_ao(&mesh, 1.0);
+        assert_eq!(floor.len(), 4);
+        assert!(floor.iter().all(|&(_, ao)| ao == MAX_AO));
+
+        // A pillar in the middle darkens the floor around its base
+        chunk.set(8, 1, 8, BlockType::Stone);
+        let mesh = build_chunk_mesh(&chunk, &NO_NEIGHBORS, &light, &no_lights, &ChunkTints::default()).opaque;
+        let floor = top_vertex_ao(&mesh, 1.0);
+        assert!(floor.len() > 4);
+        for &(pos, ao) in &floor {
+            let touches_pillar = (8.0..=9.0).contains(&pos[0]) && (8.0..=9.0).contains(&pos[1]);
+            assert_eq!(ao < MAX_AO, touches_pillar, "floor vertex at {pos:?} has occlusion {ao}");
+        }
+    }
+
+    #[test]
+    fn fluids_get_their_own_mesh_without_inner_faces() {
+        let mut chunk = Chunk::default();
+        chunk.set(5, 5, 5, BlockType::Water);
+        chunk.set(6, 5, 5, BlockType::Water);
+        let light = ChunkLight::default();
+        let no_lights: NeighborLights = [None; 6];
+
+        let meshes = build_chunk_mesh(&chunk, &NO_NEIGHBORS, &light, &no_lights, &ChunkTints::default());
+        let Some(VertexAttributeValues::Float32x3(opaque)) = meshes.opaque.attribute(Mesh::ATTRIBUTE_POSITION) else {
+            panic!("mesh has no positions");
+        };
+        assert!(opaque.is_empty());
+
+        let water = meshes.translucent.expect("water should be meshed");
+        let Some(VertexAttributeValues::Float32x3(positions)) = water.attribute(Mesh::ATTRIBUTE_POSITION) else {
+            panic!("mesh has no positions");
+        };
+        // Two blocks of five faces each: the pair of faces between them is culled
+        assert_eq!(positions.len(), 10 * 4);
+
+        chunk.set(5, 5, 5, BlockType::Stone);
+        chunk.set(6, 5, 5, BlockType::Stone);
+        assert!(build_chunk_mesh(&chunk, &NO_NEIGHBORS, &light, &no_lights, &ChunkTints::default()).translucent.is_none());
+    }
+
+    #[test]
+    fn water_depth_counts_down_the_column() {
+        let mut chunk = Chunk::default();
+        let mut below = Chunk::default();
+        for y in 0..3 {
+            chunk.set(5, y, 5, BlockType::Water);
+        }
+        assert_eq!(fluid_depth(&chunk, &NO_NEIGHBORS, 5, 2, 5), 3.0 / MAX_TINT_DEPTH as f32);
+
+        // The column continues into the chunk below, up to the tint limit
+        for y in 0..CHUNK_SIZE {
+            below.set(5, y, 5, BlockType::Water);
+        }
+        let mut neighbors = NO_NEIGHBORS;
+        neighbors[3] = Some(&below);
+        assert_eq!(fluid_depth(&chunk, &neighbors, 5, 2, 5), 1.0);
+    }
+
+    #[test]
+    fn models_keep_their_shape_and_neighbor_faces() {
+        let mut chunk = Chunk::default();
+        chunk.set(5, 5, 5, BlockType::Bed);
+        chunk.set(6, 5, 5, BlockType::Stone);
+        let light = ChunkLight::default();
+        let no_lights: NeighborLights = [None; 6];
+
+        let mesh = build_chunk_mesh(&chunk, &NO_NEIGHBORS, &light, &no_lights, &ChunkTints::default()).opaque;
+        let Some(VertexAttributeValues::Float32x3(positions)) = mesh.attribute(Mesh::ATTRIBUTE_POSITION) else {
+            panic!("mesh has no positions");
+        };
+        let Some(VertexAttributeValues::Float32x3(normals)) = mesh.attribute(Mesh::ATTRIBUTE_NORMAL) else {
+            panic!("mesh has no normals");
+        };
+        let has_face = |normal: [f32; 3], on_plane: &dyn Fn(&[f32; 3]) -> bool| {
+            positions.iter().zip(normals).any(|(p, n)| *n == normal && on_plane(p))
+        };
+
+        // The bed's top sits at 9/16 of the block
+        assert!(has_face([0.0, 1.0, 0.0], &|p| p[1] == 5.0 + 9.0 / 16.0));
+        // The stone still shows its face toward the bed; the bed's side against it is hidden
+        assert!(has_face([-1.0, 0.0, 0.0], &|p| p[0] == 6.0));
+        assert!(!has_face([1.0, 0.0, 0.0], &|p| p[0] == 6.0));
+    }
+}
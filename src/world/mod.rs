@@ -1,13 +1,21 @@
 pub mod chunk;
+pub mod colormap;
 pub mod coordinates;
+pub mod fluid;
 pub mod generation;
+pub mod light;
+pub mod lod;
 pub mod manager;
 pub mod material;
 pub mod meshing;
+pub mod visibility;
 
 use bevy::prelude::*;
 use bevy::pbr::MaterialPlugin;
 
+use chunk::CHUNK_SIZE;
+use crate::save::level::{check_height, LevelData, DEFAULT_HEIGHT, DEFAULT_MIN_Y, MAX_HEIGHT};
+
 /// Resource holding the world seed. Changing this and clearing chunks triggers a new world.
 #[derive(Resource)]
 pub struct WorldSeed(pub u32);
@@ -18,21 +26,91 @@ impl Default for WorldSeed {
     }
 }
 
+/// Vertical extent of the world: block Y from `min_y` (the bedrock floor) up to, but not
+/// including, `min_y + height`. Both are whole chunks. Comes from the world's level file.
+#[derive(Resource, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WorldHeight {
+    pub min_y: i32,
+    pub height: i32,
+}
+
+impl Default for WorldHeight {
+    fn default() -> Self {
+        Self::new(DEFAULT_MIN_Y, DEFAULT_HEIGHT)
+    }
+}
+
+impl WorldHeight {
+    /// Rounds both values down to whole chunks, keeping the height between one chunk and
+    /// `MAX_HEIGHT`.
+    pub fn new(min_y: i32, height: i32) -> Self {
+        let size = CHUNK_SIZE as i32;
+        Self {
+            min_y: min_y.div_euclid(size) * size,
+            height: (height.div_euclid(size) * size).clamp(size, MAX_HEIGHT),
+        }
+    }
+
+    /// The level's height. New worlds are checked when created, but a hand-edited level
+    /// file may not be whole chunks; that is rounded, with a warning.
+    pub fn of_level(level: &LevelData) -> Self {
+        if let Err(e) = check_height(level.min_y, level.height) {
+            warn!("Level '{}': {}; rounding to whole chunks", level.name, e);
+        }
+        Self::new(level.min_y, level.height)
+    }
+
+    /// One past the highest block Y.
+    pub fn max_y(self) -> i32 {
+        self.min_y + self.height
+    }
+
+    /// Lowest chunk layer.
+    pub fn min_chunk_y(self) -> i32 {
+        self.min_y / CHUNK_SIZE as i32
+    }
+
+    /// One past the highest chunk layer.
+    pub fn max_chunk_y(self) -> i32 {
+        self.max_y() / CHUNK_SIZE as i32
+    }
+
+    pub fn chunk_layers(self) -> i32 {
+        self.height / CHUNK_SIZE as i32
+    }
+}
+
 pub struct WorldPlugin;
 
 impl Plugin for WorldPlugin {
     fn build(&self, app: &mut App) {
         app.add_plugins(MaterialPlugin::<material::ChunkMaterialType>::default())
+            .add_plugins(MaterialPlugin::<material::WaterMaterialType>::default())
+            .add_plugins(MaterialPlugin::<material::FarTerrainMaterialType>::default())
             .init_resource::<WorldSeed>()
+            .init_resource::<WorldHeight>()
+            .init_resource::<manager::CaveCulling>()
+            .init_resource::<manager::FireTracker>()
             .add_systems(Startup, manager::setup_world)
             .add_systems(
                 Update,
                 (
                     manager::update_chunk_loading,
-                    manager::start_mesh_tasks.after(manager::update_chunk_loading),
+                    manager::apply_loaded_chunks.after(manager::update_chunk_loading),
+                    manager::remesh_light_changes.after(manager::apply_loaded_chunks),
+                    manager::start_mesh_tasks.after(manager::remesh_light_changes),
                     manager::apply_mesh_results.after(manager::start_mesh_tasks),
+                    manager::cull_hidden_chunks.after(manager::apply_mesh_results),
+                    manager::update_far_terrain,
+                    manager::apply_far_terrain
+                        .after(manager::update_far_terrain)
+                        .after(manager::apply_mesh_results),
                     manager::update_sapling_growth,
                     manager::update_crop_growth,
+                    manager::update_fires,
+                    manager::update_fluids,
+                    manager::refresh_atlas_materials,
+                    manager::sync_animation_clock.after(manager::refresh_atlas_materials),
                 ),
             );
     }
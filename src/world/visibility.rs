@@ -0,0 +1,198 @@
+use bevy::prelude::*;
+use std::collections::{HashSet, VecDeque};
+
+use crate::world::chunk::{Chunk, CHUNK_SIZE, CHUNK_VOLUME};
+
+/// Directions to a chunk's neighbors, in the order `ChunkVisibility` numbers faces.
+/// Opposite faces are paired, so `face ^ 1` is the face across from `face`.
+pub const FACE_DIRECTIONS: [IVec3; 6] = [IVec3::X, IVec3::NEG_X, IVec3::Y, IVec3::NEG_Y, IVec3::Z, IVec3::NEG_Z];
+
+/// Which pairs of a chunk's faces are joined by a path through blocks that don't block
+/// sight, so something seen through one face may show through the other.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChunkVisibility(u64);
+
+impl ChunkVisibility {
+    /// Every face sees every other, as in an open chunk.
+    pub const ALL: Self = Self((1 << 36) - 1);
+    /// No face sees another, as in a solid chunk.
+    pub const NONE: Self = Self(0);
+
+    pub fn connects(self, a: usize, b: usize) -> bool {
+        self.0 & (1 << (a * 6 + b)) != 0
+    }
+
+    /// Join every pair of the faces set in `faces`.
+    fn connect_all(&mut self, faces: u8) {
+        for a in 0..6 {
+            for b in 0..6 {
+                if faces & (1 << a) != 0 && faces & (1 << b) != 0 {
+                    self.0 |= 1 << (a * 6 + b);
+                }
+            }
+        }
+    }
+}
+
+/// Faces of the chunk a block touches, as a bit set.
+fn faces_touched(x: usize, y: usize, z: usize) -> u8 {
+    let last = CHUNK_SIZE - 1;
+    let mut faces = 0;
+    for (face, touches) in [x == last, x == 0, y == last, y == 0, z == last, z == 0].into_iter().enumerate() {
+        if touches {
+            faces |= 1 << face;
+        }
+    }
+    faces
+}
+
+/// Flood fill the chunk's see-through blocks and join the faces each open region reaches.
+/// Cheap enough to run alongside meshing on the async pool.
+pub fn compute_visibility(chunk: &Chunk) -> ChunkVisibility {
+    if chunk.is_empty() {
+        return ChunkVisibility::ALL;
+    }
+    let size = CHUNK_SIZE;
+    let index = Chunk::index;
+    let mut visited = vec![false; CHUNK_VOLUME];
+    let mut visibility = ChunkVisibility::NONE;
+    let mut stack = Vec::new();
+
+    for y in 0..size {
+        for z in 0..size {
+            for x in 0..size {
+                if visited[index(x, y, z)] || chunk.get_state(x, y, z).is_opaque_cube() {
+                    continue;
+                }
+                visited[index(x, y, z)] = true;
+                stack.push((x, y, z));
+                let mut faces = 0;
+                while let Some((x, y, z)) = stack.pop() {
+                    faces |= faces_touched(x, y, z);
+                    for dir in FACE_DIRECTIONS {
+                        let (nx, ny, nz) = (x as i32 + dir.x, y as i32 + dir.y, z as i32 + dir.z);
+                        let inside = |c: i32| (0..size as i32).contains(&c);
+                        if !(inside(nx) && inside(ny) && inside(nz)) {
+                            continue;
+                        }
+                        let (nx, ny, nz) = (nx as usize, ny as usize, nz as usize);
+                        if !visited[index(nx, ny, nz)] && !chunk.get_state(nx, ny, nz).is_opaque_cube() {
+                            visited[index(nx, ny, nz)] = true;
+                            stack.push((nx, ny, nz));
+                        }
+                    }
+                }
+                visibility.connect_all(faces);
+            }
+        }
+    }
+    visibility
+}
+
+/// Chunks that may be seen from the camera's chunk, found by walking outward from it,
+/// leaving each chunk only through a face connected to the one it was entered by. Like
+/// Minecraft, a walk never steps back against a direction it has already taken, so it
+/// can't wind around behind walls. `graph` gives each loaded chunk's visibility and
+/// `None` for chunks that aren't loaded; the result is `None` when the camera's chunk
+/// isn't loaded and nothing can be ruled out.
+pub fn visible_chunks(start: IVec3, graph: impl Fn(IVec3) -> Option<ChunkVisibility>) -> Option<HashSet<IVec3>> {
+    graph(start)?;
+    let mut visible = HashSet::from([start]);
+    // (chunk, face it was entered by, directions taken to reach it)
+    let mut queue = VecDeque::from([(start, None::<usize>, 0u8)]);
+
+    while let Some((pos, entered, taken)) = queue.pop_front() {
+        let visibility = graph(pos).unwrap_or(ChunkVisibility::ALL);
+        for (face, dir) in FACE_DIRECTIONS.iter().enumerate() {
+            if taken & (1 << (face ^ 1)) != 0 {
+                continue;
+            }
+            if entered.is_some_and(|entered| !visibility.connects(entered, face)) {
+                continue;
+            }
+            let next = pos + *dir;
+            if visible.contains(&next) || graph(next).is_none() {
+                continue;
+            }
+            visible.insert(next);
+            queue.push_back((next, Some(face ^ 1), taken | (1 << face)));
+        }
+    }
+    Some(visible)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block::BlockType;
+    use std::collections::HashMap;
+
+    fn filled(block: BlockType) -> Chunk {
+        let mut chunk = Chunk::default();
+        for y in 0..CHUNK_SIZE {
+            for z in 0..CHUNK_SIZE {
+                for x in 0..CHUNK_SIZE {
+                    chunk.set(x, y, z, block);
+                }
+            }
+        }
+        chunk
+    }
+
+    #[test]
+    fn open_and_solid_chunks() {
+        assert_eq!(compute_visibility(&Chunk::default()), ChunkVisibility::ALL);
+        assert_eq!(compute_visibility(&filled(BlockType::Stone)), ChunkVisibility::NONE);
+        // Glass and leaves are seen through
+        assert_eq!(compute_visibility(&filled(BlockType::Glass)), ChunkVisibility::ALL);
+    }
+
+    #[test]
+    fn walls_split_the_chunk() {
+        // A stone wall across the middle, from west to east
+        let mut chunk = Chunk::default();
+        for y in 0..CHUNK_SIZE {
+            for x in 0..CHUNK_SIZE {
+                chunk.set(x, y, 8, BlockType::Stone);
+            }
+        }
+        let visibility = compute_visibility(&chunk);
+        let (east, west, up, south, north) = (0, 1, 2, 4, 5);
+        assert!(!visibility.connects(south, north));
+        assert!(visibility.connects(east, west));
+        assert!(visibility.connects(up, south) && visibility.connects(up, north));
+
+        // A tunnel through the wall joins both sides again
+        chunk.set(3, 3, 8, BlockType::Air);
+        assert!(compute_visibility(&chunk).connects(south, north));
+    }
+
+    #[test]
+    fn solid_chunks_hide_what_is_behind_them() {
+        // A row of open chunks with a solid one in the middle
+        let graph: HashMap<IVec3, ChunkVisibility> = HashMap::from([
+            (IVec3::new(0, 0, 0), ChunkVisibility::ALL),
+            (IVec3::new(1, 0, 0), ChunkVisibility::NONE),
+            (IVec3::new(2, 0, 0), ChunkVisibility::ALL),
+        ]);
+        let visible = visible_chunks(IVec3::ZERO, |pos| graph.get(&pos).copied()).unwrap();
+        assert!(visible.contains(&IVec3::new(1, 0, 0)));
+        assert!(!visible.contains(&IVec3::new(2, 0, 0)));
+        assert!(visible_chunks(IVec3::new(5, 0, 0), |pos| graph.get(&pos).copied()).is_none());
+    }
+
+    #[test]
+    fn walks_never_turn_back() {
+        // A solid chunk east of the camera. Past it, the only route leads north, east
+        // and back south, which the walk refuses: that would look back behind the wall
+        let mut graph = HashMap::new();
+        for pos in [IVec3::new(0, 0, 0), IVec3::new(0, 0, -1), IVec3::new(1, 0, -1), IVec3::new(2, 0, -1)] {
+            graph.insert(pos, ChunkVisibility::ALL);
+        }
+        graph.insert(IVec3::new(1, 0, 0), ChunkVisibility::NONE);
+        graph.insert(IVec3::new(2, 0, 0), ChunkVisibility::ALL);
+        let visible = visible_chunks(IVec3::ZERO, |pos| graph.get(&pos).copied()).unwrap();
+        assert!(visible.contains(&IVec3::new(2, 0, -1)));
+        assert!(!visible.contains(&IVec3::new(2, 0, 0)));
+    }
+}